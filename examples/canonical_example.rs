@@ -19,7 +19,7 @@ fn hex_primary() -> impl Parse<Output = u8> {
     char_if(is_hex_digit).count(2).try_map(from_hex)
 }
 
-fn hex_color(input: &str) -> ParseResult<Color> {
+fn hex_color(input: &str) -> ParseResult<'_, Color> {
     let (((red, green), blue), remaining) = token("#")
         .skip_then(hex_primary().then(hex_primary()).then(hex_primary()))
         .parse(input)?;
@@ -1,10 +1,11 @@
 //! Parse JSON input somewhat according to spec.
 //!
 //! This isn't the fastest or most correct JSON parser out there, instead intended to demonstrate usage of parsely.
+//! For a stricter JSON parser, enable the `json` feature and use `parsely::formats::json`.
 
 use std::{collections::BTreeMap, io::BufRead};
 
-use parsely::{char, ws, Lex, Parse, ParseResult};
+use parsely::{char, ws0, Lex, Parse, ParseResult};
 
 // first come all the types we parse into...
 
@@ -76,15 +77,15 @@ fn array(input: &str) -> ParseResult<'_, Vec<Value>> {
     parsely::combinator::pad(
         char('['),
         char(']'),
-        value().many(..).delimiter(char(',').then(ws().many(..))),
+        value().many(..).delimiter(char(',').then(ws0())),
     )
     .parse(input)
 }
 
 fn map(input: &str) -> ParseResult<'_, Map<String, Value>> {
     parsely::combinator::pad(
-        char('{').then(ws().many(..)),
-        ws().many(..).then(char('}')),
+        char('{').then(ws0()),
+        ws0().then(char('}')),
         string().then_skip(char(':').pad()).then(value()).optional(),
     )
     .map(|inner| {
@@ -212,9 +213,15 @@ mod json_tests {
         Ok(())
     }
 
+    /// What an escape that isn't one of JSON's is reported as.
+    fn invalid_escape() -> parsely::Error {
+        let expected = [r"'\\'", "'t'", "'n'", "'r'", "'b'", "'f'", "'\"'"];
+        parsely::Error::ExpectedOneOf(expected.map(String::from).to_vec())
+    }
+
     #[test]
     fn escapes() -> Result<(), parsely::Error> {
-        assert_eq!(escape().parse(r#"\z"#), Err(parsely::Error::NoMatch));
+        assert_eq!(escape().parse(r#"\z"#), Err(invalid_escape()));
         assert_eq!(escape().parse(r#"\""#)?, ('"', ""));
         assert_eq!(escape().parse(r#"\t"#)?, ('\t', ""));
         assert_eq!(escape().parse(r#"\n"#)?, ('\n', ""));
@@ -223,7 +230,8 @@ mod json_tests {
         assert_eq!(escape().parse(r#"\f"#)?, ('\x0c', ""));
         assert_eq!(escape().parse(r#"\\"#)?, ('\\', ""));
 
-        assert_eq!(json(r#""\z""#), Err(parsely::Error::NoMatch));
+        // the reason the string stopped is reported, rather than the closing quote not matching
+        assert_eq!(json(r#""\z""#), Err(invalid_escape()));
         assert_eq!(json(r#""\"""#)?.0, Value::String(String::from("\"")));
         assert_eq!(json(r#""\n""#)?.0, Value::String(String::from("\n")));
         assert_eq!(json(r#""\\""#)?.0, Value::String(String::from("\\")));
@@ -0,0 +1,37 @@
+//! A cheap check of whether anything that records the side effects of parsing is running.
+//!
+//! [`with_captures()`](super::with_captures()), [`with_recovery()`](super::with_recovery()), [`with_diagnostics()`](super::with_diagnostics()),
+//! [`budget()`](super::budget()), [`profile()`](super::profile()) and [`trace()`](super::trace()) each keep their state in a thread local.
+//! Looking those up on every attempt is most of the cost of parsing when none of them are used,
+//! so they count themselves here while they run, and the hot paths return early when the count is zero.
+//! [`or()`](super::or()) also checks this so that it only tries each alternative once while they run.
+
+use std::cell::Cell;
+
+thread_local! {
+    /// How many recorders are running on this thread.
+    static ACTIVE: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Counts a recorder as running until it is dropped, even if parsing panics.
+pub(crate) struct Active(());
+
+impl Active {
+    /// Starts counting a recorder as running.
+    pub(crate) fn start() -> Self {
+        ACTIVE.with(|active| active.set(active.get() + 1));
+        Active(())
+    }
+}
+
+impl Drop for Active {
+    fn drop(&mut self) {
+        ACTIVE.with(|active| active.set(active.get() - 1));
+    }
+}
+
+/// Whether any recorder is running on this thread.
+#[inline]
+pub(crate) fn is_active() -> bool {
+    ACTIVE.with(|active| active.get() != 0)
+}
@@ -0,0 +1,277 @@
+use std::fmt;
+
+use crate::{error::no_match, grammar::Grammar, Lex, LexResult, Parse, ParseResult};
+
+use super::instrument::instrument;
+
+/// Which of the two matches a [`Both`] consumes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Consume {
+    Longer,
+    Shorter,
+    Left,
+    Right,
+    Same,
+}
+
+/// This combinator is returned by [`both()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Both<L, R> {
+    left: L,
+    right: R,
+    consume: Consume,
+}
+
+impl<L, R> Both<L, R> {
+    /// Consumes whichever of the two matches is shorter, instead of the longer one.
+    pub fn consume_shorter(self) -> Self {
+        Both {
+            consume: Consume::Shorter,
+            ..self
+        }
+    }
+
+    /// Consumes what the left item matched, whether it is longer or shorter than what the right item matched.
+    pub fn consume_left(self) -> Self {
+        Both {
+            consume: Consume::Left,
+            ..self
+        }
+    }
+
+    /// Consumes what the right item matched, whether it is longer or shorter than what the left item matched.
+    pub fn consume_right(self) -> Self {
+        Both {
+            consume: Consume::Right,
+            ..self
+        }
+    }
+
+    /// Requires both items to match the same input, failing with [`Error::NoMatch`](crate::Error::NoMatch) if one matched more than the other.
+    ///
+    /// This is the strictest form of "this and also that": the input matched is exactly what both items would match.
+    pub fn same_length(self) -> Self {
+        Both {
+            consume: Consume::Same,
+            ..self
+        }
+    }
+
+    /// Picks the remaining input after the match to consume, given the remaining input after each item.
+    fn remaining<'i>(
+        &self,
+        input: &'i str,
+        left: &'i str,
+        right: &'i str,
+    ) -> Result<&'i str, crate::Error> {
+        // less remaining input means a longer match
+        let remaining = match self.consume {
+            Consume::Longer if left.len() <= right.len() => left,
+            Consume::Longer => right,
+            Consume::Shorter if left.len() >= right.len() => left,
+            Consume::Shorter => right,
+            Consume::Left => left,
+            Consume::Right => right,
+            Consume::Same if left.len() == right.len() => left,
+            Consume::Same => return Err(no_match(input)),
+        };
+
+        Ok(remaining)
+    }
+}
+
+/// Creates a parser or lexer that requires both items to match at the same place in the input.
+///
+/// The left item is run first, and then the right item is run on the same input. If either fails, so does `both`.
+/// This expresses conjunctions such as "an identifier that is also at most 8 characters long",
+/// which would otherwise need a closure to check the output of one item.
+///
+/// When parsing, the output is a tuple of both outputs. When lexing, the output is the input that was consumed.
+///
+/// The longer of the two matches is consumed, see [`Both::consume_shorter()`], [`Both::consume_left()`] and [`Both::consume_right()`] to choose another,
+/// or [`Both::same_length()`] to require both items to match exactly the same input.
+///
+/// Grammars can't express that two rules must both match, so this is described by the left item alone.
+///
+/// This combinator can be chained using [`Parse::and()`] or [`Lex::and()`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{alpha, alphanum, Lex, Parse};
+///
+/// let identifier = alpha().then(alphanum().many(..)).map(str::to_string);
+/// let up_to_8 = alphanum().many(1..=8).map(str::len);
+///
+/// let short_identifier = identifier.and(up_to_8).same_length();
+///
+/// assert_eq!(short_identifier.parse("abc1 = 2")?, (("abc1".to_string(), 4), " = 2"));
+///
+/// // both match, but not the same input
+/// assert!(short_identifier.parse("abcdefghij").is_err());
+///
+/// // the right item doesn't match at all
+/// assert!(short_identifier.parse("_a").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+///
+/// Lexing:
+///
+/// ```
+/// use parsely::{digit, hex, Lex};
+/// use parsely::combinator::both;
+///
+/// // hex digits that start with a decimal digit, such as "0ff"
+/// let number = both(hex().many(1..), digit());
+///
+/// assert_eq!(number.lex("0ff!")?, ("0ff", "!"));
+/// assert!(number.lex("ff").is_err());
+///
+/// assert_eq!(number.consume_shorter().lex("0ff!")?, ("0", "ff!"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn both<L, R>(left: L, right: R) -> Both<L, R> {
+    Both {
+        left,
+        right,
+        consume: Consume::Longer,
+    }
+}
+
+impl<L, R> Parse for Both<L, R>
+where
+    L: Parse,
+    R: Parse,
+{
+    type Output = (<L as Parse>::Output, <R as Parse>::Output);
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        instrument("both", input, || {
+            let (left, left_remaining) = self.left.parse(input)?;
+            let (right, right_remaining) = self.right.parse(input)?;
+
+            let remaining = self.remaining(input, left_remaining, right_remaining)?;
+            Ok(((left, right), remaining))
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        self.left.describe()
+    }
+}
+
+impl<L: Lex, R: Lex> Lex for Both<L, R> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        instrument("both", input, || {
+            let (_, left_remaining) = self.left.lex(input)?;
+            let (_, right_remaining) = self.right.lex(input)?;
+
+            let remaining = self.remaining(input, left_remaining, right_remaining)?;
+            Ok(input.split_at(input.len() - remaining.len()))
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        self.left.describe()
+    }
+}
+
+impl<L, R> fmt::Debug for Both<L, R>
+where
+    L: fmt::Debug,
+    R: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.consume {
+            Consume::Longer => write!(f, "Both({:?}, {:?})", self.left, self.right),
+            consume => write!(f, "Both({:?}, {:?}, {consume:?})", self.left, self.right),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{char, digit, hex, token};
+    use crate::testing::*;
+    use crate::{int, Error};
+
+    #[test]
+    fn lexing() {
+        let number = || both(hex().many(1..), digit().many(1..));
+
+        test_lexer_batch(
+            "the longer match",
+            number(),
+            &[
+                ("12ab!", Some("12ab"), "!"),
+                ("12", Some("12"), ""),
+                ("ab12", None, "ab12"),
+                ("", None, ""),
+            ],
+        );
+
+        test_lexer_batch(
+            "the shorter match",
+            number().consume_shorter(),
+            &[("12ab!", Some("12"), "ab!"), ("ab", None, "ab")],
+        );
+
+        test_lexer_batch(
+            "the left match",
+            both(digit(), token("12").or(token("1"))).consume_left(),
+            &[("123", Some("1"), "23"), ("3", None, "3")],
+        );
+
+        test_lexer_batch(
+            "the right match",
+            both(digit().many(1..), token("12")).consume_right(),
+            &[("123", Some("12"), "3"), ("13", None, "13")],
+        );
+
+        test_lexer_batch(
+            "the same match",
+            number().same_length(),
+            &[
+                ("12!", Some("12"), "!"),
+                ("12ab", None, "12ab"),
+                ("ab", None, "ab"),
+            ],
+        );
+    }
+
+    #[test]
+    fn parsing() {
+        let small = || both(int::<u8>(), digit().many(1..=2).map(str::len));
+
+        test_parser_batch(
+            "a u8 and up to 2 digits",
+            small(),
+            &[
+                ("42!", Some((42, 2)), "!"),
+                ("200", Some((200, 2)), ""),
+                ("x", None, "x"),
+            ],
+        );
+
+        test_parser_batch(
+            "a u8 of up to 2 digits",
+            small().same_length(),
+            &[("42!", Some((42, 2)), "!"), ("200", None, "200")],
+        );
+
+        // the error is from whichever item failed
+        assert_eq!(
+            small().parse("300").unwrap_err(),
+            Error::Overflow {
+                digits: "300".to_string()
+            }
+        );
+        assert_eq!(
+            both(char('a').map(|_| ()), int::<u8>()).parse("b"),
+            Err(Error::NoMatch)
+        );
+    }
+}
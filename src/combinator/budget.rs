@@ -0,0 +1,213 @@
+//! [`budget()`] limits how much work a parser can do, so untrusted input can't make parsing take unreasonably long.
+
+use std::{cell::Cell, fmt};
+
+use crate::{
+    combinator::active,
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+thread_local! {
+    /// How many bytes can still be examined, while a [`Budget`] is running.
+    static REMAINING: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Restores the previous budget when dropped, even if parsing panics.
+///
+/// Whatever was used up by this budget is used up from the previous one too.
+struct Restore {
+    previous: Option<usize>,
+    start: usize,
+}
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        REMAINING.with(|remaining| {
+            let used = self.start - remaining.get().unwrap_or(0);
+            remaining.set(self.previous.map(|previous| previous.saturating_sub(used)));
+        });
+    }
+}
+
+/// Runs `f` with at most `limit` bytes to examine, or less if a budget is already running with less left.
+fn budgeted<T>(limit: usize, f: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+    let previous = REMAINING.with(Cell::get);
+    let start = previous.map_or(limit, |previous| previous.min(limit));
+
+    REMAINING.with(|remaining| remaining.set(Some(start)));
+    let _restore = Restore { previous, start };
+    let _active = active::Active::start();
+
+    f()
+}
+
+/// Uses up `len` bytes of the running budget, failing with [`Error::BudgetExceeded`] once there isn't enough left.
+///
+/// The built in lexers call this with how much of the input they looked at.
+/// Each call costs at least one byte, so that matching empty input over and over still uses up the budget.
+#[inline]
+pub(crate) fn examine(len: usize) -> Result<(), Error> {
+    if !active::is_active() {
+        return Ok(());
+    }
+
+    REMAINING.with(|remaining| match remaining.get() {
+        None => Ok(()),
+        Some(left) => {
+            let cost = len.max(1);
+
+            if cost > left {
+                remaining.set(Some(0));
+                Err(Error::BudgetExceeded.fatal())
+            } else {
+                remaining.set(Some(left - cost));
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Uses up the length of the first char of the input, for lexers that look at a single char.
+#[inline]
+pub(crate) fn examine_char(input: &str) -> Result<(), Error> {
+    examine(input.chars().next().map_or(0, char::len_utf8))
+}
+
+/// This combinator is returned by [`budget()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Budget<T> {
+    max_bytes: usize,
+    item: T,
+}
+
+impl<L: Lex> Lex for Budget<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        budgeted(self.max_bytes, || self.item.lex(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.item.canonical_into(output)
+    }
+}
+
+impl<P: Parse> Parse for Budget<P> {
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        budgeted(self.max_bytes, || self.item.parse(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+impl<P: Unparse> Unparse for Budget<P> {
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.item.unparse_into(value, output)
+    }
+}
+
+/// Fails with [`Error::BudgetExceeded`] once the item (a parser or lexer) has examined more than `max_bytes` of input.
+///
+/// Input that is examined again after backtracking, e.g. by each alternative of [`or()`](super::or()), counts again.
+/// This bounds the work done parsing untrusted input, however badly a grammar backtracks on it.
+///
+/// The built in lexers count the bytes they look at, e.g. [`token()`](crate::token()) counts as much of the token as it compared before finding a difference, and [`until()`](crate::until()) counts how far it searched.
+/// Every attempt to match counts at least one byte. Lexers and parsers written as functions count whatever the built in lexers they use count.
+///
+/// The error is [fatal](crate::Error::fatal()), so parsing stops as soon as the budget is used up.
+///
+/// A budget inside another budget can't examine more than the outer budget has left, and what it examines is used up from the outer budget too.
+///
+/// This is more conveniently created using the [`Lex::budget`] and [`Parse::budget`] methods.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{token, Error, Lex};
+///
+/// let lexer = token("ab").or(token("ac")).many(..).budget(100);
+///
+/// assert_eq!(lexer.lex("abacab")?, ("abacab", ""));
+///
+/// let long = "ac".repeat(100);
+/// assert_eq!(lexer.lex(&long).unwrap_err().root(), &Error::BudgetExceeded);
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn budget<T>(max_bytes: usize, item: T) -> Budget<T> {
+    Budget { max_bytes, item }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Budget<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Budget({}, {:?})", self.max_bytes, self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, digit, token, until};
+
+    #[test]
+    fn counts_examined_input() {
+        // each digit is examined once, and the end of the input once more
+        let digits = || digit().many(..);
+        assert_eq!(budget(4, digits()).lex("123"), Ok(("123", "")));
+        assert_eq!(
+            budget(3, digits()).lex("123"),
+            Err(Error::BudgetExceeded.fatal())
+        );
+
+        // backtracking examines the input again
+        let backtracking = || token("abc").or(token("abd"));
+        assert_eq!(budget(6, backtracking()).lex("abd"), Ok(("abd", "")));
+        assert_eq!(
+            budget(5, backtracking()).lex("abd"),
+            Err(Error::BudgetExceeded.fatal())
+        );
+
+        // searching examines everything searched through
+        let search = || until(";").then(char(';'));
+        assert!(budget(10, search()).lex("12345678;").is_ok());
+        assert!(budget(8, search()).lex("12345678;").is_err());
+
+        // a token only counts what it compared, up to the first difference
+        assert_eq!(
+            budget(2, token("function").or(token("x"))).lex("x"),
+            Ok(("x", ""))
+        );
+        assert_eq!(budget(3, token("function")).lex("fun"), Err(Error::NoMatch));
+        assert_eq!(
+            budget(2, token("function")).lex("fun"),
+            Err(Error::BudgetExceeded.fatal())
+        );
+    }
+
+    #[test]
+    fn nested_budgets() {
+        let inner = budget(100, digit().many(..));
+        let outer = budget(5, inner.then(char(';')));
+
+        assert_eq!(outer.lex("123456;"), Err(Error::BudgetExceeded.fatal()));
+        assert_eq!(outer.lex("12;"), Ok(("12;", "")));
+
+        // nothing is left running afterwards
+        assert_eq!(REMAINING.with(Cell::get), None);
+        assert_eq!(digit().many(..).lex("123456"), Ok(("123456", "")));
+    }
+}
@@ -0,0 +1,407 @@
+//! Named captures record what parts of the input were matched by tagged sub-parsers, without threading every result through the output.
+//!
+//! Tag any lexer or parser with [`.capture("name")`](crate::Lex::capture()), and run the whole parser with [`with_captures()`].
+//! After a successful parse, the [`Captures`] map holds the span and text matched by each tag.
+//!
+//! Captures made by a branch that is later abandoned, e.g. the left side of an [`or()`](crate::combinator::or()) that went on to fail, are discarded.
+//!
+//! # Examples
+//!
+//! Scraping a log line:
+//!
+//! ```
+//! use parsely::{alpha, char, digit, until, ws, Lex, Parse};
+//! use parsely::combinator::with_captures;
+//!
+//! let timestamp = digit().many(2..=2).then(char(':')).then(digit().many(2..=2));
+//! let level = alpha().many(1..);
+//!
+//! let line = with_captures(
+//!     timestamp.capture("time")
+//!         .then(ws())
+//!         .then(level.capture("level"))
+//!         .then(char(':').then(ws()))
+//!         .then(until("\n").capture("message"))
+//!         .map(|_| ()),
+//! );
+//!
+//! let ((_, captures), _) = line.parse("12:34 WARN: disk almost full\n")?;
+//!
+//! assert_eq!(&captures["time"], "12:34");
+//! assert_eq!(&captures["level"], "WARN");
+//! assert_eq!(&captures["message"], "disk almost full");
+//! assert_eq!(captures.get("level").unwrap().range, 6..10);
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::{cell::RefCell, fmt, ops::Range};
+
+use super::{active, diagnostics, recover};
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+/// The text matched by a [`.capture()`](crate::Lex::capture()), and where it is in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captured {
+    /// The byte range of the match in the input given to [`with_captures()`].
+    pub range: Range<usize>,
+
+    /// The matched text.
+    pub text: String,
+}
+
+/// The captures recorded while running [`with_captures()`], in the order they were matched.
+///
+/// Indexing by name returns the matched text of the last capture with that name, and panics if there is none.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Captures {
+    entries: Vec<(&'static str, Captured)>,
+}
+
+impl Captures {
+    /// Returns the last capture with this name.
+    pub fn get(&self, name: &str) -> Option<&Captured> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(n, _)| *n == name)
+            .map(|(_, captured)| captured)
+    }
+
+    /// Returns every capture with this name, useful when the capture was repeated e.g. by [`many()`](crate::combinator::many()).
+    pub fn get_all<'c>(&'c self, name: &'c str) -> impl Iterator<Item = &'c Captured> + 'c {
+        self.iter()
+            .filter(move |(n, _)| *n == name)
+            .map(|(_, captured)| captured)
+    }
+
+    /// Returns every capture and its name, in the order they were matched.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &Captured)> {
+        self.entries
+            .iter()
+            .map(|(name, captured)| (*name, captured))
+    }
+
+    /// The number of captures.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no captures.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl std::ops::Index<&str> for Captures {
+    type Output = str;
+
+    fn index(&self, name: &str) -> &str {
+        match self.get(name) {
+            Some(captured) => &captured.text,
+            None => panic!("no capture named {name:?}"),
+        }
+    }
+}
+
+/// The captures recorded by the innermost running [`WithCaptures`].
+struct Log {
+    /// The address of the input, used to find the offset of a match within it.
+    address: usize,
+    len: usize,
+
+    entries: Vec<(&'static str, Range<usize>)>,
+}
+
+thread_local! {
+    static LOG: RefCell<Option<Log>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous log when dropped, even if parsing panics.
+struct Restore(Option<Log>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        LOG.with(|log| *log.borrow_mut() = self.0.take());
+    }
+}
+
+/// Records a capture, if running inside [`with_captures()`] with an input that contains the match.
+fn record(name: &'static str, input: &str, remaining: &str) {
+    if !active::is_active() {
+        return;
+    }
+
+    LOG.with(|log| {
+        let mut log = log.borrow_mut();
+        let Some(log) = log.as_mut() else {
+            return;
+        };
+
+        let address = input.as_ptr() as usize;
+        if address < log.address || address + input.len() != log.address + log.len {
+            return;
+        }
+
+        let start = address - log.address;
+        let end = start + input.len() - remaining.len();
+        log.entries.push((name, start..end));
+    });
+}
+
+/// The captures, [diagnostics](super::diagnostics) and [recovered](super::recover) errors made so far, see [`mark()`].
+pub(crate) struct Mark {
+    captured: Option<usize>,
+    recovered: Option<usize>,
+    diagnosed: Option<usize>,
+}
+
+/// Marks the side effects of parsing so far, to [`rewind()`] to if an attempt is abandoned.
+///
+/// Prefer [`backtrack()`], this is for combinators that can't wrap each attempt in a closure.
+///
+/// Nothing is marked unless something that records side effects is running, so this is almost free the rest of the time.
+#[inline]
+pub(crate) fn mark() -> Mark {
+    if !active::is_active() {
+        return Mark {
+            captured: None,
+            recovered: None,
+            diagnosed: None,
+        };
+    }
+
+    Mark {
+        captured: LOG.with(|log| log.borrow().as_ref().map(|log| log.entries.len())),
+        recovered: recover::checkpoint(),
+        diagnosed: diagnostics::checkpoint(),
+    }
+}
+
+/// Discards the captures, diagnostics and recovered errors made since the [`mark()`].
+#[inline]
+pub(crate) fn rewind(mark: Mark) {
+    recover::rewind(mark.recovered);
+    diagnostics::rewind(mark.diagnosed);
+
+    if let Some(captured) = mark.captured {
+        LOG.with(|log| {
+            if let Some(log) = log.borrow_mut().as_mut() {
+                log.entries.truncate(captured);
+            }
+        });
+    }
+}
+
+/// Runs `f`, discarding any captures, [diagnostics](super::diagnostics) and [recovered](super::recover) errors it made, if it fails.
+///
+/// This is used wherever a combinator abandons an attempt and tries something else.
+pub(crate) fn backtrack<T, E>(f: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    let mark = mark();
+    let result = f();
+
+    if result.is_err() {
+        rewind(mark);
+    }
+
+    result
+}
+
+/// Runs `f` as an attempt that may be abandoned, see [`backtrack()`].
+///
+/// A failure is recovered from by returning `None`, unless the error is [fatal](Error::fatal()), in which case it is returned.
+pub(crate) fn recoverable<T>(f: impl FnOnce() -> Result<T, Error>) -> Result<Option<T>, Error> {
+    attempt(f).map(Result::ok)
+}
+
+/// Like [`recoverable()`], but a failure that is recovered from is returned as the inner error rather than discarded.
+pub(crate) fn attempt<T>(f: impl FnOnce() -> Result<T, Error>) -> Result<Result<T, Error>, Error> {
+    match backtrack(f) {
+        Err(e) if e.is_fatal() => Err(e),
+        result => Ok(result),
+    }
+}
+
+/// This combinator is returned by [`Lex::capture()`] and [`Parse::capture()`]. See [the module documentation](self) for more details.
+#[derive(Clone)]
+pub struct Capture<T> {
+    name: &'static str,
+    item: T,
+}
+
+impl<L: Lex> Lex for Capture<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let (matched, remaining) = self.item.lex(input)?;
+        record(self.name, input, remaining);
+        Ok((matched, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+}
+
+impl<P: Parse> Parse for Capture<P> {
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (output, remaining) = self.item.parse(input)?;
+        record(self.name, input, remaining);
+        Ok((output, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+/// Tags the item (a parser or lexer) so that what it matches is recorded by an enclosing [`with_captures()`].
+///
+/// This is more conveniently created using the [`Lex::capture`] and [`Parse::capture`] methods.
+pub fn capture<T>(name: &'static str, item: T) -> Capture<T> {
+    Capture { name, item }
+}
+
+/// This combinator is returned by [`with_captures()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct WithCaptures<T> {
+    item: T,
+}
+
+impl<T> WithCaptures<T> {
+    fn run<'i, O>(
+        &self,
+        input: &'i str,
+        f: impl FnOnce(&T) -> Result<(O, &'i str), crate::Error>,
+    ) -> Result<((O, Captures), &'i str), crate::Error> {
+        let log = Log {
+            address: input.as_ptr() as usize,
+            len: input.len(),
+            entries: Vec::new(),
+        };
+        let _active = active::Active::start();
+        let restore = Restore(LOG.with(|current| current.borrow_mut().replace(log)));
+
+        let result = f(&self.item);
+        let log = LOG.with(|current| current.borrow_mut().take());
+        drop(restore);
+
+        let (output, remaining) = result?;
+        let entries = log
+            .map(|log| log.entries)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, range)| {
+                let text = input[range.clone()].to_string();
+                (name, Captured { range, text })
+            })
+            .collect();
+
+        Ok(((output, Captures { entries }), remaining))
+    }
+}
+
+impl<P: Parse> Parse for WithCaptures<P> {
+    type Output = (<P as Parse>::Output, Captures);
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        self.run(input, |item| item.parse(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+/// Runs the item, and outputs its output along with the [`Captures`] recorded by any [`.capture()`](crate::Lex::capture()) inside it.
+///
+/// Captures inside a nested `with_captures()` belong to that one, and are not seen by this one.
+///
+/// See [the module documentation](self) for an example.
+pub fn with_captures<T>(item: T) -> WithCaptures<T> {
+    WithCaptures { item }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Capture<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Capture({:?}, {:?})", self.name, self.item)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for WithCaptures<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WithCaptures({:?})", self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, int, token, Lex, Parse};
+
+    #[test]
+    fn abandoned_branches_are_discarded() -> Result<(), crate::Error> {
+        let parser = with_captures(
+            token("a")
+                .capture("a")
+                .then(token("x"))
+                .or(token("a").then(token("b")).capture("ab"))
+                .map(str::len)
+                .many(..),
+        );
+
+        let ((output, captures), remaining) = parser.parse("abaxac")?;
+        assert_eq!(output, vec![2, 2]);
+        assert_eq!(remaining, "ac");
+
+        let found: Vec<_> = captures
+            .iter()
+            .map(|(name, c)| (name, c.range.clone()))
+            .collect();
+        assert_eq!(found, vec![("ab", 0..2), ("a", 2..3)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn repeated_captures() -> Result<(), crate::Error> {
+        let parser = with_captures(int::<u8>().capture("n").many(..).delimiter(char(',')));
+
+        let ((output, captures), _) = parser.parse("1,22,3")?;
+        assert_eq!(output, vec![1, 22, 3]);
+        assert_eq!(&captures["n"], "3");
+
+        let all: Vec<_> = captures.get_all("n").map(|c| c.text.as_str()).collect();
+        assert_eq!(all, vec!["1", "22", "3"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn outside_of_with_captures() -> Result<(), crate::Error> {
+        assert_eq!(token("a").capture("a").lex("ab")?, ("a", "b"));
+        assert!(LOG.with(|log| log.borrow().is_none()));
+
+        let inner = with_captures(token("b").capture("inner").map(|_| ()));
+        let nested = with_captures(
+            token("a")
+                .capture("outer")
+                .skip_then(inner.map(|(_, captures)| captures)),
+        );
+
+        let ((inner, outer), _) = nested.parse("ab")?;
+        assert_eq!(outer.len(), 1);
+        assert_eq!(&outer["outer"], "a");
+        assert_eq!(&inner["inner"], "b");
+        assert_eq!(inner.get("inner").unwrap().range, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+//! [`chainl1()`] and [`chainr1()`] parse one or more terms separated by operators, and fold them together with the operators.
+//!
+//! These are handy for simple arithmetic-like grammars. For grammars with many levels of precedence, see the [`expr`](crate::expr) module.
+
+use std::fmt;
+
+use crate::{grammar::Grammar, Parse, ParseResult};
+
+use super::{capture::recoverable, ws_insensitive::skip};
+
+/// This combinator is returned by [`chainl1()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct ChainL1<T, P> {
+    term: T,
+    operator: P,
+}
+
+/// This combinator is returned by [`chainr1()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct ChainR1<T, P> {
+    term: T,
+    operator: P,
+}
+
+/// Parses the next `(operator, term)` pair, if there is one.
+///
+/// If the operator matches but isn't followed by a term, then the operator isn't part of the chain and `None` is output, consuming nothing.
+/// Fatal errors are returned rather than ending the chain.
+fn operator_then_term<'i, T, P>(
+    term: &T,
+    operator: &P,
+    input: &'i str,
+) -> ParseResult<'i, Option<(P::Output, T::Output)>>
+where
+    T: Parse,
+    P: Parse,
+{
+    let found = recoverable(|| {
+        let (f, remaining) = operator.parse(skip(input))?;
+        let (rhs, remaining) = term.parse(skip(remaining))?;
+
+        Ok(((f, rhs), remaining))
+    })?;
+
+    Ok(match found {
+        Some((pair, remaining)) => (Some(pair), remaining),
+        None => (None, input),
+    })
+}
+
+impl<T, P, F> Parse for ChainL1<T, P>
+where
+    T: Parse,
+    P: Parse<Output = F>,
+    F: Fn(T::Output, T::Output) -> T::Output,
+{
+    type Output = <T as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (mut output, mut remaining) = self.term.parse(input)?;
+
+        while let (Some((f, rhs)), after) =
+            operator_then_term(&self.term, &self.operator, remaining)?
+        {
+            output = f(output, rhs);
+            remaining = after;
+        }
+
+        Ok((output, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        let rest = Grammar::then(self.operator.describe(), self.term.describe());
+        Grammar::then(self.term.describe(), Grammar::repeat(0, None, rest))
+    }
+}
+
+impl<T, P, F> Parse for ChainR1<T, P>
+where
+    T: Parse,
+    P: Parse<Output = F>,
+    F: Fn(T::Output, T::Output) -> T::Output,
+{
+    type Output = <T as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (first, mut remaining) = self.term.parse(input)?;
+
+        // each term is paired with the operator on its left
+        let mut rest = Vec::new();
+
+        while let (Some((f, rhs)), after) =
+            operator_then_term(&self.term, &self.operator, remaining)?
+        {
+            rest.push((f, rhs));
+            remaining = after;
+        }
+
+        let mut rest = rest.into_iter().rev();
+        let Some((mut f, mut output)) = rest.next() else {
+            return Ok((first, remaining));
+        };
+
+        for (left_f, lhs) in rest {
+            output = f(lhs, output);
+            f = left_f;
+        }
+
+        Ok((f(first, output), remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        let rest = Grammar::then(self.operator.describe(), self.term.describe());
+        Grammar::then(self.term.describe(), Grammar::repeat(0, None, rest))
+    }
+}
+
+/// Parses one or more terms separated by operators, folding them together from the left.
+///
+/// The operator is a parser that outputs a function which combines the terms on either side of it.
+/// `1 - 2 - 3` is folded as `(1 - 2) - 3`.
+///
+/// An operator that isn't followed by a term is not consumed.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{int, switch, Lex, Parse};
+/// use parsely::combinator::chainl1;
+///
+/// type Op = fn(i64, i64) -> i64;
+///
+/// let add: Op = |a, b| a + b;
+/// let sub: Op = |a, b| a - b;
+///
+/// let sum = chainl1(int::<i64>().pad(), switch([("+", add), ("-", sub)]));
+///
+/// assert_eq!(sum.parse("1 + 2 - 3")?, (0, ""));
+/// assert_eq!(sum.parse("10 - 4 - 3")?, (3, ""));
+/// assert_eq!(sum.parse("10 - ")?, (10, "- "));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn chainl1<T, P>(term: T, operator: P) -> ChainL1<T, P> {
+    ChainL1 { term, operator }
+}
+
+/// Parses one or more terms separated by operators, folding them together from the right.
+///
+/// The operator is a parser that outputs a function which combines the terms on either side of it.
+/// `2 ^ 3 ^ 2` is folded as `2 ^ (3 ^ 2)`.
+///
+/// An operator that isn't followed by a term is not consumed.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, uint, Lex, Parse};
+/// use parsely::combinator::chainr1;
+///
+/// let power = chainr1(uint::<u64>(), char('^').map(|_| |a: u64, b: u64| a.pow(b as u32)));
+///
+/// assert_eq!(power.parse("2^3^2")?, (512, ""));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn chainr1<T, P>(term: T, operator: P) -> ChainR1<T, P> {
+    ChainR1 { term, operator }
+}
+
+impl<T: fmt::Debug, P: fmt::Debug> fmt::Debug for ChainL1<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ChainL1({:?}, {:?})", self.term, self.operator)
+    }
+}
+
+impl<T: fmt::Debug, P: fmt::Debug> fmt::Debug for ChainR1<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ChainR1({:?}, {:?})", self.term, self.operator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{alpha, switch, Lex};
+
+    type Op = fn(String, String) -> String;
+
+    fn operators() -> impl Parse<Output = Op> {
+        let plus: Op = |a, b| format!("({a}+{b})");
+        let minus: Op = |a, b| format!("({a}-{b})");
+
+        switch([("+", plus), ("-", minus)])
+    }
+
+    #[test]
+    fn left() {
+        test_parser_batch(
+            "chainl1 folds from the left",
+            chainl1(alpha().map(str::to_string), operators()),
+            &[
+                ("a", Some("a".to_string()), ""), //
+                ("a+b", Some("(a+b)".to_string()), ""),
+                ("a+b-c+d", Some("(((a+b)-c)+d)".to_string()), ""),
+                ("a+b-", Some("(a+b)".to_string()), "-"),
+                ("a+-b", Some("a".to_string()), "+-b"),
+                ("+a", None, "+a"),
+            ],
+        );
+    }
+
+    #[test]
+    fn right() {
+        test_parser_batch(
+            "chainr1 folds from the right",
+            chainr1(alpha().map(str::to_string), operators()),
+            &[
+                ("a", Some("a".to_string()), ""), //
+                ("a+b", Some("(a+b)".to_string()), ""),
+                ("a+b-c+d", Some("(a+(b-(c+d)))".to_string()), ""),
+                ("a+b-", Some("(a+b)".to_string()), "-"),
+                ("+a", None, "+a"),
+            ],
+        );
+    }
+}
@@ -1,10 +1,133 @@
-use crate::{Error, Lex, LexResult, Parse, ParseResult};
+use std::fmt;
+
+use crate::{
+    error::no_match,
+    grammar::Grammar,
+    lexer::PatternLite,
+    sample::{SampleError, Sampler},
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+use super::{active, capture::backtrack};
 
 /// This combinator is returned by [`crawl()`]. See it’s documentation for more details
+#[derive(Clone)]
 pub struct Crawl<T> {
     item: T,
 }
 
+/// This combinator is returned by [`crawl_at()`]. See it’s documentation for more details
+#[derive(Clone)]
+pub struct CrawlAt<T> {
+    item: T,
+}
+
+/// This combinator is returned by [`crawl_consume()`]. See it’s documentation for more details
+#[derive(Clone)]
+pub struct CrawlConsume<T> {
+    item: T,
+}
+
+/// A match found by [`scan()`].
+struct Found<'i, O> {
+    offset: usize,
+    output: O,
+
+    /// The input from the offset onwards.
+    at: &'i str,
+
+    /// The input after the char at the offset.
+    next: &'i str,
+
+    /// The input after the match.
+    remaining: &'i str,
+}
+
+impl<'i, O> Found<'i, O> {
+    /// The input after the match, or after the char at the offset if the match was empty.
+    fn after_match(&self) -> &'i str {
+        if self.remaining.len() < self.at.len() {
+            self.remaining
+        } else {
+            self.next
+        }
+    }
+}
+
+/// Tries to match at each char boundary of the input in turn, including the very end of the input.
+///
+/// If every match starts with `prefix`, only the boundaries where it is found are tried, see [`Lex::prefix()`].
+fn scan<'i, O>(
+    input: &'i str,
+    prefix: Option<&str>,
+    attempt: impl Fn(&'i str) -> Result<(O, &'i str), Error>,
+) -> Result<Found<'i, O>, Error> {
+    if input.is_empty() {
+        return Err(Error::NoMatch);
+    }
+
+    // recorders such as budget() see every attempt
+    if let Some(prefix) = prefix.filter(|prefix| !prefix.is_empty() && !active::is_active()) {
+        return scan_prefix(input, prefix, attempt);
+    }
+
+    let boundaries = input
+        .char_indices()
+        .map(|(n, _)| n)
+        .chain(std::iter::once(input.len()));
+
+    for boundary in boundaries {
+        if let Some(found) = attempt_at(input, boundary, &attempt) {
+            return Ok(found);
+        }
+    }
+
+    Err(Error::NoMatch)
+}
+
+/// Tries to match only where `prefix` is found in the input, searching for it with [`PatternLite::find()`].
+fn scan_prefix<'i, O>(
+    input: &'i str,
+    prefix: &str,
+    attempt: impl Fn(&'i str) -> Result<(O, &'i str), Error>,
+) -> Result<Found<'i, O>, Error> {
+    let pattern = PatternLite::Str(prefix);
+    let mut from = 0;
+
+    while let Some(offset) = pattern.find(&input[from..]) {
+        let boundary = from + offset;
+
+        if let Some(found) = attempt_at(input, boundary, &attempt) {
+            return Ok(found);
+        }
+
+        // the prefix isn't empty, so there is a char to step over
+        from = boundary + input[boundary..].chars().next().map_or(1, char::len_utf8);
+    }
+
+    // as if every boundary had been tried, up to the very end of the input
+    Err(no_match(&input[input.len()..]))
+}
+
+/// Tries to match at the char boundary of the input.
+fn attempt_at<'i, O>(
+    input: &'i str,
+    boundary: usize,
+    attempt: &impl Fn(&'i str) -> Result<(O, &'i str), Error>,
+) -> Option<Found<'i, O>> {
+    let at = &input[boundary..];
+    let (output, remaining) = backtrack(|| attempt(at)).ok()?;
+    let next = at.chars().next().map_or(at, |c| &at[c.len_utf8()..]);
+
+    Some(Found {
+        offset: boundary,
+        output,
+        at,
+        next,
+        remaining,
+    })
+}
+
 impl<P> Parse for Crawl<P>
 where
     P: Parse,
@@ -12,27 +135,15 @@ where
     type Output = <P as Parse>::Output;
 
     fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
-        let mut char_indices = input.char_indices();
-        let Some((mut boundary, _)) = char_indices.next() else {
-            return Err(Error::NoMatch);
-        };
-
-        loop {
-            if let Ok((matched, _)) = self.item.parse(&input[boundary..]) {
-                boundary = match char_indices.next() {
-                    Some((n, _)) => n,
-                    None => input.len(),
-                };
-                return Ok((matched, &input[boundary..]));
-            } else if boundary == input.len() {
-                return Err(Error::NoMatch);
-            } else {
-                boundary = match char_indices.next() {
-                    Some((n, _)) => n,
-                    None => input.len(),
-                };
-            }
-        }
+        let found = scan(input, None, |input| self.item.parse(input))?;
+        Ok((found.output, found.next))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::then(
+            Grammar::repeat(0, None, Grammar::Class("any".to_string())),
+            self.item.describe(),
+        )
     }
 }
 
@@ -41,27 +152,80 @@ where
     L: Lex,
 {
     fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
-        let mut char_indices = input.char_indices();
-        let Some((mut boundary, _)) = char_indices.next() else {
-            return Err(Error::NoMatch);
-        };
-
-        loop {
-            if let Ok((matched, _)) = self.item.lex(&input[boundary..]) {
-                boundary = match char_indices.next() {
-                    Some((n, _)) => n,
-                    None => input.len(),
-                };
-                return Ok((matched, &input[boundary..]));
-            } else if boundary == input.len() {
-                return Err(Error::NoMatch);
-            } else {
-                boundary = match char_indices.next() {
-                    Some((n, _)) => n,
-                    None => input.len(),
-                };
-            }
-        }
+        let found = scan(input, self.item.prefix(), |input| self.item.lex(input))?;
+        Ok((found.output, found.next))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::then(
+            Grammar::repeat(0, None, Grammar::Class("any".to_string())),
+            self.item.describe(),
+        )
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+}
+
+impl<P> Parse for CrawlAt<P>
+where
+    P: Parse,
+{
+    type Output = (usize, <P as Parse>::Output);
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let found = scan(input, None, |input| self.item.parse(input))?;
+        Ok(((found.offset, found.output), found.next))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::then(
+            Grammar::repeat(0, None, Grammar::Class("any".to_string())),
+            self.item.describe(),
+        )
+    }
+}
+
+impl<P> Parse for CrawlConsume<P>
+where
+    P: Parse,
+{
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let found = scan(input, None, |input| self.item.parse(input))?;
+        let remaining = found.after_match();
+        Ok((found.output, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::then(
+            Grammar::repeat(0, None, Grammar::Class("any".to_string())),
+            self.item.describe(),
+        )
+    }
+}
+
+impl<L> Lex for CrawlConsume<L>
+where
+    L: Lex,
+{
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let found = scan(input, self.item.prefix(), |input| self.item.lex(input))?;
+        let remaining = found.after_match();
+        Ok((found.output, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::then(
+            Grammar::repeat(0, None, Grammar::Class("any".to_string())),
+            self.item.describe(),
+        )
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
     }
 }
 
@@ -130,3 +294,171 @@ where
 pub fn crawl<T>(item: T) -> Crawl<T> {
     Crawl { item }
 }
+
+/// Crawls through the input like [`crawl()`], and also outputs the offset (in bytes) where the match was found.
+///
+/// The offset is relative to the input given to this parser. The input is advanced in the same way as [`crawl()`].
+///
+/// To find where something is when the item is a lexer, map it to an output first, e.g. `crawl_at(lexer.map(str::len))` outputs the offset and length of the match.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{combinator::crawl_at, token, Lex, Parse};
+///
+/// let input = "bla bla bla >>>abc<<< and so on...";
+///
+/// let (output, remaining) = crawl_at(token("abc").map(str::len)).parse(input)?;
+/// assert_eq!(output, (15, 3));
+/// assert_eq!(&input[15..15 + 3], "abc");
+///
+/// assert_eq!(remaining, "bc<<< and so on...");
+///
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn crawl_at<T>(item: T) -> CrawlAt<T> {
+    CrawlAt { item }
+}
+
+/// Crawls through the input like [`crawl()`] until a match is found, but then advances past the whole match.
+///
+/// This makes `crawl_consume(item).many(..)` find every **non-overlapping** match, the way a regular expression's `find_iter` would.
+///
+/// If the match is empty, the input is advanced by a single character instead, to avoid matching in the same place forever.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{combinator::{crawl, crawl_consume}, token, Lex, Parse};
+///
+/// let input = "aaaa!";
+///
+/// let (matched, remaining) = crawl_consume(token("aa").map(str::len)).many(..).parse(input)?;
+/// assert_eq!(matched, vec![2, 2]);
+/// assert_eq!(remaining, "!");
+///
+/// // compared to crawl, which finds overlapping matches
+/// let (matched, remaining) = crawl(token("aa").map(str::len)).many(..).parse(input)?;
+/// assert_eq!(matched, vec![2, 2, 2]);
+/// assert_eq!(remaining, "a!");
+///
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn crawl_consume<T>(item: T) -> CrawlConsume<T> {
+    CrawlConsume { item }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Crawl<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Crawl({:?})", self.item)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CrawlAt<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CrawlAt({:?})", self.item)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CrawlConsume<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CrawlConsume({:?})", self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{combinator::budget, end, token};
+
+    #[test]
+    fn crawl_lex() {
+        test_lexer_batch(
+            "crawl advances one char past the start of the match",
+            crawl(token("ab")),
+            &[
+                ("ab", Some("ab"), "b"), //
+                ("xxab", Some("ab"), "b"),
+                ("âab", Some("ab"), "b"),
+                ("xa", None, "xa"),
+                ("", None, ""),
+            ],
+        );
+
+        test_lexer_batch(
+            "crawl can match at the end of the input",
+            crawl(end()),
+            &[("ab", Some(""), "")],
+        );
+    }
+
+    #[test]
+    fn offsets() {
+        test_parser_batch(
+            "crawl_at outputs the offset of the match",
+            crawl_at(token("ab").map(str::len)),
+            &[
+                ("ab", Some((0, 2)), "b"), //
+                ("xxab", Some((2, 2)), "b"),
+                ("âab", Some((2, 2)), "b"),
+                ("xa", None, "xa"),
+            ],
+        );
+    }
+
+    #[test]
+    fn consume() {
+        test_lexer_batch(
+            "crawl_consume advances past the whole match",
+            crawl_consume(token("ab")),
+            &[
+                ("ab", Some("ab"), ""), //
+                ("xxabab", Some("ab"), "ab"),
+                ("âab!", Some("ab"), "!"),
+                ("xa", None, "xa"),
+            ],
+        );
+
+        test_lexer_batch(
+            "crawl_consume advances one char past an empty match",
+            crawl_consume(end()),
+            &[("ab", Some(""), "")],
+        );
+    }
+
+    #[test]
+    fn searching_for_the_prefix() -> Result<(), crate::Error> {
+        // a function has no prefix, so every char boundary is tried
+        fn every_boundary(input: &str) -> Result<(&str, &str), crate::Error> {
+            token("éa").lex(input)
+        }
+        assert_eq!(token("éa").prefix(), Some("éa"));
+        assert_eq!(every_boundary.prefix(), None);
+
+        for input in ["", "éa", "xéa", "ééa", "éxéa!", "aéé", "é", "xxx"] {
+            assert_eq!(
+                crawl(token("éa")).lex(input),
+                crawl(every_boundary).lex(input),
+                "{input:?}"
+            );
+            assert_eq!(
+                crawl_consume(token("éa")).lex_all_owned(input),
+                crawl_consume(every_boundary).lex_all_owned(input),
+                "{input:?}"
+            );
+        }
+
+        // the search gives way to trying every boundary while recording
+        assert_eq!(budget(64, crawl(token("éa"))).lex("xéa!"), Ok(("éa", "a!")));
+
+        // overlapping matches are still found
+        let (matched, remaining) = crawl(token("aa")).many(..).lex("xaaaa")?;
+        assert_eq!((matched, remaining), ("xaaa", "a"));
+        Ok(())
+    }
+}
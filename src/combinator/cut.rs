@@ -0,0 +1,133 @@
+//! [`cut()`] commits to the parse: once reached, a failure is reported rather than trying alternatives.
+
+use std::fmt;
+
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+/// This combinator is returned by [`cut()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Cut<T> {
+    item: T,
+}
+
+impl<L: Lex> Lex for Cut<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        self.item.lex(input).map_err(|e| e.fatal())
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.item.canonical_into(output)
+    }
+}
+
+impl<P: Parse> Parse for Cut<P> {
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        self.item.parse(input).map_err(|e| e.fatal())
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+impl<P: Unparse> Unparse for Cut<P> {
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.item.unparse_into(value, output)
+    }
+}
+
+/// Marks any failure of the item (a parser or lexer) as [fatal](crate::Error::fatal()).
+///
+/// Combinators that try alternatives or stop repeating when something fails, such as [`or()`](super::or()), [`optional()`](super::optional())
+/// and [`many()`](super::many()), don't recover from fatal errors: they fail with the fatal error instead.
+///
+/// Place a cut after the point where the input can only be the thing being parsed,
+/// e.g. after an opening bracket. The syntax error inside is then reported, instead of the bracket being quietly left unparsed.
+///
+/// This is more conveniently created using the [`Lex::cut`] and [`Parse::cut`] methods,
+/// or [`Lex::expect`] and [`Parse::expect`] which also describe what was [expected](super::expected()).
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, int, Error, Lex, Parse};
+///
+/// let list = char('[').skip_then(int::<u8>().many(..).delimiter(char(',')).then_skip(char(']').expect("closing bracket")));
+/// let value = list.or(int().map(|n| vec![n]));
+///
+/// assert_eq!(value.parse("[1,2]")?, (vec![1, 2], ""));
+/// assert_eq!(value.parse("3")?, (vec![3], ""));
+///
+/// // without the cut, this would fail with just Error::NoMatch after trying `int()`
+/// let error = value.parse("[1,2").unwrap_err();
+/// assert!(error.is_fatal());
+/// assert_eq!(error.to_string(), "expected closing bracket");
+///
+/// // optional doesn't recover from the fatal error either
+/// assert!(value.optional().parse("[1,2").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn cut<T>(item: T) -> Cut<T> {
+    Cut { item }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Cut<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cut({:?})", self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, digit, error::overflow, int, token, Error};
+
+    #[test]
+    fn fatal_errors_are_not_recovered() {
+        let pair = || char('(').then(digit().cut()).then(char(')').cut());
+
+        assert_eq!(pair().lex("(1)"), Ok(("(1)", "")));
+        assert_eq!(
+            pair().or(token("(x")).lex("(x"),
+            Err(Error::NoMatch.fatal())
+        );
+        assert_eq!(pair().optional().lex("(1"), Err(Error::NoMatch.fatal()));
+        assert_eq!(pair().many(..).lex("(1)(2"), Err(Error::NoMatch.fatal()));
+        assert_eq!(
+            pair().many(..).delimiter(char(',')).lex("(1),(2"),
+            Err(Error::NoMatch.fatal())
+        );
+
+        // an item that fails before the cut is recovered from as usual
+        assert_eq!(pair().optional().lex("x"), Ok(("", "x")));
+        assert_eq!(pair().many(..).lex("(1)x"), Ok(("(1)", "x")));
+    }
+
+    #[test]
+    fn conversions_can_be_fatal() {
+        let number = char('#')
+            .skip_then(int::<u8>().cut())
+            .or(token("big").map(|_| u8::MAX));
+
+        assert_eq!(number.parse("#12"), Ok((12, "")));
+        assert_eq!(number.parse("big"), Ok((255, "")));
+        assert_eq!(number.parse("#256"), Err(overflow("256").fatal()));
+    }
+}
@@ -0,0 +1,213 @@
+//! [`.dbg("label")`](crate::Lex::dbg()) prints what a parser or lexer was given and what it did with it, to help debug a parser.
+
+use std::{cell::Cell, fmt};
+
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+thread_local! {
+    /// How many [`Dbg`] are currently running, used to indent their output.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The maximum number of chars of input to print.
+const MAX_INPUT_CHARS: usize = 40;
+
+/// Leaves one level of nesting when dropped, even if parsing panics.
+struct Leave;
+
+impl Drop for Leave {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+    }
+}
+
+/// A shortened, debug formatted version of the input.
+fn preview(input: &str) -> String {
+    match input.char_indices().nth(MAX_INPUT_CHARS) {
+        Some((n, _)) => format!("{:?}...", &input[..n]),
+        None => format!("{input:?}"),
+    }
+}
+
+fn line(depth: usize, label: &str, message: fmt::Arguments<'_>) -> String {
+    format!("{:indent$}[{label}] {message}", "", indent = depth * 2)
+}
+
+/// Prints what was given to the item and what it did, indented by the current depth.
+fn trace<'i, O>(
+    label: &str,
+    input: &'i str,
+    f: impl FnOnce() -> Result<(O, &'i str), crate::Error>,
+    describe: impl FnOnce(&O) -> String,
+) -> Result<(O, &'i str), crate::Error> {
+    let depth = DEPTH.with(|depth| depth.replace(depth.get() + 1));
+    let leave = Leave;
+
+    eprintln!(
+        "{}",
+        line(depth, label, format_args!("input: {}", preview(input)))
+    );
+
+    let result = f();
+    drop(leave);
+
+    let message = match &result {
+        Ok((output, remaining)) => line(
+            depth,
+            label,
+            format_args!(
+                "matched {}, remaining: {}",
+                describe(output),
+                preview(remaining)
+            ),
+        ),
+        Err(e) => line(depth, label, format_args!("failed: {e}")),
+    };
+    eprintln!("{message}");
+
+    result
+}
+
+/// This combinator is returned by [`Lex::dbg()`] and [`Parse::dbg()`]. See [`dbg()`] for more details.
+#[derive(Clone)]
+pub struct Dbg<T> {
+    label: &'static str,
+    item: T,
+}
+
+impl<L: Lex> Lex for Dbg<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        trace(
+            self.label,
+            input,
+            || self.item.lex(input),
+            |matched| preview(matched),
+        )
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.item.canonical_into(output)
+    }
+}
+
+impl<P> Parse for Dbg<P>
+where
+    P: Parse,
+    <P as Parse>::Output: fmt::Debug,
+{
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        trace(
+            self.label,
+            input,
+            || self.item.parse(input),
+            |output| format!("{output:?}"),
+        )
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+impl<P> Unparse for Dbg<P>
+where
+    P: Unparse,
+    <P as Parse>::Output: fmt::Debug,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.item.unparse_into(value, output)
+    }
+}
+
+/// Prints the input given to the item (a parser or lexer), whether it matched, what it matched or output, and the remaining input.
+///
+/// The output is printed to stderr, and is indented to show how the `dbg` calls are nested.
+/// Long inputs are shortened to their first few characters.
+///
+/// Every attempt is printed, so an item is printed again if it is retried: when every alternative of an [`or()`](super::or()) fails,
+/// they are tried again to find out what each of them expected.
+///
+/// This is more conveniently created using the [`Lex::dbg`] and [`Parse::dbg`] methods.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, digit, Lex};
+///
+/// let number = digit().many(1..).dbg("digits").then(char('.').dbg("dot")).dbg("number");
+///
+/// number.lex("12.5")?;
+/// // prints:
+/// // [number] input: "12.5"
+/// //   [digits] input: "12.5"
+/// //   [digits] matched "12", remaining: ".5"
+/// //   [dot] input: ".5"
+/// //   [dot] matched ".", remaining: "5"
+/// // [number] matched "12.", remaining: "5"
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn dbg<T>(label: &'static str, item: T) -> Dbg<T> {
+    Dbg { label, item }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Dbg<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Dbg({:?}, {:?})", self.label, self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{char, int, Lex};
+
+    #[test]
+    fn passes_through() {
+        test_lexer_batch(
+            "dbg doesn't change what is matched",
+            char('a').dbg("a").then(char('b').dbg("b")).dbg("ab"),
+            &[
+                ("abc", Some("ab"), "c"), //
+                ("ac", None, "ac"),
+            ],
+        );
+
+        test_parser_batch(
+            "dbg doesn't change the output",
+            int::<i32>().dbg("int"),
+            &[
+                ("-12x", Some(-12), "x"), //
+                ("x", None, "x"),
+            ],
+        );
+
+        assert_eq!(DEPTH.with(Cell::get), 0);
+    }
+
+    #[test]
+    fn formatting() {
+        assert_eq!(line(0, "a", format_args!("b")), "[a] b");
+        assert_eq!(line(2, "a", format_args!("b")), "    [a] b");
+
+        assert_eq!(preview("ab\n"), r#""ab\n""#);
+        assert_eq!(preview(&"x".repeat(50)), format!("{:?}...", "x".repeat(40)));
+    }
+}
@@ -0,0 +1,152 @@
+//! [`depth_limited()`] stops recursive parsers from nesting too deeply, so adversarial input can't overflow the stack.
+
+use std::{cell::Cell, fmt};
+
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+thread_local! {
+    /// How many [`DepthLimited`] are currently running.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Leaves one level of nesting when dropped, even if parsing panics.
+struct Leave;
+
+impl Drop for Leave {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+    }
+}
+
+/// Runs `f` one level deeper, unless that would be deeper than the limit.
+fn nested<T>(limit: usize, f: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+    let depth = DEPTH.with(|depth| depth.replace(depth.get() + 1)) + 1;
+    let _leave = Leave;
+
+    if depth > limit {
+        return Err(Error::DepthExceeded(limit).fatal());
+    }
+
+    f()
+}
+
+/// This combinator is returned by [`depth_limited()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct DepthLimited<T> {
+    limit: usize,
+    item: T,
+}
+
+impl<L: Lex> Lex for DepthLimited<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        nested(self.limit, || self.item.lex(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.item.canonical_into(output)
+    }
+}
+
+impl<P: Parse> Parse for DepthLimited<P> {
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        nested(self.limit, || self.item.parse(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+impl<P: Unparse> Unparse for DepthLimited<P> {
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.item.unparse_into(value, output)
+    }
+}
+
+/// Limits how deeply the item (a parser or lexer) can be nested inside itself.
+///
+/// Recursive parsers, such as the arrays of a JSON parser, call themselves once for each level of nesting in the input.
+/// Input like ten thousand `[` can overflow the stack, which aborts the whole program.
+/// Wrap the recursive call with `depth_limited()` to fail with [`Error::DepthExceeded`] instead.
+///
+/// The depth counts every `depth_limited()` that is running, so different recursive parsers nested inside each other share the limit.
+/// Trying the item counts too, even if the input turns out not to nest any deeper.
+///
+/// The error is [fatal](crate::Error::fatal()), so alternatives aren't tried at every level on the way back out.
+///
+/// This is more conveniently created using the [`Lex::depth_limited`] and [`Parse::depth_limited`] methods.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, Error, Lex, Parse, ParseResult};
+/// use parsely::combinator::depth_limited;
+///
+/// // counts how deeply nested some brackets are
+/// fn brackets(input: &str) -> ParseResult<'_, usize> {
+///     char('[')
+///         .skip_then(depth_limited(100, brackets).optional())
+///         .then_skip(char(']'))
+///         .map(|inner| inner.map_or(1, |depth| depth + 1))
+///         .parse(input)
+/// }
+///
+/// assert_eq!(brackets("[[[]]]")?, (3, ""));
+///
+/// let deep = "[".repeat(10_000) + &"]".repeat(10_000);
+/// assert_eq!(brackets(&deep).unwrap_err().root(), &Error::DepthExceeded(100));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn depth_limited<T>(limit: usize, item: T) -> DepthLimited<T> {
+    DepthLimited { limit, item }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DepthLimited<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DepthLimited({}, {:?})", self.limit, self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, digit};
+
+    fn nested_lexer(input: &str) -> LexResult<'_> {
+        char('(')
+            .then(depth_limited(3, nested_lexer).or(digit()))
+            .then(char(')'))
+            .lex(input)
+    }
+
+    #[test]
+    fn limits_nesting() {
+        assert_eq!(nested_lexer("((1))x"), Ok(("((1))", "x")));
+        assert_eq!(nested_lexer("(((1)))"), Ok(("(((1)))", "")));
+        assert_eq!(
+            nested_lexer("((((1))))"),
+            Err(Error::DepthExceeded(3).fatal())
+        );
+
+        // the depth is back to zero after failing
+        assert_eq!(DEPTH.with(Cell::get), 0);
+        assert_eq!(nested_lexer("((1))"), Ok(("((1))", "")));
+    }
+}
@@ -0,0 +1,294 @@
+//! Diagnostics are warnings about input that parsed successfully but deserves a second look, such as deprecated syntax or a trailing comma.
+//!
+//! Mark the input to warn about with [`.warn("message")`](crate::Lex::warn()), or call [`emit()`] from your own parsers, and run the whole parser with [`with_diagnostics()`].
+//! After a successful parse, the diagnostics are returned alongside the output, in the order they were emitted.
+//!
+//! Diagnostics don't affect parsing at all: outside of [`with_diagnostics()`] they are discarded.
+//! Diagnostics emitted by a branch that is later abandoned, e.g. the left side of an [`or()`](crate::combinator::or()) that went on to fail, are discarded too.
+//!
+//! # Examples
+//!
+//! Warning about deprecated syntax:
+//!
+//! ```
+//! use parsely::{int, token, Lex, Parse};
+//! use parsely::combinator::with_diagnostics;
+//!
+//! let not_equal = token("!=").or(token("<>").warn("`<>` is deprecated, use `!=` instead"));
+//! let comparison = int::<i32>().then_skip(not_equal.pad()).then(int::<i32>());
+//!
+//! let ((operands, diagnostics), _) = with_diagnostics(comparison).parse("1 <> 2")?;
+//!
+//! assert_eq!(operands, (1, 2));
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].message, "`<>` is deprecated, use `!=` instead");
+//! assert_eq!(diagnostics[0].range, 2..4);
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::{cell::RefCell, fmt, ops::Range};
+
+use super::active;
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+/// A warning emitted while running [`with_diagnostics()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// What is worth a second look.
+    pub message: String,
+
+    /// The byte range of the input the diagnostic is about, in the input given to [`with_diagnostics()`].
+    pub range: Range<usize>,
+}
+
+/// The diagnostics emitted within the innermost running [`WithDiagnostics`].
+struct Log {
+    /// The address of the input, used to find the offset of a diagnostic within it.
+    address: usize,
+    len: usize,
+
+    diagnostics: Vec<Diagnostic>,
+}
+
+thread_local! {
+    static LOG: RefCell<Option<Log>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous log when dropped, even if parsing panics.
+struct Restore(Option<Log>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        LOG.with(|log| *log.borrow_mut() = self.0.take());
+    }
+}
+
+/// Emits a diagnostic about `span`, which must be part of the input given to an enclosing [`with_diagnostics()`].
+///
+/// This is for your own parsers, see [`.warn()`](crate::Lex::warn()) to emit a diagnostic whenever a lexer or parser matches.
+///
+/// The diagnostic is discarded if there is no enclosing [`with_diagnostics()`], or `span` isn't part of its input.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{int, Parse, ParseResult};
+/// use parsely::combinator::{diagnostics::emit, with_diagnostics};
+///
+/// fn percent(input: &str) -> ParseResult<'_, u32> {
+///     let (value, remaining) = int::<u32>().parse(input)?;
+///
+///     if value > 100 {
+///         let matched = &input[..input.len() - remaining.len()];
+///         emit(matched, format!("{value}% is more than everything"));
+///     }
+///
+///     Ok((value, remaining))
+/// }
+///
+/// let ((value, diagnostics), _) = with_diagnostics(percent).parse("150")?;
+///
+/// assert_eq!(value, 150);
+/// assert_eq!(diagnostics[0].message, "150% is more than everything");
+/// assert_eq!(diagnostics[0].range, 0..3);
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn emit(span: &str, message: impl Into<String>) {
+    LOG.with(|log| {
+        let mut log = log.borrow_mut();
+        let Some(log) = log.as_mut() else {
+            return;
+        };
+
+        let address = span.as_ptr() as usize;
+        if address < log.address || address + span.len() > log.address + log.len {
+            return;
+        }
+
+        let start = address - log.address;
+        log.diagnostics.push(Diagnostic {
+            message: message.into(),
+            range: start..start + span.len(),
+        });
+    });
+}
+
+/// The number of diagnostics emitted so far, to go back to with [`rewind()`] if the attempt is abandoned.
+pub(crate) fn checkpoint() -> Option<usize> {
+    LOG.with(|log| log.borrow().as_ref().map(|log| log.diagnostics.len()))
+}
+
+/// Discards the diagnostics emitted since the [`checkpoint()`].
+pub(crate) fn rewind(checkpoint: Option<usize>) {
+    if let Some(checkpoint) = checkpoint {
+        LOG.with(|log| {
+            if let Some(log) = log.borrow_mut().as_mut() {
+                log.diagnostics.truncate(checkpoint);
+            }
+        });
+    }
+}
+
+/// This combinator is returned by [`warn()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Warn<T> {
+    message: &'static str,
+    item: T,
+}
+
+impl<L: Lex> Lex for Warn<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let (matched, remaining) = self.item.lex(input)?;
+        emit(matched, self.message);
+        Ok((matched, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.item.canonical_into(output)
+    }
+}
+
+impl<P: Parse> Parse for Warn<P> {
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (output, remaining) = self.item.parse(input)?;
+        emit(&input[..input.len() - remaining.len()], self.message);
+        Ok((output, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+impl<P: Unparse> Unparse for Warn<P> {
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.item.unparse_into(value, output)
+    }
+}
+
+/// Emits a diagnostic with the message about whatever the item (a parser or lexer) matches, to be collected by an enclosing [`with_diagnostics()`].
+///
+/// The item matches or fails exactly as it would without the warning.
+///
+/// This is more conveniently created using the [`Lex::warn`] and [`Parse::warn`] methods.
+pub fn warn<T>(message: &'static str, item: T) -> Warn<T> {
+    Warn { message, item }
+}
+
+/// This combinator is returned by [`with_diagnostics()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct WithDiagnostics<T> {
+    item: T,
+}
+
+impl<P: Parse> Parse for WithDiagnostics<P> {
+    type Output = (<P as Parse>::Output, Vec<Diagnostic>);
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let log = Log {
+            address: input.as_ptr() as usize,
+            len: input.len(),
+            diagnostics: Vec::new(),
+        };
+        let _active = active::Active::start();
+        let restore = Restore(LOG.with(|current| current.borrow_mut().replace(log)));
+
+        let result = self.item.parse(input);
+        let log = LOG.with(|current| current.borrow_mut().take());
+        drop(restore);
+
+        let (output, remaining) = result?;
+        let diagnostics = log.map(|log| log.diagnostics).unwrap_or_default();
+
+        Ok(((output, diagnostics), remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+/// Runs the item, and outputs its output along with the [`Diagnostic`]s emitted inside it, in the order they were emitted.
+///
+/// Diagnostics emitted inside a nested `with_diagnostics()` belong to that one, and are not seen by this one.
+///
+/// See [the module documentation](self) for an example.
+pub fn with_diagnostics<T>(item: T) -> WithDiagnostics<T> {
+    WithDiagnostics { item }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Warn<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Warn({:?}, {:?})", self.message, self.item)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for WithDiagnostics<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WithDiagnostics({:?})", self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alpha, char, int, token, Lex, Parse};
+
+    #[test]
+    fn emitting() -> Result<(), Error> {
+        let escape = char('\\').then(token("v").warn("deprecated escape").or(char('n')));
+        let text = with_diagnostics(alpha().or(escape).many(..).map(str::len));
+
+        let ((len, diagnostics), _) = text.parse(r"a\nb\vc\v")?;
+        assert_eq!(len, 9);
+
+        let ranges: Vec<_> = diagnostics.iter().map(|d| d.range.clone()).collect();
+        assert_eq!(ranges, vec![5..6, 8..9]);
+
+        // without with_diagnostics() they are discarded
+        assert_eq!(token("a").warn("a").lex("ab")?, ("a", "b"));
+        assert!(LOG.with(|log| log.borrow().is_none()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn abandoned_branches_are_discarded() -> Result<(), Error> {
+        let parser = with_diagnostics(
+            int::<u8>()
+                .warn("number")
+                .then_skip(char('!'))
+                .or(int::<u8>().then_skip(char('?'))),
+        );
+
+        let ((output, diagnostics), _) = parser.parse("1?")?;
+        assert_eq!(output, 1);
+        assert!(diagnostics.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn failed_parses_have_no_diagnostics() {
+        let parser = with_diagnostics(int::<u8>().warn("number").then_skip(char('!')));
+        assert_eq!(parser.parse("1?"), Err(Error::NoMatch));
+        assert!(LOG.with(|log| log.borrow().is_none()));
+    }
+}
@@ -0,0 +1,116 @@
+//! [`.expected("description")`](crate::Lex::expected()) describes what was expected when a parser or lexer doesn't match.
+
+use std::fmt;
+
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+/// This combinator is returned by [`expected()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Expected<T> {
+    expected: &'static str,
+    item: T,
+}
+
+impl<T> Expected<T> {
+    fn describe_error(&self, error: Error) -> Error {
+        if matches!(error.root(), Error::NoMatch | Error::ExpectedOneOf(_)) && !error.is_fatal() {
+            Error::Expected(self.expected)
+        } else {
+            error
+        }
+    }
+}
+
+impl<L: Lex> Lex for Expected<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        self.item.lex(input).map_err(|e| self.describe_error(e))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.item.canonical_into(output)
+    }
+}
+
+impl<P: Parse> Parse for Expected<P> {
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        self.item.parse(input).map_err(|e| self.describe_error(e))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+impl<P: Unparse> Unparse for Expected<P> {
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.item.unparse_into(value, output)
+    }
+}
+
+/// Replaces a [`NoMatch`](Error::NoMatch) error from the item (a parser or lexer) with [`Error::Expected`], describing what was expected.
+///
+/// The list of alternatives in an [`ExpectedOneOf`](Error::ExpectedOneOf) error is replaced too, so a choice can be described as a whole.
+///
+/// Other errors, such as [`FailedConversion`](Error::FailedConversion), are more specific and are kept as they are.
+/// [Fatal](Error::fatal()) errors from deeper inside the item are kept too.
+///
+/// This is more conveniently created using the [`Lex::expected`] and [`Parse::expected`] methods.
+/// To also stop alternatives from being tried, see [`Lex::expect`] and [`Parse::expect`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, Error, Lex};
+///
+/// let close = char('}').expected("closing '}'");
+///
+/// assert_eq!(close.lex("x"), Err(Error::Expected("closing '}'")));
+/// assert_eq!(close.lex("x").unwrap_err().to_string(), "expected closing '}'");
+/// ```
+pub fn expected<T>(expected: &'static str, item: T) -> Expected<T> {
+    Expected { expected, item }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Expected<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Expected({:?}, {:?})", self.item, self.expected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, error::overflow, int, token};
+
+    #[test]
+    fn errors() {
+        let number = int::<u8>().expected("a number");
+
+        assert_eq!(number.parse("1"), Ok((1, "")));
+        assert_eq!(number.parse("x"), Err(Error::Expected("a number")));
+        assert_eq!(number.parse("256"), Err(overflow("256")));
+
+        let block = token("{")
+            .then(char('}').expect("closing '}'"))
+            .expected("a block");
+        assert_eq!(block.lex("{x"), Err(Error::Expected("closing '}'").fatal()));
+        assert_eq!(block.lex("x"), Err(Error::Expected("a block")));
+    }
+}
@@ -0,0 +1,101 @@
+//! Reports what combinators are doing to [tracing](https://docs.rs/tracing) subscribers, when the `tracing` feature is enabled.
+//!
+//! Each instrumented combinator runs inside a `parsely` span at the TRACE level, with a `combinator` field naming it.
+//! Inside the span, a `matched` or `failed` event is emitted when the combinator finishes.
+//! When every alternative of an [`or()`](crate::combinator::or()) fails they are tried again to find out what each expected, so they are reported again.
+//!
+//! Instrumented combinators are also recorded by [`trace()`](crate::combinator::trace()), with or without the feature.
+
+use crate::{combinator::trace::record, Error};
+
+/// Runs a combinator inside a tracing span, and emits an event saying whether it matched.
+#[cfg(feature = "tracing")]
+pub(crate) fn instrument<'i, O>(
+    combinator: &'static str,
+    input: &'i str,
+    f: impl FnOnce() -> Result<(O, &'i str), Error>,
+) -> Result<(O, &'i str), Error> {
+    let span = ::tracing::trace_span!("parsely", combinator, input_len = input.len());
+    let _enter = span.enter();
+
+    let result = record(combinator, input, f);
+
+    match &result {
+        Ok((_, remaining)) => {
+            ::tracing::trace!(consumed = input.len() - remaining.len(), "matched")
+        }
+        Err(e) => ::tracing::trace!(reason = %e, "failed"),
+    }
+
+    result
+}
+
+/// Without the `tracing` feature, this just runs the combinator, recording it if [`trace()`](crate::combinator::trace()) is running.
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn instrument<'i, O>(
+    combinator: &'static str,
+    input: &'i str,
+    f: impl FnOnce() -> Result<(O, &'i str), Error>,
+) -> Result<(O, &'i str), Error> {
+    record(combinator, input, f)
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use tracing::{span, subscriber::with_default, Event, Metadata, Subscriber};
+
+    use crate::{char, token, Lex};
+
+    #[derive(Default)]
+    struct Counts {
+        spans: AtomicUsize,
+        events: AtomicUsize,
+    }
+
+    /// Counts the spans and events it sees.
+    struct Counter(Arc<Counts>);
+
+    impl Subscriber for Counter {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            let n = self.0.spans.fetch_add(1, Ordering::SeqCst);
+            span::Id::from_u64(n as u64 + 1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, _: &Event<'_>) {
+            self.0.events.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    #[test]
+    fn spans_and_events() {
+        let counts = Arc::new(Counts::default());
+
+        with_default(Counter(counts.clone()), || {
+            let lexer = token("a").then(char('b')).or(char('c'));
+            assert_eq!(lexer.lex("c"), Ok(("c", "")));
+        });
+
+        // or, then
+        assert_eq!(counts.spans.load(Ordering::SeqCst), 2);
+        // then failed, or matched
+        assert_eq!(counts.events.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,242 @@
+//! [`left_recursive()`] lets a parser refer to itself at the start of its own grammar.
+
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    fmt,
+};
+
+use crate::{grammar::Grammar, Error, Lex, LexResult, Parse, ParseResult};
+
+/// Identifies a left recursive parser running at a particular input.
+///
+/// The input is identified by its address and length, so the same text at a different position is a different input.
+type Key = (TypeId, usize, usize);
+
+/// The longest successful match so far, the output and how much input it left remaining.
+type Seed = Option<(Box<dyn Any>, usize)>;
+
+thread_local! {
+    static SEEDS: RefCell<HashMap<Key, Seed>> = RefCell::new(HashMap::new());
+}
+
+/// Removes the seed when dropped, even if parsing panics.
+struct Forget(Key);
+
+impl Drop for Forget {
+    fn drop(&mut self) {
+        SEEDS.with(|seeds| seeds.borrow_mut().remove(&self.0));
+    }
+}
+
+/// Used in place of the output when lexing, so that lexing and parsing with the same item don't share seeds.
+struct Lexing;
+
+/// Grows a seed: runs `attempt` repeatedly, each time letting recursive calls at the same input reuse the previous match, until the match stops getting longer.
+fn grow<'i, O: Clone + 'static>(
+    key: Key,
+    input: &'i str,
+    attempt: impl Fn(&'i str) -> Result<(O, &'i str), Error>,
+) -> Result<(O, &'i str), Error> {
+    let seed = SEEDS.with(|seeds| {
+        seeds.borrow().get(&key).map(|seed| {
+            seed.as_ref().map(|(output, len)| {
+                let output = output
+                    .downcast_ref::<O>()
+                    .expect("seed has the output type");
+                (output.clone(), *len)
+            })
+        })
+    });
+
+    // this is a recursive call, it uses the seed rather than recursing forever
+    if let Some(seed) = seed {
+        return match seed {
+            Some((output, len)) => Ok((output, &input[input.len() - len..])),
+            None => Err(Error::NoMatch),
+        };
+    }
+
+    SEEDS.with(|seeds| seeds.borrow_mut().insert(key, None));
+    let _forget = Forget(key);
+
+    let mut best = attempt(input)?;
+
+    loop {
+        SEEDS.with(|seeds| {
+            let seed: Box<dyn Any> = Box::new(best.0.clone());
+            seeds.borrow_mut().insert(key, Some((seed, best.1.len())));
+        });
+
+        match attempt(input) {
+            Ok(matched) if matched.1.len() < best.1.len() => best = matched,
+            Err(e) if e.is_fatal() => return Err(e),
+            _ => break,
+        }
+    }
+
+    Ok(best)
+}
+
+/// This combinator is returned by [`left_recursive()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct LeftRecursive<T> {
+    item: T,
+}
+
+impl<P> Parse for LeftRecursive<P>
+where
+    P: Parse + 'static,
+    <P as Parse>::Output: Clone + 'static,
+{
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let key = (
+            TypeId::of::<(P, P::Output)>(),
+            input.as_ptr() as usize,
+            input.len(),
+        );
+
+        grow(key, input, |input| self.item.parse(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+impl<L> Lex for LeftRecursive<L>
+where
+    L: Lex + 'static,
+{
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let key = (
+            TypeId::of::<(L, Lexing)>(),
+            input.as_ptr() as usize,
+            input.len(),
+        );
+
+        let ((), remaining) = grow(key, input, |input| {
+            self.item.lex(input).map(|(_, remaining)| ((), remaining))
+        })?;
+
+        let offset = input.len() - remaining.len();
+        Ok((&input[..offset], remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+/// Allows the item (a parser or lexer) to be directly left recursive, that is, to begin by calling itself at the same input.
+///
+/// A grammar like `expr = expr '-' term | term` would normally recurse forever.
+/// Wrapping the recursive function's body with this combinator makes it terminate:
+/// the recursive call first fails, then each following attempt reuses the previous match as a "seed" and grows it,
+/// until the match can't get any longer. This is the seed growing algorithm described by Warth et al.
+///
+/// The item is identified by its type, so it must be `'static` (a `fn` item is ideal) and the output must be `Clone + 'static`.
+///
+/// Only direct left recursion is supported, indirect left recursion (`a = b 'x'; b = a 'y' | 'z'`) still needs rewriting by hand.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, int, Lex, Parse, ParseResult};
+/// use parsely::combinator::left_recursive;
+///
+/// // expr = expr '-' int | int
+/// fn expr(input: &str) -> ParseResult<'_, i64> {
+///     left_recursive(expr_body).parse(input)
+/// }
+///
+/// fn expr_body(input: &str) -> ParseResult<'_, i64> {
+///     expr.then_skip(char('-'))
+///         .then(int::<i64>())
+///         .map(|(a, b)| a - b)
+///         .or(int::<i64>())
+///         .parse(input)
+/// }
+///
+/// // subtraction is left associative: (10 - 3) - 2
+/// assert_eq!(expr("10-3-2")?, (5, ""));
+/// assert_eq!(expr("10-")?, (10, "-"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn left_recursive<T>(item: T) -> LeftRecursive<T> {
+    LeftRecursive { item }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LeftRecursive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LeftRecursive({:?})", self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{alpha, char, digit, LexResult};
+
+    fn list(input: &str) -> ParseResult<'_, String> {
+        left_recursive(list_body).parse(input)
+    }
+
+    // list = list ',' alpha | alpha
+    fn list_body(input: &str) -> ParseResult<'_, String> {
+        list.then_skip(char(','))
+            .then(alpha().map(str::to_string))
+            .map(|(list, item)| format!("({list},{item})"))
+            .or(alpha().map(str::to_string))
+            .parse(input)
+    }
+
+    fn number(input: &str) -> LexResult<'_> {
+        left_recursive(number_body).lex(input)
+    }
+
+    // number = number digit | digit
+    fn number_body(input: &str) -> LexResult<'_> {
+        number.then(digit()).or(digit()).lex(input)
+    }
+
+    #[test]
+    fn parse() {
+        test_parser_batch(
+            "left recursion folds to the left",
+            list,
+            &[
+                ("a", Some("a".to_string()), ""), //
+                ("a,b", Some("(a,b)".to_string()), ""),
+                ("a,b,c", Some("((a,b),c)".to_string()), ""),
+                ("a,b,", Some("(a,b)".to_string()), ","),
+                (",a", None, ",a"),
+            ],
+        );
+    }
+
+    #[test]
+    fn lex() {
+        test_lexer_batch(
+            "left recursive lexer",
+            number,
+            &[
+                ("1", Some("1"), ""), //
+                ("123abc", Some("123"), "abc"),
+                ("abc", None, "abc"),
+            ],
+        );
+    }
+
+    #[test]
+    fn seeds_are_forgotten() {
+        assert_eq!(list("a,b"), Ok(("(a,b)".to_string(), "")));
+        assert!(SEEDS.with(|seeds| seeds.borrow().is_empty()));
+    }
+}
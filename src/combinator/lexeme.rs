@@ -0,0 +1,108 @@
+//! [`lexeme()`] matches an item and then skips any whitespace that follows it.
+//!
+//! This is the conventional way to ignore whitespace in between the items of a [`then()`](crate::combinator::then()) chain:
+//! wrap every item with `lexeme()`, so that each one cleans up the whitespace it leaves behind.
+//!
+//! Compared to [`pad()`](crate::Parse::pad()) which skips whitespace on both sides of an item,
+//! the whitespace in between two lexemes is only ever skipped once, and the chain behaves the same whether it is used to lex or to parse.
+
+use crate::{combinator::sequence::LexMany, ws, Lex, WhiteSpace};
+
+use super::{then_skip, ThenSkip};
+
+/// This combinator is returned by [`lexeme()`]. See it's documentation for more details.
+pub type Lexeme<T> = ThenSkip<LexMany<WhiteSpace>, T>;
+
+/// Creates a lexer/parser that runs the item and then skips zero or more whitespace characters.
+///
+/// This combinator can be chained using [`Lex::lexeme()`](crate::Lex::lexeme()) or [`Parse::lexeme()`](crate::Parse::lexeme()).
+///
+/// Leading whitespace is not skipped, if the input might start with whitespace then skip it once at the start of the chain,
+/// e.g. with `ws().many(..).skip_then(...)`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, int, Lex, Parse};
+/// use parsely::combinator::lexeme;
+///
+/// let sum = lexeme(int::<u32>())
+///     .then_skip(lexeme(char('+')))
+///     .then(lexeme(int::<u32>()));
+///
+/// assert_eq!(sum.parse("1 + 2  ")?, ((1, 2), ""));
+/// assert_eq!(sum.parse("1+2")?, ((1, 2), ""));
+///
+/// // lexing consumes exactly the same input as parsing
+/// let lexer = lexeme(char('1')).then(lexeme(char('+'))).then(lexeme(char('2')));
+///
+/// assert_eq!(lexer.lex("1 + 2  ;")?, ("1 + 2  ", ";"));
+/// assert_eq!(sum.parse("1 + 2  ;")?, ((1, 2), ";"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn lexeme<T>(item: T) -> Lexeme<T> {
+    then_skip(ws().many(..), item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{char, int, token, Parse};
+
+    #[test]
+    fn lexing() {
+        test_lexer_batch(
+            "lexemes in a then chain",
+            lexeme(token("let"))
+                .then(lexeme(token("x")))
+                .then(lexeme(char('='))),
+            &[
+                ("let x =", Some("let x ="), ""), //
+                ("let  x\t=\n1", Some("let  x\t=\n"), "1"),
+                ("letx=1", Some("letx="), "1"),
+                (" let x =", None, " let x ="),
+                ("let y =", None, "let y ="),
+            ],
+        );
+    }
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "lexemes in a then chain",
+            lexeme(int::<u8>())
+                .then_skip(lexeme(char(',')))
+                .then(lexeme(int::<u8>())),
+            &[
+                ("1, 2", Some((1, 2)), ""), //
+                ("1 ,2 \n", Some((1, 2)), ""),
+                ("1,2;", Some((1, 2)), ";"),
+                ("1;2", None, "1;2"),
+            ],
+        );
+    }
+
+    #[test]
+    fn lex_and_parse_agree() -> Result<(), crate::Error> {
+        let input = "a  b\tc\nd";
+
+        let lexer = lexeme(char('a'))
+            .then(lexeme(char('b')))
+            .then(lexeme(char('c')));
+        let parser = lexeme(char('a').map(|_| 'a'))
+            .then(lexeme(char('b').map(|_| 'b')))
+            .then(lexeme(char('c').map(|_| 'c')));
+
+        let (_, lex_remaining) = lexer.lex(input)?;
+        let (output, parse_remaining) = parser.parse(input)?;
+
+        assert_eq!(output, (('a', 'b'), 'c'));
+        assert_eq!(lex_remaining, "d");
+        assert_eq!(parse_remaining, "d");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use std::fmt;
+
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+/// This combinator is returned by [`limit()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Limit<T> {
+    max_chars: usize,
+    item: T,
+}
+
+impl<T> Limit<T> {
+    /// Runs `f` on at most the first `max_chars` chars of the input, and maps its remaining input back onto the whole input.
+    fn run<'i, O>(
+        &self,
+        input: &'i str,
+        f: impl FnOnce(&'i str) -> Result<(O, &'i str), Error>,
+    ) -> Result<(O, &'i str), Error> {
+        let end = input
+            .char_indices()
+            .nth(self.max_chars)
+            .map_or(input.len(), |(i, _)| i);
+        let prefix = &input[..end];
+
+        let (output, remaining) = f(prefix)?;
+        let consumed = prefix.len() - remaining.len();
+
+        Ok((output, &input[consumed..]))
+    }
+
+    /// Checks that what was written to `output` since `start` is short enough to be matched again.
+    fn check(&self, output: &str, start: usize) -> Result<(), Error> {
+        let written = output[start..].chars().count();
+
+        if written > self.max_chars {
+            return Err(Error::custom(format!(
+                "{written} chars can't be unparsed within a limit of {}",
+                self.max_chars
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl<L: Lex> Lex for Limit<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        self.run(input, |prefix| self.item.lex(prefix))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        let start = output.len();
+        self.item.canonical_into(output)?;
+        self.check(output, start)
+    }
+}
+
+impl<P: Parse> Parse for Limit<P> {
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        self.run(input, |prefix| self.item.parse(prefix))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+impl<P: Unparse> Unparse for Limit<P> {
+    /// Unparses the value with the item, failing if it writes more than the limit.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let start = output.len();
+        self.item.unparse_into(value, output)?;
+        self.check(output, start)
+    }
+}
+
+/// Runs the item (a parser or lexer) on only the first `max_chars` chars of the input.
+///
+/// The item can't see any further, so if it would need more input than that it fails, or stops early as it would at the end of the input.
+/// Unlike [`scoped()`](super::scoped()), the item doesn't have to match all of them: the remaining input starts wherever it stopped.
+///
+/// This is useful for fixed width fields and length prefixed data, where a field's width is known before parsing it.
+///
+/// This is more conveniently created using the [`Lex::limit`] and [`Parse::limit`] methods.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{digit, uint, Lex, Parse};
+///
+/// // a date written as YYYYMMDD, without separators
+/// let date = uint::<u16>().limit(4).then(uint::<u8>().limit(2)).then(uint::<u8>().limit(2));
+///
+/// assert_eq!(date.parse("20241231!")?, (((2024, 12), 31), "!"));
+///
+/// // digits past the limit are left for what comes next
+/// assert_eq!(digit().many(..).limit(3).lex("12345")?, ("123", "45"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn limit<T>(max_chars: usize, item: T) -> Limit<T> {
+    Limit { max_chars, item }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Limit<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Limit({}, {:?})", self.max_chars, self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{alpha, any, digit, token};
+
+    #[test]
+    fn lexing() {
+        test_lexer_batch(
+            "at most 3 chars",
+            limit(3, alpha().many(1..)),
+            &[
+                ("ab1", Some("ab"), "1"),
+                ("abcde", Some("abc"), "de"),
+                ("éüö!", Some("éüö"), "!"),
+                ("", None, ""),
+            ],
+        );
+
+        test_lexer_batch(
+            "tokens longer than the limit",
+            limit(2, token("abc")),
+            &[("abc", None, "")],
+        );
+
+        assert_eq!(limit(0, any().many(..)).lex("abc"), Ok(("", "abc")));
+    }
+
+    #[test]
+    fn unparsing() -> Result<(), Error> {
+        let number = limit(3, digit().many(1..).parse_str::<u32>());
+
+        assert_eq!(number.parse("1234")?, (123, "4"));
+        assert_eq!(number.unparse(&999)?, "999");
+        assert!(number.unparse(&1000).is_err());
+
+        Ok(())
+    }
+}
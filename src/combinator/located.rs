@@ -0,0 +1,290 @@
+//! [`located()`] lets [`position()`](crate::position()) report locations relative to the whole input.
+
+use std::{
+    cell::{OnceCell, RefCell},
+    fmt,
+};
+
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    Lex, LexResult, Location, Parse, ParseResult,
+};
+
+/// The input given to the innermost running [`Located`].
+struct Source {
+    /// The input, borrowed for as long as the [`Located`] runs, see [`Source::text()`].
+    ///
+    /// This must only be dereferenced while the `located_with()` call that made it is running.
+    /// That holds because a source is only ever stored in `SOURCE` by `located_with()`, which puts back the previous source through `Restore` when it returns or unwinds,
+    /// so a nested `located_with()` can't leave its source behind for an outer one, and no source is left once the outermost one is done.
+    text: *const str,
+
+    /// The byte offsets where each line starts, found the first time a location is asked for.
+    line_starts: OnceCell<Vec<usize>>,
+}
+
+impl Source {
+    fn text(&self) -> &str {
+        // SAFETY: see `Source::text`, the source is only reachable while the input it was made from is borrowed.
+        unsafe { &*self.text }
+    }
+
+    /// Whether the remaining input is what remains of this source's input.
+    fn contains(&self, remaining: &str) -> bool {
+        let text = self.text();
+        let address = remaining.as_ptr() as usize;
+        let start = text.as_ptr() as usize;
+
+        address >= start && address + remaining.len() == start + text.len()
+    }
+
+    fn line_starts(&self) -> &[usize] {
+        self.line_starts.get_or_init(|| {
+            std::iter::once(0)
+                .chain(self.text().match_indices('\n').map(|(i, _)| i + 1))
+                .collect()
+        })
+    }
+}
+
+thread_local! {
+    static SOURCE: RefCell<Option<Source>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous source when dropped, even if parsing panics.
+struct Restore(Option<Source>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        SOURCE.with(|source| *source.borrow_mut() = self.0.take());
+    }
+}
+
+fn located_with<T>(input: &str, f: impl FnOnce() -> T) -> T {
+    // locations stay relative to the outermost input
+    if SOURCE.with(|source| {
+        source
+            .borrow()
+            .as_ref()
+            .is_some_and(|source| source.contains(input))
+    }) {
+        return f();
+    }
+
+    let source = Source {
+        text: input,
+        line_starts: OnceCell::new(),
+    };
+
+    let _restore = Restore(SOURCE.with(|current| current.borrow_mut().replace(source)));
+    f()
+}
+
+/// Finds the location of the remaining input within the input given to the innermost running [`located()`].
+///
+/// Outside of [`located()`], or if the remaining input isn't part of that input, the location is the start of the remaining input.
+pub(crate) fn location_of(remaining: &str) -> Location {
+    SOURCE.with(|source| {
+        let source = source.borrow();
+        let Some(source) = source.as_ref() else {
+            return Location::START;
+        };
+
+        if !source.contains(remaining) {
+            return Location::START;
+        }
+
+        let text = source.text();
+        let offset = remaining.as_ptr() as usize - text.as_ptr() as usize;
+        let line_starts = source.line_starts();
+        let line = line_starts.partition_point(|&start| start <= offset);
+        let line_start = line_starts[line - 1];
+
+        Location {
+            offset,
+            line,
+            column: 1 + text[line_start..offset].chars().count(),
+        }
+    })
+}
+
+/// This combinator is returned by [`located()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Located<T> {
+    item: T,
+}
+
+impl<L: Lex> Lex for Located<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        located_with(input, || self.item.lex(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+}
+
+impl<P: Parse> Parse for Located<P> {
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        located_with(input, || self.item.parse(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+/// Runs the item (a parser or lexer) so that any [`position()`](crate::position()) inside it reports its location relative to the input given to this combinator.
+///
+/// Wrap the outermost parser with this, so that the locations are relative to the whole input.
+/// Nesting `located()` inside another has no effect, locations remain relative to the outermost input.
+///
+/// The input isn't copied. It is scanned for line breaks once, the first time a location is asked for.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{alpha, char, position, ws, Lex, Location, Parse};
+/// use parsely::combinator::located;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Word {
+///     text: String,
+///     location: Location,
+/// }
+///
+/// let word = position()
+///     .then(alpha().many(1..).map(str::to_string))
+///     .map(|(location, text)| Word { text, location });
+///
+/// let words = located(word.many(1..).delimiter(ws().many(1..)));
+///
+/// let (output, _) = words.parse("hello\nwide  world")?;
+///
+/// assert_eq!(output[1].text, "wide");
+/// assert_eq!(output[1].location, Location { offset: 6, line: 2, column: 1 });
+///
+/// assert_eq!(output[2].text, "world");
+/// assert_eq!(output[2].location.to_string(), "2:7");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn located<T>(item: T) -> Located<T> {
+    Located { item }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Located<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Located({:?})", self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, position, token};
+
+    #[test]
+    fn positions() -> Result<(), crate::Error> {
+        let parser = located(
+            token("ab\n")
+                .skip_then(position())
+                .then_skip(token("âb\n\n"))
+                .then(position()),
+        );
+
+        let (output, _) = parser.parse("ab\nâb\n\ncd")?;
+
+        assert_eq!(
+            output,
+            (
+                Location {
+                    offset: 3,
+                    line: 2,
+                    column: 1
+                },
+                Location {
+                    offset: 8,
+                    line: 4,
+                    column: 1
+                }
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn outside_of_located() -> Result<(), crate::Error> {
+        let (output, _) = char('a').skip_then(position()).parse("abc")?;
+        assert_eq!(output, Location::START);
+
+        // a separate input doesn't use the location of the located input
+        let inner = || position().parse("xyz").map(|(location, _)| location);
+        let (output, _) = located(char('a').map(|_| inner())).parse("abc")?;
+        assert_eq!(output, Ok(Location::START));
+
+        Ok(())
+    }
+
+    #[test]
+    fn nested() -> Result<(), crate::Error> {
+        let parser = located(char('a').skip_then(located(char('b').skip_then(position()))));
+
+        let (output, _) = parser.parse("abc")?;
+        assert_eq!(
+            output,
+            Location {
+                offset: 2,
+                line: 1,
+                column: 3
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn nested_separate_input() -> Result<(), crate::Error> {
+        // the inner located() has its own input, and the outer one is restored after it
+        let inner = || {
+            located(position())
+                .parse("xyz")
+                .map(|(location, _)| location)
+        };
+        let parser = located(char('a').map(|_| inner()).then(position()));
+
+        let ((inner, outer), _) = parser.parse("ab")?;
+        assert_eq!(inner, Ok(Location::START));
+        assert_eq!(outer.offset, 1);
+        assert!(SOURCE.with(|source| source.borrow().is_none()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn panicking() {
+        let input = String::from("a\nb");
+        let parser = located(
+            char('a')
+                .skip_then(position())
+                .map(|_| -> Location { panic!() }),
+        );
+
+        let result = std::panic::catch_unwind(|| parser.parse(&input));
+        assert!(result.is_err());
+
+        // the input is gone, and so is its source
+        drop(input);
+        assert!(SOURCE.with(|source| source.borrow().is_none()));
+        assert_eq!(location_of("b"), Location::START);
+    }
+}
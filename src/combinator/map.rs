@@ -1,6 +1,11 @@
-use std::{any::type_name, fmt};
+use std::{any::type_name, fmt, marker::PhantomData, str::FromStr};
 
-use crate::{Lex, Parse};
+use crate::{
+    error::failed_conversion,
+    grammar::Grammar,
+    unparse::{matched, Unparse},
+    Error, Lex, Parse,
+};
 
 /// This combinator is returned by [`map()`]. See it's documentation for more details.
 #[derive(Clone)]
@@ -26,11 +31,50 @@ where
     type Output = O;
 
     fn parse<'i>(&self, input: &'i str) -> crate::ParseResult<'i, Self::Output> {
-        let (matched, remaining) = self.lexer.lex(input).map_err(|_| crate::Error::NoMatch)?;
+        let (matched, remaining) = self.lexer.lex(input)?;
         let output = (self.f)(matched);
 
         Ok((output, remaining))
     }
+
+    fn describe(&self) -> Grammar {
+        self.lexer.describe()
+    }
+}
+
+/// This combinator is returned by [`map_with_input()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct MapWithInput<L, F> {
+    lexer: L,
+    f: F,
+}
+
+/// This combinator is used to build a custom parser from a lexer by mapping the matched &str, and the remaining input after it, to an output type.
+///
+/// See [`Lex::map_with_input()`] for more details and examples.
+pub fn map_with_input<L, F, O>(lexer: L, f: F) -> MapWithInput<L, F>
+where
+    F: Fn(&str, &str) -> O,
+{
+    MapWithInput { lexer, f }
+}
+
+impl<L: Lex, F, O> Parse for MapWithInput<L, F>
+where
+    F: Fn(&str, &str) -> O,
+{
+    type Output = O;
+
+    fn parse<'i>(&self, input: &'i str) -> crate::ParseResult<'i, Self::Output> {
+        let (matched, remaining) = self.lexer.lex(input)?;
+        let output = (self.f)(matched, remaining);
+
+        Ok((output, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.lexer.describe()
+    }
 }
 
 /// This combinator is returned by [`try_map()`]. See it's documentation for more details.
@@ -42,12 +86,13 @@ pub struct TryMap<L, F> {
 
 /// This combinator is used to build a custom parser from a lexer by mapping the matched &str to an output type.
 ///
-/// The mapping function is fallible.
+/// The mapping function is fallible. Its error's message is kept in the [`Error::FailedConversion`] the parser fails with.
 ///
 /// See [`Lex::try_map()`] for more details and examples.
 pub fn try_map<L, F, O, E>(lexer: L, f: F) -> TryMap<L, F>
 where
     F: Fn(&str) -> Result<O, E>,
+    E: fmt::Display,
 {
     TryMap { lexer, f }
 }
@@ -55,15 +100,183 @@ where
 impl<L: Lex, F, O, E> Parse for TryMap<L, F>
 where
     F: Fn(&str) -> Result<O, E>,
+    E: fmt::Display,
 {
     type Output = O;
 
     fn parse<'i>(&self, input: &'i str) -> crate::ParseResult<'i, Self::Output> {
-        let (matched, remaining) = self.lexer.lex(input).map_err(|_| crate::Error::NoMatch)?;
-        let output = (self.f)(matched).map_err(|_| crate::Error::FailedConversion)?;
+        let (matched, remaining) = self.lexer.lex(input)?;
+        let output = (self.f)(matched).map_err(|error| failed_conversion(matched, error))?;
 
         Ok((output, remaining))
     }
+
+    fn describe(&self) -> Grammar {
+        self.lexer.describe()
+    }
+}
+
+/// This combinator is returned by [`parse_str()`]. See it's documentation for more details.
+pub struct ParseStr<L, T> {
+    lexer: L,
+    output: PhantomData<fn() -> T>,
+}
+
+/// This combinator is used to build a parser from a lexer by converting the matched &str to an output type using [`FromStr`].
+///
+/// This is equivalent to `try_map(lexer, T::from_str)`, without needing to name the conversion function.
+///
+/// See [`Lex::parse_str()`] for more details and examples.
+pub fn parse_str<T: FromStr, L>(lexer: L) -> ParseStr<L, T>
+where
+    T::Err: fmt::Display,
+{
+    ParseStr {
+        lexer,
+        output: PhantomData,
+    }
+}
+
+impl<L: Lex, T: FromStr> Parse for ParseStr<L, T>
+where
+    T::Err: fmt::Display,
+{
+    type Output = T;
+
+    fn parse<'i>(&self, input: &'i str) -> crate::ParseResult<'i, Self::Output> {
+        let (matched, remaining) = self.lexer.lex(input)?;
+        let output = matched
+            .parse()
+            .map_err(|error| failed_conversion(matched, error))?;
+
+        Ok((output, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.lexer.describe()
+    }
+}
+
+impl<L: Lex, T: FromStr + fmt::Display> Unparse for ParseStr<L, T>
+where
+    T::Err: fmt::Display,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        matched(&self.lexer, &value.to_string(), output)
+    }
+}
+
+/// This combinator is returned by [`bimap()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct BiMap<P, F, G> {
+    parser: P,
+    f: F,
+    g: G,
+}
+
+/// This combinator maps the output of a parser to another type like [`Parse::map()`], and is also given the function that maps it back again.
+///
+/// The second function lets the parser be [unparsed](crate::unparse): the value is mapped back and then unparsed by the inner parser.
+///
+/// See [`Parse::bimap()`] for more details and examples.
+pub fn bimap<P, F, G, O>(parser: P, f: F, g: G) -> BiMap<P, F, G>
+where
+    P: Parse,
+    F: Fn(<P as Parse>::Output) -> O,
+    G: Fn(&O) -> <P as Parse>::Output,
+{
+    BiMap { parser, f, g }
+}
+
+impl<P, F, G, O> Parse for BiMap<P, F, G>
+where
+    P: Parse,
+    F: Fn(<P as Parse>::Output) -> O,
+    G: Fn(&O) -> <P as Parse>::Output,
+{
+    type Output = O;
+
+    fn parse<'i>(&self, input: &'i str) -> crate::ParseResult<'i, Self::Output> {
+        let (output, remaining) = self.parser.parse(input)?;
+
+        Ok(((self.f)(output), remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.parser.describe()
+    }
+}
+
+impl<P, F, G, O> Unparse for BiMap<P, F, G>
+where
+    P: Unparse,
+    F: Fn(<P as Parse>::Output) -> O,
+    G: Fn(&O) -> <P as Parse>::Output,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.parser.unparse_into(&(self.g)(value), output)
+    }
+}
+
+/// This combinator is returned by [`map_into()`]. See it's documentation for more details.
+pub struct MapInto<P, T> {
+    parser: P,
+    output: PhantomData<fn() -> T>,
+}
+
+/// This combinator converts the output of a parser to another type using [`Into`].
+///
+/// This is equivalent to `parser.map(Into::into)`, without needing to name the conversion function.
+///
+/// See [`Parse::map_into()`] for more details and examples.
+pub fn map_into<T, P>(parser: P) -> MapInto<P, T>
+where
+    P: Parse,
+    <P as Parse>::Output: Into<T>,
+{
+    MapInto {
+        parser,
+        output: PhantomData,
+    }
+}
+
+impl<P, T> Parse for MapInto<P, T>
+where
+    P: Parse,
+    <P as Parse>::Output: Into<T>,
+{
+    type Output = T;
+
+    fn parse<'i>(&self, input: &'i str) -> crate::ParseResult<'i, Self::Output> {
+        let (output, remaining) = self.parser.parse(input)?;
+
+        Ok((output.into(), remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.parser.describe()
+    }
+}
+
+/// Unparsing converts the value back with [`Into`] too, which needs a clone of it.
+impl<P, T> Unparse for MapInto<P, T>
+where
+    P: Unparse,
+    <P as Parse>::Output: Into<T>,
+    T: Clone + Into<<P as Parse>::Output>,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.parser.unparse_into(&value.clone().into(), output)
+    }
+}
+
+impl<L: Clone, T> Clone for ParseStr<L, T> {
+    fn clone(&self) -> Self {
+        ParseStr {
+            lexer: self.lexer.clone(),
+            output: PhantomData,
+        }
+    }
 }
 
 impl<L, F, O> fmt::Debug for Map<L, F>
@@ -76,6 +289,16 @@ where
     }
 }
 
+impl<L, F, O> fmt::Debug for MapWithInput<L, F>
+where
+    L: fmt::Debug,
+    F: Fn(&str, &str) -> O,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MapWithInput<{:?} -> {}>", self.lexer, type_name::<O>())
+    }
+}
+
 impl<L, F, O, E> fmt::Debug for TryMap<L, F>
 where
     L: fmt::Debug,
@@ -90,3 +313,123 @@ where
         )
     }
 }
+
+impl<P: Clone, T> Clone for MapInto<P, T> {
+    fn clone(&self) -> Self {
+        MapInto {
+            parser: self.parser.clone(),
+            output: PhantomData,
+        }
+    }
+}
+
+impl<P: fmt::Debug, T> fmt::Debug for MapInto<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MapInto<{:?} -> {}>", self.parser, type_name::<T>())
+    }
+}
+
+impl<L: fmt::Debug, T> fmt::Debug for ParseStr<L, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ParseStr<{:?} -> {}>", self.lexer, type_name::<T>())
+    }
+}
+
+impl<P, F, G, O> fmt::Debug for BiMap<P, F, G>
+where
+    P: Parse + fmt::Debug,
+    F: Fn(<P as Parse>::Output) -> O,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BiMap<{:?} -> {}>", self.parser, type_name::<O>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{alpha, digit};
+
+    #[test]
+    fn from_str() {
+        test_parser_batch(
+            "digits parse to a u8",
+            parse_str::<u8, _>(digit().many(1..)),
+            &[
+                ("12a", Some(12), "a"),
+                ("255", Some(255), ""),
+                ("a", None, "a"),
+            ],
+        );
+
+        assert_eq!(
+            parse_str::<u8, _>(digit().many(1..)).parse("256"),
+            Err(failed_conversion(
+                "256",
+                "number too large to fit in target type"
+            ))
+        );
+        assert_eq!(
+            alpha().many(1..).parse_str::<bool>().parse("true"),
+            Ok((true, ""))
+        );
+    }
+
+    #[test]
+    fn with_input() {
+        test_parser_batch(
+            "the remaining input is passed along with the match",
+            map_with_input(alpha().many(1..), |matched: &str, remaining: &str| {
+                (matched.to_string(), remaining.len())
+            }),
+            &[
+                ("abc", Some(("abc".to_string(), 0)), ""),
+                ("ab12", Some(("ab".to_string(), 2)), "12"),
+                ("1", None, "1"),
+            ],
+        );
+    }
+
+    #[test]
+    fn bimapping() -> Result<(), crate::Error> {
+        #[derive(Debug, PartialEq)]
+        struct Celsius(i16);
+
+        let temperature = parse_str::<i16, _>(digit().many(1..)).bimap(Celsius, |c| c.0);
+
+        assert_eq!(temperature.parse("21")?, (Celsius(21), ""));
+        assert_eq!(temperature.unparse(&Celsius(5))?, "5");
+        assert!(temperature.unparse(&Celsius(-5)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn mapping_into() -> Result<(), crate::Error> {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Id(u32);
+
+        impl From<u32> for Id {
+            fn from(id: u32) -> Self {
+                Id(id)
+            }
+        }
+
+        impl From<Id> for u32 {
+            fn from(id: Id) -> Self {
+                id.0
+            }
+        }
+
+        let id = map_into::<Id, _>(parse_str::<u32, _>(digit().many(1..)));
+
+        assert_eq!(id.parse("42;")?, (Id(42), ";"));
+        assert_eq!(id.unparse(&Id(7))?, "7");
+
+        let name = alpha().many(1..).map_into::<Box<str>>();
+        assert_eq!(name.parse("abc1")?, (Box::from("abc"), "1"));
+
+        Ok(())
+    }
+}
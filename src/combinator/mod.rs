@@ -3,28 +3,105 @@
 // Combinator TODO list:
 // * then_with -> <https://docs.rs/chumsky/latest/chumsky/trait.Parser.html#method.then_with>
 
+pub(crate) mod active;
+mod both;
+pub(crate) mod budget;
+pub mod capture;
+pub mod chain;
 mod crawl;
+mod cut;
+mod dbg;
+mod depth_limited;
+pub mod diagnostics;
+mod expected;
+mod instrument;
+mod left_recursive;
+pub mod lexeme;
+mod limit;
+pub(crate) mod located;
 mod map;
+mod name;
 mod optional;
 mod or;
 mod pad;
+pub mod profile;
+pub mod program;
+pub mod recover;
+mod scoped;
 pub mod sequence;
 pub mod skip;
 mod then;
+pub mod trace;
+mod void;
+pub mod ws_insensitive;
 
 #[doc(inline)]
-pub use self::crawl::{crawl, Crawl};
+pub use self::both::{both, Both};
 #[doc(inline)]
-pub use self::map::{map, try_map, Map, TryMap};
+pub use self::budget::{budget, Budget};
 #[doc(inline)]
-pub use self::optional::{optional, Optional};
+pub use self::capture::{capture, with_captures, Capture, Captures, WithCaptures};
 #[doc(inline)]
-pub use self::or::{or, Or};
+pub use self::chain::{chainl1, chainr1, ChainL1, ChainR1};
 #[doc(inline)]
-pub use self::pad::{pad, Pad};
+pub use self::crawl::{crawl, crawl_at, crawl_consume, Crawl, CrawlAt, CrawlConsume};
 #[doc(inline)]
-pub use self::sequence::{count, delimited, many, Delimited, Many};
+pub use self::cut::{cut, Cut};
+#[doc(inline)]
+pub use self::dbg::{dbg, Dbg};
+#[doc(inline)]
+pub use self::depth_limited::{depth_limited, DepthLimited};
+#[doc(inline)]
+pub use self::diagnostics::{warn, with_diagnostics, Diagnostic, Warn, WithDiagnostics};
+#[doc(inline)]
+pub use self::expected::{expected, Expected};
+#[doc(inline)]
+pub use self::left_recursive::{left_recursive, LeftRecursive};
+#[doc(inline)]
+pub use self::lexeme::{lexeme, Lexeme};
+#[doc(inline)]
+pub use self::limit::{limit, Limit};
+#[doc(inline)]
+pub use self::located::{located, Located};
+#[doc(inline)]
+pub use self::map::{
+    bimap, map, map_into, map_with_input, parse_str, try_map, BiMap, Map, MapInto, MapWithInput,
+    ParseStr, TryMap,
+};
+#[doc(inline)]
+pub use self::name::{name, Named};
+#[doc(inline)]
+pub use self::optional::{
+    optional, optional_or, optional_or_else, or_default, Optional, OptionalOr, OptionalOrElse,
+    OrDefault,
+};
+#[doc(inline)]
+pub use self::or::{or, or_either, or_else_with, Either, Or, OrEither, OrElseWith};
+#[doc(inline)]
+pub use self::pad::{pad, pad_left, pad_right, NoPadding, Pad};
+#[doc(inline)]
+pub use self::profile::{profile, Counts, Profile};
+#[doc(inline)]
+pub use self::program::{program, Program, Rule};
+#[doc(inline)]
+pub use self::recover::{
+    insert_default, recover, skip_balanced, skip_until, with_recovery, InsertDefault, Recover,
+    Recovered, SkipBalanced, SkipUntil, WithRecovery,
+};
+#[doc(inline)]
+pub use self::scoped::{scoped, Scoped};
+#[doc(inline)]
+pub use self::sequence::{
+    all, count, delimited, for_each, interleave, many, max_iterations, Delimited, Enumerate,
+    ForEach, Interleave, Many, MaxIterations, WithIndex,
+};
 #[doc(inline)]
 pub use self::skip::{skip_then, then_skip, SkipThen, ThenSkip};
 #[doc(inline)]
 pub use self::then::{then, Then};
+#[doc(inline)]
+pub use self::trace::{trace, Attempt, Outcome, Trace};
+#[doc(inline)]
+pub use self::void::{void, Void};
+#[doc(inline)]
+pub use self::ws_insensitive::{atomic, ws_insensitive, Atomic, WsInsensitive};
@@ -0,0 +1,138 @@
+//! [`.name("label")`](crate::Lex::name()) gives a parser or lexer a readable name for its Debug output and errors.
+
+use std::fmt;
+
+use crate::{
+    combinator::{profile, trace},
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+/// This combinator is returned by [`name()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Named<T> {
+    name: &'static str,
+    item: T,
+}
+
+impl<T> Named<T> {
+    /// Runs an attempt of the item, counting it for [`profile()`](crate::combinator::profile()) and recording it for [`trace()`](crate::combinator::trace()).
+    fn attempt<'i, O>(
+        &self,
+        input: &'i str,
+        f: impl FnOnce() -> Result<(O, &'i str), Error>,
+    ) -> Result<(O, &'i str), Error> {
+        profile::record(self.name, input, || trace::record(self.name, input, f))
+            .map_err(|e| self.label(e))
+    }
+
+    fn label(&self, error: Error) -> Error {
+        Error::Named {
+            name: self.name,
+            error: Box::new(error),
+        }
+    }
+}
+
+impl<L: Lex> Lex for Named<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        self.attempt(input, || self.item.lex(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Rule {
+            name: self.name,
+            body: Box::new(self.item.describe()),
+        }
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.item.canonical_into(output)
+    }
+}
+
+impl<P: Parse> Parse for Named<P> {
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        self.attempt(input, || self.item.parse(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Rule {
+            name: self.name,
+            body: Box::new(self.item.describe()),
+        }
+    }
+}
+
+impl<P: Unparse> Unparse for Named<P> {
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.item.unparse_into(value, output)
+    }
+}
+
+/// Labels the item (a parser or lexer) with a name.
+///
+/// The name replaces the item's (often deeply nested) structure in its Debug output,
+/// and errors from the item are wrapped in [`Error::Named`] so that failures say which part of the grammar failed.
+///
+/// Named items are counted by [`profile()`](crate::combinator::profile()) and recorded by [`trace()`](crate::combinator::trace()).
+///
+/// This is more conveniently created using the [`Lex::name`] and [`Parse::name`] methods.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, hex, Lex, Parse};
+///
+/// let hex_color = char('#').then(hex().many(6..=6)).name("hex_color");
+///
+/// let error = hex_color.lex("#12345").unwrap_err();
+/// assert_eq!(error.to_string(), "hex_color: No Match");
+///
+/// assert_eq!(format!("{:?}", hex_color.many(1..)), "Many(1.., hex_color)");
+/// ```
+pub fn name<T>(name: &'static str, item: T) -> Named<T> {
+    Named { name, item }
+}
+
+impl<T> fmt::Debug for Named<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::overflow, int, token};
+
+    #[test]
+    fn errors_are_named() {
+        let parser = int::<u8>()
+            .name("byte")
+            .then_skip(token(";"))
+            .name("statement");
+
+        assert_eq!(parser.parse("12;"), Ok((12, "")));
+
+        let error = parser.parse("256;").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "statement: byte: The number 256 doesn't fit in the output type"
+        );
+        assert_eq!(error.root(), &overflow("256"));
+
+        let error = parser.parse("12").unwrap_err();
+        assert_eq!(error.to_string(), "statement: No Match");
+    }
+}
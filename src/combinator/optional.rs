@@ -1,10 +1,20 @@
 //! [`lexer.optional()`](Lex::optional) will succeed even if `lexer` fails.
 //!
 //! [`parser.optional()`](Parse::optional) will succeed even if `parser` fails.
+//!
+//! [`parser.optional_or(default)`](Parse::optional_or), [`parser.optional_or_else(f)`](Parse::optional_or_else)
+//! and [`parser.or_default()`](Parse::or_default) output a fallback value instead of `None`.
 
 use std::fmt;
 
-use crate::{Lex, Parse, ParseResult};
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, Parse, ParseResult,
+};
+
+use super::capture::recoverable;
 
 /// This combinator is returned by [`optional()`]. See it’s documentation for more details.
 #[derive(Clone)]
@@ -17,12 +27,29 @@ where
     T: Lex,
 {
     fn lex<'i>(&self, input: &'i str) -> crate::LexResult<'i> {
-        if let Ok((matched, remaining)) = self.item.lex(input) {
+        if let Some((matched, remaining)) = recoverable(|| self.item.lex(input))? {
             Ok((matched, remaining))
         } else {
             Ok(("", input))
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Optional(Box::new(self.item.describe()))
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        if sampler.coin() {
+            self.item.sample_into(sampler, output)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        let _ = output;
+        Ok(())
+    }
 }
 
 impl<T> Parse for Optional<T>
@@ -32,12 +59,28 @@ where
     type Output = Option<<T as Parse>::Output>;
 
     fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
-        if let Ok((output, remaining)) = self.item.parse(input) {
+        if let Some((output, remaining)) = recoverable(|| self.item.parse(input))? {
             Ok((Some(output), remaining))
         } else {
             Ok((None, input))
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Optional(Box::new(self.item.describe()))
+    }
+}
+
+impl<T> Unparse for Optional<T>
+where
+    T: Unparse,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        match value {
+            Some(value) => self.item.unparse_into(value, output),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Makes an optional parser/lexer.
@@ -54,3 +97,254 @@ impl<T: fmt::Debug> fmt::Debug for Optional<T> {
         write!(f, "Optional({:?})", self.item)
     }
 }
+
+/// This combinator is returned by [`optional_or()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct OptionalOr<P, O> {
+    parser: P,
+    default: O,
+}
+
+impl<P> Parse for OptionalOr<P, <P as Parse>::Output>
+where
+    P: Parse,
+    <P as Parse>::Output: Clone,
+{
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        if let Some((output, remaining)) = recoverable(|| self.parser.parse(input))? {
+            Ok((output, remaining))
+        } else {
+            Ok((self.default.clone(), input))
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Optional(Box::new(self.parser.describe()))
+    }
+}
+
+impl<P> Unparse for OptionalOr<P, <P as Parse>::Output>
+where
+    P: Unparse,
+    <P as Parse>::Output: Clone,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.parser.unparse_into(value, output)
+    }
+}
+
+/// Makes an optional parser that outputs a clone of the default value if the parser fails.
+///
+/// This is equivalent to `optional(parser).map(|o| o.unwrap_or(default))`, without needing a closure to own the default.
+///
+/// This is more conveniently created using the [`Parse::optional_or`] method.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, int, Lex, Parse};
+///
+/// let port = char(':').skip_then(int::<u16>()).optional_or(80);
+///
+/// assert_eq!(port.parse(":8080")?, (8080, ""));
+/// assert_eq!(port.parse("/index.html")?, (80, "/index.html"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn optional_or<P: Parse>(
+    parser: P,
+    default: <P as Parse>::Output,
+) -> OptionalOr<P, <P as Parse>::Output> {
+    OptionalOr { parser, default }
+}
+
+impl<P: fmt::Debug, O: fmt::Debug> fmt::Debug for OptionalOr<P, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OptionalOr({:?}, {:?})", self.parser, self.default)
+    }
+}
+
+/// This combinator is returned by [`optional_or_else()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct OptionalOrElse<P, F> {
+    parser: P,
+    f: F,
+}
+
+impl<P, F> Parse for OptionalOrElse<P, F>
+where
+    P: Parse,
+    F: Fn() -> <P as Parse>::Output,
+{
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        if let Some((output, remaining)) = recoverable(|| self.parser.parse(input))? {
+            Ok((output, remaining))
+        } else {
+            Ok(((self.f)(), input))
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Optional(Box::new(self.parser.describe()))
+    }
+}
+
+impl<P, F> Unparse for OptionalOrElse<P, F>
+where
+    P: Unparse,
+    F: Fn() -> <P as Parse>::Output,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.parser.unparse_into(value, output)
+    }
+}
+
+/// Makes an optional parser that outputs the result of calling `f` if the parser fails.
+///
+/// Unlike [`optional_or()`], the fallback value is only created when it is needed, and doesn't need to be [`Clone`].
+///
+/// This is more conveniently created using the [`Parse::optional_or_else`] method.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{alpha, char, Lex, Parse};
+///
+/// let name = char('@').skip_then(alpha().many(1..).map(str::to_string)).optional_or_else(|| "anonymous".to_string());
+///
+/// assert_eq!(name.parse("@ferris")?, ("ferris".to_string(), ""));
+/// assert_eq!(name.parse("")?, ("anonymous".to_string(), ""));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn optional_or_else<P, F>(parser: P, f: F) -> OptionalOrElse<P, F>
+where
+    P: Parse,
+    F: Fn() -> <P as Parse>::Output,
+{
+    OptionalOrElse { parser, f }
+}
+
+impl<P: fmt::Debug, F> fmt::Debug for OptionalOrElse<P, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OptionalOrElse({:?})", self.parser)
+    }
+}
+
+/// This combinator is returned by [`or_default()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct OrDefault<P> {
+    parser: P,
+}
+
+impl<P> Parse for OrDefault<P>
+where
+    P: Parse,
+    <P as Parse>::Output: Default,
+{
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        if let Some((output, remaining)) = recoverable(|| self.parser.parse(input))? {
+            Ok((output, remaining))
+        } else {
+            Ok((Default::default(), input))
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Optional(Box::new(self.parser.describe()))
+    }
+}
+
+impl<P> Unparse for OrDefault<P>
+where
+    P: Unparse,
+    <P as Parse>::Output: Default,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.parser.unparse_into(value, output)
+    }
+}
+
+/// Makes an optional parser that outputs the [`Default`] value of its output type if the parser fails, consuming no input.
+///
+/// This is more conveniently created using the [`Parse::or_default`] method.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, int, Lex, Parse};
+///
+/// let retries = int::<u32>().or_default();
+/// assert_eq!(retries.parse("3")?, (3, ""));
+/// assert_eq!(retries.parse("none")?, (0, "none"));
+///
+/// let tags = char('#').skip_then(int::<u32>()).many(1..).or_default();
+/// assert_eq!(tags.parse("#1#2")?, (vec![1, 2], ""));
+/// assert_eq!(tags.parse("")?, (vec![], ""));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn or_default<P>(parser: P) -> OrDefault<P>
+where
+    P: Parse,
+    <P as Parse>::Output: Default,
+{
+    OrDefault { parser }
+}
+
+impl<P: fmt::Debug> fmt::Debug for OrDefault<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OrDefault({:?})", self.parser)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{alpha, char, int, Lex};
+
+    #[test]
+    fn fallback() {
+        test_parser_batch(
+            "a missing number defaults to 0",
+            char('+').skip_then(int::<i32>()).optional_or(0),
+            &[
+                ("+12", Some(12), ""), //
+                ("12", Some(0), "12"),
+                ("+", Some(0), "+"),
+                ("", Some(0), ""),
+            ],
+        );
+
+        test_parser_batch(
+            "a missing list is empty",
+            int::<i32>()
+                .many(1..)
+                .delimiter(char(','))
+                .optional_or_else(Vec::new),
+            &[
+                ("1,2", Some(vec![1, 2]), ""), //
+                ("a", Some(vec![]), "a"),
+            ],
+        );
+
+        test_parser_batch(
+            "a missing name is empty",
+            alpha().many(1..).map(str::to_string).or_default(),
+            &[
+                ("ab1", Some("ab".to_string()), "1"), //
+                ("1", Some(String::new()), "1"),
+            ],
+        );
+    }
+}
@@ -1,19 +1,443 @@
-use std::fmt;
+use std::{cell::Cell, fmt, sync::OnceLock};
 
-use crate::{Lex, LexResult, Parse, ParseResult};
+use crate::{
+    error::{forward, track_failures, Failures},
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+use super::{active, capture::backtrack, instrument::instrument};
 
 /// This combinator is returned by [`or()`]. See it's documentation for more details.
 #[derive(Clone)]
 pub struct Or<L, R> {
     left: L,
     right: R,
+    described: Described,
 }
 
 /// Creates a parser that will attempt to parse with the left parser, and if it fails try to parse with the right parser.
 ///
 /// This short-circuits such that the right parser isn't attempted if the left one matches.
+///
+/// If both fail, the error is from whichever got further into the input.
+/// If they got equally far, what they expected is merged into an [`Error::ExpectedOneOf`] listing every alternative that was tried there,
+/// so a chain of `or`s reports everything it expected. An error more specific than [`Error::NoMatch`], such as a failed conversion, is kept instead.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, int, token, Error, Lex, Parse};
+///
+/// let value = token("true").or(token("false")).or(token("null"));
+///
+/// let error = value.lex("nil").unwrap_err();
+/// assert_eq!(error.to_string(), "expected one of 'true', 'false' or 'null'");
+///
+/// // only the alternatives that got furthest are listed
+/// let list = char('[').then(char(']').or(char('1'))).or(token("{}"));
+/// assert_eq!(
+///     list.lex("[x").unwrap_err(),
+///     Error::ExpectedOneOf(vec!["']'".to_string(), "'1'".to_string()])
+/// );
+/// ```
 pub fn or<L, R>(left: L, right: R) -> Or<L, R> {
-    Or { left, right }
+    Or {
+        left,
+        right,
+        described: Described::default(),
+    }
+}
+
+/// This combinator is returned by [`or_either()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct OrEither<L, R> {
+    left: L,
+    right: R,
+    described: Described,
+}
+
+/// The output of [`or_either()`]: the output of whichever of the two parsers matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Either<A, B> {
+    /// The left parser matched.
+    Left(A),
+
+    /// The right parser matched.
+    Right(B),
+}
+
+impl<A, B> Either<A, B> {
+    /// Converts either output into the same type, e.g. the variant of an enum.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::combinator::Either;
+    ///
+    /// let value: Either<u8, bool> = Either::Right(true);
+    /// assert_eq!(value.either(|n| n.to_string(), |b| b.to_string()), "true");
+    /// ```
+    pub fn either<O>(self, left: impl FnOnce(A) -> O, right: impl FnOnce(B) -> O) -> O {
+        match self {
+            Either::Left(a) => left(a),
+            Either::Right(b) => right(b),
+        }
+    }
+}
+
+impl<T> Either<T, T> {
+    /// Returns the output of whichever parser matched, when both output the same type.
+    pub fn into_inner(self) -> T {
+        match self {
+            Either::Left(value) | Either::Right(value) => value,
+        }
+    }
+}
+
+/// Creates a parser that will attempt to parse with the left parser, and if it fails try to parse with the right parser,
+/// where the two parsers can have different output types.
+///
+/// This works the same way as [`or()`], including how errors are merged when both fail,
+/// but rather than requiring both parsers to output the same type, the output is an [`Either`] of the two.
+/// This means the outputs can be mapped into one type once, at the end, instead of mapping each alternative up front.
+///
+/// Chaining `or_either` nests the [`Either`], e.g. `a.or_either(b).or_either(c)` outputs `Either<Either<A, B>, C>`.
+///
+/// This combinator can be chained using [`Parse::or_either()`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{int, token, Lex, Parse};
+/// use parsely::combinator::Either;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Value {
+///     Number(i64),
+///     Bool(bool),
+/// }
+///
+/// let boolean = token("true").map(|_| true).or(token("false").map(|_| false));
+/// let value = int::<i64>().or_either(boolean);
+///
+/// assert_eq!(value.parse("42")?, (Either::Left(42), ""));
+/// assert_eq!(value.parse("true")?, (Either::Right(true), ""));
+///
+/// // map into a single type at the end
+/// let value = value.map(|output| output.either(Value::Number, Value::Bool));
+/// assert_eq!(value.parse("false")?, (Value::Bool(false), ""));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn or_either<L, R>(left: L, right: R) -> OrEither<L, R> {
+    OrEither {
+        left,
+        right,
+        described: Described::default(),
+    }
+}
+
+/// This combinator is returned by [`or_else_with()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct OrElseWith<L, F, R> {
+    left: L,
+    f: F,
+    right: OnceLock<R>,
+    described: Described,
+}
+
+impl<L, F, R> OrElseWith<L, F, R>
+where
+    F: Fn() -> R,
+{
+    /// Returns the right parser, creating it the first time it is needed.
+    fn right(&self) -> &R {
+        self.right.get_or_init(&self.f)
+    }
+}
+
+/// Creates a parser that will attempt to parse with the left parser, and if it fails try to parse with the parser returned by `f`.
+///
+/// This works the same way as [`or()`], including how errors are merged when both fail,
+/// but the right parser isn't created until the left parser first fails. It is then kept, so `f` is called at most once.
+/// This is useful when the alternative is expensive to build, such as a large [`switch()`](crate::switch()), and is rarely needed.
+///
+/// Describing the parser, and unparsing a value the left parser can't unparse, also create the right parser.
+/// A clone keeps the right parser if it was already created, otherwise the clone creates its own when it needs it.
+///
+/// This combinator can be chained using [`Parse::or_else_with()`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// use parsely::{int, switch, Parse};
+///
+/// static BUILT: AtomicUsize = AtomicUsize::new(0);
+///
+/// let number = int::<u32>().or_else_with(|| {
+///     BUILT.fetch_add(1, Ordering::Relaxed);
+///     switch([("one", 1), ("two", 2), ("three", 3)])
+/// });
+///
+/// assert_eq!(number.parse("42")?, (42, ""));
+/// assert_eq!(BUILT.load(Ordering::Relaxed), 0);
+///
+/// assert_eq!(number.parse("two")?, (2, ""));
+/// assert_eq!(number.parse("three")?, (3, ""));
+/// assert_eq!(BUILT.load(Ordering::Relaxed), 1);
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn or_else_with<L, F, R>(left: L, f: F) -> OrElseWith<L, F, R>
+where
+    F: Fn() -> R,
+{
+    OrElseWith {
+        left,
+        f,
+        right: OnceLock::new(),
+        described: Described::default(),
+    }
+}
+
+/// Tries `left` and then `right`, merging their errors if both fail, see [`or()`].
+///
+/// Merging errors is only worth it if the error reaches the caller, and most errors are recovered from instead,
+/// such as the last item of [`many()`](super::many()). So the outermost running `or` first tries its alternatives with [`first_match()`],
+/// as do all the `or`s within it, and only if both fail does it try them again to merge their errors.
+/// Whether an attempt succeeds doesn't depend on which error a failed alternative returned, only on whether it is fatal,
+/// so this gives the same result as merging errors all the time.
+/// The one way such an error can outlive a successful `or` is as the reason a repetition stopped,
+/// which is marked so that it is found again with errors merged if it is reported, see [`sequence()`](super::sequence::stopped::sequence()).
+///
+/// While anything records the side effects of parsing, the alternatives are tried once, merging their errors, so nothing is recorded twice.
+fn either<T>(
+    input: &str,
+    left: impl Fn() -> Result<T, Error>,
+    right: impl Fn() -> Result<T, Error>,
+    described: &Described,
+    describe: impl Fn(Side) -> Grammar,
+) -> Result<T, Error> {
+    if active::is_active() {
+        return merged(input, left, right, described, describe);
+    }
+
+    match MERGING.with(Cell::get) {
+        Merging::Deferred => first_match(left, right),
+        Merging::Always => merged(input, left, right, described, describe),
+        Merging::Outermost => {
+            let result = {
+                let _restore = merging(Merging::Deferred);
+                first_match(&left, &right)
+            };
+
+            match result {
+                Err(error) if !error.is_fatal() => {
+                    let _restore = merging(Merging::Always);
+                    merged(input, left, right, described, describe)
+                }
+                result => result,
+            }
+        }
+    }
+}
+
+/// How a running `or` handles the errors of its alternatives, see [`either()`].
+#[derive(Clone, Copy)]
+enum Merging {
+    /// No `or` is running, so the next one's error may reach the caller.
+    Outermost,
+
+    /// An enclosing `or` will try its alternatives again if they fail, so errors aren't merged.
+    Deferred,
+
+    /// An enclosing `or` is trying its alternatives again, so errors are merged.
+    Always,
+}
+
+thread_local! {
+    static MERGING: Cell<Merging> = const { Cell::new(Merging::Outermost) };
+}
+
+/// Restores the previous way of merging when dropped, even if parsing panics.
+struct Restore(Merging);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        MERGING.with(|merging| merging.set(self.0));
+    }
+}
+
+fn merging(merging: Merging) -> Restore {
+    Restore(MERGING.with(|current| current.replace(merging)))
+}
+
+/// Whether an enclosing `or` will try its alternatives again, merging their errors, if they fail.
+#[inline]
+pub(crate) fn is_deferred() -> bool {
+    MERGING.with(|merging| matches!(merging.get(), Merging::Deferred))
+}
+
+/// Runs `f` with the errors of every `or` within it merged, even if an enclosing `or` deferred merging them.
+pub(crate) fn with_merged_errors<T>(f: impl FnOnce() -> T) -> T {
+    let _restore = merging(Merging::Always);
+    f()
+}
+
+/// Tries `left` and then `right`, returning the error of one of them as it is if both fail.
+///
+/// The left error is returned if it says more than what was expected, such as a failed conversion, otherwise the right one.
+fn first_match<T>(
+    left: impl FnOnce() -> Result<T, Error>,
+    right: impl FnOnce() -> Result<T, Error>,
+) -> Result<T, Error> {
+    let left_error = match backtrack(left) {
+        Err(error) if !error.is_fatal() => error,
+        result => return result,
+    };
+
+    match backtrack(right) {
+        Err(error) if !error.is_fatal() && !is_expectation(&left_error) => Err(left_error),
+        result => result,
+    }
+}
+
+/// Whether an error only says what was expected, rather than something more specific such as a failed conversion.
+fn is_expectation(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::NoMatch | Error::Expected(_) | Error::ExpectedOneOf(_)
+    )
+}
+
+/// Tries `left` and then `right`, merging their errors if both fail.
+///
+/// `describe` is only called to say what an alternative expected, and only the first time, see [`Described`].
+fn merged<T>(
+    input: &str,
+    left: impl FnOnce() -> Result<T, Error>,
+    right: impl FnOnce() -> Result<T, Error>,
+    described: &Described,
+    describe: impl Fn(Side) -> Grammar,
+) -> Result<T, Error> {
+    let (result, left_failures) = track_failures(input, || backtrack(left));
+    forward(input, &left_failures);
+
+    let left_error = match result {
+        Err(error) if !error.is_fatal() => error,
+        result => return result,
+    };
+
+    let (result, right_failures) = track_failures(input, || backtrack(right));
+    forward(input, &right_failures);
+
+    let right_error = match result {
+        Err(error) if !error.is_fatal() => error,
+        result => return result,
+    };
+
+    let left = Failure::new(left_error, &left_failures);
+    let right = Failure::new(right_error, &right_failures);
+
+    Err(left.merge(right, |side| described.get(side, &describe)))
+}
+
+#[derive(Clone, Copy)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// The grammar of each alternative, rendered the first time it is needed to say what the alternative expected.
+///
+/// Each `or` keeps its own, so failing over and over doesn't describe its alternatives over and over.
+#[derive(Clone, Default)]
+struct Described {
+    left: OnceLock<String>,
+    right: OnceLock<String>,
+}
+
+impl Described {
+    /// Returns the rendered grammar of one side, describing it with `describe` if it hasn't been already.
+    fn get(&self, side: Side, describe: impl Fn(Side) -> Grammar) -> String {
+        let rendered = match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+        };
+
+        rendered.get_or_init(|| describe(side).to_string()).clone()
+    }
+}
+
+/// Why an alternative failed, and where.
+struct Failure {
+    error: Error,
+    offset: usize,
+}
+
+impl Failure {
+    fn new(error: Error, failures: &Failures) -> Self {
+        Failure {
+            error,
+            offset: failures.furthest,
+        }
+    }
+
+    /// What the alternative expected where it failed, if that is known.
+    fn expected(&self, describe: impl FnOnce() -> String) -> Option<Vec<String>> {
+        match &self.error {
+            Error::ExpectedOneOf(expected) => Some(expected.clone()),
+            Error::Expected(expected) => Some(vec![expected.to_string()]),
+            // the alternative's grammar is what it expected, as long as it didn't get any further
+            Error::NoMatch if self.offset == 0 => Some(vec![describe()]),
+            _ => None,
+        }
+    }
+
+    /// Merges the failures of the left and right alternatives, describing them with `describe` only if what they expected is listed.
+    fn merge(self, other: Failure, describe: impl Fn(Side) -> String) -> Error {
+        if self.offset != other.offset {
+            return if self.offset > other.offset {
+                self.error
+            } else {
+                other.error
+            };
+        }
+
+        let expected = self.expected(|| describe(Side::Left));
+        let other_expected = other.expected(|| describe(Side::Right));
+
+        match (expected, other_expected) {
+            (Some(mut expected), Some(other)) => {
+                for item in other {
+                    if !expected.contains(&item) {
+                        expected.push(item);
+                    }
+                }
+                Error::ExpectedOneOf(expected)
+            }
+            // an error that says more than "no match" is kept
+            (Some(expected), None) if other.error == Error::NoMatch => {
+                Error::ExpectedOneOf(expected)
+            }
+            (None, Some(expected)) if self.error == Error::NoMatch => {
+                Error::ExpectedOneOf(expected)
+            }
+            (None, _) if self.error != Error::NoMatch => self.error,
+            _ => other.error,
+        }
+    }
 }
 
 impl<L, R, O> Parse for Or<L, R>
@@ -24,7 +448,126 @@ where
     type Output = O;
 
     fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, O> {
-        self.left.parse(input).or_else(|_| self.right.parse(input))
+        instrument("or", input, || {
+            either(
+                input,
+                || self.left.parse(input),
+                || self.right.parse(input),
+                &self.described,
+                |side| match side {
+                    Side::Left => self.left.describe(),
+                    Side::Right => self.right.describe(),
+                },
+            )
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::or(self.left.describe(), self.right.describe())
+    }
+}
+
+impl<L, R, O> Unparse for Or<L, R>
+where
+    L: Unparse<Output = O>,
+    R: Unparse<Output = O>,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        // the first alternative that can unparse the value is used
+        let start = output.len();
+        self.left.unparse_into(value, output).or_else(|_| {
+            output.truncate(start);
+            self.right.unparse_into(value, output)
+        })
+    }
+}
+
+impl<L, R> Parse for OrEither<L, R>
+where
+    L: Parse,
+    R: Parse,
+{
+    type Output = Either<<L as Parse>::Output, <R as Parse>::Output>;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        instrument("or", input, || {
+            either(
+                input,
+                || {
+                    let (output, remaining) = self.left.parse(input)?;
+                    Ok((Either::Left(output), remaining))
+                },
+                || {
+                    let (output, remaining) = self.right.parse(input)?;
+                    Ok((Either::Right(output), remaining))
+                },
+                &self.described,
+                |side| match side {
+                    Side::Left => self.left.describe(),
+                    Side::Right => self.right.describe(),
+                },
+            )
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::or(self.left.describe(), self.right.describe())
+    }
+}
+
+impl<L, R> Unparse for OrEither<L, R>
+where
+    L: Unparse,
+    R: Unparse,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        match value {
+            Either::Left(value) => self.left.unparse_into(value, output),
+            Either::Right(value) => self.right.unparse_into(value, output),
+        }
+    }
+}
+
+impl<L, F, R, O> Parse for OrElseWith<L, F, R>
+where
+    L: Parse<Output = O>,
+    F: Fn() -> R,
+    R: Parse<Output = O>,
+{
+    type Output = O;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, O> {
+        instrument("or", input, || {
+            either(
+                input,
+                || self.left.parse(input),
+                || self.right().parse(input),
+                &self.described,
+                |side| match side {
+                    Side::Left => self.left.describe(),
+                    Side::Right => self.right().describe(),
+                },
+            )
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::or(self.left.describe(), self.right().describe())
+    }
+}
+
+impl<L, F, R, O> Unparse for OrElseWith<L, F, R>
+where
+    L: Unparse<Output = O>,
+    F: Fn() -> R,
+    R: Unparse<Output = O>,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let start = output.len();
+        self.left.unparse_into(value, output).or_else(|_| {
+            output.truncate(start);
+            self.right().unparse_into(value, output)
+        })
     }
 }
 
@@ -34,7 +577,49 @@ where
     R: Lex,
 {
     fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
-        self.left.lex(input).or_else(|_| self.right.lex(input))
+        instrument("or", input, || {
+            either(
+                input,
+                || self.left.lex(input),
+                || self.right.lex(input),
+                &self.described,
+                |side| match side {
+                    Side::Left => self.left.describe(),
+                    Side::Right => self.right.describe(),
+                },
+            )
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::or(self.left.describe(), self.right.describe())
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        let (first, second): (&dyn Lex, &dyn Lex) = if sampler.coin() {
+            (&self.left, &self.right)
+        } else {
+            (&self.right, &self.left)
+        };
+
+        // fall back to the other alternative if one can't generate input
+        let start = output.len();
+        match first.sample_into(sampler, output) {
+            Err(SampleError::Unsupported(_)) => {
+                output.truncate(start);
+                second.sample_into(sampler, output)
+            }
+            result => result,
+        }
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        // the first alternative that has canonical input is used
+        let start = output.len();
+        self.left.canonical_into(output).or_else(|_| {
+            output.truncate(start);
+            self.right.canonical_into(output)
+        })
     }
 }
 
@@ -48,11 +633,34 @@ where
     }
 }
 
+impl<L, R> fmt::Debug for OrEither<L, R>
+where
+    L: fmt::Debug,
+    R: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OrEither({:?}, {:?})", self.left, self.right)
+    }
+}
+
+impl<L, F, R> fmt::Debug for OrElseWith<L, F, R>
+where
+    L: fmt::Debug,
+    R: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.right.get() {
+            Some(right) => write!(f, "OrElseWith({:?}, {:?})", self.left, right),
+            None => write!(f, "OrElseWith({:?}, <not yet created>)", self.left),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::lexer::{char, token};
-    use crate::test_utils::*;
+    use crate::testing::*;
 
     #[test]
     fn parsing() {
@@ -113,4 +721,203 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn errors() {
+        let expected = |items: &[&str]| {
+            Err(Error::ExpectedOneOf(
+                items.iter().map(|item| item.to_string()).collect(),
+            ))
+        };
+
+        // nested alternatives are flattened, and repeats are listed once
+        let keyword = or(or(token("foo"), char('X')), or(token("foo"), token("bar")));
+        assert_eq!(keyword.lex("zzz"), expected(&["'foo'", "'X'", "'bar'"]));
+
+        // an alternative that got further wins
+        let pair = or(token("a").then(char('b')), token("c"));
+        assert_eq!(pair.lex("ax"), Err(Error::NoMatch));
+
+        // descriptions from expected() are listed as they are
+        let labelled = or(char('a').expected("an a"), char('b'));
+        assert_eq!(labelled.lex("x"), expected(&["an a", "'b'"]));
+        assert_eq!(
+            labelled.expected("a or b").lex("x"),
+            Err(Error::Expected("a or b"))
+        );
+
+        // a more specific error is kept
+        let number = or(crate::int::<u8>(), token("none").map(|_| 0));
+        assert_eq!(
+            number.parse("300").unwrap_err(),
+            Error::Overflow {
+                digits: "300".to_string()
+            }
+        );
+
+        // fatal errors stop the right alternative from being tried
+        let cut = or(char('a').then(char('b').cut()), token("ac"));
+        assert_eq!(cut.lex("ac"), Err(Error::NoMatch.fatal()));
+    }
+
+    #[test]
+    fn describing_lazily() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Matches a char, counting how many times it is described.
+        struct Counted<'c>(char, &'c AtomicUsize);
+
+        impl Lex for Counted<'_> {
+            fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+                char(self.0).lex(input)
+            }
+
+            fn describe(&self) -> Grammar {
+                self.1.fetch_add(1, Ordering::SeqCst);
+                char(self.0).describe()
+            }
+        }
+
+        // each alternative is described once, however many times it fails
+        let described = AtomicUsize::new(0);
+        let letter = or(Counted('a', &described), Counted('b', &described));
+        for _ in 0..3 {
+            assert_eq!(
+                letter.lex("c"),
+                Err(Error::ExpectedOneOf(vec![
+                    "'a'".to_string(),
+                    "'b'".to_string()
+                ]))
+            );
+        }
+        assert_eq!(described.load(Ordering::SeqCst), 2);
+
+        // and not at all if what it expected isn't reported
+        let described = AtomicUsize::new(0);
+        let pair = or(
+            Counted('a', &described).then(char('b')),
+            Counted('c', &described),
+        );
+        assert_eq!(pair.lex("ax"), Err(Error::NoMatch));
+        assert_eq!(described.load(Ordering::SeqCst), 0);
+
+        // nor if the error of a nested or is recovered from
+        let described = AtomicUsize::new(0);
+        let letters = or(Counted('a', &described), Counted('b', &described))
+            .many(1..)
+            .or(token("1"));
+        assert_eq!(letters.lex("abba!"), Ok(("abba", "!")));
+        assert_eq!(described.load(Ordering::SeqCst), 0);
+
+        // but it is once the outermost or fails
+        assert_eq!(
+            letters.lex("!"),
+            Err(Error::ExpectedOneOf(vec![
+                "'a'".to_string(),
+                "'b'".to_string(),
+                "'1'".to_string()
+            ]))
+        );
+        assert_eq!(described.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn stopping_within_a_successful_or() {
+        // the reason the inner repetition stopped outlives the or that succeeded around it
+        let letters = || or(char('a'), char('b')).many(..).or(char('c'));
+        let expected = || Error::ExpectedOneOf(vec!["'a'".to_string(), "'b'".to_string()]);
+
+        assert_eq!(letters().then(char(';')).lex("ab!"), Err(expected()));
+        assert_eq!(
+            or(char('x'), letters().then(char(';')))
+                .many(..)
+                .or(char('z'))
+                .then(char(';'))
+                .lex("ab!"),
+            Err(expected())
+        );
+    }
+
+    #[test]
+    fn either_output() {
+        let value = || crate::int::<u8>().or_either(token("none").map(|_| ()));
+
+        test_parser_batch(
+            "int or none",
+            value(),
+            &[
+                ("12!", Some(Either::Left(12)), "!"),
+                ("none!", Some(Either::Right(())), "!"),
+                ("nil", None, "nil"),
+            ],
+        );
+
+        // errors are merged the same way as or()
+        assert_eq!(
+            value().parse("300").unwrap_err(),
+            Error::Overflow {
+                digits: "300".to_string()
+            }
+        );
+
+        let bytes = crate::hex_array::<1>().or_either(crate::hex_bytes());
+        assert_eq!(bytes.unparse(&Either::Left([7])), Ok("07".to_string()));
+        assert_eq!(
+            bytes.unparse(&Either::Right(vec![1, 2])),
+            Ok("0102".to_string())
+        );
+
+        let same = crate::int::<u8>().or_either(token("x").map(|_| 0));
+        assert_eq!(same.parse("x").unwrap().0.into_inner(), 0);
+    }
+
+    #[test]
+    fn lazy_alternative() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let built = AtomicUsize::new(0);
+        let size = crate::ordinal().or_else_with(|| {
+            built.fetch_add(1, Ordering::Relaxed);
+            crate::byte_size()
+        });
+
+        assert_eq!(size.parse("3rd!"), Ok((3, "!")));
+        assert_eq!(built.load(Ordering::Relaxed), 0);
+        assert_eq!(
+            format!("{size:?}"),
+            "OrElseWith(Ordinal, <not yet created>)"
+        );
+
+        assert_eq!(size.parse("2 KiB"), Ok((2048, "")));
+        assert_eq!(built.load(Ordering::Relaxed), 1);
+
+        // a clone keeps the alternative that was already created
+        test_parser_batch(
+            "ordinal or else byte size",
+            size.clone(),
+            &[
+                ("3rd!", Some(3), "!"),
+                ("10 kB!", Some(10_000), "!"),
+                ("x", None, "x"),
+            ],
+        );
+
+        assert_eq!(size.parse("4 kB"), Ok((4000, "")));
+        assert_eq!(built.load(Ordering::Relaxed), 1);
+        assert_eq!(format!("{size:?}"), "OrElseWith(Ordinal, ByteSize)");
+
+        // errors are merged the same way as or()
+        let boolean = token("true")
+            .map(|_| true)
+            .or_else_with(|| token("false").map(|_| false));
+        assert_eq!(
+            boolean.parse("nil"),
+            Err(Error::ExpectedOneOf(vec![
+                "'true'".to_string(),
+                "'false'".to_string()
+            ]))
+        );
+
+        assert_eq!(size.unparse(&7), Ok("7th".to_string()));
+    }
 }
@@ -1,4 +1,11 @@
-use crate::{Lex, Parse};
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, LexResult, Parse,
+};
+
+use super::sequence::sequence;
 
 /// This combinator is returned by [`pad()`]. See it's documentation for more details.
 #[derive(Clone, Debug)]
@@ -13,12 +20,37 @@ where
     T: Lex,
 {
     fn lex<'i>(&self, input: &'i str) -> crate::LexResult<'i> {
-        let (_, remaining) = self.left.lex(input)?;
-        let (output, remaining) = self.item.lex(remaining)?;
-        let (_, remaining) = self.right.lex(remaining)?;
+        let (((_, output), _), remaining) = sequence(
+            input,
+            |input| {
+                sequence(
+                    input,
+                    |input| self.left.lex(input),
+                    |remaining| self.item.lex(remaining),
+                )
+            },
+            |remaining| self.right.lex(remaining),
+        )?;
 
         Ok((output, remaining))
     }
+
+    fn describe(&self) -> Grammar {
+        let item = Grammar::then(self.left.describe(), self.item.describe());
+        Grammar::then(item, self.right.describe())
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.left.sample_into(sampler, output)?;
+        self.item.sample_into(sampler, output)?;
+        self.right.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.left.canonical_into(output)?;
+        self.item.canonical_into(output)?;
+        self.right.canonical_into(output)
+    }
 }
 
 impl<L, R, T> Parse for Pad<L, R, T>
@@ -30,12 +62,38 @@ where
     type Output = <T as Parse>::Output;
 
     fn parse<'i>(&self, input: &'i str) -> crate::ParseResult<'i, Self::Output> {
-        let (_, remaining) = self.left.lex(input)?;
-        let (output, remaining) = self.item.parse(remaining)?;
-        let (_, remaining) = self.right.lex(remaining)?;
+        let (((_, output), _), remaining) = sequence(
+            input,
+            |input| {
+                sequence(
+                    input,
+                    |input| self.left.lex(input),
+                    |remaining| self.item.parse(remaining),
+                )
+            },
+            |remaining| self.right.lex(remaining),
+        )?;
 
         Ok((output, remaining))
     }
+
+    fn describe(&self) -> Grammar {
+        let item = Grammar::then(self.left.describe(), self.item.describe());
+        Grammar::then(item, self.right.describe())
+    }
+}
+
+impl<L, R, T> Unparse for Pad<L, R, T>
+where
+    L: Lex,
+    R: Lex,
+    T: Unparse,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.left.canonical_into(output)?;
+        self.item.unparse_into(value, output)?;
+        self.right.canonical_into(output)
+    }
 }
 
 /// Creates a parser that will lex with the left lexer, ignoring the ouput, then parse with the parser, and then lex with the right lexer, ignoring the ouput.
@@ -49,11 +107,53 @@ pub fn pad<L: Lex, R: Lex, T>(left: L, right: R, item: T) -> Pad<L, R, T> {
     Pad { left, right, item }
 }
 
+/// Creates a parser that will lex with the left lexer, ignoring the output, and then parse with the parser.
+///
+/// This works the same way as [`pad()`], but only pads the left side: no input after the item is skipped.
+///
+/// See [`Parse::pad_left()`] for more documentation and examples.
+pub fn pad_left<L: Lex, T>(left: L, item: T) -> Pad<L, NoPadding, T> {
+    pad(left, NoPadding, item)
+}
+
+/// Creates a parser that will parse with the parser, and then lex with the right lexer, ignoring the output.
+///
+/// This works the same way as [`pad()`], but only pads the right side: no input before the item is skipped.
+///
+/// See [`Parse::pad_right()`] for more documentation and examples.
+pub fn pad_right<R: Lex, T>(right: R, item: T) -> Pad<NoPadding, R, T> {
+    pad(NoPadding, right, item)
+}
+
+/// The side of a [`Pad`] that isn't padded, see [`pad_left()`] and [`pad_right()`].
+///
+/// This lexer always matches, without consuming any input.
+#[derive(Clone, Copy, Debug)]
+pub struct NoPadding;
+
+impl Lex for NoPadding {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        Ok(("", input))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Empty
+    }
+
+    fn sample_into(&self, _: &mut Sampler, _: &mut String) -> Result<(), SampleError> {
+        Ok(())
+    }
+
+    fn canonical_into(&self, _: &mut String) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::test_utils::*;
-    use crate::{char, digit, int};
+    use crate::testing::*;
+    use crate::{char, digit, int, ws0};
 
     #[test]
     fn parsing() {
@@ -84,4 +184,36 @@ mod test {
             ],
         );
     }
+
+    #[test]
+    fn one_side() {
+        test_parser_batch(
+            "leading whitespace is skipped, trailing newlines are not",
+            pad_left(ws0(), int::<u8>()),
+            &[
+                ("  12\n", Some(12), "\n"),
+                ("12", Some(12), ""),
+                ("\n", None, "\n"),
+            ],
+        );
+
+        test_lexer_batch(
+            "digit() then ';'",
+            pad_right(char(';'), digit()),
+            &[
+                ("1;2", Some("1"), "2"),
+                (";1;", None, ";1;"),
+                ("1", None, "1"),
+            ],
+        );
+
+        assert_eq!(
+            pad_left(char('>'), digit()).describe(),
+            char('>').then(digit()).describe()
+        );
+        assert_eq!(
+            crate::hex_bytes().pad_right(char(';')).unparse(&vec![7]),
+            Ok("07;".to_string())
+        );
+    }
 }
@@ -0,0 +1,280 @@
+//! Count how often each [named](crate::Lex::name()) parser or lexer is tried, and how it went, to find out where parsing spends its time.
+//!
+//! Name the parts of the grammar you are interested in with [`.name("label")`](crate::Lex::name()), and run the parse inside [`profile()`].
+//! Afterwards the [`Profile`] holds the [`Counts`] of each name, whether the parse succeeded or not.
+//!
+//! Nothing is counted unless [`profile()`] is running, so named parsers cost next to nothing the rest of the time.
+//!
+//! # Examples
+//!
+//! Finding which branch of an [`or()`](crate::combinator::or()) is tried most:
+//!
+//! ```
+//! use parsely::{alpha, digit, Lex};
+//! use parsely::combinator::profile;
+//!
+//! let word = alpha().many(1..).name("word");
+//! let number = digit().many(1..).name("number");
+//! let tokens = number.or(word).pad().many(..);
+//!
+//! let (result, profile) = profile(|| tokens.lex("one 2 three 4 five"));
+//! assert_eq!(result?, ("one 2 three 4 five", ""));
+//!
+//! let number = profile.get("number").unwrap();
+//! assert_eq!((number.attempts, number.successes, number.failures), (6, 2, 4));
+//!
+//! let word = profile.get("word").unwrap();
+//! assert_eq!((word.attempts, word.successes, word.consumed), (4, 3, 12));
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::{cell::RefCell, fmt};
+
+use crate::Error;
+
+use super::active;
+
+/// How often a named parser or lexer was tried while running [`profile()`], and how it went.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counts {
+    /// How many times it was tried.
+    pub attempts: usize,
+
+    /// How many times it matched.
+    pub successes: usize,
+
+    /// How many times it failed.
+    pub failures: usize,
+
+    /// The total number of bytes of input it matched, over all of its successes.
+    pub consumed: usize,
+}
+
+impl Counts {
+    fn add(&mut self, other: &Counts) {
+        self.attempts += other.attempts;
+        self.successes += other.successes;
+        self.failures += other.failures;
+        self.consumed += other.consumed;
+    }
+}
+
+/// The counts recorded while running [`profile()`], for each name in the order it was first tried.
+///
+/// Its Display impl formats the counts as a table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    entries: Vec<(&'static str, Counts)>,
+}
+
+impl Profile {
+    /// Returns the counts for this name, or `None` if nothing with this name was tried.
+    pub fn get(&self, name: &str) -> Option<&Counts> {
+        self.entries
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, counts)| counts)
+    }
+
+    /// Returns every name and its counts, in the order each name was first tried.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &Counts)> {
+        self.entries.iter().map(|(name, counts)| (*name, counts))
+    }
+
+    /// The number of names that were tried.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing named was tried.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn counts_mut(&mut self, name: &'static str) -> &mut Counts {
+        match self.entries.iter().position(|(n, _)| *n == name) {
+            Some(i) => &mut self.entries[i].1,
+            None => {
+                self.entries.push((name, Counts::default()));
+                &mut self.entries.last_mut().expect("just pushed").1
+            }
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .entries
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0)
+            .max("name".len());
+
+        writeln!(
+            f,
+            "{:width$}  {:>10}  {:>10}  {:>10}  {:>10}",
+            "name", "attempts", "successes", "failures", "consumed"
+        )?;
+
+        for (name, counts) in &self.entries {
+            writeln!(
+                f,
+                "{name:width$}  {:>10}  {:>10}  {:>10}  {:>10}",
+                counts.attempts, counts.successes, counts.failures, counts.consumed
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+thread_local! {
+    /// The counts of the innermost running [`profile()`].
+    static PROFILE: RefCell<Option<Profile>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous profile when dropped, even if parsing panics.
+///
+/// Whatever was counted by this profile is counted by the previous one too.
+struct Restore(Option<Profile>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        PROFILE.with(|profile| {
+            let mut current = profile.borrow_mut();
+            if let (Some(previous), Some(inner)) = (self.0.as_mut(), current.as_ref()) {
+                for (name, counts) in &inner.entries {
+                    previous.counts_mut(name).add(counts);
+                }
+            }
+            *current = self.0.take();
+        });
+    }
+}
+
+/// Runs `f`, counting every attempt of a named parser or lexer, see [`.name()`](crate::Lex::name()).
+///
+/// The counts are returned along with the result of `f`, so they are available even if parsing failed.
+///
+/// See [the module documentation](self) for more details.
+pub fn profile<T>(f: impl FnOnce() -> T) -> (T, Profile) {
+    let restore = Restore(PROFILE.with(|profile| profile.borrow_mut().replace(Profile::default())));
+    let _active = active::Active::start();
+
+    let output = f();
+    let profile = PROFILE.with(|profile| profile.borrow().clone().unwrap_or_default());
+    drop(restore);
+
+    (output, profile)
+}
+
+/// Runs an attempt of the parser or lexer with this name, counting it if [`profile()`] is running.
+pub(crate) fn record<'i, O>(
+    name: &'static str,
+    input: &'i str,
+    f: impl FnOnce() -> Result<(O, &'i str), Error>,
+) -> Result<(O, &'i str), Error> {
+    let profiling = PROFILE.with(|profile| match profile.borrow_mut().as_mut() {
+        Some(profile) => {
+            profile.counts_mut(name).attempts += 1;
+            true
+        }
+        None => false,
+    });
+
+    let result = f();
+
+    if profiling {
+        PROFILE.with(|profile| {
+            if let Some(profile) = profile.borrow_mut().as_mut() {
+                let counts = profile.counts_mut(name);
+                match &result {
+                    Ok((_, remaining)) => {
+                        counts.successes += 1;
+                        counts.consumed += input.len().saturating_sub(remaining.len());
+                    }
+                    Err(_) => counts.failures += 1,
+                }
+            }
+        });
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, digit, int, Lex, Parse};
+
+    #[test]
+    fn counting() {
+        let item = int::<u8>().name("int").or(char('x').name("x").map(|_| 0));
+        let list = item.many(..).delimiter(char(',')).name("list");
+
+        let (result, profile) = profile(|| list.parse("1,x,300"));
+        assert_eq!(result, Ok((vec![1, 0], "300")));
+
+        assert_eq!(
+            profile.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            vec!["list", "int", "x"]
+        );
+        assert_eq!(
+            profile.get("int"),
+            Some(&Counts {
+                attempts: 3,
+                successes: 1,
+                failures: 2,
+                consumed: 1,
+            })
+        );
+        assert_eq!(
+            profile.get("x"),
+            Some(&Counts {
+                attempts: 2,
+                successes: 1,
+                failures: 1,
+                consumed: 1,
+            })
+        );
+        assert_eq!(profile.get("list").map(|counts| counts.consumed), Some(4));
+        assert_eq!(profile.get("missing"), None);
+    }
+
+    #[test]
+    fn only_while_profiling() {
+        let digits = digit().many(1..).name("digits");
+        assert_eq!(digits.lex("12"), Ok(("12", "")));
+
+        let (_, profile) = profile(|| digits.lex("34"));
+        assert_eq!(profile.get("digits").map(|counts| counts.attempts), Some(1));
+    }
+
+    #[test]
+    fn nested_profiles() {
+        let digits = digit().many(1..).name("digits");
+
+        let ((_, inner), outer) = profile(|| {
+            let _ = digits.lex("1");
+            profile(|| digits.lex("23"))
+        });
+
+        assert_eq!(inner.get("digits").map(|counts| counts.consumed), Some(2));
+        assert_eq!(outer.get("digits").map(|counts| counts.consumed), Some(3));
+    }
+
+    #[test]
+    fn table() {
+        let (_, profile) = profile(|| char('a').name("a").lex("a"));
+
+        assert_eq!(
+            profile.to_string(),
+            "\
+name    attempts   successes    failures    consumed
+a              1           1           0           1
+"
+        );
+    }
+}
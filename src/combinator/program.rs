@@ -0,0 +1,554 @@
+//! [`program()`] runs a grammar of any depth without nesting a function call for each level of it.
+//!
+//! Combinators like [`then()`](crate::combinator::then()) and [`or()`](crate::combinator::or()) call the items inside them,
+//! so a chain of them hundreds of levels deep, as machine-generated grammars often are, uses stack space for every level and can overflow the stack.
+//!
+//! Instead, build the grammar as a tree of [`Rule`]s, with any lexers as its leaves, and compile it with [`program()`].
+//! The rules are compiled into a flat list of instructions which are run in a loop, keeping track of the alternatives left to try on the heap.
+//! Compiling, running and dropping a program use the same amount of stack space however deeply its rules are nested.
+//! Describing one only describes its rules 100 levels deep, and rules any deeper are described as `? nested rules ?`.
+//!
+//! A [`Program`] is a lexer, use [`.map()`](crate::Lex::map()) and friends to parse with it.
+//!
+//! # Examples
+//!
+//! A grammar nested far too deeply for [`then()`](crate::combinator::then()):
+//!
+//! ```
+//! use parsely::{char, Lex};
+//! use parsely::combinator::{program, Rule};
+//!
+//! // "(((...)))" nested 100,000 times
+//! let depth = 100_000;
+//! let mut rule = Rule::Sequence(vec![]);
+//! for _ in 0..depth {
+//!     rule = Rule::Sequence(vec![Rule::lexer(char('(')), rule, Rule::lexer(char(')'))]);
+//! }
+//!
+//! let nested = program(rule);
+//! let input = format!("{}{}!", "(".repeat(depth), ")".repeat(depth));
+//!
+//! let (matched, remaining) = nested.lex(&input)?;
+//! assert_eq!(matched.len(), depth * 2);
+//! assert_eq!(remaining, "!");
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::{fmt, mem, sync::Arc};
+
+use crate::{error::no_match, grammar::Grammar, Error, Lex, LexResult};
+
+use super::{
+    capture::{mark, rewind, Mark},
+    ws_insensitive::skip,
+};
+
+/// A part of a grammar to be compiled into a [`Program`] by [`program()`].
+///
+/// Rules can be nested as deeply as you like, see [the module documentation](self) for more details.
+#[derive(Clone)]
+pub enum Rule {
+    /// Matches whatever the lexer matches, see [`Rule::lexer()`].
+    Lexer(Arc<dyn Lex + Send + Sync>),
+
+    /// Matches each rule in turn, like [`then()`](crate::combinator::then()).
+    ///
+    /// An empty sequence matches without consuming any input.
+    Sequence(Vec<Rule>),
+
+    /// Matches the first rule that matches, trying them in turn, like [`or()`](crate::combinator::or()).
+    ///
+    /// An empty choice never matches.
+    Choice(Vec<Rule>),
+
+    /// Matches the rule or nothing, like [`optional()`](crate::combinator::optional()).
+    Optional(Box<Rule>),
+
+    /// Matches the rule as many times as it will match, including none at all, like [`many(..)`](crate::combinator::many()).
+    ///
+    /// Repetition stops early if the rule matches without consuming any input.
+    Many(Box<Rule>),
+}
+
+/// How many levels of nested rules are described, see [the module documentation](self).
+///
+/// The [`Grammar`] a rule is described by is displayed, validated and dropped recursively, so it can't be nested much deeper than this.
+const DESCRIBED_DEPTH: usize = 100;
+
+impl Rule {
+    /// Creates a rule that matches whatever the lexer matches.
+    pub fn lexer<L: Lex + Send + Sync + 'static>(lexer: L) -> Rule {
+        Rule::Lexer(Arc::new(lexer))
+    }
+
+    /// Removes the rules inside this one, so they can be dropped one at a time rather than recursively.
+    fn take_children(&mut self) -> Vec<Rule> {
+        match self {
+            Rule::Lexer(_) => Vec::new(),
+            Rule::Sequence(rules) | Rule::Choice(rules) => mem::take(rules),
+            Rule::Optional(rule) | Rule::Many(rule) => {
+                vec![mem::replace(&mut **rule, Rule::Sequence(Vec::new()))]
+            }
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        self.describe_to(DESCRIBED_DEPTH)
+    }
+
+    /// Describes this rule, and the rules inside it down to `depth` levels deep.
+    fn describe_to(&self, depth: usize) -> Grammar {
+        let Some(depth) = depth.checked_sub(1) else {
+            return match self {
+                Rule::Lexer(lexer) => lexer.describe(),
+                _ => Grammar::Special("nested rules".to_string()),
+            };
+        };
+        let describe = |rule: &Rule| rule.describe_to(depth);
+
+        match self {
+            Rule::Lexer(lexer) => lexer.describe(),
+            Rule::Sequence(rules) => rules
+                .iter()
+                .map(describe)
+                .fold(Grammar::Empty, Grammar::then),
+            Rule::Choice(rules) => Grammar::Choice(rules.iter().map(describe).collect()),
+            Rule::Optional(rule) => Grammar::Optional(Box::new(describe(rule))),
+            Rule::Many(rule) => Grammar::repeat(0, None, describe(rule)),
+        }
+    }
+}
+
+impl Drop for Rule {
+    fn drop(&mut self) {
+        let mut rules = self.take_children();
+        while let Some(mut rule) = rules.pop() {
+            rules.append(&mut rule.take_children());
+        }
+    }
+}
+
+/// The instructions a [`Program`] is compiled into.
+///
+/// Jumps are to the index of another instruction.
+#[derive(Debug, Clone, Copy)]
+enum Instruction {
+    /// Runs the lexer with this index, failing if it doesn't match.
+    Lex(usize),
+
+    /// Skips whitespace, if running inside [`ws_insensitive()`](super::ws_insensitive()).
+    Skip,
+
+    /// Fails unconditionally.
+    Fail,
+
+    /// Remembers to try the alternative at this index if anything fails before the matching [`Instruction::Commit`].
+    Try(usize),
+
+    /// Forgets the alternative of the last [`Instruction::Try`], and jumps to this index.
+    Commit(usize),
+
+    /// Forgets the alternative of the last [`Instruction::Try`], and repeats the body starting at this index if it consumed any input.
+    Repeat(usize),
+}
+
+/// The steps taken to compile a [`Rule`], kept on the heap rather than recursing.
+enum Step<'r> {
+    /// Compiles the rule.
+    Rule(&'r Rule),
+
+    /// Emits an [`Instruction::Skip`].
+    Skip,
+
+    /// Emits an [`Instruction::Try`], to be given its alternative by [`Step::Commit`] or [`Step::Repeat`].
+    Try,
+
+    /// Ends an alternative of the innermost choice.
+    Commit,
+
+    /// Ends the innermost choice, all of its alternatives jump to here.
+    EndChoice,
+
+    /// Ends a repetition.
+    Repeat,
+}
+
+/// Compiles the rule into instructions, collecting the lexers they use.
+fn compile(rule: &Rule) -> (Vec<Instruction>, Vec<Arc<dyn Lex + Send + Sync>>) {
+    let mut code = Vec::new();
+    let mut lexers = Vec::new();
+
+    let mut steps = vec![Step::Rule(rule)];
+
+    // the indexes of the unfinished `Try`s, and the `Commit`s of each unfinished choice
+    let mut tries = Vec::new();
+    let mut choices: Vec<Vec<usize>> = Vec::new();
+
+    // steps are popped, so they are pushed in reverse
+    while let Some(step) = steps.pop() {
+        match step {
+            Step::Rule(Rule::Lexer(lexer)) => {
+                code.push(Instruction::Lex(lexers.len()));
+                lexers.push(Arc::clone(lexer));
+            }
+            Step::Rule(Rule::Sequence(rules)) => {
+                for (i, rule) in rules.iter().enumerate().rev() {
+                    steps.push(Step::Rule(rule));
+                    if i > 0 {
+                        steps.push(Step::Skip);
+                    }
+                }
+            }
+            Step::Rule(Rule::Choice(rules)) => match rules.split_last() {
+                None => code.push(Instruction::Fail),
+                Some((last, rules)) => {
+                    choices.push(Vec::new());
+                    steps.push(Step::EndChoice);
+                    steps.push(Step::Rule(last));
+                    for rule in rules.iter().rev() {
+                        steps.extend([Step::Commit, Step::Rule(rule), Step::Try]);
+                    }
+                }
+            },
+            Step::Rule(Rule::Optional(rule)) => {
+                choices.push(Vec::new());
+                steps.extend([Step::EndChoice, Step::Commit, Step::Rule(rule), Step::Try]);
+            }
+            Step::Rule(Rule::Many(rule)) => {
+                steps.extend([Step::Repeat, Step::Rule(rule), Step::Try]);
+            }
+            Step::Skip => code.push(Instruction::Skip),
+            Step::Try => {
+                tries.push(code.len());
+                code.push(Instruction::Try(0));
+            }
+            Step::Commit => {
+                let commit = code.len();
+                code.push(Instruction::Commit(0));
+
+                let attempt = tries.pop().expect("a try for each commit");
+                code[attempt] = Instruction::Try(commit + 1);
+                choices
+                    .last_mut()
+                    .expect("commits are inside a choice")
+                    .push(commit);
+            }
+            Step::EndChoice => {
+                let end = code.len();
+                for commit in choices.pop().expect("a choice to end") {
+                    code[commit] = Instruction::Commit(end);
+                }
+            }
+            Step::Repeat => {
+                let attempt = tries.pop().expect("a try for each repeat");
+                code.push(Instruction::Repeat(attempt + 1));
+                code[attempt] = Instruction::Try(code.len());
+            }
+        }
+    }
+
+    (code, lexers)
+}
+
+/// An alternative to try if the attempt after an [`Instruction::Try`] fails.
+struct Alternative {
+    /// The index of the instruction to continue from.
+    next: usize,
+
+    /// How much input had been matched.
+    offset: usize,
+
+    /// The side effects of parsing so far, to undo those of the failed attempt.
+    mark: Mark,
+}
+
+/// This lexer is returned by [`program()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Program {
+    code: Vec<Instruction>,
+    lexers: Vec<Arc<dyn Lex + Send + Sync>>,
+
+    /// Only used to describe the grammar.
+    rule: Arc<Rule>,
+}
+
+impl Program {
+    /// Runs the program, returning how much of the input it matched.
+    fn run(&self, input: &str) -> Result<usize, Error> {
+        let mut next = 0;
+        let mut offset = 0;
+        let mut alternatives: Vec<Alternative> = Vec::new();
+
+        while let Some(&instruction) = self.code.get(next) {
+            let failure = match instruction {
+                Instruction::Lex(lexer) => match self.lexers[lexer].lex(&input[offset..]) {
+                    Ok((matched, _)) => {
+                        offset += matched.len();
+                        None
+                    }
+                    Err(error) if error.is_fatal() => return Err(error),
+                    Err(error) => Some(error),
+                },
+                Instruction::Skip => {
+                    offset = input.len() - skip(&input[offset..]).len();
+                    None
+                }
+                Instruction::Fail => Some(no_match(&input[offset..])),
+                Instruction::Try(alternative) => {
+                    alternatives.push(Alternative {
+                        next: alternative,
+                        offset,
+                        mark: mark(),
+                    });
+                    None
+                }
+                Instruction::Commit(to) => {
+                    alternatives.pop();
+                    next = to;
+                    continue;
+                }
+                Instruction::Repeat(body) => {
+                    let attempt = alternatives.pop().expect("a try for each repeat");
+
+                    // stop repeating a body that doesn't consume any input, rather than looping forever
+                    if offset > attempt.offset {
+                        alternatives.push(Alternative {
+                            next: next + 1,
+                            offset,
+                            mark: mark(),
+                        });
+                        offset = input.len() - skip(&input[offset..]).len();
+                        next = body;
+                        continue;
+                    }
+                    None
+                }
+            };
+
+            match failure {
+                None => next += 1,
+                Some(error) => {
+                    let Some(alternative) = alternatives.pop() else {
+                        return Err(error);
+                    };
+
+                    rewind(alternative.mark);
+                    next = alternative.next;
+                    offset = alternative.offset;
+                }
+            }
+        }
+
+        Ok(offset)
+    }
+}
+
+impl Lex for Program {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let matched = self.run(input)?;
+        Ok(input.split_at(matched))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.rule.describe()
+    }
+}
+
+/// Compiles the rule into a lexer that doesn't use any more stack space however deeply the rules are nested.
+///
+/// See [the module documentation](self) for more details.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, digit, token, Lex};
+/// use parsely::combinator::{program, Rule};
+///
+/// let number = Rule::Sequence(vec![
+///     Rule::Optional(Box::new(Rule::lexer(char('-')))),
+///     Rule::lexer(digit()),
+///     Rule::Many(Box::new(Rule::lexer(digit()))),
+/// ]);
+/// let constant = Rule::Choice(vec![Rule::lexer(token("true")), Rule::lexer(token("false")), number]);
+///
+/// let value = program(constant);
+///
+/// assert_eq!(value.lex("-12,")?, ("-12", ","));
+/// assert_eq!(value.lex("false")?, ("false", ""));
+/// assert!(value.lex("-x").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn program(rule: Rule) -> Program {
+    let (code, lexers) = compile(&rule);
+
+    Program {
+        code,
+        lexers,
+        rule: Arc::new(rule),
+    }
+}
+
+impl fmt::Debug for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Rule({})", self.describe())
+    }
+}
+
+impl fmt::Debug for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Program({} instructions)", self.code.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinator::{with_captures, ws_insensitive};
+    use crate::testing::*;
+    use crate::{alpha, char, digit, token, Parse};
+
+    fn lexer(lexer: impl Lex + Send + Sync + 'static) -> Rule {
+        Rule::lexer(lexer)
+    }
+
+    #[test]
+    fn sequences_and_choices() {
+        let greeting = Rule::Sequence(vec![
+            Rule::Choice(vec![lexer(token("hello")), lexer(token("hi"))]),
+            lexer(char(' ')),
+            Rule::Optional(Box::new(lexer(token("there ")))),
+            Rule::Many(Box::new(lexer(alpha()))),
+        ]);
+
+        test_lexer_batch(
+            "greeting",
+            program(greeting),
+            &[
+                ("hello world!", Some("hello world"), "!"),
+                ("hi there you", Some("hi there you"), ""),
+                ("hi ", Some("hi "), ""),
+                ("hey you", None, "hey you"),
+                ("", None, ""),
+            ],
+        );
+
+        test_lexer_batch(
+            "empty rules",
+            program(Rule::Choice(vec![
+                Rule::Choice(vec![]),
+                Rule::Sequence(vec![]),
+            ])),
+            &[("abc", Some(""), "abc"), ("", Some(""), "")],
+        );
+    }
+
+    #[test]
+    fn backtracking() {
+        // the first alternative matches part of the input before failing
+        let rule = Rule::Choice(vec![
+            Rule::Sequence(vec![lexer(token("ab")), lexer(token("cd"))]),
+            Rule::Sequence(vec![lexer(token("a")), lexer(token("bce"))]),
+        ]);
+        assert_eq!(program(rule).lex("abce"), Ok(("abce", "")));
+
+        // a failed iteration of a repetition isn't consumed
+        let pairs = Rule::Many(Box::new(Rule::Sequence(vec![
+            lexer(digit()),
+            lexer(alpha()),
+        ])));
+        assert_eq!(program(pairs).lex("1a2b3!"), Ok(("1a2b", "3!")));
+
+        // repeating a rule that matches nothing stops
+        let nothing = Rule::Many(Box::new(Rule::Optional(Box::new(lexer(char('x'))))));
+        assert_eq!(program(nothing).lex("xxy"), Ok(("xx", "y")));
+    }
+
+    #[test]
+    fn abandoned_captures_are_discarded() -> Result<(), Error> {
+        let rule = Rule::Choice(vec![
+            Rule::Sequence(vec![lexer(digit().capture("digit")), lexer(char('!'))]),
+            lexer(digit()),
+        ]);
+        let parser = with_captures(program(rule).map(str::len));
+
+        let ((_, captures), _) = parser.parse("1?")?;
+        assert_eq!(captures.get("digit"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn skipping_whitespace() {
+        let list = Rule::Sequence(vec![
+            lexer(char('[')),
+            Rule::Many(Box::new(lexer(digit()))),
+            lexer(char(']')),
+        ]);
+
+        assert_eq!(
+            ws_insensitive(program(list)).lex("[ 1 2 ] x"),
+            Ok(("[ 1 2 ] ", "x"))
+        );
+    }
+
+    #[test]
+    fn deeply_nested_rules() {
+        let depth = 100_000;
+
+        let mut rule = lexer(char('x'));
+        for _ in 0..depth {
+            rule = Rule::Choice(vec![
+                Rule::Sequence(vec![lexer(char('(')), rule, lexer(char(')'))]),
+                lexer(char('y')),
+            ]);
+        }
+        let nested = program(rule);
+
+        let input = format!("{}x{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(nested.lex(&input), Ok((input.as_str(), "")));
+
+        // the innermost level matches its second alternative
+        let input = format!("{}y{}", "(".repeat(depth - 1), ")".repeat(depth - 1));
+        assert_eq!(nested.lex(&input), Ok((input.as_str(), "")));
+
+        // every level backtracks when the last `)` is missing
+        let input = format!("{}x{}", "(".repeat(depth), ")".repeat(depth - 1));
+        assert!(nested.lex(&input).is_err());
+    }
+
+    #[test]
+    fn describing() {
+        let rule = Rule::Sequence(vec![
+            lexer(char('a')),
+            Rule::Optional(Box::new(lexer(char('b')))),
+            Rule::Many(Box::new(Rule::Choice(vec![
+                lexer(char('c')),
+                lexer(char('d')),
+            ]))),
+        ]);
+
+        assert_eq!(
+            program(rule).describe().to_string(),
+            "'a' 'b'? ('c' | 'd')*"
+        );
+    }
+
+    #[test]
+    fn describing_deeply_nested_rules() {
+        let mut rule = lexer(char('x'));
+        for _ in 0..100_000 {
+            rule = Rule::Optional(Box::new(rule));
+        }
+
+        // rules deeper than can be described are summarised
+        let described = format!("? nested rules ?{}", "?".repeat(DESCRIBED_DEPTH));
+        assert_eq!(rule.describe().to_string(), described);
+        assert_eq!(format!("{rule:?}"), format!("Rule({described})"));
+
+        let nested = program(rule);
+        assert!(nested.validate().is_ok());
+        assert_eq!(nested.lex("xy"), Ok(("x", "y")));
+    }
+}
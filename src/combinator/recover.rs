@@ -0,0 +1,566 @@
+//! Error recovery lets a parser skip past input it doesn't understand and carry on, collecting the errors instead of stopping at the first one.
+//!
+//! This is what editor tooling needs: a single typo shouldn't hide every problem after it.
+//!
+//! Mark the places where parsing can resync with [`.recover(strategy)`](crate::Parse::recover()), and run the whole parser with [`with_recovery()`].
+//! When the marked parser fails, the strategy (any lexer) decides how much input to skip, and the failure is recorded.
+//! A recovered parser outputs the [`Default`] of its output type, while a recovered lexer matches the skipped input.
+//!
+//! A few standard strategies are provided:
+//!
+//! * [`skip_until()`] skips up to where a lexer matches, e.g. the `;` at the end of a statement
+//! * [`skip_balanced()`] skips up to an unmatched closing bracket, stepping over nested pairs
+//! * [`insert_default()`] skips nothing, as if the missing input had been there
+//!
+//! The errors recovered from while running [`with_recovery()`] are returned alongside its output.
+//! Recovered errors made by a branch that is later abandoned, e.g. the left side of an [`or()`](crate::combinator::or()) that went on to fail, are discarded.
+//!
+//! # Examples
+//!
+//! Collecting every bad statement:
+//!
+//! ```
+//! use parsely::{alpha, char, int, ws, Lex, Parse};
+//! use parsely::combinator::{skip_until, with_recovery};
+//!
+//! let statement = alpha()
+//!     .many(1..)
+//!     .map(str::to_string)
+//!     .then_skip(char('=').pad())
+//!     .then(int::<i32>())
+//!     .map(Some)
+//!     .recover(skip_until(char(';')))
+//!     .then_skip(char(';').then(ws().many(..)));
+//!
+//! let program = with_recovery(statement.many(..));
+//!
+//! let ((statements, errors), _) = program.parse("a = 1; b = ?; c = 3;")?;
+//!
+//! assert_eq!(statements, vec![Some(("a".to_string(), 1)), None, Some(("c".to_string(), 3))]);
+//! assert_eq!(errors.len(), 1);
+//! assert_eq!(errors[0].offset, 11);
+//! assert_eq!(errors[0].skipped, 7..12);
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::{cell::RefCell, fmt, ops::Range};
+
+use crate::{
+    combinator::{active, budget::examine},
+    error::{no_match, track_failures},
+    grammar::Grammar,
+    unparse::Unparse,
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+/// An error that was recovered from while running [`with_recovery()`].
+#[derive(Debug, PartialEq)]
+pub struct Recovered {
+    /// The error the recovered parser or lexer failed with.
+    pub error: Error,
+
+    /// The byte offset in the input given to [`with_recovery()`] where the error most likely happened.
+    pub offset: usize,
+
+    /// The byte range of the input skipped by the recovery strategy.
+    pub skipped: Range<usize>,
+}
+
+/// The errors recovered from by the innermost running [`WithRecovery`].
+struct Log {
+    /// The address of the input, used to find the offset of a recovery within it.
+    address: usize,
+    len: usize,
+
+    recovered: Vec<Recovered>,
+}
+
+thread_local! {
+    static LOG: RefCell<Option<Log>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous log when dropped, even if parsing panics.
+struct Restore(Option<Log>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        LOG.with(|log| *log.borrow_mut() = self.0.take());
+    }
+}
+
+/// Records a recovered error, if running inside [`with_recovery()`] with an input that contains the recovery.
+///
+/// `offset` is where the error happened and `skipped` is how much was skipped, both relative to `input`.
+fn record(error: Error, input: &str, offset: usize, skipped: usize) {
+    LOG.with(|log| {
+        let mut log = log.borrow_mut();
+        let Some(log) = log.as_mut() else {
+            return;
+        };
+
+        let address = input.as_ptr() as usize;
+        if address < log.address || address + input.len() != log.address + log.len {
+            return;
+        }
+
+        let start = address - log.address;
+        log.recovered.push(Recovered {
+            error,
+            offset: start + offset,
+            skipped: start..start + skipped,
+        });
+    });
+}
+
+/// The number of errors recovered from so far, to go back to with [`rewind()`] if the attempt is abandoned.
+pub(crate) fn checkpoint() -> Option<usize> {
+    LOG.with(|log| log.borrow().as_ref().map(|log| log.recovered.len()))
+}
+
+/// Discards the errors recovered from since the [`checkpoint()`].
+pub(crate) fn rewind(checkpoint: Option<usize>) {
+    if let Some(checkpoint) = checkpoint {
+        LOG.with(|log| {
+            if let Some(log) = log.borrow_mut().as_mut() {
+                log.recovered.truncate(checkpoint);
+            }
+        });
+    }
+}
+
+/// This combinator is returned by [`recover()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Recover<T, S> {
+    item: T,
+    strategy: S,
+}
+
+/// The result of running a [`Recover`], when it didn't fail.
+enum Recovery<'i, O> {
+    /// The item matched.
+    Matched(O, &'i str),
+
+    /// The item failed, and the strategy skipped some input.
+    Skipped(&'i str, &'i str),
+}
+
+impl<T, S: Lex> Recover<T, S> {
+    /// Runs `f`, and if it fails runs the strategy instead, returning what it skipped.
+    fn run<'i, O>(
+        &self,
+        input: &'i str,
+        f: impl FnOnce() -> Result<(O, &'i str), Error>,
+    ) -> Result<Recovery<'i, O>, Error> {
+        let (result, failures) = track_failures(input, f);
+
+        let error = match result {
+            Ok((output, remaining)) => return Ok(Recovery::Matched(output, remaining)),
+            // running out of resources isn't a problem with the input
+            Err(error)
+                if matches!(
+                    error.root(),
+                    Error::BudgetExceeded | Error::DepthExceeded(_) | Error::IterationsExceeded(_)
+                ) =>
+            {
+                return Err(error)
+            }
+            Err(error) => error,
+        };
+
+        let Ok((skipped, remaining)) = self.strategy.lex(input) else {
+            // let an enclosing track_failures() see where the item got to
+            no_match(&input[failures.furthest..]);
+            return Err(error);
+        };
+
+        let offset = failures.offset_of(&error);
+        record(error, input, offset, skipped.len());
+
+        Ok(Recovery::Skipped(skipped, remaining))
+    }
+}
+
+impl<L: Lex, S: Lex> Lex for Recover<L, S> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        match self.run(input, || self.item.lex(input))? {
+            Recovery::Matched(matched, remaining) | Recovery::Skipped(matched, remaining) => {
+                Ok((matched, remaining))
+            }
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+impl<P, S> Parse for Recover<P, S>
+where
+    P: Parse,
+    P::Output: Default,
+    S: Lex,
+{
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        match self.run(input, || self.item.parse(input))? {
+            Recovery::Matched(output, remaining) => Ok((output, remaining)),
+            Recovery::Skipped(_, remaining) => Ok((Default::default(), remaining)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+impl<P, S> Unparse for Recover<P, S>
+where
+    P: Unparse,
+    P::Output: Default,
+    S: Lex,
+{
+    /// Unparses the value with the item, recovery only happens when parsing.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.item.unparse_into(value, output)
+    }
+}
+
+/// Recovers from a failure of the item (a parser or lexer) by skipping the input matched by the strategy, and records the error in an enclosing [`with_recovery()`].
+///
+/// A recovered parser outputs the [`Default`] of its output type, e.g. `None` when the parser is mapped to an [`Option`].
+/// A recovered lexer matches the skipped input.
+///
+/// Any lexer can be a strategy, see the [module documentation](self) for the standard ones.
+/// If the strategy doesn't match either, the item's error is returned as usual.
+///
+/// Fatal errors are recovered from too, which makes a [`cut()`](crate::combinator::cut()) inside the item a good way to mark where it is sure it should have matched.
+/// Running out of [`budget()`](crate::combinator::budget()) or [`depth_limited()`](crate::combinator::depth_limited()) isn't recovered from.
+///
+/// Outside of [`with_recovery()`], the error is still recovered from but isn't recorded anywhere.
+///
+/// This is more conveniently created using the [`Lex::recover`] and [`Parse::recover`] methods.
+pub fn recover<T, S: Lex>(item: T, strategy: S) -> Recover<T, S> {
+    Recover { item, strategy }
+}
+
+/// This combinator is returned by [`with_recovery()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct WithRecovery<T> {
+    item: T,
+}
+
+impl<P: Parse> Parse for WithRecovery<P> {
+    type Output = (<P as Parse>::Output, Vec<Recovered>);
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let log = Log {
+            address: input.as_ptr() as usize,
+            len: input.len(),
+            recovered: Vec::new(),
+        };
+        let _active = active::Active::start();
+        let restore = Restore(LOG.with(|current| current.borrow_mut().replace(log)));
+
+        let result = self.item.parse(input);
+        let log = LOG.with(|current| current.borrow_mut().take());
+        drop(restore);
+
+        let (output, remaining) = result?;
+        let recovered = log.map(|log| log.recovered).unwrap_or_default();
+
+        Ok(((output, recovered), remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+/// Runs the item, and outputs its output along with the errors that any [`.recover()`](crate::Parse::recover()) inside it recovered from, in the order they happened.
+///
+/// Errors recovered from inside a nested `with_recovery()` belong to that one, and are not seen by this one.
+///
+/// See [the module documentation](self) for an example.
+pub fn with_recovery<T>(item: T) -> WithRecovery<T> {
+    WithRecovery { item }
+}
+
+/// This lexer is returned by [`skip_until()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct SkipUntil<L> {
+    lexer: L,
+}
+
+impl<L: Lex> Lex for SkipUntil<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        for (i, _) in input.char_indices() {
+            if self.lexer.lex(&input[i..]).is_ok() {
+                examine(i)?;
+                return Ok(input.split_at(i));
+            }
+        }
+
+        match self.lexer.lex(&input[input.len()..]) {
+            Ok(_) => Ok(input.split_at(input.len())),
+            Err(_) => Err(no_match(input)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special(format!("anything until {}", self.lexer.describe()))
+    }
+}
+
+/// A recovery strategy that skips input up to, but not including, where the lexer matches.
+///
+/// The lexer is usually what the recovered parser expects to come next, e.g. the `;` ending a statement, so parsing resyncs there.
+/// Like [`until()`](crate::until()), this fails if the lexer doesn't match anywhere: use `skip_until(char(';').or(end()))` to skip to the end of the input instead.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, end, Lex};
+/// use parsely::combinator::skip_until;
+///
+/// assert_eq!(skip_until(char(';')).lex("x + ; y")?, ("x + ", "; y"));
+/// assert!(skip_until(char(';')).lex("x +").is_err());
+/// assert_eq!(skip_until(char(';').or(end())).lex("x +")?, ("x +", ""));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn skip_until<L: Lex>(lexer: L) -> SkipUntil<L> {
+    SkipUntil { lexer }
+}
+
+/// This lexer is returned by [`skip_balanced()`]. See it's documentation for more details.
+#[derive(Clone, Copy)]
+pub struct SkipBalanced<'p> {
+    open: &'p str,
+    close: &'p str,
+}
+
+impl Lex for SkipBalanced<'_> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let mut depth = 0_usize;
+
+        for (i, _) in input.char_indices() {
+            let rest = &input[i..];
+
+            if rest.starts_with(self.close) {
+                if depth == 0 {
+                    examine(i)?;
+                    return Ok(input.split_at(i));
+                }
+                depth -= 1;
+            } else if rest.starts_with(self.open) {
+                depth += 1;
+            }
+        }
+
+        examine(input.len())?;
+        Err(no_match(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special(format!("anything until an unmatched {:?}", self.close))
+    }
+}
+
+/// A recovery strategy that skips input up to, but not including, the `close` that isn't matched by an earlier `open`.
+///
+/// This resyncs inside a bracketed group: whatever went wrong inside it, including nested groups, is skipped so that the closing bracket can be matched as usual.
+/// It fails if the input ends before the group is closed.
+///
+/// # Panics
+///
+/// Panics if `open` or `close` is empty.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, int, Lex, Parse};
+/// use parsely::combinator::{skip_balanced, with_recovery};
+///
+/// // once inside the parentheses, each argument must be a number
+/// let args = int::<i32>().expect("a number").many(1..).delimiter(char(','));
+/// let call = char('(')
+///     .skip_then(args.recover(skip_balanced("(", ")")))
+///     .then_skip(char(')'));
+///
+/// assert_eq!(call.parse("(1,2)")?, (vec![1, 2], ""));
+/// assert_eq!(call.parse("(1,(2 3),x)!")?, (vec![], "!"));
+///
+/// let ((_, errors), _) = with_recovery(call).parse("(1,(2 3),x)")?;
+/// assert_eq!(errors[0].skipped, 1..10);
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn skip_balanced<'p>(open: &'p str, close: &'p str) -> SkipBalanced<'p> {
+    assert!(
+        !open.is_empty() && !close.is_empty(),
+        "skip_balanced() needs non empty delimiters"
+    );
+
+    SkipBalanced { open, close }
+}
+
+/// This lexer is returned by [`insert_default()`]. See it's documentation for more details.
+#[derive(Debug, Clone, Copy)]
+pub struct InsertDefault;
+
+impl Lex for InsertDefault {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        Ok(("", input))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Empty
+    }
+}
+
+/// A recovery strategy that skips nothing, carrying on as if the recovered parser had matched and output its [`Default`].
+///
+/// This suits input that is likely missing rather than wrong, such as an optional looking value that is actually required.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, int, Lex, Parse};
+/// use parsely::combinator::{insert_default, with_recovery};
+///
+/// let port = char(':').skip_then(int::<u16>().recover(insert_default()));
+///
+/// let ((port, errors), remaining) = with_recovery(port).parse(":/index.html")?;
+/// assert_eq!((port, remaining), (0, "/index.html"));
+/// assert_eq!(errors[0].offset, 1);
+/// assert_eq!(errors[0].skipped, 1..1);
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn insert_default() -> InsertDefault {
+    InsertDefault
+}
+
+impl<T: fmt::Debug, S: fmt::Debug> fmt::Debug for Recover<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Recover({:?}, {:?})", self.item, self.strategy)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for WithRecovery<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WithRecovery({:?})", self.item)
+    }
+}
+
+impl<L: fmt::Debug> fmt::Debug for SkipUntil<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SkipUntil({:?})", self.lexer)
+    }
+}
+
+impl fmt::Debug for SkipBalanced<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SkipBalanced({:?}, {:?})", self.open, self.close)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{alpha, char, end, int, token, ws};
+
+    #[test]
+    fn lexing_strategies() {
+        test_lexer_batch(
+            "skip until ;",
+            skip_until(char(';')),
+            &[
+                ("ab;c", Some("ab"), ";c"),
+                (";", Some(""), ";"),
+                ("abc", None, ""),
+            ],
+        );
+
+        test_lexer_batch(
+            "skip until ; or the end",
+            skip_until(char(';').or(end())),
+            &[("ab;c", Some("ab"), ";c"), ("abc", Some("abc"), "")],
+        );
+
+        test_lexer_batch(
+            "skip balanced parentheses",
+            skip_balanced("(", ")"),
+            &[
+                ("a)b", Some("a"), ")b"),
+                ("a(b)c)", Some("a(b)c"), ")"),
+                ("(()))", Some("(())"), ")"),
+                ("(a)", None, ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn recovering() -> Result<(), Error> {
+        let item = alpha()
+            .many(1..)
+            .map(str::to_string)
+            .recover(skip_until(char(',').or(end())));
+        let list = with_recovery(item.many(..).delimiter(char(',')));
+
+        let ((items, errors), remaining) = list.parse("ab,12,c,")?;
+        assert_eq!(items, vec!["ab", "", "c", ""]);
+        assert_eq!(remaining, "");
+
+        let skipped: Vec<_> = errors.iter().map(|e| e.skipped.clone()).collect();
+        assert_eq!(skipped, vec![3..5, 8..8]);
+
+        // fatal errors are recovered from, and reported where they happened
+        let assignment = alpha()
+            .many(1..)
+            .then(char('='))
+            .skip_then(int::<u8>().expect("a number"))
+            .map(|_| ())
+            .recover(skip_until(char(';')));
+        let ((_, errors), _) = with_recovery(assignment).parse("ab=x;")?;
+        assert_eq!(
+            errors,
+            vec![Recovered {
+                error: Error::Expected("a number").fatal(),
+                offset: 3,
+                skipped: 0..4,
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn unrecoverable() {
+        // the strategy doesn't match either
+        let statement = int::<u8>().recover(skip_until(char(';')));
+        assert_eq!(statement.parse("x"), Err(Error::NoMatch));
+
+        // running out of budget
+        let greedy = ws().many(..).then(token("x")).budget(2);
+        let error = greedy.recover(insert_default()).lex("    x").unwrap_err();
+        assert_eq!(error.root(), &Error::BudgetExceeded);
+    }
+
+    #[test]
+    fn abandoned_branches_are_discarded() -> Result<(), Error> {
+        let recovered_then_fails = int::<u8>().recover(insert_default()).then_skip(char('!'));
+        let parser = with_recovery(recovered_then_fails.or(alpha().many(1..).map(|_| 1)));
+
+        let ((output, errors), _) = parser.parse("abc")?;
+        assert_eq!(output, 1);
+        assert!(errors.is_empty());
+
+        Ok(())
+    }
+}
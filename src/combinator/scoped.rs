@@ -0,0 +1,151 @@
+use std::fmt;
+
+use crate::{
+    grammar::Grammar,
+    unparse::{matched, Unparse},
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+/// This combinator is returned by [`scoped()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Scoped<L, T> {
+    lexer: L,
+    item: T,
+}
+
+impl<L: Lex, M: Lex> Lex for Scoped<L, M> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let (region, remaining) = self.lexer.lex(input)?;
+        self.item.lex_all(region)?;
+
+        Ok((region, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.lexer.describe()
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        let mut text = String::new();
+        self.item.canonical_into(&mut text)?;
+        matched(&self.lexer, &text, output)
+    }
+}
+
+impl<L: Lex, P: Parse> Parse for Scoped<L, P> {
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (region, remaining) = self.lexer.lex(input)?;
+        let output = self.item.parse_all(region)?;
+
+        Ok((output, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.lexer.describe()
+    }
+}
+
+impl<L: Lex, P: Unparse> Unparse for Scoped<L, P> {
+    /// Unparses the value with the inner parser, and checks that the lexer matches all of it.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let mut text = String::new();
+        self.item.unparse_into(value, &mut text)?;
+        matched(&self.lexer, &text, output)
+    }
+}
+
+/// Runs the lexer to find a region of the input, then runs the item (a parser or lexer) on just that region.
+///
+/// The item must match all of the region, otherwise this fails with [`Error::NoMatch`].
+/// It can't see past the end of the region, so it can't overrun into input that belongs to something else.
+///
+/// This makes two phase grammars easy: first isolate a line, a quoted string or a bracketed group, then parse what's inside it.
+///
+/// This combinator can be chained using [`Lex::scoped()`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, int, until, Lex, Parse};
+/// use parsely::combinator::scoped;
+///
+/// let numbers = int::<i32>().pad().many(1..).delimiter(char(','));
+/// let line = scoped(until("\n"), numbers.clone()).then_skip(char('\n'));
+///
+/// assert_eq!(line.parse("1, 2, 3\n4")?, (vec![1, 2, 3], "4"));
+///
+/// // the list doesn't continue onto the next line, as it would without scoped()
+/// assert_eq!(line.parse("1, 2\n, 3\n")?, (vec![1, 2], ", 3\n"));
+/// assert_eq!(numbers.parse("1, 2\n, 3\n")?, (vec![1, 2, 3], ""));
+///
+/// // but it must reach the end of the line
+/// assert!(line.parse("1, 2 3\n").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn scoped<L: Lex, T>(lexer: L, item: T) -> Scoped<L, T> {
+    Scoped { lexer, item }
+}
+
+impl<L: fmt::Debug, T: fmt::Debug> fmt::Debug for Scoped<L, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Scoped({:?}, {:?})", self.lexer, self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{alpha, balanced, char, char_if, digit, int, until, Lex};
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "braced int",
+            scoped(
+                balanced("{", "}"),
+                char('{').skip_then(int::<u8>()).then_skip(char('}')),
+            ),
+            &[
+                ("{12}x", Some(12), "x"),
+                ("{12 }", None, ""),
+                ("{300}", None, ""),
+                ("12", None, ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn lexing() {
+        test_lexer_batch(
+            "a word on its own line",
+            scoped(until("\n"), alpha().many(1..)),
+            &[
+                ("abc\ndef", Some("abc"), "\ndef"),
+                ("abc def\n", None, ""),
+                ("", None, ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn unparsing() -> Result<(), Error> {
+        let number = || digit().many(1..).parse_str::<u32>();
+
+        let line = scoped(
+            char_if(|c| c != '\n').many(1..),
+            number().many(1..).delimiter(char(',')),
+        );
+        assert_eq!(line.unparse(&vec![1, 20])?, "1,20");
+
+        let one_digit = scoped(digit(), number());
+        assert_eq!(one_digit.unparse(&7)?, "7");
+        assert!(one_digit.unparse(&12).is_err());
+
+        Ok(())
+    }
+}
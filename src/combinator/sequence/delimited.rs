@@ -2,9 +2,21 @@
 
 use std::{marker::PhantomData, ops::RangeBounds};
 
-use crate::{Lex, LexResult, Parse, ParseResult};
+use crate::{
+    grammar::Grammar,
+    parse::Mapped,
+    sample::{SampleError, Sampler},
+    unparse::{repeat, Unparse},
+    Error, Lex, LexResult, Parse, ParseResult,
+};
 
-use super::min_max_from_bounds;
+use super::with_index::{Enumerate, IndexedVec, WithIndex};
+use super::{bounded, check_iterations, iteration_cap, min_max_from_bounds, stopped, too_few};
+use crate::combinator::{
+    capture::{attempt, recoverable},
+    instrument::instrument,
+    ws_insensitive::skip,
+};
 
 /// This combinator is returned by [`Many::delimiter()`](super::many::Many::delimiter()). See it's documentation for more details.
 #[derive(Debug, Clone)]
@@ -13,7 +25,8 @@ pub struct Delimited<L, T, C> {
     item: T,
     min: usize,
     max: usize,
-    collection: PhantomData<C>,
+    pub(super) max_iterations: Option<usize>,
+    collection: PhantomData<fn() -> C>,
 }
 
 impl<L: Lex, T, C> Delimited<L, T, C> {
@@ -24,7 +37,8 @@ impl<L: Lex, T, C> Delimited<L, T, C> {
             max,
             item,
             delimiter,
-            collection: PhantomData::<C>,
+            max_iterations: None,
+            collection: PhantomData::<fn() -> C>,
         }
     }
 }
@@ -38,38 +52,103 @@ where
     type Output = C;
 
     fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
-        let mut count = 0;
-        let mut offset = 0;
-        let mut working_input = input;
+        instrument("delimited", input, || {
+            let mut count = 0;
+            let mut offset = 0;
+            let mut working_input = input;
+            let cap = iteration_cap(self.max_iterations);
+            let mut stop = None;
 
-        let mut outputs = C::default();
+            let mut outputs = C::default();
 
-        while count < self.max {
-            match self.item.parse(working_input) {
-                Ok((output, remaining)) => match self.delimiter.lex(remaining) {
-                    Ok((_, remaining)) => {
-                        count += 1;
-                        offset = input.len() - remaining.len();
-                        outputs.extend(Some(output));
-                        working_input = remaining;
-                    }
-                    Err(_) => {
-                        count += 1;
-                        outputs.extend(Some(output));
-                        offset = input.len() - remaining.len();
+            while count < self.max {
+                match attempt(|| self.item.parse(skip_after_first(count, working_input)))? {
+                    Ok((output, remaining)) => {
+                        match recoverable(|| self.delimiter.lex(skip(remaining)))? {
+                            Some((_, remaining)) => {
+                                count += 1;
+                                check_iterations(count, cap)?;
+                                offset = input.len() - remaining.len();
+                                outputs.extend(Some(output));
+                                working_input = remaining;
+                            }
+                            None => {
+                                count += 1;
+                                check_iterations(count, cap)?;
+                                outputs.extend(Some(output));
+                                offset = input.len() - remaining.len();
 
+                                break;
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        stop = Some(error);
                         break;
                     }
-                },
-                Err(_) => break,
+                }
             }
-        }
 
-        if count < self.min {
-            Err(crate::Error::NoMatch)
-        } else {
-            Ok((outputs, &input[offset..]))
-        }
+            if count < self.min {
+                Err(too_few(stop))
+            } else {
+                stopped(&input[offset..], stop);
+                Ok((outputs, &input[offset..]))
+            }
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        let separator = self.delimiter.describe();
+        let items = Grammar::separated(
+            self.min,
+            bounded(self.max),
+            self.item.describe(),
+            separator.clone(),
+        );
+
+        // a trailing delimiter is allowed
+        Grammar::then(items, Grammar::Optional(Box::new(separator)))
+    }
+}
+
+impl<L, T> Unparse for Delimited<L, T, Vec<<T as Parse>::Output>>
+where
+    T: Unparse,
+    L: Lex,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let grammar = || Parse::describe(self);
+        let delimiter = Some(&self.delimiter as &dyn Lex);
+        repeat(
+            grammar,
+            (self.min, self.max),
+            value,
+            &self.item,
+            delimiter,
+            output,
+        )
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<L, T, const N: usize> Unparse
+    for Delimited<L, T, smallvec::SmallVec<[<T as Parse>::Output; N]>>
+where
+    T: Unparse,
+    L: Lex,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let grammar = || Parse::describe(self);
+        let delimiter = Some(&self.delimiter as &dyn Lex);
+        repeat(
+            grammar,
+            (self.min, self.max),
+            value,
+            &self.item,
+            delimiter,
+            output,
+        )
     }
 }
 
@@ -79,34 +158,83 @@ where
     L: Lex,
 {
     fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
-        let mut count = 0;
-        let mut offset = 0;
-        let mut working_input = input;
+        instrument("delimited", input, || {
+            let mut count = 0;
+            let mut offset = 0;
+            let mut working_input = input;
+            let cap = iteration_cap(self.max_iterations);
+            let mut stop = None;
 
-        while count < self.max {
-            match self.item.lex(working_input) {
-                Ok((_, remaining)) => match self.delimiter.lex(remaining) {
+            while count < self.max {
+                match attempt(|| self.item.lex(skip_after_first(count, working_input)))? {
                     Ok((_, remaining)) => {
-                        count += 1;
-                        offset = input.len() - remaining.len();
-                        working_input = remaining;
-                    }
-                    Err(_) => {
-                        count += 1;
-                        offset = input.len() - remaining.len();
+                        match recoverable(|| self.delimiter.lex(skip(remaining)))? {
+                            Some((_, remaining)) => {
+                                count += 1;
+                                check_iterations(count, cap)?;
+                                offset = input.len() - remaining.len();
+                                working_input = remaining;
+                            }
+                            None => {
+                                count += 1;
+                                check_iterations(count, cap)?;
+                                offset = input.len() - remaining.len();
 
+                                break;
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        stop = Some(error);
                         break;
                     }
-                },
-                Err(_) => break,
+                }
             }
-        }
 
-        if count < self.min {
-            Err(crate::Error::NoMatch)
-        } else {
-            Ok((&input[..offset], &input[offset..]))
+            if count < self.min {
+                Err(too_few(stop))
+            } else {
+                stopped(&input[offset..], stop);
+                Ok((&input[..offset], &input[offset..]))
+            }
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        let separator = self.delimiter.describe();
+        let items = Grammar::separated(
+            self.min,
+            bounded(self.max),
+            self.item.describe(),
+            separator.clone(),
+        );
+
+        // a trailing delimiter is allowed
+        Grammar::then(items, Grammar::Optional(Box::new(separator)))
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        let count = sampler.count(self.min, self.max);
+        sampler.repeat(count, output, &self.item, Some(&self.delimiter))
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        for i in 0..self.min {
+            if i > 0 {
+                self.delimiter.canonical_into(output)?;
+            }
+            self.item.canonical_into(output)?;
         }
+        Ok(())
+    }
+}
+
+/// The first item is never preceded by whitespace that needs skipping, only items after a delimiter are.
+fn skip_after_first(count: usize, input: &str) -> &str {
+    if count > 0 {
+        skip(input)
+    } else {
+        input
     }
 }
 
@@ -122,15 +250,90 @@ impl<L, T, O> Delimited<L, T, Vec<O>> {
             item,
             min,
             max,
+            max_iterations,
+            collection: _,
+        } = self;
+
+        Delimited {
+            delimiter,
+            item,
+            min,
+            max,
+            max_iterations,
+            collection: PhantomData::<fn() -> C>,
+        }
+    }
+
+    /// This method works the same way as [`Many::map_items`](crate::combinator::Many::map_items()). See it’s documentation for more details.
+    pub fn map_items<F, U>(self, f: F) -> Delimited<L, Mapped<T, F>, Vec<U>>
+    where
+        T: Parse<Output = O>,
+        F: Fn(O) -> U,
+    {
+        let Delimited {
+            delimiter,
+            item,
+            min,
+            max,
+            max_iterations,
             collection: _,
         } = self;
 
         Delimited {
+            delimiter,
+            item: Mapped::new(f, item),
+            min,
+            max,
+            max_iterations,
+            collection: PhantomData::<fn() -> Vec<U>>,
+        }
+    }
+}
+
+impl<L, T, O> Delimited<L, T, Vec<O>> {
+    /// This method works the same way as [`Many::with_index`](crate::combinator::Many::with_index()). See it’s documentation for more details.
+    pub fn with_index(self) -> WithIndex<Delimited<L, T, IndexedVec<O>>> {
+        WithIndex {
+            sequence: self.collect(),
+        }
+    }
+}
+
+impl<L, T, O> WithIndex<Delimited<L, T, IndexedVec<O>>> {
+    /// This method works the same way as [`Many::collect`](crate::combinator::Many::collect()), collecting the `(index, output)` pairs.
+    /// See it’s documentation for more details.
+    pub fn collect<C>(self) -> WithIndex<Delimited<L, T, Enumerate<C>>>
+    where
+        C: Extend<(usize, O)>,
+    {
+        let Delimited {
             delimiter,
             item,
             min,
             max,
-            collection: PhantomData::<C>,
+            max_iterations,
+            collection: _,
+        } = self.sequence;
+
+        WithIndex {
+            sequence: Delimited {
+                delimiter,
+                item,
+                min,
+                max,
+                max_iterations,
+                collection: PhantomData::<fn() -> Enumerate<C>>,
+            },
+        }
+    }
+}
+
+impl<L, T, C> Delimited<L, T, C> {
+    /// This method works the same way as [`Many::max_iterations`](crate::combinator::Many::max_iterations()). See it’s documentation for more details.
+    pub fn max_iterations(self, cap: usize) -> Self {
+        Delimited {
+            max_iterations: Some(cap),
+            ..self
         }
     }
 }
@@ -0,0 +1,167 @@
+//! [`.many(..).for_each(f)`](super::many::Many::for_each()) hands each item to a closure as it is parsed, instead of collecting them.
+
+use std::{fmt, ops::RangeBounds};
+
+use crate::{grammar::Grammar, Parse, ParseResult};
+
+use super::{bounded, check_iterations, iteration_cap, min_max_from_bounds, stopped, too_few};
+use crate::combinator::{capture::attempt, instrument::instrument, ws_insensitive::skip};
+
+/// This combinator is returned by [`Many::for_each()`](super::many::Many::for_each()). See it's documentation for more details.
+#[derive(Clone)]
+pub struct ForEach<T, F> {
+    item: T,
+    f: F,
+    min: usize,
+    max: usize,
+    pub(super) max_iterations: Option<usize>,
+}
+
+impl<T, F> ForEach<T, F> {
+    /// Returns a new ForEach combinator. See also [`for_each()`]
+    pub fn new(min: usize, max: usize, item: T, f: F) -> Self {
+        ForEach {
+            item,
+            f,
+            min,
+            max,
+            max_iterations: None,
+        }
+    }
+
+    /// This method works the same way as [`Many::max_iterations`](crate::combinator::Many::max_iterations()). See it’s documentation for more details.
+    pub fn max_iterations(self, cap: usize) -> Self {
+        ForEach {
+            max_iterations: Some(cap),
+            ..self
+        }
+    }
+}
+
+impl<T, F> Parse for ForEach<T, F>
+where
+    T: Parse,
+    F: Fn(<T as Parse>::Output),
+{
+    type Output = usize;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        instrument("many", input, || {
+            let mut count = 0;
+            let cap = iteration_cap(self.max_iterations);
+            let mut working_input = input;
+            let mut remaining = input;
+            let mut stop = None;
+
+            while count < self.max {
+                if count > 0 {
+                    working_input = skip(working_input);
+                }
+
+                match attempt(|| self.item.parse(working_input))? {
+                    Ok((output, after)) => {
+                        count += 1;
+                        check_iterations(count, cap)?;
+                        (self.f)(output);
+                        remaining = after;
+                        working_input = after;
+                    }
+                    Err(error) => {
+                        stop = Some(error);
+                        break;
+                    }
+                }
+            }
+
+            if count < self.min {
+                Err(too_few(stop))
+            } else {
+                stopped(remaining, stop);
+                Ok((count, remaining))
+            }
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::repeat(self.min, bounded(self.max), self.item.describe())
+    }
+}
+
+/// Creates a parser that calls `f` with the output of each item as it is parsed, and outputs how many items there were.
+///
+/// Like [`many()`](crate::combinator::many()) this function takes a range to specify a minimum and maximum number of items.
+/// See the module docs of [`many`](crate::combinator::many) for more details.
+///
+/// This is more conveniently created using [`Many::for_each()`](super::many::Many::for_each()).
+pub fn for_each<T, F>(range: impl RangeBounds<usize>, item: T, f: F) -> ForEach<T, F> {
+    let (min, max) = min_max_from_bounds(range);
+
+    ForEach::new(min, max, item, f)
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for ForEach<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForEach")
+            .field("item", &self.item)
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+
+    use crate::testing::*;
+    use crate::{char, int, Error, Lex, Parse};
+
+    #[test]
+    fn calling_back() {
+        let total = Cell::new(0);
+        let sum = int::<u32>()
+            .then_skip(char(',').optional())
+            .many(..)
+            .for_each(|n| total.set(total.get() + n));
+
+        assert_eq!(sum.parse("1,2,3!"), Ok((3, "!")));
+        assert_eq!(total.get(), 6);
+
+        test_parser_batch(
+            "the count is output",
+            int::<u8>()
+                .then_skip(char(',').optional())
+                .many(2..=3)
+                .for_each(|_| {}),
+            &[
+                ("1,", None, "1,"),
+                ("1,2", Some(2), ""),
+                ("1,2,3,4", Some(3), "4"),
+                ("", None, ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn items_are_handed_over_as_they_are_parsed() {
+        let seen = RefCell::new(Vec::new());
+        let items = int::<u8>()
+            .then_skip(char(','))
+            .many(2..)
+            .for_each(|n| seen.borrow_mut().push(n));
+
+        // the items before the failure have already been handed over
+        assert_eq!(items.parse("1,x"), Err(Error::NoMatch));
+        assert_eq!(*seen.borrow(), vec![1]);
+
+        assert_eq!(
+            int::<u8>()
+                .then_skip(char(','))
+                .many(..)
+                .for_each(|_| {})
+                .max_iterations(1)
+                .parse("1,2,"),
+            Err(Error::IterationsExceeded(1).fatal())
+        );
+    }
+}
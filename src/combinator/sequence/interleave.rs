@@ -0,0 +1,271 @@
+//! [`.many(..).interleave(separator)`](super::many::Many::interleave()) keeps the separators between each item as well as the items.
+
+use std::ops::RangeBounds;
+
+use crate::{
+    grammar::Grammar,
+    parse::Mapped,
+    sample::{SampleError, Sampler},
+    Lex, LexResult, Parse, ParseResult,
+};
+
+use super::{bounded, check_iterations, iteration_cap, min_max_from_bounds, stopped, too_few};
+use crate::combinator::{capture::attempt, ws_insensitive::skip};
+
+/// This combinator is returned by [`Many::interleave()`](super::many::Many::interleave()). See it's documentation for more details.
+#[derive(Debug, Clone)]
+pub struct Interleave<S, T> {
+    separator: S,
+    item: T,
+    min: usize,
+    max: usize,
+    pub(super) max_iterations: Option<usize>,
+}
+
+impl<S, T> Interleave<S, T> {
+    /// Returns a new Interleave combinator. See also [`interleave()`]
+    pub fn new(min: usize, max: usize, item: T, separator: S) -> Self {
+        Interleave {
+            min,
+            max,
+            item,
+            separator,
+            max_iterations: None,
+        }
+    }
+
+    /// This method works the same way as [`Many::max_iterations`](crate::combinator::Many::max_iterations()). See it’s documentation for more details.
+    pub fn max_iterations(self, cap: usize) -> Self {
+        Interleave {
+            max_iterations: Some(cap),
+            ..self
+        }
+    }
+
+    /// This method works the same way as [`Many::map_items`](crate::combinator::Many::map_items()), leaving the separators as they are.
+    /// See it’s documentation for more details.
+    pub fn map_items<F, O, U>(self, f: F) -> Interleave<S, Mapped<T, F>>
+    where
+        T: Parse<Output = O>,
+        F: Fn(O) -> U,
+    {
+        let Interleave {
+            separator,
+            item,
+            min,
+            max,
+            max_iterations,
+        } = self;
+
+        Interleave {
+            separator,
+            item: Mapped::new(f, item),
+            min,
+            max,
+            max_iterations,
+        }
+    }
+}
+
+impl<S, T> Parse for Interleave<S, T>
+where
+    T: Parse,
+    S: Parse,
+{
+    type Output = (Vec<<T as Parse>::Output>, Vec<<S as Parse>::Output>);
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let mut items = Vec::new();
+        let mut separators = Vec::new();
+
+        if self.max == 0 {
+            return if self.min == 0 {
+                Ok(((items, separators), input))
+            } else {
+                Err(crate::Error::NoMatch)
+            };
+        }
+
+        let cap = iteration_cap(self.max_iterations);
+        let mut remaining = match self.item.parse(input) {
+            Ok((output, remaining)) => {
+                items.push(output);
+                check_iterations(items.len(), cap)?;
+                remaining
+            }
+            Err(e) if self.min > 0 || e.is_fatal() => return Err(e),
+            Err(_) => return Ok(((items, separators), input)),
+        };
+
+        let mut stop = None;
+
+        while items.len() < self.max {
+            // a separator that isn't followed by an item is left unconsumed
+            let ((separator, item), after_item) = match attempt(|| {
+                let (separator, after_separator) = self.separator.parse(skip(remaining))?;
+                let (item, after_item) = self.item.parse(skip(after_separator))?;
+                Ok::<_, crate::Error>(((separator, item), after_item))
+            })? {
+                Ok(matched) => matched,
+                Err(error) => {
+                    stop = Some(error);
+                    break;
+                }
+            };
+
+            separators.push(separator);
+            items.push(item);
+            check_iterations(items.len(), cap)?;
+            remaining = after_item;
+        }
+
+        if items.len() < self.min {
+            Err(too_few(stop))
+        } else {
+            stopped(remaining, stop);
+            Ok(((items, separators), remaining))
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::separated(
+            self.min,
+            bounded(self.max),
+            self.item.describe(),
+            self.separator.describe(),
+        )
+    }
+}
+
+impl<S, T> Lex for Interleave<S, T>
+where
+    T: Lex,
+    S: Lex,
+{
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        if self.max == 0 {
+            return if self.min == 0 {
+                Ok(("", input))
+            } else {
+                Err(crate::Error::NoMatch)
+            };
+        }
+
+        let cap = iteration_cap(self.max_iterations);
+        let mut remaining = match self.item.lex(input) {
+            Ok((_, remaining)) => remaining,
+            Err(e) if self.min > 0 || e.is_fatal() => return Err(e),
+            Err(_) => return Ok(("", input)),
+        };
+        let mut count = 1;
+        check_iterations(count, cap)?;
+
+        let mut stop = None;
+
+        while count < self.max {
+            let after_item = match attempt(|| {
+                let (_, after_separator) = self.separator.lex(skip(remaining))?;
+                let (_, after_item) = self.item.lex(skip(after_separator))?;
+                Ok::<_, crate::Error>(after_item)
+            })? {
+                Ok(after_item) => after_item,
+                Err(error) => {
+                    stop = Some(error);
+                    break;
+                }
+            };
+
+            count += 1;
+            check_iterations(count, cap)?;
+            remaining = after_item;
+        }
+
+        if count < self.min {
+            Err(too_few(stop))
+        } else {
+            stopped(remaining, stop);
+            let offset = input.len() - remaining.len();
+            Ok((&input[..offset], remaining))
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::separated(
+            self.min,
+            bounded(self.max),
+            self.item.describe(),
+            self.separator.describe(),
+        )
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        let count = sampler.count(self.min, self.max);
+        sampler.repeat(count, output, &self.item, Some(&self.separator))
+    }
+}
+
+/// Creates a parser that expects a separator in between each item, and outputs both the items and the separators.
+///
+/// Like [`many()`](crate::combinator::many()) this function takes a range to specify a minimum and maximum number of items.
+/// See the module docs of [`many`](crate::combinator::many) for more details.
+///
+/// Unlike [`delimited()`](super::delimited()), a trailing separator is not consumed,
+/// so there is always exactly one fewer separator than there are items (unless there are no items at all).
+pub fn interleave<S, T>(separator: S, range: impl RangeBounds<usize>, item: T) -> Interleave<S, T> {
+    let (min, max) = min_max_from_bounds(range);
+
+    Interleave::new(min, max, item, separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::*;
+    use crate::{char, digit, int, switch, Lex, Parse};
+
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    enum Op {
+        Add,
+        Sub,
+    }
+
+    #[test]
+    fn parse() {
+        test_parser_batch(
+            "items and separators are both kept",
+            int::<i32>()
+                .many(1..)
+                .interleave(switch([("+", Op::Add), ("-", Op::Sub)])),
+            &[
+                ("1", Some((vec![1], vec![])), ""), //
+                ("1+2-3", Some((vec![1, 2, 3], vec![Op::Add, Op::Sub])), ""),
+                ("1+2-", Some((vec![1, 2], vec![Op::Add])), "-"),
+                ("1+-2", Some((vec![1, -2], vec![Op::Add])), ""),
+                ("1++2", Some((vec![1], vec![])), "++2"),
+                ("+1", None, "+1"),
+            ],
+        );
+
+        test_parser_batch(
+            "a maximum number of items",
+            int::<i32>().many(..=2).interleave(switch([(",", ',')])),
+            &[
+                ("", Some((vec![], vec![])), ""), //
+                ("1,2,3", Some((vec![1, 2], vec![','])), ",3"),
+            ],
+        );
+    }
+
+    #[test]
+    fn lex() {
+        test_lexer_batch(
+            "trailing separators are not consumed",
+            digit().many(2..).interleave(char(',')),
+            &[
+                ("1,2,3", Some("1,2,3"), ""), //
+                ("1,2,", Some("1,2"), ","),
+                ("1,", None, "1,"),
+                ("", None, ""),
+            ],
+        );
+    }
+}
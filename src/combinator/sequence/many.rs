@@ -1,10 +1,24 @@
 use std::marker::PhantomData;
 use std::{fmt, ops::RangeBounds};
 
-use crate::{Lex, LexResult, Parse, ParseResult};
+use crate::{
+    grammar::Grammar,
+    parse::Mapped,
+    sample::{SampleError, Sampler},
+    unparse::{repeat, Unparse},
+    Error, Lex, LexResult, Parse, ParseResult,
+};
 
 use super::delimited::Delimited;
-use super::{min_max_from_bounds, MAX_LIMIT};
+use super::for_each::ForEach;
+use super::interleave::Interleave;
+use super::with_index::{Enumerate, IndexedVec, WithIndex};
+use super::{
+    bounded, check_iterations, iteration_cap, min_max_from_bounds, stopped, too_few, MAX_LIMIT,
+};
+use crate::combinator::{capture::attempt, instrument::instrument, ws_insensitive::skip};
+use crate::combinator::{then_skip, ThenSkip};
+use crate::{end, End};
 
 /// This type alias is used where [`Many`] requires a generic type to collect into that we can ignore because we're lexing.
 pub(crate) type LexMany<T> = Many<T, Vec<()>>;
@@ -27,7 +41,10 @@ pub struct Many<T, C> {
     /// To enforce that input is fully consumed, see [`crate::lexer::end()`]
     max: usize,
 
-    collection: PhantomData<C>,
+    /// The most times the item can match before failing, if capped, see [`Many::max_iterations()`].
+    max_iterations: Option<usize>,
+
+    collection: PhantomData<fn() -> C>,
 }
 
 impl<P, C> Parse for Many<P, C>
@@ -38,52 +55,135 @@ where
     type Output = C;
 
     fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
-        let mut count = 0;
-        let mut offset = 0;
-        let mut working_input = input;
-
-        let mut outputs = C::default();
-
-        while count < self.max {
-            if let Ok((output, remaining)) = self.item.parse(working_input) {
-                count += 1;
-                offset = input.len() - remaining.len();
-                outputs.extend(Some(output));
-                working_input = remaining;
+        instrument("many", input, || {
+            let mut count = 0;
+            let mut offset = 0;
+            let mut working_input = input;
+            let cap = iteration_cap(self.max_iterations);
+
+            let mut outputs = C::default();
+            let mut stop = None;
+
+            while count < self.max {
+                if count > 0 {
+                    working_input = skip(working_input);
+                }
+
+                match attempt(|| self.item.parse(working_input))? {
+                    Ok((output, remaining)) => {
+                        count += 1;
+                        check_iterations(count, cap)?;
+                        offset = input.len() - remaining.len();
+                        outputs.extend(Some(output));
+                        working_input = remaining;
+                    }
+                    Err(error) => {
+                        stop = Some(error);
+                        break;
+                    }
+                }
+            }
+
+            if count < self.min {
+                Err(too_few(stop))
             } else {
-                break;
+                stopped(&input[offset..], stop);
+                Ok((outputs, &input[offset..]))
             }
-        }
+        })
+    }
 
-        if count < self.min {
-            Err(crate::Error::NoMatch)
-        } else {
-            Ok((outputs, &input[offset..]))
-        }
+    fn describe(&self) -> Grammar {
+        Grammar::repeat(self.min, bounded(self.max), self.item.describe())
+    }
+}
+
+impl<P> Unparse for Many<P, Vec<<P as Parse>::Output>>
+where
+    P: Unparse,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let grammar = || Parse::describe(self);
+        repeat(
+            grammar,
+            (self.min, self.max),
+            value,
+            &self.item,
+            None,
+            output,
+        )
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<P, const N: usize> Unparse for Many<P, smallvec::SmallVec<[<P as Parse>::Output; N]>>
+where
+    P: Unparse,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let grammar = || Parse::describe(self);
+        repeat(
+            grammar,
+            (self.min, self.max),
+            value,
+            &self.item,
+            None,
+            output,
+        )
     }
 }
 
 impl<L: Lex, C> Lex for Many<L, C> {
     fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
-        let mut count = 0;
-        let mut offset = 0;
-        let mut working_input = input;
-
-        while count < self.max {
-            if let Ok((matched, remaining)) = self.item.lex(working_input) {
-                count += 1;
-                offset += matched.len();
-                working_input = remaining;
+        instrument("many", input, || {
+            let mut count = 0;
+            let mut offset = 0;
+            let mut working_input = input;
+            let cap = iteration_cap(self.max_iterations);
+            let mut stop = None;
+
+            while count < self.max {
+                if count > 0 {
+                    working_input = skip(working_input);
+                }
+
+                match attempt(|| self.item.lex(working_input))? {
+                    Ok((_, remaining)) => {
+                        count += 1;
+                        check_iterations(count, cap)?;
+                        offset = input.len() - remaining.len();
+                        working_input = remaining;
+                    }
+                    Err(error) => {
+                        stop = Some(error);
+                        break;
+                    }
+                }
+            }
+
+            if count < self.min {
+                Err(too_few(stop))
             } else {
-                break;
+                stopped(&input[offset..], stop);
+                Ok(input.split_at(offset))
             }
-        }
+        })
+    }
 
-        if count < self.min {
-            Err(crate::Error::NoMatch)
-        } else {
-            Ok(input.split_at(offset))
+    fn describe(&self) -> Grammar {
+        Grammar::repeat(self.min, bounded(self.max), self.item.describe())
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        let count = sampler.count(self.min, self.max);
+        sampler.repeat(count, output, &self.item, None)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        for _ in 0..self.min {
+            self.item.canonical_into(output)?;
         }
+        Ok(())
     }
 }
 
@@ -166,7 +266,8 @@ pub fn many<T, O>(range: impl RangeBounds<usize>, item: T) -> Many<T, Vec<O>> {
         item,
         min,
         max,
-        collection: PhantomData::<Vec<O>>,
+        max_iterations: None,
+        collection: PhantomData::<fn() -> Vec<O>>,
     }
 }
 
@@ -182,10 +283,39 @@ pub fn count<T, O>(count: usize, item: T) -> Many<T, Vec<O>> {
         item,
         min: count,
         max: count,
-        collection: PhantomData::<Vec<O>>,
+        max_iterations: None,
+        collection: PhantomData::<fn() -> Vec<O>>,
     }
 }
 
+/// Creates a parser that applies a given parser multiple times, and then expects there to be no remaining input.
+///
+/// This takes a range of the number of times the item must match, just like [`many()`],
+/// so it can express e.g. "between 2 and 5 items and then the end of the input".
+///
+/// This is the same as `many(range, item).then_end()`, and can be chained using [`Parse::all()`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, int, Parse};
+/// use parsely::combinator::all;
+///
+/// let two_to_five = all(2..=5, int::<u8>().then_skip(char(';')));
+///
+/// assert_eq!(two_to_five.parse("1;2;3;")?, (vec![1, 2, 3], ""));
+/// assert!(two_to_five.parse("1;").is_err());
+///
+/// // too many items are left over as remaining input, so this fails too
+/// assert!(two_to_five.parse("1;2;3;4;5;6;").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn all<T, O>(range: impl RangeBounds<usize>, item: T) -> ThenSkip<End, Many<T, Vec<O>>> {
+    then_skip(end(), many(range, item))
+}
+
 impl<T, C> Many<T, C> {
     /// Creates a new parser that matches the same number of times, but expects the input to be separated by `delimiter`.
     ///
@@ -210,10 +340,195 @@ impl<T, C> Many<T, C> {
             min,
             max,
             item,
+            max_iterations,
+            collection: _,
+        } = self;
+
+        let mut delimited = Delimited::new(min, max, item, delimiter);
+        delimited.max_iterations = max_iterations;
+        delimited
+    }
+
+    /// Creates a new parser that matches the same number of times, but expects the input to be separated by `separator`.
+    ///
+    /// Unlike [`Many::delimiter()`], the separator is a parser and its outputs are kept.
+    /// The output is a tuple of the items and the separators found between them.
+    /// This is useful when the separators mean something, such as operators or units.
+    ///
+    /// A trailing separator is not consumed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{int, switch, Parse};
+    ///
+    /// let sum_parser = int::<i32>().many(1..).interleave(switch([("+", 1), ("-", -1)]));
+    ///
+    /// let (output, remaining) = sum_parser.parse("1+2-3")?;
+    /// assert_eq!(output, (vec![1, 2, 3], vec![1, -1]));
+    /// assert_eq!(remaining, "");
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn interleave<S>(self, separator: S) -> Interleave<S, T> {
+        let Many {
+            min,
+            max,
+            item,
+            max_iterations,
+            collection: _,
+        } = self;
+
+        let mut interleave = Interleave::new(min, max, item, separator);
+        interleave.max_iterations = max_iterations;
+        interleave
+    }
+
+    /// Creates a new parser that matches the same number of times, but hands each output to `f` as it is parsed instead of collecting them.
+    ///
+    /// The output is the number of items matched.
+    /// When all you need is to aggregate the items, or pass them on somewhere else, this saves building a collection only to throw it away.
+    ///
+    /// `f` is called as soon as each item is parsed, so it has already been called for the items before a failure,
+    /// whether that is this parser matching fewer than the minimum number of items, or an enclosing parser failing later on.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use parsely::{char, int, Lex, Parse};
+    ///
+    /// let total = Cell::new(0);
+    /// let sum = int::<u32>()
+    ///     .then_skip(char('+').optional())
+    ///     .many(1..)
+    ///     .for_each(|n| total.set(total.get() + n));
+    ///
+    /// assert_eq!(sum.parse("1+2+3")?, (3, ""));
+    /// assert_eq!(total.get(), 6);
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn for_each<F>(self, f: F) -> ForEach<T, F> {
+        let Many {
+            min,
+            max,
+            item,
+            max_iterations,
             collection: _,
         } = self;
 
-        Delimited::new(min, max, item, delimiter)
+        let mut for_each = ForEach::new(min, max, item, f);
+        for_each.max_iterations = max_iterations;
+        for_each
+    }
+
+    /// Sets the minimum number of times the item must match, keeping the maximum.
+    ///
+    /// This is a more explicit alternative to the start of the range given to [`many()`].
+    ///
+    /// If the maximum is less than `min`, it is raised to `min`: the bound set last wins.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{digit, Lex};
+    ///
+    /// // the same as digit().many(2..)
+    /// let digits = digit().many(..).at_least(2);
+    ///
+    /// assert_eq!(digits.lex("123")?, ("123", ""));
+    /// assert!(digits.lex("1").is_err());
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn at_least(self, min: usize) -> Self {
+        Many {
+            min,
+            max: self.max.max(min),
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of times the item will match, keeping the minimum.
+    ///
+    /// Unlike the exclusive end of a range such as `..3`, the item can match `max` times.
+    /// It is then left to match whatever follows, see [`Many::max_iterations()`] to fail instead.
+    ///
+    /// If the minimum is more than `max`, it is lowered to `max`: the bound set last wins.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{digit, Lex};
+    ///
+    /// // the same as digit().many(1..=3)
+    /// let digits = digit().many(..).at_least(1).at_most(3);
+    ///
+    /// assert_eq!(digits.lex("12345")?, ("123", "45"));
+    /// assert!(digits.lex("").is_err());
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn at_most(self, max: usize) -> Self {
+        Many {
+            min: self.min.min(max),
+            max,
+            ..self
+        }
+    }
+
+    /// Sets the item to match exactly `n` times, no more and no fewer.
+    ///
+    /// This is the same as [`count()`] or a range of `n..=n`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{digit, Lex};
+    ///
+    /// let year = digit().many(..).exactly(4);
+    ///
+    /// assert_eq!(year.lex("20241")?, ("2024", "1"));
+    /// assert!(year.lex("202").is_err());
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn exactly(self, n: usize) -> Self {
+        Many {
+            min: n,
+            max: n,
+            ..self
+        }
+    }
+
+    /// Fails with [`Error::IterationsExceeded`] if the item matches more than `cap` times, instead of carrying on.
+    ///
+    /// This overrides the default set by an enclosing [`max_iterations()`](super::max_iterations()), see it's documentation for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{digit, Error, Lex};
+    ///
+    /// let digits = digit().many(1..).max_iterations(4);
+    ///
+    /// assert_eq!(digits.lex("1234")?, ("1234", ""));
+    /// assert_eq!(digits.lex("12345").unwrap_err().root(), &Error::IterationsExceeded(4));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn max_iterations(self, cap: usize) -> Self {
+        Many {
+            max_iterations: Some(cap),
+            ..self
+        }
     }
 }
 
@@ -261,6 +576,25 @@ impl<T, O> Many<T, Vec<O>> {
     ///     map
     /// });
     /// # Ok::<(), parsely::Error>(())
+    /// ```
+    ///
+    /// Most repetitions only match a handful of times, so allocating a `Vec` for the outputs can take longer than the parsing itself.
+    /// With the `smallvec` feature enabled, collect into a [`SmallVec`](https://docs.rs/smallvec) (re-exported as `parsely::smallvec`) to keep up to `N` outputs inline, only allocating if there are more.
+    /// This is especially suited to [`count()`](super::count()), where the number of outputs is known up front:
+    ///
+    /// ```
+    /// # #[cfg(feature = "smallvec")] {
+    /// use parsely::{char, hex, Lex, Parse};
+    /// use parsely::smallvec::SmallVec;
+    ///
+    /// let byte = hex().count(2).try_map(|s| u8::from_str_radix(s, 16));
+    /// let color = char('#').skip_then(byte.count(3).collect::<SmallVec<[u8; 3]>>());
+    ///
+    /// let (rgb, _) = color.parse("#ff8000")?;
+    /// assert_eq!(rgb.as_slice(), [255, 128, 0]);
+    /// assert!(!rgb.spilled());
+    /// # }
+    /// # Ok::<(), parsely::Error>(())
     pub fn collect<C>(self) -> Many<T, C>
     where
         Self: Sized,
@@ -270,6 +604,7 @@ impl<T, O> Many<T, Vec<O>> {
             item,
             min,
             max,
+            max_iterations,
             collection: _,
         } = self;
 
@@ -277,7 +612,133 @@ impl<T, O> Many<T, Vec<O>> {
             item,
             min,
             max,
-            collection: PhantomData::<C>,
+            max_iterations,
+            collection: PhantomData::<fn() -> C>,
+        }
+    }
+
+    /// Creates a new parser that matches the same number of times, but transforms each output with `f` before it is collected.
+    ///
+    /// This is the same as mapping the item, as in `item.map(f).many(..)`, which can be awkward when the item is shared by other parsers.
+    /// The result is still a [`Many`], so it can be [delimited](Many::delimiter()) or [collected](Many::collect()) as usual.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use parsely::{alpha, char, Lex, Parse};
+    ///
+    /// let word = alpha().many(1..).map(str::to_string);
+    ///
+    /// let shout = word.clone().many(1..).delimiter(char(' ')).map_items(|word| word.to_uppercase());
+    /// assert_eq!(shout.parse("hello world")?, (vec!["HELLO".to_string(), "WORLD".to_string()], ""));
+    ///
+    /// let lengths = word.many(1..).delimiter(char(' ')).map_items(|word| word.len()).collect::<HashSet<_>>();
+    /// assert_eq!(lengths.parse("to be or not")?.0, HashSet::from([2, 3]));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn map_items<F, U>(self, f: F) -> Many<Mapped<T, F>, Vec<U>>
+    where
+        T: Parse<Output = O>,
+        F: Fn(O) -> U,
+    {
+        let Many {
+            item,
+            min,
+            max,
+            max_iterations,
+            collection: _,
+        } = self;
+
+        Many {
+            item: Mapped::new(f, item),
+            min,
+            max,
+            max_iterations,
+            collection: PhantomData::<fn() -> Vec<U>>,
+        }
+    }
+}
+
+impl<T, O> Many<T, Vec<O>> {
+    /// Creates a new parser that matches the same number of times, but pairs each output with its index in the sequence, starting from 0.
+    ///
+    /// This is useful to say which item was wrong in an error message, such as "invalid value in field 3", without zipping the outputs afterwards.
+    /// To know where each item was found too, put a [`position()`](crate::position()) before it inside a [`located()`](crate::combinator::located()).
+    ///
+    /// The pairs can be collected into a different collection with [`WithIndex::collect()`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{alpha, char, position, Lex, Location, Parse};
+    /// use parsely::combinator::located;
+    ///
+    /// let field = alpha().many(..).map(str::to_string);
+    /// let fields = field.clone().many(1..).delimiter(char(',')).with_index();
+    ///
+    /// let (output, _) = fields.parse("id,,name")?;
+    /// assert_eq!(output, vec![(0, "id".to_string()), (1, "".to_string()), (2, "name".to_string())]);
+    ///
+    /// let empty = output.iter().find(|(_, field)| field.is_empty()).map(|(i, _)| i);
+    /// assert_eq!(empty, Some(&1));
+    ///
+    /// // the offset of each field in the input
+    /// let fields = located(position().then(field).many(1..).delimiter(char(',')).with_index());
+    ///
+    /// let (output, _) = fields.parse("id,name")?;
+    /// let (index, (Location { offset, .. }, name)) = &output[1];
+    /// assert_eq!((*index, *offset, name.as_str()), (1, 3, "name"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn with_index(self) -> WithIndex<Many<T, IndexedVec<O>>> {
+        WithIndex {
+            sequence: self.collect(),
+        }
+    }
+}
+
+impl<T, O> WithIndex<Many<T, IndexedVec<O>>> {
+    /// This method works the same way as [`Many::collect`], collecting the `(index, output)` pairs. See it’s documentation for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use parsely::{char, int, Parse};
+    ///
+    /// let numbers = int::<i32>().many(..).delimiter(char(' ')).with_index().collect::<HashMap<_, _>>();
+    ///
+    /// let (output, _) = numbers.parse("7 -3 12")?;
+    /// assert_eq!(output[&1], -3);
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn collect<C>(self) -> WithIndex<Many<T, Enumerate<C>>>
+    where
+        C: Extend<(usize, O)>,
+    {
+        let Many {
+            item,
+            min,
+            max,
+            max_iterations,
+            collection: _,
+        } = self.sequence;
+
+        WithIndex {
+            sequence: Many {
+                item,
+                min,
+                max,
+                max_iterations,
+                collection: PhantomData::<fn() -> Enumerate<C>>,
+            },
         }
     }
 }
@@ -301,7 +762,7 @@ mod tests {
 
     use super::*;
     use crate::char;
-    use crate::test_utils::*;
+    use crate::testing::*;
 
     #[derive(PartialEq, Debug, Clone)]
     struct A;
@@ -363,6 +824,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn all_items() {
+        let item = || char('a').try_map(A::from_str).then_skip(char(','));
+
+        test_parser_batch(
+            "2..=3 items and then the end of input",
+            item().all(2..=3),
+            &[
+                ("", None, ""),
+                ("a,", None, "a,"),
+                ("a,a,", Some(vec![A, A]), ""),
+                ("a,a,a,", Some(vec![A, A, A]), ""),
+                ("a,a,a,a,", None, "a,a,a,a,"),
+                ("a,a,b", None, "a,a,b"),
+            ],
+        );
+
+        test_parser_batch(
+            ".. matches no items at the end of input",
+            all(.., item()),
+            &[("", Some(vec![]), ""), ("b", None, "b")],
+        );
+    }
+
+    #[test]
+    fn reason_for_stopping() {
+        let byte = || crate::digit().many(1..=3).try_map(u8::from_str);
+        let list = || byte().then_skip(char(',')).many(..);
+        let bytes = list().then_skip(char(';'));
+        let list_of_two = || byte().then_skip(char(',')).many(2..);
+
+        assert_eq!(bytes.parse("1,2,;"), Ok((vec![1, 2], "")));
+        assert_eq!(bytes.parse("1,2,"), Err(Error::NoMatch));
+        assert_eq!(
+            bytes.parse("1,300,;"),
+            Err(crate::error::failed_conversion(
+                "300",
+                "number too large to fit in target type"
+            ))
+        );
+
+        // too few items
+        assert_eq!(
+            list_of_two().parse("7,256,"),
+            Err(crate::error::failed_conversion(
+                "256",
+                "number too large to fit in target type"
+            ))
+        );
+        assert_eq!(list_of_two().parse("7,x,"), Err(Error::NoMatch));
+
+        // an optional item in between doesn't hide the reason
+        let then_letters = list()
+            .then(crate::alpha().many(1..).map(str::to_string).optional())
+            .then_skip(char(';'));
+        assert_eq!(
+            then_letters.parse("1,300,;"),
+            Err(crate::error::failed_conversion(
+                "300",
+                "number too large to fit in target type"
+            ))
+        );
+    }
+
     #[test]
     fn min_and_max_lex() {
         test_lexer_batch(
@@ -406,4 +931,164 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn explicit_bounds() {
+        test_lexer_batch(
+            "at_least(2) matches 2 or more times",
+            many::<_, char>(.., char('a')).at_least(2),
+            &[
+                ("a", None, "a"),
+                ("aab", Some("aa"), "b"),
+                ("aaaa", Some("aaaa"), ""),
+            ],
+        );
+
+        test_lexer_batch(
+            "at_most(2) matches 0, 1 or 2 times",
+            many::<_, char>(.., char('a')).at_most(2),
+            &[
+                ("", Some(""), ""),
+                ("ab", Some("a"), "b"),
+                ("aaa", Some("aa"), "a"),
+            ],
+        );
+
+        test_lexer_batch(
+            "at_most(2) after at_least(5) lowers the minimum",
+            many::<_, char>(.., char('a')).at_least(5).at_most(2),
+            &[("", None, ""), ("a", None, "a"), ("aaa", Some("aa"), "a")],
+        );
+
+        test_lexer_batch(
+            "at_least(5) after at_most(2) raises the maximum",
+            many::<_, char>(.., char('a')).at_most(2).at_least(5),
+            &[("aaaa", None, "aaaa"), ("aaaaaa", Some("aaaaa"), "a")],
+        );
+
+        test_lexer_batch(
+            "exactly(2) matches 2 times",
+            many::<_, char>(1..5, char('a')).exactly(2),
+            &[
+                ("a", None, "a"),
+                ("aa", Some("aa"), ""),
+                ("aaa", Some("aa"), "a"),
+            ],
+        );
+    }
+
+    #[test]
+    fn mapping_items() -> Result<(), Error> {
+        use crate::{digit, switch};
+
+        let number = || digit().parse_str::<u8>();
+
+        test_parser_batch(
+            "digits doubled",
+            number().many(1..=3).map_items(|n| n * 2),
+            &[
+                ("123", Some(vec![2, 4, 6]), ""),
+                ("1234", Some(vec![2, 4, 6]), "4"),
+                ("x", None, "x"),
+            ],
+        );
+
+        let strings = number()
+            .many(..)
+            .delimiter(char(','))
+            .map_items(|n| n.to_string());
+        assert_eq!(
+            strings.parse("1,2,")?,
+            (vec!["1".to_string(), "2".to_string()], "")
+        );
+
+        // the mapped items can be collected into a different collection
+        let digits = number()
+            .many(..)
+            .map_items(u32::from)
+            .collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(digits.parse("3133")?.0, [1, 3].into());
+
+        // the separators are left as they are
+        let signed = number()
+            .many(1..)
+            .interleave(switch([("+", 1), ("-", -1)]))
+            .map_items(i32::from);
+        assert_eq!(signed.parse("1+2-3")?, ((vec![1, 2, 3], vec![1, -1]), ""));
+
+        Ok(())
+    }
+
+    #[test]
+    fn indexing_items() -> Result<(), Error> {
+        use std::collections::BTreeMap;
+
+        use crate::digit;
+
+        let number = || digit().parse_str::<u8>();
+
+        test_parser_batch(
+            "digits with their index",
+            number().many(2..=3).with_index(),
+            &[
+                ("789", Some(vec![(0, 7), (1, 8), (2, 9)]), ""),
+                ("7890", Some(vec![(0, 7), (1, 8), (2, 9)]), "0"),
+                ("7", None, "7"),
+            ],
+        );
+
+        test_parser_batch(
+            "delimited digits with their index",
+            number().many(..).delimiter(char(',')).with_index(),
+            &[
+                ("5,6", Some(vec![(0, 5), (1, 6)]), ""),
+                ("", Some(vec![]), ""),
+            ],
+        );
+
+        let indexed = number()
+            .many(..)
+            .map_items(|n| n * 10)
+            .with_index()
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(indexed.parse("12")?.0, BTreeMap::from([(0, 10), (1, 20)]));
+
+        // each parse counts from 0 again
+        assert_eq!(indexed.parse("3")?.0, BTreeMap::from([(0, 30)]));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn collecting_into_smallvec() -> Result<(), Error> {
+        use smallvec::{smallvec, SmallVec};
+
+        use crate::{digit, unparse::Unparse};
+
+        let small = digit()
+            .parse_str::<u8>()
+            .many(..)
+            .delimiter(char(','))
+            .collect::<SmallVec<[u8; 2]>>();
+        let expected: SmallVec<[u8; 2]> = smallvec![1, 2];
+
+        let (output, _) = small.parse("1,2")?;
+        assert_eq!(output, expected);
+        assert!(!output.spilled());
+
+        // more outputs than fit inline spill onto the heap
+        let (output, _) = small.parse("1,2,3")?;
+        assert_eq!(output.as_slice(), [1, 2, 3]);
+        assert!(output.spilled());
+
+        assert_eq!(small.unparse(&expected)?, "1,2");
+        let digits = digit()
+            .parse_str::<u8>()
+            .many(1..)
+            .collect::<SmallVec<[u8; 2]>>();
+        assert_eq!(digits.unparse(&expected)?, "12");
+
+        Ok(())
+    }
 }
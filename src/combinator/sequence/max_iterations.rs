@@ -0,0 +1,178 @@
+//! [`max_iterations()`] caps how many times repetitions can match, so untrusted input can't make them run unreasonably long.
+
+use std::{cell::Cell, fmt};
+
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+thread_local! {
+    /// The cap for repetitions without their own, while a [`MaxIterations`] is running.
+    static DEFAULT: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Restores the previous cap when dropped, even if parsing panics.
+struct Restore(Option<usize>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        DEFAULT.with(|default| default.set(self.0));
+    }
+}
+
+/// The cap of a repetition: its own cap, or else that of the running [`max_iterations()`].
+///
+/// The sequence combinators call this once before matching their item, as the running cap is the same for all of their iterations.
+#[inline]
+pub(crate) fn iteration_cap(own: Option<usize>) -> Option<usize> {
+    own.or_else(|| DEFAULT.with(Cell::get))
+}
+
+/// Checks that a repetition that has now matched `count` times is within its cap, see [`iteration_cap()`].
+///
+/// The sequence combinators call this each time their item matches.
+#[inline]
+pub(crate) fn check_iterations(count: usize, cap: Option<usize>) -> Result<(), Error> {
+    match cap {
+        Some(cap) if count > cap => Err(Error::IterationsExceeded(cap).fatal()),
+        _ => Ok(()),
+    }
+}
+
+/// This combinator is returned by [`max_iterations()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct MaxIterations<T> {
+    cap: usize,
+    item: T,
+}
+
+impl<T> MaxIterations<T> {
+    fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+        let previous = DEFAULT.with(Cell::get);
+        let cap = previous.map_or(self.cap, |previous| previous.min(self.cap));
+
+        DEFAULT.with(|default| default.set(Some(cap)));
+        let _restore = Restore(previous);
+
+        f()
+    }
+}
+
+impl<L: Lex> Lex for MaxIterations<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        self.run(|| self.item.lex(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.item.canonical_into(output)
+    }
+}
+
+impl<P: Parse> Parse for MaxIterations<P> {
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        self.run(|| self.item.parse(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+impl<P: Unparse> Unparse for MaxIterations<P> {
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.item.unparse_into(value, output)
+    }
+}
+
+/// Fails with [`Error::IterationsExceeded`] if any repetition inside the item (a parser or lexer) matches more than `cap` times.
+///
+/// This sets the default for every [`many()`](super::many()), [`delimiter()`](super::Many::delimiter()) and [`interleave()`](super::Many::interleave()) inside the item.
+/// A repetition with its own cap, set with e.g. [`Many::max_iterations()`](super::Many::max_iterations()), uses that instead.
+///
+/// Without a cap, a repetition with an open range such as `many(1..)` keeps matching as long as its item does.
+/// Capping it bounds the work done, and the size of the output, when parsing untrusted input.
+///
+/// The error is [fatal](crate::Error::fatal()), so parsing stops as soon as the cap is exceeded.
+/// A cap inside another cap can't allow more iterations than the outer cap.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, int, Error, Parse};
+/// use parsely::combinator::max_iterations;
+///
+/// let list = max_iterations(3, int::<u8>().many(..).delimiter(char(',')));
+///
+/// assert_eq!(list.parse("1,2,3")?, (vec![1, 2, 3], ""));
+/// assert_eq!(list.parse("1,2,3,4").unwrap_err().root(), &Error::IterationsExceeded(3));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn max_iterations<T>(cap: usize, item: T) -> MaxIterations<T> {
+    MaxIterations { cap, item }
+}
+
+impl<T: fmt::Debug> fmt::Debug for MaxIterations<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MaxIterations({}, {:?})", self.cap, self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, digit, int, switch, Lex};
+
+    #[test]
+    fn capping_repetitions() {
+        let exceeded = |cap| Error::IterationsExceeded(cap).fatal();
+
+        let digits = || digit().many(..);
+        assert_eq!(max_iterations(3, digits()).lex("123"), Ok(("123", "")));
+        assert_eq!(max_iterations(3, digits()).lex("1234"), Err(exceeded(3)));
+
+        // a repetition's own cap is used instead
+        let own = max_iterations(3, digits().max_iterations(5));
+        assert_eq!(own.lex("12345"), Ok(("12345", "")));
+
+        // nested caps can't loosen the outer cap
+        let nested = max_iterations(2, max_iterations(5, digits()));
+        assert_eq!(nested.lex("123"), Err(exceeded(2)));
+
+        let delimited = int::<u8>().many(..).delimiter(char(',')).max_iterations(2);
+        assert_eq!(delimited.parse("1,2"), Ok((vec![1, 2], "")));
+        assert_eq!(delimited.parse("1,2,3"), Err(exceeded(2)));
+
+        let sum = int::<i32>()
+            .many(..)
+            .interleave(switch([("+", 1)]))
+            .max_iterations(2);
+        assert_eq!(sum.parse("1+2"), Ok(((vec![1, 2], vec![1]), "")));
+        assert_eq!(sum.parse("1+2+3"), Err(exceeded(2)));
+        assert_eq!(
+            digit()
+                .many(..)
+                .interleave(char('+'))
+                .max_iterations(0)
+                .lex("1"),
+            Err(exceeded(0))
+        );
+
+        // the cap is only in effect while the item runs
+        assert_eq!(digits().lex("1234"), Ok(("1234", "")));
+    }
+}
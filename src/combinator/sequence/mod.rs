@@ -3,6 +3,10 @@
 //! * [`many()`] - match multiple times
 //! * [`count()`] - match exactly n times
 //! * [`.many().delimiter(lexer)`](many::Many::delimiter) - match multiple times, separated by something
+//! * [`.many().interleave(parser)`](many::Many::interleave) - match multiple times, separated by something that is kept in the output
+//! * [`.many().for_each(f)`](many::Many::for_each) - match multiple times, handing each output to a closure instead of collecting them
+//! * [`.many().map_items(f)`](many::Many::map_items) - match multiple times, transforming each output before it is collected
+//! * [`.many().with_index()`](many::Many::with_index) - match multiple times, pairing each output with its index
 //!
 //! You might not need a sequence combinator. To match something and then another thing, see the humble [`then()`](crate::combinator::then()).
 //!
@@ -53,19 +57,63 @@
 //!
 //! This reflects the way [`std::ops::Range`] works with inclusive and exclusive bounds.
 //!
+//! If you'd rather spell it out, the bounds can also be set with [`Many::at_least()`], [`Many::at_most()`] and [`Many::exactly()`]:
+//!
+//! ```
+//! # use parsely::{digit, Lex};
+//! // the same as digit().many(3..=5)
+//! digit().many(..).at_least(3).at_most(5);
+//! ```
+//!
+//! # Errors
+//!
+//! A repetition stops as soon as its item fails to match, and that isn't an error as long as it matched enough times.
+//! If the item failed for a more specific reason than not matching, such as a [`try_map()`](crate::Lex::try_map()) conversion failing,
+//! that reason is reported if the repetition matched too few times, or if what follows it in a sequence then doesn't match:
+//!
+//! ```
+//! use parsely::{char, digit, Error, Lex, Parse};
+//!
+//! let byte = digit().many(1..=3).try_map(|digits| digits.parse::<u8>());
+//! let list = byte.then_skip(char(',')).many(..).pad_with(char('['), char(']'));
+//!
+//! assert_eq!(list.parse("[1,2,]")?, (vec![1, 2], ""));
+//!
+//! // rather than Error::NoMatch for the ']' that was expected after "2,"
+//! assert!(matches!(list.parse("[1,2,300,]"), Err(Error::FailedConversion { .. })));
+//! # Ok::<(), parsely::Error>(())
+//! ```
+//!
 //! [^max]: open-ended ranges limit themselves to matching `isize::MAX / 2` times, which for most purposes is more than plenty!
+//! To fail sooner on untrusted input, cap the number of iterations with [`Many::max_iterations()`] or [`max_iterations()`].
 mod delimited;
+mod for_each;
+mod interleave;
 mod many;
+mod max_iterations;
+mod stopped;
+mod with_index;
 
 use std::ops::{Bound, RangeBounds};
 
 pub use delimited::{delimited, Delimited};
+pub use for_each::{for_each, ForEach};
+pub use interleave::{interleave, Interleave};
 pub(crate) use many::LexMany;
-pub use many::{count, many, Many};
+pub use many::{all, count, many, Many};
+pub(crate) use max_iterations::{check_iterations, iteration_cap};
+pub use max_iterations::{max_iterations, MaxIterations};
+pub(crate) use stopped::{sequence, stopped, too_few};
+pub use with_index::{Enumerate, WithIndex};
 
 /// The maximum number of times to attempt to match a repeated parser and the implicit maximum for an open range.
 pub(crate) const MAX_LIMIT: usize = (isize::MAX / 2) as usize;
 
+/// The maximum as used by a [`Grammar`](crate::grammar::Grammar), where there is no maximum for an open range.
+pub(crate) fn bounded(max: usize) -> Option<usize> {
+    (max != MAX_LIMIT).then_some(max)
+}
+
 pub(crate) fn min_max_from_bounds(range: impl RangeBounds<usize>) -> (usize, usize) {
     let min = match range.start_bound() {
         Bound::Included(&n) => n,
@@ -0,0 +1,118 @@
+//! Keeping the reason a repetition stopped, so that a sequence can report it rather than [`Error::NoMatch`].
+//!
+//! A repetition such as `many(..)` stops as soon as its item fails, and succeeds as long as it matched enough times.
+//! If the item failed for a more specific reason than not matching, e.g. an invalid escape sequence that failed to convert,
+//! whatever comes next in the sequence usually fails right where the repetition stopped, with a much less helpful [`Error::NoMatch`].
+//!
+//! Within an [`or()`](crate::combinator::or()) that defers merging the errors of its alternatives, the reason may say less than it would otherwise.
+//! Such a reason is kept even if it is [`Error::NoMatch`], and if it is reported, the repetition is run again with errors merged to find the full reason.
+
+use std::cell::{Cell, RefCell};
+
+use crate::{
+    combinator::or::{is_deferred, with_merged_errors},
+    Error,
+};
+
+/// Why a repetition stopped, and the remaining input it stopped at.
+struct Stopped {
+    address: usize,
+    len: usize,
+    error: Error,
+
+    /// Whether an enclosing `or` deferred merging errors when the repetition stopped, see [`is_deferred()`].
+    deferred: bool,
+}
+
+impl Stopped {
+    fn at(&self, remaining: &str) -> bool {
+        self.address == remaining.as_ptr() as usize && self.len == remaining.len()
+    }
+}
+
+thread_local! {
+    static STOPPED: RefCell<Option<Stopped>> = const { RefCell::new(None) };
+
+    /// Whether `STOPPED` holds anything, which is much cheaper to check on every sequence.
+    static KEPT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The error a repetition fails with when it matched fewer times than its minimum, given the error that stopped it.
+pub(crate) fn too_few(stopped: Option<Error>) -> Error {
+    match stopped {
+        Some(Error::NoMatch) | None => Error::NoMatch,
+        Some(error) => error,
+    }
+}
+
+/// Records that a repetition stopped at `remaining` because its item failed with `error`.
+///
+/// Only errors more specific than [`Error::NoMatch`] are kept, for the enclosing [`sequence()`] to report,
+/// unless errors aren't being merged, in which case the error might have been more specific.
+pub(crate) fn stopped(remaining: &str, error: Option<Error>) {
+    let deferred = is_deferred();
+    let Some(error) = error.filter(|error| *error != Error::NoMatch || deferred) else {
+        return;
+    };
+
+    keep(Stopped {
+        address: remaining.as_ptr() as usize,
+        len: remaining.len(),
+        error,
+        deferred,
+    });
+}
+
+fn keep(stop: Stopped) {
+    STOPPED.with(|stopped| *stopped.borrow_mut() = Some(stop));
+    KEPT.with(|kept| kept.set(true));
+}
+
+#[inline]
+fn take() -> Option<Stopped> {
+    if !KEPT.with(Cell::get) {
+        return None;
+    }
+
+    KEPT.with(|kept| kept.set(false));
+    STOPPED.with(|stopped| stopped.borrow_mut().take())
+}
+
+/// Runs `first` on the input and then `second` on what remains, as the combinators that run items one after another do.
+///
+/// If `second` doesn't match where a repetition at the end of `first` stopped, the reason the repetition stopped is returned instead.
+pub(crate) fn sequence<'i, A, B>(
+    input: &'i str,
+    first: impl Fn(&'i str) -> Result<(A, &'i str), Error>,
+    second: impl FnOnce(&'i str) -> Result<(B, &'i str), Error>,
+) -> Result<((A, B), &'i str), Error> {
+    // only repetitions within this sequence are relevant
+    take();
+
+    let (a, remaining) = first(input)?;
+    let stop = take().filter(|stop| stop.at(remaining));
+
+    match second(remaining) {
+        Ok((b, after)) => {
+            // e.g. an optional item matched nothing, so the repetition's reason is still relevant to what comes next
+            // unless `second` has a reason of its own
+            if let Some(stop) = stop.filter(|stop| stop.at(after) && !KEPT.with(Cell::get)) {
+                keep(stop);
+            }
+            Ok(((a, b), after))
+        }
+        Err(Error::NoMatch) => match stop {
+            // the reason can reach the caller now, so find out all it has to say
+            Some(stop) if stop.deferred && !is_deferred() => with_merged_errors(|| {
+                take();
+                let _ = first(input);
+                Err(take()
+                    .filter(|stop| stop.at(remaining))
+                    .map_or(Error::NoMatch, |stop| stop.error))
+            }),
+            Some(stop) => Err(stop.error),
+            None => Err(Error::NoMatch),
+        },
+        Err(error) => Err(error),
+    }
+}
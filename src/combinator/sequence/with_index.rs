@@ -0,0 +1,57 @@
+//! [`.many(..).with_index()`](super::many::Many::with_index()) collects each item along with its index in the sequence.
+
+use std::fmt;
+
+use crate::{grammar::Grammar, Parse, ParseResult};
+
+/// A collection that pairs each item extended into it with its index, before extending `C` with the pair.
+///
+/// This is what [`WithIndex`] collects into, so that the sequence it wraps counts its items as it collects them.
+#[derive(Clone, Debug, Default)]
+pub struct Enumerate<C> {
+    next: usize,
+    items: C,
+}
+
+impl<O, C> Extend<O> for Enumerate<C>
+where
+    C: Extend<(usize, O)>,
+{
+    fn extend<I: IntoIterator<Item = O>>(&mut self, iter: I) {
+        for item in iter {
+            self.items.extend(Some((self.next, item)));
+            self.next += 1;
+        }
+    }
+}
+
+/// This type alias is what [`WithIndex`] collects into by default, a `Vec` of `(index, output)` pairs.
+pub(crate) type IndexedVec<O> = Enumerate<Vec<(usize, O)>>;
+
+/// This combinator is returned by [`Many::with_index()`](super::many::Many::with_index()). See it's documentation for more details.
+#[derive(Clone)]
+pub struct WithIndex<P> {
+    pub(super) sequence: P,
+}
+
+impl<P, C> Parse for WithIndex<P>
+where
+    P: Parse<Output = Enumerate<C>>,
+{
+    type Output = C;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (enumerate, remaining) = self.sequence.parse(input)?;
+        Ok((enumerate.items, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.sequence.describe()
+    }
+}
+
+impl<P: fmt::Debug> fmt::Debug for WithIndex<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WithIndex({:?})", self.sequence)
+    }
+}
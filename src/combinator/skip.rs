@@ -53,8 +53,35 @@
 //! assert_eq!(hex_rgb.parse("#AABBCC")?, (Rgb { red: 170, green: 187, blue: 204 }, ""));
 //! # Ok::<(), parsely::Error>(())
 //! ```
+//!
+//! # Lexers followed by lexers
+//!
+//! When both sides are lexers, the skipped input is consumed but is not part of the matched input:
+//! `lexer_a.then_skip(lexer_b)` matches only what `lexer_a` matched, and the remaining input starts after what `lexer_b` matched.
+//! Likewise `lexer_a.skip_then(lexer_b)` matches only what `lexer_b` matched.
+//!
+//! So the matched input isn't always the same as the input consumed.
+//! If you want the matched input to cover both lexers, use [`Lex::then()`] instead:
+//!
+//! ```
+//! use parsely::{char, digit, Lex};
+//!
+//! let skipped = digit().many(1..).then_skip(char(';'));
+//! assert_eq!(skipped.lex("123;rest")?, ("123", "rest"));
+//!
+//! let consumed = digit().many(1..).then(char(';'));
+//! assert_eq!(consumed.lex("123;rest")?, ("123;", "rest"));
+//! # Ok::<(), parsely::Error>(())
+//! ```
 
-use crate::{Lex, Parse};
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, Parse,
+};
+
+use super::{sequence::sequence, ws_insensitive::skip};
 
 /// This combinator is returned by [`then_skip()`]. See it's documentation for more details.
 #[derive(Debug, Clone)]
@@ -67,30 +94,62 @@ pub struct ThenSkip<L, T> {
 ///
 /// If the lexer fails, it is still a parse failure. Use `.optional()` if the input to be skipped isn't required.
 ///
-/// This combinator can be chained using [`Parse::then_skip()`].
-//
-// TODO: what actually happens if you do a then_skip(lexer_a, lexer_b) ???
+/// When the item is a lexer, its matched input is the output: what the given lexer matched is consumed but not included.
+/// Use [`Lex::then()`] for a lexer whose matched input includes both, see the [module documentation](self) for more details.
+///
+/// This combinator can be chained using [`Parse::then_skip()`] or [`Lex::then_skip()`].
 pub fn then_skip<L: Lex, T>(lexer: L, item: T) -> ThenSkip<L, T> {
     ThenSkip { lexer, item }
 }
 
 impl<L: Lex, T: Lex> Lex for ThenSkip<L, T> {
     fn lex<'i>(&self, input: &'i str) -> crate::LexResult<'i> {
-        let (output, remaining) = self.item.lex(input)?;
-        let (_, remaining) = self.lexer.lex(remaining)?;
+        let ((output, _), remaining) = sequence(
+            input,
+            |input| self.item.lex(input),
+            |remaining| self.lexer.lex(skip(remaining)),
+        )?;
         Ok((output, remaining))
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::then(self.item.describe(), self.lexer.describe())
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)?;
+        self.lexer.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.item.canonical_into(output)?;
+        self.lexer.canonical_into(output)
+    }
 }
 
 impl<L: Lex, T: Parse> Parse for ThenSkip<L, T> {
     type Output = <T as Parse>::Output;
 
     fn parse<'i>(&self, input: &'i str) -> crate::ParseResult<'i, Self::Output> {
-        let (output, remaining) = self.item.parse(input)?;
-        let (_, remaining) = self.lexer.lex(remaining)?;
+        let ((output, _), remaining) = sequence(
+            input,
+            |input| self.item.parse(input),
+            |remaining| self.lexer.lex(skip(remaining)),
+        )?;
 
         Ok((output, remaining))
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::then(self.item.describe(), self.lexer.describe())
+    }
+}
+
+impl<L: Lex, T: Unparse> Unparse for ThenSkip<L, T> {
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.item.unparse_into(value, output)?;
+        self.lexer.canonical_into(output)
+    }
 }
 
 /// This combinator is returned by [`skip_then()`]. See it's documentation for more details.
@@ -104,6 +163,8 @@ pub struct SkipThen<L, T> {
 ///
 /// If the lexer fails, it is still a parse failure. Use `.optional()` if the input to be skipped isn't required.
 ///
+/// When the item is a lexer, only its matched input is the output, see the [module documentation](self) for more details.
+///
 /// This combinator can be chained using [`Lex::skip_then()`].
 pub fn skip_then<L: Lex, T>(lexer: L, item: T) -> SkipThen<L, T> {
     SkipThen { lexer, item }
@@ -111,21 +172,52 @@ pub fn skip_then<L: Lex, T>(lexer: L, item: T) -> SkipThen<L, T> {
 
 impl<L: Lex, T: Lex> Lex for SkipThen<L, T> {
     fn lex<'i>(&self, input: &'i str) -> crate::LexResult<'i> {
-        let (_, remaining) = self.lexer.lex(input)?;
-        let (output, remaining) = self.item.lex(remaining)?;
+        let ((_, output), remaining) = sequence(
+            input,
+            |input| self.lexer.lex(input),
+            |remaining| self.item.lex(skip(remaining)),
+        )?;
         Ok((output, remaining))
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::then(self.lexer.describe(), self.item.describe())
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.lexer.sample_into(sampler, output)?;
+        self.item.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.lexer.canonical_into(output)?;
+        self.item.canonical_into(output)
+    }
 }
 
 impl<L: Lex, T: Parse> Parse for SkipThen<L, T> {
     type Output = <T as Parse>::Output;
 
     fn parse<'i>(&self, input: &'i str) -> crate::ParseResult<'i, Self::Output> {
-        let (_, remaining) = self.lexer.lex(input)?;
-        let (output, remaining) = self.item.parse(remaining)?;
+        let ((_, output), remaining) = sequence(
+            input,
+            |input| self.lexer.lex(input),
+            |remaining| self.item.parse(skip(remaining)),
+        )?;
 
         Ok((output, remaining))
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::then(self.lexer.describe(), self.item.describe())
+    }
+}
+
+impl<L: Lex, T: Unparse> Unparse for SkipThen<L, T> {
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.lexer.canonical_into(output)?;
+        self.item.unparse_into(value, output)
+    }
 }
 
 #[cfg(test)]
@@ -139,6 +231,16 @@ mod test {
 
         assert_eq!(test.lex("abc...")?, ("abc", ""));
 
+        // the skipped input is consumed, but only `then` includes it in the matched input
+        assert_eq!(
+            token("abc").then_skip(token("...")).lex("abc...!")?,
+            ("abc", "!")
+        );
+        assert_eq!(
+            token("abc").then(token("...")).lex("abc...!")?,
+            ("abc...", "!")
+        );
+
         let test = then_skip(char('.').many(1..=3), token("abc"));
 
         assert_eq!(test.lex("abc"), Err(crate::Error::NoMatch));
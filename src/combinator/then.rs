@@ -1,6 +1,13 @@
 use std::fmt;
 
-use crate::{End, Lex, LexResult, Parse, ParseResult};
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    End, Error, Lex, LexResult, Parse, ParseResult,
+};
+
+use super::{instrument::instrument, sequence::sequence, ws_insensitive::skip};
 
 /// This combinator is returned by [`then()`]. See it's documentation for more details.
 #[derive(Clone)]
@@ -30,13 +37,33 @@ where
     type Output = (<L as Parse>::Output, <R as Parse>::Output);
 
     fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
-        let (left, remaining) = self.left.parse(input)?;
-        let (right, remaining) = self.right.parse(remaining)?;
+        instrument("then", input, || {
+            let ((left, right), remaining) = sequence(
+                input,
+                |input| self.left.parse(input),
+                |remaining| self.right.parse(skip(remaining)),
+            )?;
+
+            let boundary = input.len() - remaining.len();
+            let (_, remaining) = input.split_at(boundary);
+
+            Ok(((left, right), remaining))
+        })
+    }
 
-        let boundary = input.len() - remaining.len();
-        let (_, remaining) = input.split_at(boundary);
+    fn describe(&self) -> Grammar {
+        Grammar::then(self.left.describe(), self.right.describe())
+    }
+}
 
-        Ok(((left, right), remaining))
+impl<L, R> Unparse for Then<L, R>
+where
+    L: Unparse,
+    R: Unparse,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.left.unparse_into(&value.0, output)?;
+        self.right.unparse_into(&value.1, output)
     }
 }
 
@@ -47,20 +74,58 @@ where
     type Output = <L as Parse>::Output;
 
     fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
-        let (left, remaining) = self.left.parse(input)?;
-        let (_, remaining) = self.right.lex(remaining)?;
+        instrument("then", input, || {
+            let ((left, _), remaining) = sequence(
+                input,
+                |input| self.left.parse(input),
+                |remaining| self.right.lex(skip(remaining)),
+            )?;
+
+            Ok((left, remaining))
+        })
+    }
 
-        Ok((left, remaining))
+    fn describe(&self) -> Grammar {
+        Grammar::then(self.left.describe(), self.right.describe())
+    }
+}
+
+impl<L> Unparse for Then<L, End>
+where
+    L: Unparse,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.left.unparse_into(value, output)
     }
 }
 
 impl<L: Lex, R: Lex> Lex for Then<L, R> {
     fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
-        let (left, remaining) = self.left.lex(input)?;
-        let (right, _) = self.right.lex(remaining)?;
+        instrument("then", input, || {
+            let (_, remaining) = sequence(
+                input,
+                |input| self.left.lex(input),
+                |remaining| self.right.lex(skip(remaining)),
+            )?;
+
+            // everything consumed by either lexer is matched, including any input they skipped such as padding
+            let boundary = input.len() - remaining.len();
+            Ok(input.split_at(boundary))
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::then(self.left.describe(), self.right.describe())
+    }
 
-        let boundary = left.len() + right.len();
-        Ok(input.split_at(boundary))
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.left.sample_into(sampler, output)?;
+        self.right.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.left.canonical_into(output)?;
+        self.right.canonical_into(output)
     }
 }
 
@@ -78,7 +143,7 @@ where
 mod tests {
     use super::*;
     use crate::lexer::{char, token};
-    use crate::test_utils::*;
+    use crate::testing::*;
     use crate::{int, Lex, Parse};
 
     #[test]
@@ -97,6 +162,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lexing_consumes_skipped_input() {
+        test_lexer_batch(
+            "padded lexers in a then chain",
+            then(char('a').pad(), char('b').pad()),
+            &[
+                (" a b ", Some(" a b "), ""), //
+                ("ab", Some("ab"), ""),
+                (
+                    "a	b
+;",
+                    Some(
+                        "a	b
+",
+                    ),
+                    ";",
+                ),
+                ("a c", None, "a c"),
+            ],
+        );
+
+        test_lexer_batch(
+            "then_skip in a then chain",
+            then(token("foo"), char('X').then_skip(char(';'))),
+            &[
+                ("fooX;123", Some("fooX;"), "123"), //
+                ("fooX123", None, "fooX123"),
+            ],
+        );
+    }
+
     #[derive(Debug, PartialEq)]
     pub enum Color {
         Red,
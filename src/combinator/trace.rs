@@ -0,0 +1,285 @@
+//! Record every attempt a parse makes as a tree, to find out why a grammar took the path it did.
+//!
+//! Run the parse inside [`trace()`], and afterwards the [`Trace`] holds an [`Attempt`] for each combinator that was tried,
+//! such as [`or()`](crate::combinator::or()), [`then()`](crate::combinator::then()) and [`many()`](crate::combinator::many()),
+//! and for each parser or lexer labelled with [`.name()`](crate::Lex::name()).
+//! Each attempt says where in the input it started, whether it matched, and holds the attempts made inside it.
+//!
+//! The Display impl of [`Trace`] renders it as an indented tree.
+//!
+//! Naming the parts of the grammar you are interested in makes the tree much easier to follow.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! use parsely::{char, digit, Lex};
+//! use parsely::combinator::trace;
+//!
+//! let number = digit().many(1..).name("number");
+//! let sign = char('-').name("sign");
+//! let lexer = number.or(sign.then(digit()));
+//!
+//! let input = "-7";
+//! let (result, trace) = trace(input, || lexer.lex(input));
+//! assert_eq!(result?, ("-7", ""));
+//!
+//! assert_eq!(
+//!     trace.to_string(),
+//!     "\
+//! or at 0: matched 2 bytes
+//!   number at 0: failed: No Match
+//!     many at 0: failed: No Match
+//!   then at 0: matched 2 bytes
+//!     sign at 0: matched 1 bytes
+//! "
+//! );
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::{cell::RefCell, fmt};
+
+use crate::Error;
+
+use super::active;
+
+/// How an [`Attempt`] turned out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// It matched this many bytes of input.
+    Matched(usize),
+
+    /// It failed with this error, formatted with its Display impl.
+    Failed(String),
+}
+
+/// One attempt of a combinator or named parser, recorded by [`trace()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    /// The name of the combinator, or the name given with [`.name()`](crate::Lex::name()).
+    pub label: &'static str,
+
+    /// The byte offset into the input given to [`trace()`] where the attempt started.
+    pub offset: usize,
+
+    /// Whether it matched.
+    pub outcome: Outcome,
+
+    /// The attempts made while running this one, in the order they were made.
+    pub children: Vec<Attempt>,
+}
+
+impl Attempt {
+    fn render(&self, depth: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}{} at {}: ",
+            "",
+            self.label,
+            self.offset,
+            indent = depth * 2
+        )?;
+
+        match &self.outcome {
+            Outcome::Matched(len) => writeln!(f, "matched {len} bytes")?,
+            Outcome::Failed(error) => writeln!(f, "failed: {error}")?,
+        }
+
+        self.children
+            .iter()
+            .try_for_each(|child| child.render(depth + 1, f))
+    }
+}
+
+/// The tree of attempts recorded while running [`trace()`].
+///
+/// See [the module documentation](self) for more details.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    roots: Vec<Attempt>,
+}
+
+impl Trace {
+    /// The attempts that weren't made inside another attempt, in the order they were made.
+    pub fn roots(&self) -> &[Attempt] {
+        &self.roots
+    }
+
+    /// Whether nothing was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.roots.iter().try_for_each(|root| root.render(0, f))
+    }
+}
+
+/// The attempts recorded by the innermost running [`trace()`].
+struct Recorder {
+    /// The address of the input, used to find the offset of an attempt within it.
+    address: usize,
+    len: usize,
+
+    /// The attempts that haven't finished yet, innermost last.
+    open: Vec<Attempt>,
+    roots: Vec<Attempt>,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous recorder when dropped, even if parsing panics.
+struct Restore(Option<Recorder>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        RECORDER.with(|recorder| *recorder.borrow_mut() = self.0.take());
+    }
+}
+
+/// Runs `f`, recording every attempt made within `input` as a tree.
+///
+/// `input` should be the same `&str` that `f` parses, not just an equal one, so that attempts can be placed within it.
+/// Attempts on any other input, e.g. one built while parsing, aren't recorded.
+///
+/// The trace is returned along with the result of `f`, so it is available even if parsing failed.
+///
+/// See [the module documentation](self) for more details.
+pub fn trace<T>(input: &str, f: impl FnOnce() -> T) -> (T, Trace) {
+    let recorder = Recorder {
+        address: input.as_ptr() as usize,
+        len: input.len(),
+        open: Vec::new(),
+        roots: Vec::new(),
+    };
+    let restore = Restore(RECORDER.with(|current| current.borrow_mut().replace(recorder)));
+    let active = active::Active::start();
+
+    let output = f();
+    let roots = RECORDER.with(|current| {
+        current
+            .borrow_mut()
+            .as_mut()
+            .map(|recorder| std::mem::take(&mut recorder.roots))
+            .unwrap_or_default()
+    });
+    drop(active);
+    drop(restore);
+
+    (output, Trace { roots })
+}
+
+/// Runs an attempt with this label, recording it if [`trace()`] is running with an input that contains `input`.
+pub(crate) fn record<'i, O>(
+    label: &'static str,
+    input: &'i str,
+    f: impl FnOnce() -> Result<(O, &'i str), Error>,
+) -> Result<(O, &'i str), Error> {
+    if !active::is_active() {
+        return f();
+    }
+
+    let recording = RECORDER.with(|recorder| {
+        let mut recorder = recorder.borrow_mut();
+        let Some(recorder) = recorder.as_mut() else {
+            return false;
+        };
+
+        let address = input.as_ptr() as usize;
+        if address < recorder.address || address + input.len() != recorder.address + recorder.len {
+            return false;
+        }
+
+        recorder.open.push(Attempt {
+            label,
+            offset: address - recorder.address,
+            outcome: Outcome::Matched(0),
+            children: Vec::new(),
+        });
+        true
+    });
+
+    let result = f();
+
+    if recording {
+        RECORDER.with(|recorder| {
+            let mut recorder = recorder.borrow_mut();
+            let Some(recorder) = recorder.as_mut() else {
+                return;
+            };
+            let Some(mut attempt) = recorder.open.pop() else {
+                return;
+            };
+
+            attempt.outcome = match &result {
+                Ok((_, remaining)) => Outcome::Matched(input.len().saturating_sub(remaining.len())),
+                Err(error) => Outcome::Failed(error.to_string()),
+            };
+
+            match recorder.open.last_mut() {
+                Some(parent) => parent.children.push(attempt),
+                None => recorder.roots.push(attempt),
+            }
+        });
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, int, token, Lex, Parse};
+
+    #[test]
+    fn tree() {
+        let parser = int::<u8>()
+            .name("int")
+            .then_skip(char(','))
+            .then(int::<u8>().name("int"))
+            .or(token("none").map(|_| (0, 0)).name("none"));
+
+        let input = "1,none";
+        let (result, trace) = trace(input, || parser.parse(input));
+        assert!(result.is_err());
+
+        let or = &trace.roots()[0];
+        assert_eq!(or.label, "or");
+        // the first alternative got further into the input, so its error is kept
+        assert_eq!(or.outcome, Outcome::Failed("int: No Match".to_string()));
+
+        let then = &or.children[0];
+        assert_eq!(then.label, "then");
+        assert_eq!(
+            then.children
+                .iter()
+                .map(|attempt| (attempt.label, attempt.offset, attempt.outcome.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("int", 0, Outcome::Matched(1)),
+                ("int", 2, Outcome::Failed("No Match".to_string())),
+            ]
+        );
+        assert_eq!(or.children[1].label, "none");
+    }
+
+    #[test]
+    fn other_inputs() {
+        let digits = char('1').name("one");
+
+        let input = String::from("1");
+        let (_, trace) = trace(&input, || {
+            let _ = digits.lex("1");
+            digits.lex(&input)
+        });
+
+        assert_eq!(trace.to_string(), "one at 0: matched 1 bytes\n");
+
+        assert!(super::trace("", || ()).1.is_empty());
+    }
+}
@@ -0,0 +1,55 @@
+//! [`parser.void()`](Parse::void) discards the output of a parser.
+
+use std::fmt;
+
+use crate::{grammar::Grammar, Parse, ParseResult};
+
+/// This combinator is returned by [`void()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Void<P> {
+    parser: P,
+}
+
+impl<P: Parse> Parse for Void<P> {
+    type Output = ();
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (_, remaining) = self.parser.parse(input)?;
+        Ok(((), remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.parser.describe()
+    }
+}
+
+/// Runs the parser, discarding its output.
+///
+/// This is useful when it only matters that the parser matched, e.g. to validate part of the input.
+/// The parser still runs in full, including any conversion that may fail.
+///
+/// This is more conveniently created using the [`Parse::void`] method.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, int, Lex, Parse};
+///
+/// // the second number must be valid, but isn't needed
+/// let first = int::<u8>().then_skip(char(',')).then(int::<u8>().void());
+///
+/// assert_eq!(first.parse("1,2")?, ((1, ()), ""));
+/// assert!(first.parse("1,256").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn void<P>(parser: P) -> Void<P> {
+    Void { parser }
+}
+
+impl<P: fmt::Debug> fmt::Debug for Void<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Void({:?})", self.parser)
+    }
+}
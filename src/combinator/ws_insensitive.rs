@@ -0,0 +1,351 @@
+//! [`ws_insensitive()`] automatically skips whitespace in between the items of sequences run inside it.
+//!
+//! Grammars where whitespace is insignificant otherwise need [`.pad()`](crate::Parse::pad()) or [`lexeme()`](crate::combinator::lexeme())
+//! sprinkled throughout. Instead, wrap the whole grammar once:
+//!
+//! ```
+//! use parsely::{char, int, Lex, Parse};
+//! use parsely::combinator::ws_insensitive;
+//!
+//! let point = char('(')
+//!     .skip_then(int::<i32>())
+//!     .then_skip(char(','))
+//!     .then(int::<i32>())
+//!     .then_skip(char(')'));
+//!
+//! assert_eq!(point.parse("( 1 ,\t-2 )"), Err(parsely::Error::NoMatch));
+//!
+//! let point = ws_insensitive(point);
+//!
+//! assert_eq!(point.parse("( 1 ,\t-2 ) ")?, ((1, -2), ""));
+//! assert_eq!(point.parse("(1,-2)")?, ((1, -2), ""));
+//! # Ok::<(), parsely::Error>(())
+//! ```
+//!
+//! Whitespace is skipped:
+//!
+//! * before and after the wrapped item
+//! * in between the two sides of [`then()`](crate::combinator::then()), [`then_skip()`](crate::combinator::then_skip()) and [`skip_then()`](crate::combinator::skip_then())
+//! * in between the repetitions of [`many()`](crate::combinator::many()) and in between the items and delimiters of [`delimited()`](crate::combinator::delimited())
+//!
+//! Primitive lexers such as [`token()`](crate::token()) never skip whitespace inside themselves.
+//!
+//! Sometimes a sequence must not have whitespace in the middle of it, for example the digits of a number. Wrap those parts with [`atomic()`] to turn off skipping inside them.
+//! The built-in parsers like [`int()`](crate::int()) and [`float()`](crate::float()) are already atomic.
+//!
+//! # Requiring whitespace
+//!
+//! Whitespace is skipped before *every* item of a sequence, including a lexer such as [`ws()`](crate::ws()) that is meant to match it.
+//! By the time that lexer runs the whitespace is gone, so it fails. Wrap the part that requires whitespace with [`atomic()`],
+//! so that the whitespace is left for the lexer to match:
+//!
+//! ```
+//! use parsely::{alpha, token, ws, Lex};
+//! use parsely::combinator::{atomic, ws_insensitive};
+//!
+//! // the whitespace after "let" is skipped, so ws() has nothing to match
+//! let binding = ws_insensitive(token("let").then(ws()).then(alpha().many(1..)));
+//! assert_eq!(binding.lex("let x"), Err(parsely::Error::NoMatch));
+//!
+//! let binding = ws_insensitive(atomic(token("let").then(ws())).then(alpha().many(1..)));
+//! assert_eq!(binding.lex("let x")?, ("let x", ""));
+//! assert!(binding.lex("letx").is_err());
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::{cell::Cell, fmt};
+
+use crate::{
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    unparse::Unparse,
+    Error, Lex, LexResult, Parse, ParseResult,
+};
+
+/// Which chars to skip, `None` when no whitespace should be skipped.
+type Skipping = Option<fn(char) -> bool>;
+
+thread_local! {
+    /// The chars to skip while a [`WsInsensitive`] is running.
+    static SKIPPING: Cell<Skipping> = const { Cell::new(None) };
+}
+
+/// Restores the previous skipping state when dropped, even if parsing panics.
+struct Restore(Skipping);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        SKIPPING.with(|skipping| skipping.set(self.0));
+    }
+}
+
+fn skipping_with<T>(is_skipped: Skipping, f: impl FnOnce() -> T) -> T {
+    let _restore = Restore(SKIPPING.with(|skipping| skipping.replace(is_skipped)));
+    f()
+}
+
+/// Skips leading whitespace if running inside [`ws_insensitive()`], otherwise the input is returned unchanged.
+///
+/// Sequence combinators call this in between their items.
+#[inline]
+pub(crate) fn skip(input: &str) -> &str {
+    match SKIPPING.with(Cell::get) {
+        Some(is_skipped) => input.trim_start_matches(is_skipped),
+        None => input,
+    }
+}
+
+/// This combinator is returned by [`ws_insensitive()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct WsInsensitive<T> {
+    item: T,
+    is_skipped: fn(char) -> bool,
+}
+
+impl<T> WsInsensitive<T> {
+    /// Changes which characters are skipped, by default any [`char::is_whitespace()`] is skipped.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, Lex};
+    /// use parsely::combinator::ws_insensitive;
+    ///
+    /// // newlines are significant, so only skip spaces and tabs
+    /// let lexer = ws_insensitive(char('a').then(char('b'))).skipping(|c| c == ' ' || c == '\t');
+    ///
+    /// assert_eq!(lexer.lex("a \tb ")?, ("a \tb ", ""));
+    /// assert_eq!(lexer.lex("a\nb"), Err(parsely::Error::NoMatch));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn skipping(self, is_skipped: fn(char) -> bool) -> Self {
+        WsInsensitive { is_skipped, ..self }
+    }
+}
+
+impl<L: Lex> Lex for WsInsensitive<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        skipping_with(Some(self.is_skipped), || {
+            let (_, remaining) = self.item.lex(skip(input))?;
+            let remaining = skip(remaining);
+
+            Ok(input.split_at(input.len() - remaining.len()))
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.item.canonical_into(output)
+    }
+}
+
+impl<P: Parse> Parse for WsInsensitive<P> {
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        skipping_with(Some(self.is_skipped), || {
+            let (output, remaining) = self.item.parse(skip(input))?;
+
+            Ok((output, skip(remaining)))
+        })
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+impl<P: Unparse> Unparse for WsInsensitive<P> {
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.item.unparse_into(value, output)
+    }
+}
+
+/// Runs the item (a parser or lexer) skipping whitespace in between the items of every sequence inside it.
+///
+/// Whitespace that a lexer such as [`ws()`](crate::ws()) must match is skipped before it gets the chance, unless it is inside [`atomic()`],
+/// see [requiring whitespace](self#requiring-whitespace).
+///
+/// See the [module level documentation](self) for more details.
+pub fn ws_insensitive<T>(item: T) -> WsInsensitive<T> {
+    WsInsensitive {
+        item,
+        is_skipped: char::is_whitespace,
+    }
+}
+
+/// This combinator is returned by [`atomic()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Atomic<T> {
+    item: T,
+}
+
+impl<L: Lex> Lex for Atomic<L> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        skipping_with(None, || self.item.lex(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        self.item.sample_into(sampler, output)
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        self.item.canonical_into(output)
+    }
+}
+
+impl<P: Parse> Parse for Atomic<P> {
+    type Output = <P as Parse>::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        skipping_with(None, || self.item.parse(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.item.describe()
+    }
+}
+
+impl<P: Unparse> Unparse for Atomic<P> {
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.item.unparse_into(value, output)
+    }
+}
+
+/// Runs the item (a parser or lexer) without skipping any whitespace inside it, even when inside [`ws_insensitive()`].
+///
+/// Outside of [`ws_insensitive()`] this has no effect.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{alpha, char, Lex};
+/// use parsely::combinator::{atomic, ws_insensitive};
+///
+/// let word = || alpha().many(1..);
+///
+/// let words = ws_insensitive(word().then(char(',')).then(word()));
+/// assert_eq!(words.lex("a b , c d")?, ("a b , c d", ""));
+///
+/// let words = ws_insensitive(atomic(word()).then(char(',')).then(atomic(word())));
+/// assert_eq!(words.lex("ab , cd ef")?, ("ab , cd ", "ef"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn atomic<T>(item: T) -> Atomic<T> {
+    Atomic { item }
+}
+
+impl<T: fmt::Debug> fmt::Debug for WsInsensitive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WsInsensitive({:?})", self.item)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Atomic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Atomic({:?})", self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{char, digit, float, int, token, ws};
+
+    #[test]
+    fn lexing() {
+        test_lexer_batch(
+            "whitespace is skipped between items",
+            ws_insensitive(token("let").then(char('x')).then_skip(char('='))),
+            &[
+                ("let x =", Some("let x ="), ""), //
+                ("  let\tx\n= 1", Some("  let\tx\n= "), "1"),
+                ("letx=", Some("letx="), ""),
+                ("l et x =", None, "l et x ="),
+            ],
+        );
+
+        test_lexer_batch(
+            "whitespace is skipped between repetitions",
+            ws_insensitive(char('a').many(1..).then(char(';'))),
+            &[
+                ("a a  a;", Some("a a  a;"), ""), //
+                ("aaa ;", Some("aaa ;"), ""),
+                (";", None, ";"),
+            ],
+        );
+
+        test_lexer_batch(
+            "required whitespace is skipped unless atomic",
+            ws_insensitive(token("let").then(ws()).then(char('x'))),
+            &[("let x", None, "let x"), ("letx", None, "letx")],
+        );
+
+        test_lexer_batch(
+            "required whitespace inside atomic",
+            ws_insensitive(atomic(token("let").then(ws())).then(char('x'))),
+            &[
+                ("let x", Some("let x"), ""), //
+                ("let \t x ", Some("let \t x "), ""),
+                ("letx", None, "letx"),
+            ],
+        );
+
+        test_lexer_batch(
+            "atomic items don't skip whitespace",
+            ws_insensitive(atomic(digit().many(1..)).then(char(';'))),
+            &[
+                ("12 ;", Some("12 ;"), ""), //
+                ("1 2;", None, "1 2;"),
+            ],
+        );
+    }
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "whitespace is skipped between delimited items",
+            ws_insensitive(int::<i32>().many(1..).delimiter(char(','))),
+            &[
+                ("1, 2 ,3", Some(vec![1, 2, 3]), ""), //
+                (" -1 ,\n-2 ", Some(vec![-1, -2]), ""),
+                ("1 2", Some(vec![1]), "2"),
+            ],
+        );
+
+        test_parser_batch(
+            "number parsers are atomic",
+            ws_insensitive(float::<f32>().then(int::<u8>())),
+            &[
+                ("1.5 2", Some((1.5, 2)), ""), //
+                ("1 .5 2", None, "1 .5 2"),
+            ],
+        );
+    }
+
+    #[test]
+    fn skipping_is_scoped() -> Result<(), crate::Error> {
+        let sequence = || char('a').then(char('b'));
+
+        assert!(ws_insensitive(sequence()).lex("a b").is_ok());
+        assert_eq!(sequence().lex("a b"), Err(crate::Error::NoMatch));
+
+        Ok(())
+    }
+}
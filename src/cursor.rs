@@ -0,0 +1,245 @@
+//! A position in the input that imperative code can move through with lexers and parsers, see [`Cursor`].
+
+use std::fmt;
+
+use crate::{Error, ErrorOwned, Lex, Location, Parse, ParseResult};
+
+/// A position in the input that lexers and parsers move forward, for writing parsers as ordinary loops and `if`s.
+///
+/// Sometimes a grammar is easier to write imperatively than to build out of combinators, e.g. when what comes next depends on a value parsed earlier.
+/// A cursor keeps track of the remaining input so that this code doesn't have to thread it through every call.
+///
+/// * [`expect()`](Cursor::expect()) and [`parse()`](Cursor::parse()) run a lexer or parser, moving past what it matched
+/// * [`checkpoint()`](Cursor::checkpoint()) and [`rewind()`](Cursor::rewind()) go back to an earlier position to try something else
+/// * [`finish()`](Cursor::finish()) returns a [`ParseResult`], so a function using a cursor is a parser itself
+/// * [`error_owned()`](Cursor::error_owned()) attaches the current position to an error
+///
+/// A lexer or parser that fails doesn't move the cursor.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{alpha, char, int, Cursor, Lex, Parse, ParseResult};
+///
+/// /// Parses a count followed by that many comma separated words, e.g. `2:a,b`
+/// fn words(input: &str) -> ParseResult<'_, Vec<String>> {
+///     let mut cursor = Cursor::new(input);
+///
+///     let count = cursor.parse(&int::<usize>())?;
+///     cursor.expect(&char(':'))?;
+///
+///     let mut words = Vec::new();
+///     for i in 0..count {
+///         if i > 0 {
+///             cursor.expect(&char(','))?;
+///         }
+///         words.push(cursor.expect(&alpha().many(1..))?.to_string());
+///     }
+///
+///     cursor.finish(words)
+/// }
+///
+/// assert_eq!(words("2:ab,c,d")?, (vec!["ab".to_string(), "c".to_string()], ",d"));
+/// assert!(words("3:ab,c").is_err());
+///
+/// // functions are parsers, so they can be combined like any other
+/// assert_eq!(words.then_skip(char(';')).parse("1:x;")?, (vec!["x".to_string()], ""));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Cursor<'i> {
+    input: &'i str,
+    offset: usize,
+}
+
+/// A position of a [`Cursor`] to go back to with [`Cursor::rewind()`], returned by [`Cursor::checkpoint()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Checkpoint {
+    offset: usize,
+}
+
+impl<'i> Cursor<'i> {
+    /// Creates a cursor at the start of the input.
+    pub fn new(input: &'i str) -> Self {
+        Cursor { input, offset: 0 }
+    }
+
+    /// The whole input the cursor was created with.
+    pub fn input(&self) -> &'i str {
+        self.input
+    }
+
+    /// The input after the cursor.
+    pub fn remaining(&self) -> &'i str {
+        &self.input[self.offset..]
+    }
+
+    /// The byte offset of the cursor into the input.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The location of the cursor in the input.
+    pub fn location(&self) -> Location {
+        Location::at(self.input, self.offset)
+    }
+
+    /// Whether the cursor is at the end of the input.
+    pub fn is_at_end(&self) -> bool {
+        self.offset == self.input.len()
+    }
+
+    /// Returns the current position, to go back to later with [`rewind()`](Cursor::rewind()).
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            offset: self.offset,
+        }
+    }
+
+    /// Moves the cursor back (or forward) to a position returned by [`checkpoint()`](Cursor::checkpoint()).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the checkpoint was taken from a cursor over a shorter input.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{token, Cursor};
+    ///
+    /// let mut cursor = Cursor::new("let x");
+    /// let start = cursor.checkpoint();
+    ///
+    /// cursor.expect(&token("let"))?;
+    /// assert_eq!(cursor.remaining(), " x");
+    ///
+    /// cursor.rewind(start);
+    /// assert_eq!(cursor.remaining(), "let x");
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn rewind(&mut self, checkpoint: Checkpoint) {
+        assert!(
+            checkpoint.offset <= self.input.len(),
+            "the checkpoint is past the end of the input"
+        );
+
+        self.offset = checkpoint.offset;
+    }
+
+    /// Runs the lexer on the remaining input, moving the cursor past what it matched and returning it.
+    ///
+    /// If the lexer fails the cursor doesn't move.
+    pub fn expect(&mut self, lexer: &impl Lex) -> Result<&'i str, Error> {
+        let (matched, remaining) = lexer.lex(self.remaining())?;
+        self.advance(remaining);
+
+        Ok(matched)
+    }
+
+    /// Runs the parser on the remaining input, moving the cursor past what it matched and returning its output.
+    ///
+    /// If the parser fails the cursor doesn't move.
+    pub fn parse<P: Parse>(&mut self, parser: &P) -> Result<P::Output, Error> {
+        let (output, remaining) = parser.parse(self.remaining())?;
+        self.advance(remaining);
+
+        Ok(output)
+    }
+
+    /// Whether the lexer matches the remaining input, without moving the cursor.
+    pub fn peek(&self, lexer: &impl Lex) -> bool {
+        lexer.lex(self.remaining()).is_ok()
+    }
+
+    /// Returns the output along with the remaining input, as a parser returns them.
+    pub fn finish<O>(self, output: O) -> ParseResult<'i, O> {
+        Ok((output, self.remaining()))
+    }
+
+    /// Attaches the input and the position of the cursor to the error.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, digit, Cursor, Lex};
+    ///
+    /// let mut cursor = Cursor::new("1\n2x");
+    /// cursor.expect(&digit().then(char('\n')).then(digit()))?;
+    ///
+    /// let error = cursor.expect(&char(';')).unwrap_err();
+    /// assert_eq!(cursor.error_owned(error).to_string(), "No Match at 2:2");
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn error_owned(&self, error: Error) -> ErrorOwned {
+        ErrorOwned::new(error, self.input, self.offset)
+    }
+
+    fn advance(&mut self, remaining: &'i str) {
+        self.offset = self
+            .input
+            .len()
+            .checked_sub(remaining.len())
+            .expect("the remaining input is longer than the input");
+    }
+}
+
+impl fmt::Debug for Cursor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cursor({}, {:?})", self.offset, self.remaining())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, digit, int, token, ws};
+
+    #[test]
+    fn moving() -> Result<(), Error> {
+        let mut cursor = Cursor::new("12 + 3");
+
+        assert_eq!(cursor.parse(&int::<u8>())?, 12);
+        assert_eq!(cursor.offset(), 2);
+
+        // failures don't move the cursor
+        assert_eq!(cursor.expect(&token("+")), Err(Error::NoMatch));
+        assert_eq!(cursor.offset(), 2);
+
+        let before_plus = cursor.checkpoint();
+        cursor.expect(&ws().then(char('+')).then(ws()))?;
+        assert!(cursor.peek(&digit()));
+        assert_eq!(cursor.expect(&digit())?, "3");
+        assert!(cursor.is_at_end());
+
+        cursor.rewind(before_plus);
+        assert_eq!(cursor.remaining(), " + 3");
+        assert_eq!(cursor.location().column, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn finishing() {
+        let cursor = Cursor::new("abc");
+        assert_eq!(cursor.finish(1), Ok((1, "abc")));
+        assert_eq!(format!("{cursor:?}"), r#"Cursor(0, "abc")"#);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rewinding_past_the_end() {
+        let checkpoint = Cursor {
+            input: "abc",
+            offset: 3,
+        }
+        .checkpoint();
+
+        Cursor::new("a").rewind(checkpoint);
+    }
+}
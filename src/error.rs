@@ -2,7 +2,9 @@
 //!
 //! Parsely's error handling strategy is currently unstable. Expect these types to change.
 
-use std::fmt;
+use std::{cell::RefCell, fmt};
+
+use crate::Location;
 
 /// This is a simple "all the possible errors while parsing" enum.
 ///
@@ -14,15 +16,575 @@ pub enum Error {
     NoMatch,
 
     /// When converting to the output type there was an error
-    FailedConversion,
+    ///
+    /// See [`Lex::try_map()`](crate::Lex::try_map()) and [`Lex::parse_str()`](crate::Lex::parse_str()).
+    FailedConversion {
+        /// The matched input that couldn't be converted
+        matched: String,
+
+        /// Why it couldn't be converted, usually the conversion's own error message
+        message: Option<String>,
+    },
+
+    /// The matched digits are a number too large or too small to fit in the output type, e.g. `"300"` for a `u8`
+    ///
+    /// See [`int()`](crate::int()) and [`uint()`](crate::uint()).
+    Overflow {
+        /// The digits that didn't fit, including any sign
+        digits: String,
+    },
+
+    /// Something in particular was expected but not found, e.g. `Expected("closing bracket")`
+    ///
+    /// See [`expected()`](crate::combinator::expected()).
+    Expected(&'static str),
+
+    /// None of the alternatives of an [`or()`](crate::combinator::or()) matched, and this is what they expected where they got furthest
+    ///
+    /// Each alternative that failed where it started is described by its [grammar](crate::Lex::describe()),
+    /// unless it failed with an [`Error::Expected`] or another [`Error::ExpectedOneOf`].
+    ExpectedOneOf(Vec<String>),
+
+    /// A custom error message, for errors raised by your own parsers
+    Custom(String),
+
+    /// A parser or lexer labelled with [`.name()`](crate::Lex::name()) failed
+    Named {
+        /// The name given to the parser or lexer
+        name: &'static str,
+
+        /// Why it failed
+        error: Box<Error>,
+    },
+
+    /// Parsers were nested deeper than the limit given to [`depth_limited()`](crate::combinator::depth_limited())
+    DepthExceeded(usize),
+
+    /// More input was examined than the limit given to [`budget()`](crate::combinator::budget())
+    BudgetExceeded,
+
+    /// A repetition matched more times than the limit given to [`max_iterations()`](crate::combinator::max_iterations())
+    IterationsExceeded(usize),
+
+    /// A failure that can't be recovered from, so no alternatives are tried.
+    ///
+    /// See [`cut()`](crate::combinator::cut()) and [`Error::fatal()`].
+    Fatal(Box<Error>),
+}
+
+impl Error {
+    /// Creates an [`Error::Custom`] with the given message.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{alpha, Error, Lex, ParseResult};
+    ///
+    /// fn block_name(input: &str) -> ParseResult<'_, String> {
+    ///     let (name, remaining) = alpha().many(1..).lex(input)?;
+    ///
+    ///     if !remaining.starts_with('{') {
+    ///         return Err(Error::custom(format!("expected '{{' to open block '{name}'")));
+    ///     }
+    ///
+    ///     Ok((name.to_string(), remaining))
+    /// }
+    ///
+    /// assert_eq!(block_name("foo }").unwrap_err().to_string(), "expected '{' to open block 'foo'");
+    /// ```
+    pub fn custom(message: impl Into<String>) -> Error {
+        Error::Custom(message.into())
+    }
+
+    /// Returns the underlying error, without any [`Error::Named`] wrapping it.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{digit, Error, Lex};
+    ///
+    /// let error = digit().name("digit").lex("x").unwrap_err();
+    ///
+    /// assert_eq!(error.to_string(), "digit: No Match");
+    /// assert_eq!(error.root(), &Error::NoMatch);
+    /// ```
+    pub fn root(&self) -> &Error {
+        match self {
+            Error::Named { error, .. } | Error::Fatal(error) => error.root(),
+            error => error,
+        }
+    }
+
+    /// Marks this error as fatal, so that combinators such as [`or()`](crate::combinator::or()),
+    /// [`optional()`](crate::combinator::optional()) and [`many()`](crate::combinator::many()) don't recover from it.
+    ///
+    /// Marking an error that is already fatal has no effect.
+    ///
+    /// Usually errors are marked as fatal by [`cut()`](crate::combinator::cut()).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::Error;
+    ///
+    /// let error = Error::NoMatch.fatal();
+    ///
+    /// assert!(error.is_fatal());
+    /// assert_eq!(Error::NoMatch.fatal().fatal(), error);
+    /// assert_eq!(error.root(), &Error::NoMatch);
+    /// ```
+    pub fn fatal(self) -> Error {
+        if self.is_fatal() {
+            self
+        } else {
+            Error::Fatal(Box::new(self))
+        }
+    }
+
+    /// Attaches the input and where in it the error happened, making an [`ErrorOwned`].
+    ///
+    /// The remaining input must be what remains of `input` where the error happened, e.g. as returned by a successful lexer.
+    /// Only the input is copied, the position is stored as an offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `remaining` is longer than `input`, or doesn't start on a char boundary of it.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, Error, Lex};
+    ///
+    /// let input = "a=1";
+    /// let (_, remaining) = char('a').then(char('=')).lex(input)?;
+    ///
+    /// let error = Error::Expected("a letter").into_owned(input, remaining);
+    /// assert_eq!(error.to_string(), "expected a letter at 1:3");
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn into_owned(self, input: &str, remaining: &str) -> ErrorOwned {
+        let offset = input
+            .len()
+            .checked_sub(remaining.len())
+            .expect("the remaining input is longer than the input");
+
+        ErrorOwned::new(self, input, offset)
+    }
+
+    /// Whether this error is fatal, see [`Error::fatal()`].
+    ///
+    /// A fatal error labelled with [`.name()`](crate::Lex::name()) is still fatal.
+    pub fn is_fatal(&self) -> bool {
+        match self {
+            Error::Fatal(_) => true,
+            Error::Named { error, .. } => error.is_fatal(),
+            _ => false,
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Named { error, .. } | Error::Fatal(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
 }
 
-impl std::error::Error for Error {}
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Error::NoMatch => write!(f, "No Match"),
-            Error::FailedConversion => write!(f, "Failed to convert matched input"),
+            Error::FailedConversion {
+                matched,
+                message: None,
+            } => write!(f, "Failed to convert matched input {matched:?}"),
+            Error::FailedConversion {
+                matched,
+                message: Some(message),
+            } => write!(f, "Failed to convert matched input {matched:?}: {message}"),
+            Error::Overflow { digits } => {
+                write!(f, "The number {digits} doesn't fit in the output type")
+            }
+            Error::Expected(expected) => write!(f, "expected {expected}"),
+            Error::ExpectedOneOf(expected) => match expected.as_slice() {
+                [] => write!(f, "No Match"),
+                [expected] => write!(f, "expected {expected}"),
+                [init @ .., last] => write!(f, "expected one of {} or {last}", init.join(", ")),
+            },
+            Error::Custom(message) => f.write_str(message),
+            Error::BudgetExceeded => write!(f, "Examined more input than the budget allows"),
+            Error::DepthExceeded(limit) => write!(f, "Nested deeper than the limit of {limit}"),
+            Error::IterationsExceeded(limit) => {
+                write!(f, "Repeated more times than the limit of {limit}")
+            }
+            Error::Named { name, error } => write!(f, "{name}: {error}"),
+            Error::Fatal(error) => write!(f, "{error}"),
         }
     }
 }
+
+/// The furthest failure within the input given to the innermost running [`track_failures()`].
+struct Furthest {
+    /// The address of the input, used to check that a failure happened within this input.
+    address: usize,
+    len: usize,
+
+    /// The length of the shortest remaining input a lexer failed to match.
+    remaining: usize,
+
+    /// Whether any lexer failed to match, as `remaining` can't tell a failure at the start of the input from no failure.
+    failed: bool,
+
+    /// The length of the remaining input from the start of the last matched input that failed to convert.
+    conversion: Option<usize>,
+}
+
+thread_local! {
+    static FURTHEST: RefCell<Option<Furthest>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous tracking state when dropped, even if parsing panics.
+struct Restore(Option<Furthest>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        FURTHEST.with(|furthest| *furthest.borrow_mut() = self.0.take());
+    }
+}
+
+/// Records that a lexer failed to match the input, and returns [`Error::NoMatch`].
+///
+/// Lexers use this so that a failed parse can report how far into the input it got, see [`track_failures()`].
+pub(crate) fn no_match(input: &str) -> Error {
+    FURTHEST.with(|furthest| {
+        if let Some(furthest) = furthest.borrow_mut().as_mut() {
+            let address = input.as_ptr() as usize;
+
+            // only inputs that are what remains of the tracked input are counted
+            if address >= furthest.address
+                && address + input.len() == furthest.address + furthest.len
+            {
+                furthest.remaining = furthest.remaining.min(input.len());
+                furthest.failed = true;
+            }
+        }
+    });
+
+    Error::NoMatch
+}
+
+/// Records that the matched input failed to convert to an output, and returns [`Error::FailedConversion`] with the given message.
+///
+/// Parsers use this so that a failed parse can report where the offending input starts, see [`track_failures()`].
+pub(crate) fn failed_conversion(matched: &str, message: impl fmt::Display) -> Error {
+    track_conversion(matched);
+
+    Error::FailedConversion {
+        matched: matched.to_string(),
+        message: Some(message.to_string()),
+    }
+}
+
+/// Records that the matched digits didn't fit in the output type, and returns [`Error::Overflow`].
+pub(crate) fn overflow(digits: &str) -> Error {
+    track_conversion(digits);
+
+    Error::Overflow {
+        digits: digits.to_string(),
+    }
+}
+
+fn track_conversion(matched: &str) {
+    FURTHEST.with(|furthest| {
+        if let Some(furthest) = furthest.borrow_mut().as_mut() {
+            let address = matched.as_ptr() as usize;
+            let end = furthest.address + furthest.len;
+
+            if address >= furthest.address && address + matched.len() <= end {
+                furthest.conversion = Some(end - address);
+            }
+        }
+    });
+}
+
+/// Where the failures recorded by [`track_failures()`] happened, as byte offsets into the input.
+pub(crate) struct Failures {
+    /// The offset of the furthest failure, or 0 if nothing failed.
+    pub(crate) furthest: usize,
+
+    /// Whether any lexer failed to match.
+    failed: bool,
+
+    /// The offset of the start of the last matched input that failed to convert.
+    conversion: Option<usize>,
+}
+
+impl Failures {
+    /// The best guess for where `error` happened.
+    ///
+    /// A conversion failure or overflow happened where its matched input starts, anything else at the furthest failure.
+    pub(crate) fn offset_of(&self, error: &Error) -> usize {
+        match (error.root(), self.conversion) {
+            (Error::FailedConversion { .. } | Error::Overflow { .. }, Some(offset)) => offset,
+            _ => self.furthest,
+        }
+    }
+}
+
+/// Runs `f`, returning its result along with where failures happened within the input.
+///
+/// The furthest failure is usually the best guess for where the input is wrong, as every alternative failed at or before it.
+pub(crate) fn track_failures<T>(input: &str, f: impl FnOnce() -> T) -> (T, Failures) {
+    let tracking = Furthest {
+        address: input.as_ptr() as usize,
+        len: input.len(),
+        remaining: input.len(),
+        failed: false,
+        conversion: None,
+    };
+
+    let restore = Restore(FURTHEST.with(|furthest| furthest.borrow_mut().replace(tracking)));
+    let output = f();
+
+    let (remaining, failed, conversion) = FURTHEST.with(|furthest| {
+        furthest
+            .borrow()
+            .as_ref()
+            .map_or((input.len(), false, None), |f| {
+                (f.remaining, f.failed, f.conversion)
+            })
+    });
+    drop(restore);
+
+    let failures = Failures {
+        furthest: input.len() - remaining,
+        failed,
+        conversion: conversion.map(|remaining| input.len() - remaining),
+    };
+
+    (output, failures)
+}
+
+/// Records the failures that a [`track_failures()`] of `input` saw with the enclosing [`track_failures()`], as if they weren't tracked separately.
+pub(crate) fn forward(input: &str, failures: &Failures) {
+    if failures.failed {
+        no_match(&input[failures.furthest..]);
+    }
+
+    if let Some(offset) = failures.conversion {
+        track_conversion(&input[offset..]);
+    }
+}
+
+/// An [`Error`] together with the input it happened in and where it happened.
+///
+/// This is returned by [`Parse::parse_all_owned()`](crate::Parse::parse_all_owned()) and [`Lex::lex_all_owned()`](crate::Lex::lex_all_owned()),
+/// and owns a copy of the input so that it can be returned from functions such as [`FromStr::from_str`](std::str::FromStr::from_str).
+///
+/// Use [`to_report()`](ErrorOwned::to_report()) to describe the error to a person.
+#[derive(PartialEq, Debug)]
+pub struct ErrorOwned {
+    error: Error,
+    input: String,
+
+    /// The byte offset into the input where parsing failed.
+    offset: usize,
+}
+
+impl ErrorOwned {
+    /// Creates an error that happened at the byte offset into the input.
+    ///
+    /// The input is only copied if it isn't already an owned [`String`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the offset is past the end of the input, or isn't on a char boundary.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{Error, ErrorOwned};
+    ///
+    /// let error = ErrorOwned::new(Error::Expected("a number"), String::from("x = ?"), 4);
+    ///
+    /// assert_eq!(error.remaining(), "?");
+    /// assert_eq!(error.to_string(), "expected a number at 1:5");
+    /// ```
+    pub fn new(error: Error, input: impl Into<String>, offset: usize) -> Self {
+        let input = input.into();
+        assert!(
+            input.is_char_boundary(offset),
+            "offset {offset} is not a char boundary of the input"
+        );
+
+        ErrorOwned {
+            error,
+            input,
+            offset,
+        }
+    }
+
+    /// The error.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Unwraps the error, discarding the input.
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+
+    /// The whole input that was being parsed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The byte offset into the input where parsing failed.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The remaining input at the point where parsing failed.
+    pub fn remaining(&self) -> &str {
+        &self.input[self.offset..]
+    }
+
+    /// The location in the input where parsing failed.
+    pub fn location(&self) -> Location {
+        Location::at(&self.input, self.offset)
+    }
+
+    /// Formats a multi-line report of the error, suitable for showing to a person.
+    ///
+    /// The report includes the error, its line and column, and the line of input it happened on with the failure pointed out.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, int, Lex, Parse};
+    ///
+    /// let list = char('[').skip_then(int::<i32>().pad().many(..).delimiter(char(','))).then_skip(char(']'));
+    ///
+    /// let error = list.parse_all_owned("[1,\n 2, x]").unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.to_report(),
+    ///     "\
+    /// error: No Match
+    ///  --> 2:5
+    ///   |
+    /// 2 |  2, x]
+    ///   |     ^
+    /// "
+    /// );
+    /// ```
+    pub fn to_report(&self) -> String {
+        let location = self.location();
+
+        let line_start = self.input[..location.offset]
+            .rfind('\n')
+            .map_or(0, |n| n + 1);
+        let line_end = self.input[location.offset..]
+            .find('\n')
+            .map_or(self.input.len(), |n| location.offset + n);
+        let line = self.input[line_start..line_end].trim_end_matches('\r');
+
+        let gutter = " ".repeat(location.line.to_string().len());
+        let indent = " ".repeat(location.column - 1);
+
+        format!(
+            "error: {error}\n{gutter}--> {location}\n{gutter} |\n{number} | {line}\n{gutter} | {indent}^\n",
+            error = self.error,
+            number = location.line,
+        )
+    }
+}
+
+impl std::error::Error for ErrorOwned {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for ErrorOwned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.error, self.location())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, digit, int, token, Lex, Parse};
+
+    #[test]
+    fn furthest_failure() {
+        let pair = || digit().then(char(',')).then(digit());
+
+        let error = pair().lex_all_owned("1,x").unwrap_err();
+        assert_eq!(error.remaining(), "x");
+        assert_eq!(error.location().column, 3);
+
+        // the furthest failure of any alternative is reported
+        let error = pair().or(token("1;")).lex_all_owned("1,x").unwrap_err();
+        assert_eq!(error.remaining(), "x");
+
+        // unparsed input is reported where it starts
+        let error = pair().lex_all_owned("1,2;").unwrap_err();
+        assert_eq!(error.remaining(), ";");
+        assert_eq!(error.to_string(), "No Match at 1:4");
+
+        assert_eq!(pair().map(str::len).parse_all_owned("1,2"), Ok(3));
+    }
+
+    #[test]
+    fn nested_tracking() {
+        // an unrelated input parsed while tracking doesn't move the furthest failure
+        let inner = char('a').map(|_| digit().lex_all_owned("x").unwrap_err());
+        let (error, failures) = track_failures("ab", || inner.parse("ab"));
+
+        assert_eq!(error.map(|(e, _)| e.location().offset), Ok(0));
+        assert_eq!(failures.furthest, 0);
+    }
+
+    #[test]
+    fn failed_conversion() {
+        let pair = || int::<u8>().then_skip(char(',')).then(int::<u8>());
+
+        let error = pair().parse_all_owned("12,300").unwrap_err();
+        assert_eq!(
+            error.error(),
+            &Error::Overflow {
+                digits: "300".to_string(),
+            }
+        );
+        assert_eq!(error.remaining(), "300");
+        assert_eq!(
+            error.to_string(),
+            "The number 300 doesn't fit in the output type at 1:4"
+        );
+    }
+
+    #[test]
+    fn report() {
+        let error = ErrorOwned::new(Error::Expected("a digit"), "abc\r\ndef", 7);
+        assert_eq!(
+            error.to_report(),
+            "error: expected a digit\n --> 2:3\n  |\n2 | def\n  |   ^\n"
+        );
+    }
+}
@@ -0,0 +1,312 @@
+//! Operator precedence ("Pratt") parsing of expressions.
+//!
+//! Expressions like `-1 + 2 * 3 ^ 2 ^ 2` are awkward to parse with [`then()`](crate::combinator::then()) and [`or()`](crate::combinator::or()) alone,
+//! the grammar has to be carefully layered for each level of precedence.
+//!
+//! Instead, create an [`Expression`] from a parser for the *atoms* of your expressions (numbers, variables, parenthesised expressions, ...)
+//! and register each operator along with its precedence. Operators with a higher precedence bind more tightly.
+//!
+//! Operators are lexers and each comes with a function to combine the operands into a new output:
+//!
+//! * [`prefix`](Expression::prefix) operators such as `-x` or `!x`
+//! * [`infix`](Expression::infix) operators such as `x + y`, which are either [left](Associativity::Left) or [right](Associativity::Right) associative
+//! * [`postfix`](Expression::postfix) operators such as `x!` or `x?`
+//!
+//! # Examples
+//!
+//! A calculator:
+//!
+//! ```
+//! use parsely::{char, int, Lex, Parse, ParseResult};
+//! use parsely::expr::{expression, Associativity::*};
+//!
+//! fn atom(input: &str) -> ParseResult<'_, i64> {
+//!     int::<i64>().or(calculator.pad_with(char('('), char(')'))).parse(input)
+//! }
+//!
+//! fn calculator(input: &str) -> ParseResult<'_, i64> {
+//!     expression(atom.pad())
+//!         .infix(char('+'), 1, Left, |a, b| a + b)
+//!         .infix(char('-'), 1, Left, |a, b| a - b)
+//!         .infix(char('*'), 2, Left, |a, b| a * b)
+//!         .infix(char('/'), 2, Left, |a, b| a / b)
+//!         .infix(char('^'), 3, Right, |a, b| a.pow(b as u32))
+//!         .prefix(char('-'), 4, |a| -a)
+//!         .parse(input)
+//! }
+//!
+//! assert_eq!(calculator("1 + 2 * 3")?, (7, ""));
+//! assert_eq!(calculator("(1 + 2) * 3")?, (9, ""));
+//! assert_eq!(calculator("10 - 4 - 3")?, (3, ""));
+//! assert_eq!(calculator("2 ^ 3 ^ 2")?, (512, ""));
+//! assert_eq!(calculator("-2 ^ 2")?, (4, ""));
+//! assert_eq!(calculator("1 + ")?, (1, "+ "));
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::{fmt, sync::Arc};
+
+use crate::{combinator::ws_insensitive::skip, grammar::Grammar, Lex, Parse, ParseResult};
+
+/// Whether a chain of infix operators of the same precedence groups to the left or to the right.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Associativity {
+    /// `a - b - c` is parsed as `(a - b) - c`
+    Left,
+
+    /// `a ^ b ^ c` is parsed as `a ^ (b ^ c)`
+    Right,
+}
+
+// shared so that cloning an expression doesn't need to clone its operators
+type Unary<O> = Arc<dyn Fn(O) -> O + Send + Sync>;
+type Binary<O> = Arc<dyn Fn(O, O) -> O + Send + Sync>;
+type Operator = Arc<dyn Lex + Send + Sync>;
+
+/// This parser is returned by [`expression()`]. See the [module level documentation](self) for more details.
+pub struct Expression<A, O> {
+    atom: A,
+    prefix: Vec<(Operator, u32, Unary<O>)>,
+    infix: Vec<(Operator, u32, u32, Binary<O>)>,
+    postfix: Vec<(Operator, u32, Unary<O>)>,
+}
+
+/// Creates a parser for expressions built out of atoms parsed by `atom` combined with operators.
+///
+/// No operators are registered to begin with, see the [module level documentation](self) for more details.
+pub fn expression<A: Parse>(atom: A) -> Expression<A, <A as Parse>::Output> {
+    Expression {
+        atom,
+        prefix: Vec::new(),
+        infix: Vec::new(),
+        postfix: Vec::new(),
+    }
+}
+
+/// Precedences become a pair of binding powers so that associativity can be decided by comparing them.
+fn binding_power(precedence: u16) -> u32 {
+    (precedence as u32 + 1) * 2
+}
+
+impl<A, O> Expression<A, O>
+where
+    A: Parse<Output = O>,
+{
+    /// Registers a prefix operator such as `-x`, operators with a higher precedence bind more tightly.
+    pub fn prefix<L, F>(mut self, operator: L, precedence: u16, f: F) -> Self
+    where
+        L: Lex + Send + Sync + 'static,
+        F: Fn(O) -> O + Send + Sync + 'static,
+    {
+        self.prefix
+            .push((Arc::new(operator), binding_power(precedence), Arc::new(f)));
+        self
+    }
+
+    /// Registers an infix operator such as `x + y`, operators with a higher precedence bind more tightly.
+    pub fn infix<L, F>(
+        mut self,
+        operator: L,
+        precedence: u16,
+        associativity: Associativity,
+        f: F,
+    ) -> Self
+    where
+        L: Lex + Send + Sync + 'static,
+        F: Fn(O, O) -> O + Send + Sync + 'static,
+    {
+        let power = binding_power(precedence);
+        let (left, right) = match associativity {
+            Associativity::Left => (power, power + 1),
+            Associativity::Right => (power + 1, power),
+        };
+
+        self.infix
+            .push((Arc::new(operator), left, right, Arc::new(f)));
+        self
+    }
+
+    /// Registers a postfix operator such as `x!`, operators with a higher precedence bind more tightly.
+    pub fn postfix<L, F>(mut self, operator: L, precedence: u16, f: F) -> Self
+    where
+        L: Lex + Send + Sync + 'static,
+        F: Fn(O) -> O + Send + Sync + 'static,
+    {
+        self.postfix
+            .push((Arc::new(operator), binding_power(precedence), Arc::new(f)));
+        self
+    }
+
+    /// Parses an expression whose operators all bind at least as tightly as `min_power`.
+    fn parse_power<'i>(&self, input: &'i str, min_power: u32) -> ParseResult<'i, O> {
+        let prefix = self.prefix.iter().find_map(|(operator, power, f)| {
+            let (_, remaining) = operator.lex(input).ok()?;
+            Some((remaining, *power, f))
+        });
+
+        let (mut output, mut remaining) = match prefix {
+            Some((remaining, power, f)) => {
+                let (operand, remaining) = self.parse_power(skip(remaining), power)?;
+                (f(operand), remaining)
+            }
+            None => self.atom.parse(input)?,
+        };
+
+        'operators: loop {
+            let working_input = skip(remaining);
+
+            for (operator, power, f) in self.postfix.iter() {
+                if *power < min_power {
+                    continue;
+                }
+
+                if let Ok((_, after)) = operator.lex(working_input) {
+                    output = f(output);
+                    remaining = after;
+                    continue 'operators;
+                }
+            }
+
+            for (operator, left, right, f) in self.infix.iter() {
+                if *left < min_power {
+                    continue;
+                }
+
+                if let Ok((_, after)) = operator.lex(working_input) {
+                    // without a right hand side, the operator isn't part of this expression
+                    if let Ok((rhs, after)) = self.parse_power(skip(after), *right) {
+                        output = f(output, rhs);
+                        remaining = after;
+                        continue 'operators;
+                    }
+                }
+            }
+
+            break;
+        }
+
+        Ok((output, remaining))
+    }
+}
+
+impl<A, O> Parse for Expression<A, O>
+where
+    A: Parse<Output = O>,
+{
+    type Output = O;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        self.parse_power(input, 0)
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special(format!("expression of {}", self.atom.describe()))
+    }
+}
+
+impl<A: Clone, O> Clone for Expression<A, O> {
+    fn clone(&self) -> Self {
+        Expression {
+            atom: self.atom.clone(),
+            prefix: self.prefix.clone(),
+            infix: self.infix.clone(),
+            postfix: self.postfix.clone(),
+        }
+    }
+}
+
+impl<A: fmt::Debug, O> fmt::Debug for Expression<A, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Expression({:?}, {} prefix, {} infix, {} postfix)",
+            self.atom,
+            self.prefix.len(),
+            self.infix.len(),
+            self.postfix.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{alpha, char, token, uint, ParseResult};
+
+    use Associativity::*;
+
+    /// Renders the structure of the parsed expression to test grouping
+    fn grouping(input: &str) -> ParseResult<'_, String> {
+        let atom = alpha()
+            .map(str::to_string)
+            .or(grouping.pad_with(char('('), char(')')));
+
+        expression(atom)
+            .infix(char('='), 1, Right, |a, b| format!("({a} = {b})"))
+            .infix(char('+'), 2, Left, |a, b| format!("({a} + {b})"))
+            .infix(char('-'), 2, Left, |a, b| format!("({a} - {b})"))
+            .infix(char('*'), 3, Left, |a, b| format!("({a} * {b})"))
+            .infix(char('^'), 4, Right, |a, b| format!("({a} ^ {b})"))
+            .prefix(char('-'), 5, |a| format!("(-{a})"))
+            .postfix(char('!'), 6, |a| format!("({a}!)"))
+            .postfix(token("[]"), 6, |a| format!("({a}[])"))
+            .parse(input)
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        test_parser_batch(
+            "operators group by precedence and associativity",
+            grouping,
+            &[
+                ("a", Some("a".to_string()), ""), //
+                ("a+b*c", Some("(a + (b * c))".to_string()), ""),
+                ("a*b+c", Some("((a * b) + c)".to_string()), ""),
+                ("a-b-c", Some("((a - b) - c)".to_string()), ""),
+                ("a^b^c", Some("(a ^ (b ^ c))".to_string()), ""),
+                ("a=b=c+d", Some("(a = (b = (c + d)))".to_string()), ""),
+                ("-a*b", Some("((-a) * b)".to_string()), ""),
+                ("--a", Some("(-(-a))".to_string()), ""),
+                ("-a!", Some("(-(a!))".to_string()), ""),
+                ("a![]^b", Some("(((a!)[]) ^ b)".to_string()), ""),
+                ("(a+b)*c", Some("((a + b) * c)".to_string()), ""),
+                ("a+", Some("a".to_string()), "+"),
+                ("a+b)", Some("(a + b)".to_string()), ")"),
+                ("+a", None, "+a"),
+                ("", None, ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn whitespace() {
+        let calculator = || {
+            expression(uint::<u32>().pad())
+                .infix(char('+'), 1, Left, |a, b| a + b)
+                .infix(char('*'), 2, Left, |a, b| a * b)
+        };
+
+        test_parser_batch(
+            "padded atoms",
+            calculator(),
+            &[
+                (" 1 + 2 * 3 ", Some(7), ""), //
+                ("2*3+1", Some(7), ""),
+            ],
+        );
+
+        test_parser_batch(
+            "whitespace insensitive",
+            crate::combinator::ws_insensitive(
+                expression(uint::<u32>())
+                    .infix(char('+'), 1, Left, |a, b| a + b)
+                    .prefix(token("double"), 1, |a| a * 2),
+            ),
+            &[
+                (" 1 + 2 + 3 ", Some(6), ""), //
+                ("double 1 + 2", Some(6), ""),
+            ],
+        );
+    }
+}
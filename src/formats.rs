@@ -0,0 +1,11 @@
+//! Ready made parsers for common text formats, built with parsely.
+//!
+//! Each format lives in its own module, so only the formats that are used need to be imported.
+
+pub mod csv;
+pub mod dotenv;
+pub mod http;
+pub mod ini;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod query;
@@ -0,0 +1,443 @@
+//! Parse comma separated values according to [RFC 4180](https://www.rfc-editor.org/rfc/rfc4180).
+//!
+//! A record is a line of fields separated by a delimiter, which is `,` unless changed with [`Csv::delimiter()`].
+//! Records end with `\r\n` or `\n`, and the line ending of the last record is optional.
+//!
+//! Fields that contain the delimiter, a quote or a line ending are wrapped in quotes, and each quote inside them is doubled: `"say ""hi"", then leave"`.
+//!
+//! [`csv()`] parses every record in the input, [`Csv::rows()`] iterates over the records one at a time,
+//! and [`Csv::record()`] parses a single record, to combine with other parsers.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! use parsely::{formats::csv::csv, Parse};
+//!
+//! let input = "name,quote\r\nAda,\"say \"\"hi\"\"\"\r\nBob,\"one, two\"\r\n";
+//!
+//! let records = csv().parse_all(input)?;
+//! assert_eq!(
+//!     records,
+//!     vec![
+//!         vec!["name", "quote"],
+//!         vec!["Ada", "say \"hi\""],
+//!         vec!["Bob", "one, two"],
+//!     ]
+//! );
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::fmt;
+
+use crate::{
+    combinator::budget, error, grammar::Grammar, unparse::Unparse, Error, Parse, ParseResult,
+};
+
+/// This parser is returned by [`csv()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Csv {
+    delimiter: char,
+}
+
+impl Csv {
+    /// Separates fields with `delimiter` instead of `,`, e.g. `';'` or `'\t'`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{formats::csv::csv, Parse};
+    ///
+    /// let records = csv().delimiter('\t').parse_all("a\tb,c\n1\t2\n")?;
+    /// assert_eq!(records, vec![vec!["a", "b,c"], vec!["1", "2"]]);
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn delimiter(self, delimiter: char) -> Csv {
+        Csv { delimiter }
+    }
+
+    /// Creates a parser of a single record, including its line ending.
+    ///
+    /// This is useful for combining with other parsers, e.g. to parse a header differently to the rest of the records.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{formats::csv::csv, Parse};
+    ///
+    /// let (record, remaining) = csv().record().parse("1,\"\",3\r\n4,5,6")?;
+    /// assert_eq!(record, vec!["1", "", "3"]);
+    /// assert_eq!(remaining, "4,5,6");
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn record(&self) -> Record {
+        Record {
+            delimiter: self.delimiter,
+        }
+    }
+
+    /// Returns an iterator over the records in `input`, parsing each one as it is needed.
+    ///
+    /// The iterator stops after the first error.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::formats::csv::csv;
+    ///
+    /// let mut rows = csv().rows("a,b\n\"c\nd\",e\n\"f");
+    ///
+    /// assert_eq!(rows.next(), Some(Ok(vec!["a".to_string(), "b".to_string()])));
+    /// assert_eq!(rows.next(), Some(Ok(vec!["c\nd".to_string(), "e".to_string()])));
+    /// assert!(matches!(rows.next(), Some(Err(_))));
+    /// assert_eq!(rows.next(), None);
+    /// ```
+    pub fn rows<'i>(&self, input: &'i str) -> Rows<'i> {
+        Rows {
+            delimiter: self.delimiter,
+            remaining: input,
+        }
+    }
+}
+
+impl Parse for Csv {
+    type Output = Vec<Vec<String>>;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let mut records = Vec::new();
+        let mut remaining = input;
+
+        while !remaining.is_empty() {
+            let (fields, rest) = record(self.delimiter, remaining)?;
+            records.push(fields);
+            remaining = rest;
+        }
+
+        Ok((records, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::repeat(0, None, self.record().describe())
+    }
+}
+
+impl Unparse for Csv {
+    /// Writes each record followed by `\r\n`.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        for fields in value {
+            self.record().unparse_into(fields, output)?;
+            output.push_str("\r\n");
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses comma separated values, outputting a `Vec` of records, each a `Vec` of fields.
+///
+/// Quoted fields are unquoted, so the output is the text of each field.
+/// A quoted field that is never closed, or that is followed by something other than a delimiter or line ending, is a [fatal](crate::Error::fatal()) error.
+///
+/// Unquoted fields are taken as they are, including any whitespace or quotes in them.
+///
+/// See the [module documentation](self) for more details.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{formats::csv::csv, Parse, Unparse};
+///
+/// let records = csv().parse_all("id,tags\n1,\"a,b\"\n2,\n")?;
+/// assert_eq!(records, vec![vec!["id", "tags"], vec!["1", "a,b"], vec!["2", ""]]);
+///
+/// // fields are only quoted when they need to be
+/// assert_eq!(csv().unparse(&records)?, "id,tags\r\n1,\"a,b\"\r\n2,\r\n");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn csv() -> Csv {
+    Csv { delimiter: ',' }
+}
+
+/// This parser is returned by [`Csv::record()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Record {
+    delimiter: char,
+}
+
+impl Parse for Record {
+    type Output = Vec<String>;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        record(self.delimiter, input)
+    }
+
+    fn describe(&self) -> Grammar {
+        let field = Grammar::Special("csv field".to_string());
+        let fields = Grammar::separated(1, None, field, Grammar::Token(self.delimiter.to_string()));
+        let line_ending = Grammar::or(
+            Grammar::Token("\r\n".to_string()),
+            Grammar::Token("\n".to_string()),
+        );
+
+        Grammar::Rule {
+            name: "csv_record",
+            body: Box::new(Grammar::then(
+                fields,
+                Grammar::Optional(Box::new(line_ending)),
+            )),
+        }
+    }
+}
+
+impl Unparse for Record {
+    /// Writes the fields, quoting those that need it, without a line ending.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        if value.is_empty() {
+            return Err(Error::custom("a csv record must have at least one field"));
+        }
+
+        for (i, field) in value.iter().enumerate() {
+            if i > 0 {
+                output.push(self.delimiter);
+            }
+
+            // a lone empty field is quoted, otherwise it would be an empty line
+            let needs_quotes = (value.len() == 1 && field.is_empty())
+                || field.contains([self.delimiter, '"', '\r', '\n']);
+
+            if needs_quotes {
+                output.push('"');
+                output.push_str(&field.replace('"', "\"\""));
+                output.push('"');
+            } else {
+                output.push_str(field);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// This iterator is returned by [`Csv::rows()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Rows<'i> {
+    delimiter: char,
+    remaining: &'i str,
+}
+
+impl<'i> Rows<'i> {
+    /// The input that hasn't been parsed yet.
+    pub fn remaining(&self) -> &'i str {
+        self.remaining
+    }
+}
+
+impl Iterator for Rows<'_> {
+    type Item = Result<Vec<String>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+
+        match record(self.delimiter, self.remaining) {
+            Ok((fields, remaining)) => {
+                self.remaining = remaining;
+                Some(Ok(fields))
+            }
+            Err(error) => {
+                self.remaining = "";
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+/// Parses the fields of one record and its line ending, if it has one.
+fn record(delimiter: char, input: &str) -> ParseResult<'_, Vec<String>> {
+    if input.is_empty() {
+        return Err(error::no_match(input));
+    }
+
+    let mut fields = Vec::new();
+    let mut remaining = input;
+
+    loop {
+        let (value, rest) = field(delimiter, remaining)?;
+        fields.push(value);
+
+        match rest.strip_prefix(delimiter) {
+            Some(rest) => remaining = rest,
+            None => {
+                remaining = rest;
+                break;
+            }
+        }
+    }
+
+    let remaining = remaining
+        .strip_prefix("\r\n")
+        .or_else(|| remaining.strip_prefix('\n'))
+        .unwrap_or(remaining);
+
+    budget::examine(input.len() - remaining.len())?;
+
+    Ok((fields, remaining))
+}
+
+/// Parses a quoted or unquoted field, stopping before the delimiter or line ending after it.
+fn field(delimiter: char, input: &str) -> ParseResult<'_, String> {
+    let Some(mut remaining) = input.strip_prefix('"') else {
+        let mut end = input.find([delimiter, '\n']).unwrap_or(input.len());
+
+        if input[end..].starts_with('\n') && input[..end].ends_with('\r') {
+            end -= 1;
+        }
+
+        return Ok((input[..end].to_string(), &input[end..]));
+    };
+
+    let mut value = String::new();
+
+    loop {
+        let Some(quote) = remaining.find('"') else {
+            return Err(Error::Expected("closing quote").fatal());
+        };
+
+        value.push_str(&remaining[..quote]);
+        remaining = &remaining[quote + 1..];
+
+        // a doubled quote is a quote inside the field
+        match remaining.strip_prefix('"') {
+            Some(rest) => {
+                value.push('"');
+                remaining = rest;
+            }
+            None => break,
+        }
+    }
+
+    let ended = remaining.is_empty()
+        || remaining.starts_with(delimiter)
+        || remaining.starts_with('\n')
+        || remaining.starts_with("\r\n");
+
+    if !ended {
+        return Err(Error::Expected("delimiter or line ending after closing quote").fatal());
+    }
+
+    Ok((value, remaining))
+}
+
+impl fmt::Debug for Csv {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Csv({:?})", self.delimiter)
+    }
+}
+
+impl fmt::Debug for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Record({:?})", self.delimiter)
+    }
+}
+
+impl fmt::Debug for Rows<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Rows({:?}, {:?})", self.delimiter, self.remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    fn strings(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|field| field.to_string()).collect()
+    }
+
+    #[test]
+    fn records() {
+        test_parser_batch(
+            "unquoted and quoted fields",
+            csv().record(),
+            &[
+                ("a,b,c", Some(strings(&["a", "b", "c"])), ""),
+                ("a,b\nc", Some(strings(&["a", "b"])), "c"),
+                ("a,b\r\nc", Some(strings(&["a", "b"])), "c"),
+                ("a,,", Some(strings(&["a", "", ""])), ""),
+                ("\n", Some(strings(&[""])), ""),
+                (" a , b ", Some(strings(&[" a ", " b "])), ""),
+                ("a\rb,c", Some(strings(&["a\rb", "c"])), ""),
+                ("\"a,b\",c", Some(strings(&["a,b", "c"])), ""),
+                ("\"a\r\nb\"\r\n", Some(strings(&["a\r\nb"])), ""),
+                ("\"\"\"a\"\"\",\"\"", Some(strings(&["\"a\"", ""])), ""),
+                ("", None, ""),
+            ],
+        );
+
+        test_parser_batch(
+            "semicolon delimiter",
+            csv().delimiter(';').record(),
+            &[("a;\"b;c\";d,e\n", Some(strings(&["a", "b;c", "d,e"])), "")],
+        );
+    }
+
+    #[test]
+    fn errors() {
+        test_parser_errors(
+            "badly quoted fields",
+            csv().record(),
+            &[
+                ("\"abc", Error::Expected("closing quote").fatal()),
+                ("a,\"b\"\"", Error::Expected("closing quote").fatal()),
+                (
+                    "\"a\"b,c",
+                    Error::Expected("delimiter or line ending after closing quote").fatal(),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn whole_input() -> Result<(), Error> {
+        assert_eq!(csv().parse_all("")?, Vec::<Vec<String>>::new());
+        assert_eq!(
+            csv().parse_all("a\n\nb")?,
+            vec![strings(&["a"]), strings(&[""]), strings(&["b"])]
+        );
+
+        let rows = csv().rows("1,2\r\n3,4").collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(rows, vec![strings(&["1", "2"]), strings(&["3", "4"])]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        let records = vec![
+            strings(&["plain", "with,comma", "with \"quotes\""]),
+            strings(&["multi\nline", "", "\r"]),
+            strings(&[""]),
+        ];
+
+        let text = csv().unparse(&records)?;
+        assert_eq!(csv().parse_all(&text)?, records);
+
+        let text = csv().delimiter('\t').unparse(&records)?;
+        assert_eq!(csv().delimiter('\t').parse_all(&text)?, records);
+
+        assert!(csv().record().unparse(&vec![]).is_err());
+
+        Ok(())
+    }
+}
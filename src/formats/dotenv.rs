@@ -0,0 +1,351 @@
+//! Parse `.env` style files of `KEY=VALUE` lines, as used to configure services through environment variables.
+//!
+//! * each line is an entry, a comment or blank
+//! * a comment is a line starting with `#`, or a `#` after whitespace following an unquoted value
+//! * an entry can start with `export `, so that the file can also be sourced by a shell
+//! * keys are made of ASCII letters, digits, `_` and `.`, and there can be whitespace around the `=`
+//! * an unquoted value is the rest of the line, with the whitespace around it removed
+//! * a value in single quotes is taken literally, and can span several lines
+//! * a value in double quotes can also span several lines, and decodes the escapes `\n`, `\r`, `\t`, `\"` and `\\`
+//! * variables such as `$HOME` aren't expanded
+//!
+//! The pairs are output in the order they appear, collect them into a map to keep only the last value of each key.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use parsely::{formats::dotenv::dotenv, Parse};
+//!
+//! let env = r#"
+//! ## database
+//! export DATABASE_URL=postgres://localhost/app  # local only
+//! GREETING="Hello,\nWorld"
+//! PATTERN='[a-z]+\d'
+//! "#;
+//!
+//! let vars: HashMap<_, _> = dotenv().parse_all(env)?.into_iter().collect();
+//!
+//! assert_eq!(vars["DATABASE_URL"], "postgres://localhost/app");
+//! assert_eq!(vars["GREETING"], "Hello,\nWorld");
+//! assert_eq!(vars["PATTERN"], r"[a-z]+\d");
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine, grammar::Grammar, unparse::Unparse, Error, Parse, ParseResult,
+};
+
+/// This parser is returned by [`dotenv()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Dotenv;
+
+impl Parse for Dotenv {
+    type Output = Vec<(String, String)>;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let mut pairs = Vec::new();
+        let mut remaining = input;
+
+        while !remaining.is_empty() {
+            let (pair, rest) = line(remaining)?;
+            examine(remaining.len() - rest.len())?;
+
+            pairs.extend(pair);
+            remaining = rest;
+        }
+
+        Ok((pairs, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        let line = Grammar::Choice(vec![
+            Grammar::Sequence(vec![
+                Grammar::Optional(Box::new(Grammar::Token("export ".to_string()))),
+                Grammar::Special("key".to_string()),
+                Grammar::Token("=".to_string()),
+                Grammar::Special("value".to_string()),
+            ]),
+            Grammar::Special("comment".to_string()),
+            Grammar::Empty,
+        ]);
+
+        Grammar::separated(0, None, line, Grammar::Token("\n".to_string()))
+    }
+}
+
+impl Unparse for Dotenv {
+    /// Writes each pair on its own line as `KEY=value`.
+    ///
+    /// Values that are empty or contain whitespace, quotes, `#`, `\` or `$` are written in double quotes, with escapes.
+    /// Keys that aren't made of ASCII letters, digits, `_` and `.` can't be written, and fail with a custom error.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        for (key, value) in value {
+            if key.is_empty() || !key.chars().all(is_key_char) {
+                return Err(Error::custom(format!(
+                    "{key:?} can't be written as a dotenv key"
+                )));
+            }
+
+            output.push_str(key);
+            output.push('=');
+
+            let needs_quotes = value.is_empty()
+                || value.contains(|c: char| {
+                    c.is_whitespace() || matches!(c, '"' | '\'' | '#' | '\\' | '$')
+                });
+
+            if needs_quotes {
+                output.push('"');
+                for c in value.chars() {
+                    match c {
+                        '"' => output.push_str("\\\""),
+                        '\\' => output.push_str("\\\\"),
+                        '\n' => output.push_str("\\n"),
+                        '\r' => output.push_str("\\r"),
+                        '\t' => output.push_str("\\t"),
+                        c => output.push(c),
+                    }
+                }
+                output.push('"');
+            } else {
+                output.push_str(value);
+            }
+
+            output.push('\n');
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a `.env` style file, outputting the `(key, value)` pairs in the order they appear.
+///
+/// A line that isn't an entry, a comment or blank is an error, as is a quoted value that is never closed.
+///
+/// See the [module documentation](self) for more details.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{formats::dotenv::dotenv, Error, Parse, Unparse};
+///
+/// let pairs = dotenv().parse_all("A=1\n\n  export B = two words \nC=\n")?;
+/// assert_eq!(
+///     pairs,
+///     vec![
+///         ("A".to_string(), "1".to_string()),
+///         ("B".to_string(), "two words".to_string()),
+///         ("C".to_string(), "".to_string()),
+///     ]
+/// );
+///
+/// assert_eq!(dotenv().unparse(&pairs)?, "A=1\nB=\"two words\"\nC=\"\"\n");
+///
+/// assert_eq!(dotenv().parse("A=1\nB"), Err(Error::Expected("'=' after the key")));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn dotenv() -> Dotenv {
+    Dotenv
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+fn trim_blanks(text: &str) -> &str {
+    text.trim_start_matches([' ', '\t'])
+}
+
+/// Splits `input` after its first line ending, or at the end of the input.
+fn split_line(input: &str) -> (&str, &str) {
+    let end = input.find('\n').map_or(input.len(), |newline| newline + 1);
+    input.split_at(end)
+}
+
+/// Parses one line, including its line ending, outputting its pair if it is an entry.
+///
+/// A quoted value can continue onto the lines after it, which are included too.
+fn line(input: &str) -> ParseResult<'_, Option<(String, String)>> {
+    let text = trim_blanks(input);
+
+    if text.is_empty() || text.starts_with(['\n', '\r', '#']) {
+        let (_, remaining) = split_line(text);
+        return Ok((None, remaining));
+    }
+
+    let text = match text.strip_prefix("export") {
+        Some(after) if after.starts_with([' ', '\t']) => trim_blanks(after),
+        _ => text,
+    };
+
+    let key_len = text.find(|c| !is_key_char(c)).unwrap_or(text.len());
+    if key_len == 0 {
+        return Err(Error::Expected("a key"));
+    }
+    let (key, after) = text.split_at(key_len);
+
+    let after_equals = trim_blanks(after)
+        .strip_prefix('=')
+        .ok_or(Error::Expected("'=' after the key"))?;
+    let value = trim_blanks(after_equals);
+
+    let (value, after) = match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => quoted(&value[1..], quote)?,
+        _ => {
+            let (line, remaining) = split_line(after_equals);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            // a '#' after whitespace starts a comment
+            let end = line
+                .char_indices()
+                .find(|&(i, c)| c == '#' && line[..i].ends_with([' ', '\t']))
+                .map_or(line.len(), |(i, _)| i);
+
+            let value = line[..end].trim_matches([' ', '\t']).to_string();
+            return Ok((Some((key.to_string(), value)), remaining));
+        }
+    };
+
+    // only a comment can follow a quoted value
+    let (rest, remaining) = split_line(after);
+    let rest = trim_blanks(rest);
+    if !(rest.trim_end_matches(['\n', '\r']).is_empty() || rest.starts_with('#')) {
+        return Err(Error::Expected("the end of the line after the value"));
+    }
+
+    Ok((Some((key.to_string(), value)), remaining))
+}
+
+/// Parses a value after its opening quote, up to and including its closing quote.
+fn quoted(input: &str, quote: char) -> ParseResult<'_, String> {
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            c if c == quote => return Ok((value, &input[i + 1..])),
+            '\\' if quote == '"' => match chars.next() {
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 'r')) => value.push('\r'),
+                Some((_, 't')) => value.push('\t'),
+                Some((_, c @ ('"' | '\\'))) => value.push(c),
+                // other escapes are kept as they are
+                Some((_, c)) => {
+                    value.push('\\');
+                    value.push(c);
+                }
+                None => break,
+            },
+            c => value.push(c),
+        }
+    }
+
+    Err(Error::Expected("a closing quote"))
+}
+
+impl fmt::Debug for Dotenv {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Dotenv")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn lines() -> Result<(), Error> {
+        let input = "
+            # a comment
+            A=1
+            export B = 2\r
+            exportC=3
+            D=a=b # comment
+            E=x#y
+            F = #empty
+            G=\"multi
+line\" # comment
+            H='no \\n escapes'
+            I=\"\\\"quoted\\\" \\t \\$HOME\"
+            J=
+            A=last
+        ";
+
+        assert_eq!(
+            dotenv().parse_all(input)?,
+            pairs(&[
+                ("A", "1"),
+                ("B", "2"),
+                ("exportC", "3"),
+                ("D", "a=b"),
+                ("E", "x#y"),
+                ("F", ""),
+                ("G", "multi\nline"),
+                ("H", "no \\n escapes"),
+                ("I", "\"quoted\" \t \\$HOME"),
+                ("J", ""),
+                ("A", "last"),
+            ])
+        );
+
+        assert!(dotenv().parse_all("")?.is_empty());
+        assert_eq!(dotenv().parse_all("A=1")?, pairs(&[("A", "1")]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        test_parser_errors(
+            "bad lines",
+            dotenv(),
+            &[
+                ("A", Error::Expected("'=' after the key")),
+                ("A B=1", Error::Expected("'=' after the key")),
+                ("=1", Error::Expected("a key")),
+                ("export =1", Error::Expected("a key")),
+                ("A=\"open", Error::Expected("a closing quote")),
+                ("A='open\nB=1", Error::Expected("a closing quote")),
+                (
+                    "A=\"x\" y",
+                    Error::Expected("the end of the line after the value"),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        let value = pairs(&[
+            ("A", "1"),
+            ("B", ""),
+            ("C", "two words"),
+            ("D", "say \"hi\"\n\\ $x #y"),
+            ("E", "it's"),
+        ]);
+
+        let written = dotenv().unparse(&value)?;
+        assert_eq!(dotenv().parse_all(&written)?, value);
+        assert!(written.starts_with("A=1\nB=\"\"\n"));
+
+        assert!(dotenv().unparse(&pairs(&[("A B", "1")])).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,490 @@
+//! Parse header lines like `Content-Type: text/html; charset=utf-8`, following the rules of [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110) (which replaces RFC 7230).
+//!
+//! The same header syntax is used by HTTP, SIP and email, so these helpers are useful for all sorts of small tools:
+//!
+//! * [`tchar()`] and [`field_name()`] match the token chars that header names and parameter names are made of
+//! * [`header()`] parses a `Name: value` line, skipping optional whitespace around the value
+//! * [`parameterized()`] parses a value followed by `; name=value` parameters, such as a media type
+//! * [`quoted_string()`] parses a `"quoted \"string\""`, as used by parameter values
+//!
+//! Header names are case insensitive, but are output as they appear in the input.
+//! Values that are folded across multiple lines, which RFC 9110 deprecates, aren't supported.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! use parsely::{formats::http::{header, parameterized}, token, Parse};
+//!
+//! let request = "Host: example.com\r\nContent-Type: text/html; charset=\"utf-8\"\r\n\r\n<html>";
+//! let (headers, body) = header().many(..).then_skip(token("\r\n")).parse(request)?;
+//!
+//! assert_eq!(headers[0], ("Host".to_string(), "example.com".to_string()));
+//! assert_eq!(body, "<html>");
+//!
+//! let (media_type, parameters) = parameterized().parse_all(&headers[1].1)?;
+//! assert_eq!(media_type, "text/html");
+//! assert_eq!(parameters, vec![("charset".to_string(), "utf-8".to_string())]);
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine,
+    error::no_match,
+    grammar::Grammar,
+    lexer::{char_class, CharIf},
+    unparse::Unparse,
+    Error, Lex, Parse, ParseResult,
+};
+
+/// The chars that can appear in a token, without needing to be quoted.
+const TCHAR_SYMBOLS: &str = "!#$%&'*+-.^_`|~";
+
+/// Matches a single token char: an ascii letter or digit, or one of ``!#$%&'*+-.^_`|~``.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{formats::http::tchar, Lex};
+///
+/// assert_eq!(tchar().many(1..).lex("X-Request-Id: 1")?, ("X-Request-Id", ": 1"));
+/// assert!(tchar().lex("(comment)").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn tchar() -> CharIf<fn(char) -> bool> {
+    char_class("tchar", |c| {
+        c.is_ascii_alphanumeric() || TCHAR_SYMBOLS.contains(c)
+    })
+}
+
+/// Matches a token, one or more [`tchar()`], which is what header names and parameter names are made of.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{formats::http::field_name, Lex};
+///
+/// assert_eq!(field_name().lex("Accept-Encoding: gzip")?, ("Accept-Encoding", ": gzip"));
+/// assert!(field_name().lex(": gzip").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn field_name() -> impl Lex + Clone {
+    tchar().many(1..)
+}
+
+/// This parser is returned by [`header()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Header;
+
+impl Parse for Header {
+    type Output = (String, String);
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (name, remaining) = field_name().lex(input)?;
+        let remaining = remaining
+            .strip_prefix(':')
+            .ok_or_else(|| no_match(remaining))?;
+
+        let end = remaining.find('\n').unwrap_or(remaining.len());
+        examine(end)?;
+
+        let (line, remaining) = remaining.split_at(end);
+        let value = line.strip_suffix('\r').unwrap_or(line);
+        let remaining = remaining.strip_prefix('\n').unwrap_or(remaining);
+
+        let value = value.trim_matches([' ', '\t']).to_string();
+
+        Ok(((name.to_string(), value), remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        let line_ending = Grammar::or(
+            Grammar::Token("\r\n".to_string()),
+            Grammar::Token("\n".to_string()),
+        );
+
+        Grammar::Rule {
+            name: "header",
+            body: Box::new(Grammar::Sequence(vec![
+                field_name().describe(),
+                Grammar::Token(":".to_string()),
+                Grammar::Special("field value".to_string()),
+                Grammar::Optional(Box::new(line_ending)),
+            ])),
+        }
+    }
+}
+
+impl Unparse for Header {
+    /// Writes `Name: value` followed by `\r\n`.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let (name, value) = value;
+
+        if field_name().lex_all(name).is_err() {
+            return Err(Error::custom(format!(
+                "{name:?} is not a valid header name"
+            )));
+        }
+        if value.contains(['\r', '\n']) {
+            return Err(Error::custom(format!(
+                "{value:?} can't be written on one header line"
+            )));
+        }
+
+        output.push_str(name);
+        output.push_str(": ");
+        output.push_str(value.trim_matches([' ', '\t']));
+        output.push_str("\r\n");
+
+        Ok(())
+    }
+}
+
+/// Parses a `Name: value` header line, outputting the name and value as a tuple `(name, value)`.
+///
+/// The name must be a [`field_name()`] followed immediately by `:`, whitespace before the `:` is not allowed.
+/// Spaces and tabs around the value are skipped, and the line ending (`\r\n` or `\n`) is consumed if there is one.
+///
+/// A block of headers ends with an empty line, which doesn't match, so `header().many(..)` stops there.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{formats::http::header, Parse, Unparse};
+///
+/// let (accept, remaining) = header().parse("Accept:\t */* \r\nHost: a")?;
+/// assert_eq!(accept, ("Accept".to_string(), "*/*".to_string()));
+/// assert_eq!(remaining, "Host: a");
+///
+/// assert!(header().parse("Accept : */*").is_err());
+///
+/// assert_eq!(header().unparse(&accept)?, "Accept: */*\r\n");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn header() -> Header {
+    Header
+}
+
+/// This parser is returned by [`quoted_string()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct QuotedString;
+
+impl Parse for QuotedString {
+    type Output = String;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let Some(mut chars) = input.strip_prefix('"').map(str::char_indices) else {
+            return Err(no_match(input));
+        };
+
+        let mut value = String::new();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    // the opening quote is 1 byte before the chars
+                    let end = 1 + i + 1;
+                    examine(end)?;
+                    return Ok((value, &input[end..]));
+                }
+                '\\' => match chars.next() {
+                    Some((_, escaped)) => value.push(escaped),
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+
+        Err(no_match(input))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special("quoted string".to_string())
+    }
+}
+
+impl Unparse for QuotedString {
+    /// Writes the value in quotes, escaping `"` and `\` with a `\`.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        output.push('"');
+        for c in value.chars() {
+            if c == '"' || c == '\\' {
+                output.push('\\');
+            }
+            output.push(c);
+        }
+        output.push('"');
+
+        Ok(())
+    }
+}
+
+/// Parses a quoted string, outputting its contents with any `\` escapes removed.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{formats::http::quoted_string, Parse};
+///
+/// assert_eq!(quoted_string().parse(r#""a \"b\" c";"#)?, (r#"a "b" c"#.to_string(), ";"));
+/// assert!(quoted_string().parse(r#""unclosed"#).is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn quoted_string() -> QuotedString {
+    QuotedString
+}
+
+/// This parser is returned by [`parameterized()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Parameterized;
+
+impl Parameterized {
+    /// Parses `; name=value` with optional whitespace around the `;`, or just the `;` of an empty parameter.
+    fn parameter<'i>(input: &'i str) -> ParseResult<'i, Option<(String, String)>> {
+        let ows = |input: &'i str| input.trim_start_matches([' ', '\t']);
+
+        let remaining = ows(input)
+            .strip_prefix(';')
+            .ok_or_else(|| no_match(input))?;
+        let remaining = ows(remaining);
+
+        if remaining.is_empty() || remaining.starts_with(';') {
+            return Ok((None, remaining));
+        }
+
+        let (name, remaining) = field_name().lex(remaining)?;
+        let remaining = remaining
+            .strip_prefix('=')
+            .ok_or_else(|| no_match(remaining))?;
+
+        let (value, remaining) = match field_name().lex(remaining) {
+            Ok((value, remaining)) => (value.to_string(), remaining),
+            Err(_) => quoted_string().parse(remaining)?,
+        };
+
+        Ok((Some((name.to_string(), value)), remaining))
+    }
+}
+
+impl Parse for Parameterized {
+    type Output = (String, Vec<(String, String)>);
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let end = input.find(['\r', '\n', ';']).unwrap_or(input.len());
+        examine(end)?;
+
+        let value = input[..end].trim_matches([' ', '\t']).to_string();
+        let mut remaining = &input[end..];
+        let mut parameters = Vec::new();
+
+        // a parameter that doesn't match is left in the remaining input
+        while let Ok((parameter, rest)) = Self::parameter(remaining) {
+            parameters.extend(parameter);
+            remaining = rest;
+        }
+
+        Ok(((value, parameters), remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        let parameter = Grammar::Sequence(vec![
+            Grammar::Token(";".to_string()),
+            field_name().describe(),
+            Grammar::Token("=".to_string()),
+            Grammar::or(field_name().describe(), quoted_string().describe()),
+        ]);
+
+        Grammar::then(
+            Grammar::Special("field value".to_string()),
+            Grammar::repeat(0, None, parameter),
+        )
+    }
+}
+
+impl Unparse for Parameterized {
+    /// Writes the value then each parameter as `; name=value`, quoting values that aren't tokens.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let (value, parameters) = value;
+
+        if value.contains(['\r', '\n', ';']) {
+            return Err(Error::custom(format!(
+                "{value:?} can't be written before parameters"
+            )));
+        }
+        output.push_str(value);
+
+        for (name, value) in parameters {
+            if field_name().lex_all(name).is_err() {
+                return Err(Error::custom(format!(
+                    "{name:?} is not a valid parameter name"
+                )));
+            }
+
+            output.push_str("; ");
+            output.push_str(name);
+            output.push('=');
+
+            if field_name().lex_all(value).is_ok() {
+                output.push_str(value);
+            } else {
+                quoted_string().unparse_into(value, output)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a header value followed by parameters, such as `text/html; charset=utf-8`, outputting the value and a list of `(name, value)` parameters.
+///
+/// The value is everything before the first `;`, without the whitespace around it.
+/// Each parameter is a [`field_name()`], then `=`, then a value that is either a [`field_name()`] or a [`quoted_string()`].
+/// Empty parameters are skipped, and parsing stops before a parameter that doesn't match.
+///
+/// Parameter names are case insensitive, but are output as they appear in the input.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{formats::http::parameterized, Parse, Unparse};
+///
+/// let (disposition, remaining) = parameterized().parse("attachment; filename=\"my file.txt\";; size=42")?;
+///
+/// assert_eq!(disposition.0, "attachment");
+/// assert_eq!(disposition.1[0], ("filename".to_string(), "my file.txt".to_string()));
+/// assert_eq!(disposition.1[1], ("size".to_string(), "42".to_string()));
+/// assert_eq!(remaining, "");
+///
+/// assert_eq!(
+///     parameterized().unparse(&disposition)?,
+///     "attachment; filename=\"my file.txt\"; size=42"
+/// );
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn parameterized() -> Parameterized {
+    Parameterized
+}
+
+impl fmt::Debug for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Header")
+    }
+}
+
+impl fmt::Debug for QuotedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "QuotedString")
+    }
+}
+
+impl fmt::Debug for Parameterized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Parameterized")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    fn header_of(name: &str, value: &str) -> (String, String) {
+        (name.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn headers() {
+        test_parser_batch(
+            "header lines",
+            header(),
+            &[
+                ("Host: a", Some(header_of("Host", "a")), ""),
+                ("Host:a\r\n", Some(header_of("Host", "a")), ""),
+                (
+                    "X-Empty:\r\nHost: a",
+                    Some(header_of("X-Empty", "")),
+                    "Host: a",
+                ),
+                ("A: b c \t\nB", Some(header_of("A", "b c")), "B"),
+                ("A: b:c", Some(header_of("A", "b:c")), ""),
+                ("Host : a", None, ""),
+                (" Host: a", None, ""),
+                ("Ho(st): a", None, ""),
+                ("\r\n", None, ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn parameters() {
+        let parameterized_of = |value: &str, parameters: &[(&str, &str)]| {
+            (
+                value.to_string(),
+                parameters
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        test_parser_batch(
+            "parameterized values",
+            parameterized(),
+            &[
+                ("text/plain", Some(parameterized_of("text/plain", &[])), ""),
+                (
+                    "text/html;charset=utf-8",
+                    Some(parameterized_of("text/html", &[("charset", "utf-8")])),
+                    "",
+                ),
+                (
+                    "a ; b=\"c;d\" ; e=f",
+                    Some(parameterized_of("a", &[("b", "c;d"), ("e", "f")])),
+                    "",
+                ),
+                ("a;", Some(parameterized_of("a", &[])), ""),
+                ("a; b", Some(parameterized_of("a", &[])), "; b"),
+                ("a; b=\"c", Some(parameterized_of("a", &[])), "; b=\"c"),
+                (
+                    "a; b=c\r\n",
+                    Some(parameterized_of("a", &[("b", "c")])),
+                    "\r\n",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        let value = (
+            "multipart/form-data".to_string(),
+            vec![
+                ("boundary".to_string(), "a b\"c\\".to_string()),
+                ("x".to_string(), "".to_string()),
+            ],
+        );
+        let text = parameterized().unparse(&value)?;
+        assert_eq!(text, r#"multipart/form-data; boundary="a b\"c\\"; x="""#);
+        assert_eq!(parameterized().parse_all(&text)?, value);
+
+        assert!(header().unparse(&header_of("Bad Name", "a")).is_err());
+        assert!(header()
+            .unparse(&header_of("Name", "a\r\nInjected: b"))
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,323 @@
+//! Parse INI style config files, made of `[section]` headers and `key = value` entries.
+//!
+//! * each line is a section header, an entry, a comment or blank
+//! * a comment is a line starting with `;` or `#`, comments can't follow an entry on the same line
+//! * entries before the first section header are in the section named `""`
+//! * keys, values and section names have the whitespace around them removed
+//! * a value is everything after the first `=`, so it can contain `=` itself
+//! * when a key appears twice in a section, the last value is used
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! use parsely::{formats::ini::ini, Parse};
+//!
+//! let config = "\
+//! name = demo
+//!
+//! ; where to listen
+//! [server]
+//! host = 127.0.0.1
+//! port = 8080
+//! ";
+//!
+//! let sections = ini().parse_all(config)?;
+//! assert_eq!(sections[""]["name"], "demo");
+//! assert_eq!(sections["server"]["port"], "8080");
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::{collections::HashMap, fmt};
+
+use crate::{combinator::budget::examine, grammar::Grammar, Error, Parse, ParseResult};
+
+/// This parser is returned by [`ini()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Ini;
+
+impl Ini {
+    /// Returns an iterator over the `(section, key, value)` of each entry in `input`, in the order they appear.
+    ///
+    /// Unlike parsing into a map, keys that appear more than once are all kept.
+    /// The iterator stops after the first error.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::formats::ini::ini;
+    ///
+    /// let entries = ini()
+    ///     .entries("top = 1\n[a]\nx = 2\nx = 3\n")
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    ///
+    /// assert_eq!(entries[0], ("".to_string(), "top".to_string(), "1".to_string()));
+    /// assert_eq!(entries[2], ("a".to_string(), "x".to_string(), "3".to_string()));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn entries<'i>(&self, input: &'i str) -> Entries<'i> {
+        Entries {
+            section: String::new(),
+            remaining: input,
+        }
+    }
+}
+
+impl Parse for Ini {
+    type Output = HashMap<String, HashMap<String, String>>;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let mut sections: Self::Output = HashMap::new();
+        let mut section = String::new();
+        let mut remaining = input;
+
+        while !remaining.is_empty() {
+            let (parsed, rest) = line(remaining)?;
+            remaining = rest;
+
+            match parsed {
+                Line::Blank => {}
+                Line::Section(name) => {
+                    sections.entry(name.clone()).or_default();
+                    section = name;
+                }
+                Line::Entry(key, value) => {
+                    sections
+                        .entry(section.clone())
+                        .or_default()
+                        .insert(key, value);
+                }
+            }
+        }
+
+        Ok((sections, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        let line = Grammar::Choice(vec![
+            Grammar::Sequence(vec![
+                Grammar::Token("[".to_string()),
+                Grammar::Special("section name".to_string()),
+                Grammar::Token("]".to_string()),
+            ]),
+            Grammar::Sequence(vec![
+                Grammar::Special("key".to_string()),
+                Grammar::Token("=".to_string()),
+                Grammar::Special("value".to_string()),
+            ]),
+            Grammar::Special("comment".to_string()),
+            Grammar::Empty,
+        ]);
+
+        Grammar::separated(0, None, line, Grammar::Token("\n".to_string()))
+    }
+}
+
+/// Parses an INI style config file, outputting a map from each section name to a map of its keys and values.
+///
+/// Every section that has a header is in the map, even if it has no entries.
+/// A line that isn't a section header, an entry, a comment or blank is an error.
+///
+/// See the [module documentation](self) for more details.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{formats::ini::ini, Error, Parse};
+///
+/// let sections = ini().parse_all("# empty\n[empty]\n[db]\nurl = postgres://u@h/db?x=1\n")?;
+/// assert!(sections["empty"].is_empty());
+/// assert_eq!(sections["db"]["url"], "postgres://u@h/db?x=1");
+///
+/// assert_eq!(
+///     ini().parse("[db]\nurl postgres://").unwrap_err(),
+///     Error::Expected("'=' after the key")
+/// );
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn ini() -> Ini {
+    Ini
+}
+
+/// This iterator is returned by [`Ini::entries()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Entries<'i> {
+    section: String,
+    remaining: &'i str,
+}
+
+impl<'i> Entries<'i> {
+    /// The input that hasn't been parsed yet.
+    pub fn remaining(&self) -> &'i str {
+        self.remaining
+    }
+}
+
+impl Iterator for Entries<'_> {
+    type Item = Result<(String, String, String), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.remaining.is_empty() {
+            match line(self.remaining) {
+                Ok((parsed, remaining)) => {
+                    self.remaining = remaining;
+
+                    match parsed {
+                        Line::Blank => {}
+                        Line::Section(name) => self.section = name,
+                        Line::Entry(key, value) => {
+                            return Some(Ok((self.section.clone(), key, value)))
+                        }
+                    }
+                }
+                Err(error) => {
+                    self.remaining = "";
+                    return Some(Err(error));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// One line of an INI file.
+enum Line {
+    /// A blank line or a comment.
+    Blank,
+    Section(String),
+    Entry(String, String),
+}
+
+fn is_comment(text: &str) -> bool {
+    text.starts_with([';', '#'])
+}
+
+/// Parses one line, including its line ending.
+fn line(input: &str) -> ParseResult<'_, Line> {
+    let end = input.find('\n').map_or(input.len(), |newline| newline + 1);
+    examine(end)?;
+
+    let (text, remaining) = input.split_at(end);
+    let text = text.trim();
+
+    let line = if text.is_empty() || is_comment(text) {
+        Line::Blank
+    } else if let Some(header) = text.strip_prefix('[') {
+        let (name, after) = header
+            .split_once(']')
+            .ok_or(Error::Expected("']' to close the section header"))?;
+
+        let after = after.trim_start();
+        if !after.is_empty() && !is_comment(after) {
+            return Err(Error::Expected(
+                "the end of the line after the section header",
+            ));
+        }
+
+        Line::Section(name.trim().to_string())
+    } else {
+        let (key, value) = text
+            .split_once('=')
+            .ok_or(Error::Expected("'=' after the key"))?;
+
+        let key = key.trim_end();
+        if key.is_empty() {
+            return Err(Error::Expected("a key before '='"));
+        }
+
+        Line::Entry(key.to_string(), value.trim_start().to_string())
+    };
+
+    Ok((line, remaining))
+}
+
+impl fmt::Debug for Ini {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ini")
+    }
+}
+
+impl fmt::Debug for Entries<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entries({:?}, {:?})", self.section, self.remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn sections() -> Result<(), Error> {
+        let input = "
+            a = 1
+            # comment
+            [ one ]  ; comment
+            b=2
+            c = x = y\r
+            b = 3
+
+            [two]
+            [one]
+            d =
+        ";
+
+        let sections = ini().parse_all(input)?;
+
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[""]["a"], "1");
+        assert_eq!(sections["one"]["b"], "3");
+        assert_eq!(sections["one"]["c"], "x = y");
+        assert_eq!(sections["one"]["d"], "");
+        assert!(sections["two"].is_empty());
+
+        assert!(ini().parse_all("")?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        test_parser_errors(
+            "bad lines",
+            ini(),
+            &[
+                ("[one", Error::Expected("']' to close the section header")),
+                (
+                    "[one] two",
+                    Error::Expected("the end of the line after the section header"),
+                ),
+                ("a = 1\nb", Error::Expected("'=' after the key")),
+                (" = 1", Error::Expected("a key before '='")),
+            ],
+        );
+    }
+
+    #[test]
+    fn entries() {
+        let mut entries = ini().entries("[a]\nx = 1\n[b]\ny = 2\nz\nw = 3");
+        let entry = |section: &str, key: &str, value: &str| {
+            Some(Ok((
+                section.to_string(),
+                key.to_string(),
+                value.to_string(),
+            )))
+        };
+
+        assert_eq!(entries.next(), entry("a", "x", "1"));
+        assert_eq!(entries.next(), entry("b", "y", "2"));
+        assert_eq!(entries.remaining(), "z\nw = 3");
+        assert_eq!(
+            entries.next(),
+            Some(Err(Error::Expected("'=' after the key")))
+        );
+        assert_eq!(entries.next(), None);
+    }
+}
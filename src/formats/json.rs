@@ -0,0 +1,763 @@
+//! Parse [JSON](https://www.rfc-editor.org/rfc/rfc8259) into a [`Value`], such as config files or the output of other tools.
+//!
+//! This requires the `json` feature.
+//!
+//! Parsing follows RFC 8259 strictly:
+//!
+//! * whitespace is only spaces, tabs, line feeds and carriage returns
+//! * control characters in strings must be escaped
+//! * a `\u` escape of a UTF-16 surrogate pair is combined into one char, a lone surrogate is an error
+//! * numbers can't have leading zeros, a leading `+`, or a `.` or exponent without digits after it
+//! * integers that fit in an `i64` are [`Number::Int`], any other number is a [`Number::Float`]
+//! * a number too large for an `f64` is an [`Error::Overflow`], rather than infinity
+//! * when a key appears twice in an object, the last value is used
+//! * arrays and objects can be nested at most [`DEFAULT_MAX_DEPTH`] deep, see [`Json::max_depth()`]
+//!
+//! Whitespace around the value is skipped. Anything after it is left in the remaining input, use [`parse_all()`](Parse::parse_all()) to reject it.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! use parsely::{formats::json::{json, Number, Value}, Parse};
+//!
+//! let config = json().parse_all(r#"
+//!     {
+//!         "name": "demo",
+//!         "port": 8080,
+//!         "hosts": ["a.example", "b.example"],
+//!         "debug": false
+//!     }
+//! "#)?;
+//!
+//! assert_eq!(config.get("name").and_then(Value::as_str), Some("demo"));
+//! assert_eq!(config.get("port"), Some(&Value::Number(Number::Int(8080))));
+//! assert_eq!(config.get("debug").and_then(Value::as_bool), Some(false));
+//!
+//! let Some(Value::Array(hosts)) = config.get("hosts") else {
+//!     panic!("hosts is an array");
+//! };
+//! assert_eq!(hosts[1].as_str(), Some("b.example"));
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    combinator::budget::examine,
+    error::{failed_conversion, no_match, overflow},
+    grammar::Grammar,
+    unparse::Unparse,
+    Error, Parse, ParseResult,
+};
+
+/// How deeply arrays and objects can be nested by default, see [`Json::max_depth()`].
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// This parser is returned by [`json()`]. See it's documentation for more details.
+#[derive(Clone, Copy)]
+pub struct Json {
+    max_depth: usize,
+}
+
+impl Json {
+    /// Sets how deeply arrays and objects can be nested, [`DEFAULT_MAX_DEPTH`] by default.
+    ///
+    /// Input nested deeper than this fails with [`Error::DepthExceeded`], so untrusted input can't overflow the stack.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{formats::json::json, Error, Parse};
+    ///
+    /// let shallow = json().max_depth(2);
+    ///
+    /// assert!(shallow.parse_all("[[1]]").is_ok());
+    /// assert_eq!(shallow.parse_all("[[[1]]]"), Err(Error::DepthExceeded(2)));
+    /// ```
+    pub fn max_depth(self, limit: usize) -> Json {
+        Json { max_depth: limit }
+    }
+}
+
+/// A JSON value, as output by [`json()`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// `null`
+    Null,
+
+    /// `true` or `false`
+    Bool(bool),
+
+    /// A number such as `-12` or `1.5e3`
+    Number(Number),
+
+    /// A string, with its escape sequences decoded
+    String(String),
+
+    /// An array of values, such as `[1, "two"]`
+    Array(Vec<Value>),
+
+    /// An object such as `{"key": "value"}`, with its keys in sorted order
+    Object(BTreeMap<String, Value>),
+}
+
+/// A JSON number.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    /// A number without a fraction or exponent, that fits in an `i64`
+    Int(i64),
+
+    /// Any other number
+    Float(f64),
+}
+
+impl Value {
+    /// Returns the value of the given key, if this is an object that has it.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(object) => object.get(key),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, if this is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns the bool, if this is `true` or `false`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(bool) => Some(*bool),
+            _ => None,
+        }
+    }
+
+    /// Returns the number, if this is a [`Number::Int`].
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Number(Number::Int(n)) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the number as an `f64`, if this is a number.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(Number::Int(n)) => Some(*n as f64),
+            Value::Number(Number::Float(n)) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+impl Parse for Json {
+    type Output = Value;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (value, remaining) = value(skip_ws(input)?, 0, self.max_depth)?;
+        Ok((value, skip_ws(remaining)?))
+    }
+
+    fn describe(&self) -> Grammar {
+        let value = || Grammar::Reference("json_value".to_string());
+        let token = |token: &str| Grammar::Token(token.to_string());
+
+        let array = Grammar::then(
+            token("["),
+            Grammar::then(Grammar::separated(0, None, value(), token(",")), token("]")),
+        );
+        let member = Grammar::then(
+            Grammar::Special("json string".to_string()),
+            Grammar::then(token(":"), value()),
+        );
+        let object = Grammar::then(
+            token("{"),
+            Grammar::then(Grammar::separated(0, None, member, token(",")), token("}")),
+        );
+
+        Grammar::Rule {
+            name: "json_value",
+            body: Box::new(Grammar::Choice(vec![
+                token("null"),
+                token("true"),
+                token("false"),
+                Grammar::Special("json number".to_string()),
+                Grammar::Special("json string".to_string()),
+                array,
+                object,
+            ])),
+        }
+    }
+}
+
+impl Unparse for Json {
+    /// Writes the value as compact JSON, without any whitespace.
+    ///
+    /// A float that is NaN or infinite, which JSON can't represent, is written as `null`.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        write_value(value, output);
+        Ok(())
+    }
+}
+
+impl fmt::Display for Value {
+    /// Writes the value as compact JSON, the same way as unparsing it with [`json()`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut output = String::new();
+        write_value(self, &mut output);
+        f.write_str(&output)
+    }
+}
+
+/// Parses a JSON value, outputting it as a [`Value`].
+///
+/// See the [module documentation](self) for more details.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{formats::json::{json, Number, Value}, Error, Parse, Unparse};
+///
+/// let (value, remaining) = json().parse(r#"["caf\u00e9", 1.5, null] rest"#)?;
+/// assert_eq!(
+///     value,
+///     Value::Array(vec![
+///         Value::String("café".to_string()),
+///         Value::Number(Number::Float(1.5)),
+///         Value::Null,
+///     ])
+/// );
+/// assert_eq!(remaining, "rest");
+///
+/// assert_eq!(json().unparse(&value)?, r#"["café",1.5,null]"#);
+///
+/// assert_eq!(json().parse("[1, 2,]"), Err(Error::Expected("a value")));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn json() -> Json {
+    Json {
+        max_depth: DEFAULT_MAX_DEPTH,
+    }
+}
+
+/// Skips JSON whitespace, which unlike [`char::is_whitespace()`] is only spaces, tabs, line feeds and carriage returns.
+fn skip_ws(input: &str) -> Result<&str, Error> {
+    let remaining = input.trim_start_matches([' ', '\t', '\n', '\r']);
+
+    // the char after the whitespace was looked at too
+    examine(input.len() - remaining.len() + 1)?;
+
+    Ok(remaining)
+}
+
+/// Parses a value that starts at the beginning of `input`, nested `depth` levels deep.
+fn value(input: &str, depth: usize, limit: usize) -> ParseResult<'_, Value> {
+    match input.as_bytes().first() {
+        Some(b'n') => literal(input, "null", Value::Null),
+        Some(b't') => literal(input, "true", Value::Bool(true)),
+        Some(b'f') => literal(input, "false", Value::Bool(false)),
+        Some(b'"') => {
+            let (string, remaining) = string(input)?;
+            Ok((Value::String(string), remaining))
+        }
+        Some(b'-' | b'0'..=b'9') => {
+            let (number, remaining) = number(input)?;
+            Ok((Value::Number(number), remaining))
+        }
+        Some(b'[') => {
+            let (array, remaining) = array(input, nested(depth, limit)?, limit)?;
+            Ok((Value::Array(array), remaining))
+        }
+        Some(b'{') => {
+            let (object, remaining) = object(input, nested(depth, limit)?, limit)?;
+            Ok((Value::Object(object), remaining))
+        }
+        _ => {
+            examine(1)?;
+            Err(no_match(input))
+        }
+    }
+}
+
+/// Parses a value inside an array or object, where a value is required.
+fn item(input: &str, depth: usize, limit: usize) -> ParseResult<'_, Value> {
+    match value(input, depth, limit) {
+        Err(Error::NoMatch) => Err(Error::Expected("a value")),
+        result => result,
+    }
+}
+
+/// Goes one level deeper into an array or object.
+fn nested(depth: usize, limit: usize) -> Result<usize, Error> {
+    if depth < limit {
+        Ok(depth + 1)
+    } else {
+        Err(Error::DepthExceeded(limit))
+    }
+}
+
+fn literal<'i>(input: &'i str, literal: &str, value: Value) -> ParseResult<'i, Value> {
+    examine(literal.len())?;
+
+    match input.strip_prefix(literal) {
+        Some(remaining) => Ok((value, remaining)),
+        None => Err(no_match(input)),
+    }
+}
+
+fn number(input: &str) -> ParseResult<'_, Number> {
+    let bytes = input.as_bytes();
+    let digits_from = |start: usize| {
+        start
+            + bytes[start..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count()
+    };
+
+    let mut end = usize::from(bytes[0] == b'-');
+    let mut float = false;
+
+    // no leading zeros: a 0 is the whole integer part
+    end = match bytes.get(end) {
+        Some(b'0') => end + 1,
+        Some(b'1'..=b'9') => digits_from(end),
+        _ => {
+            examine(end + 1)?;
+            return Err(Error::Expected("a digit after '-'"));
+        }
+    };
+
+    if bytes.get(end) == Some(&b'.') {
+        float = true;
+        let digits_end = digits_from(end + 1);
+        if digits_end == end + 1 {
+            examine(digits_end + 1)?;
+            return Err(Error::Expected("a digit after '.'"));
+        }
+        end = digits_end;
+    }
+
+    if let Some(b'e' | b'E') = bytes.get(end) {
+        float = true;
+        let sign = usize::from(matches!(bytes.get(end + 1), Some(b'+' | b'-')));
+        let digits_end = digits_from(end + 1 + sign);
+        if digits_end == end + 1 + sign {
+            examine(digits_end + 1)?;
+            return Err(Error::Expected("a digit in the exponent"));
+        }
+        end = digits_end;
+    }
+
+    // the char after the number was looked at too
+    examine(end + 1)?;
+
+    let (text, remaining) = input.split_at(end);
+
+    if !float {
+        if let Ok(n) = text.parse() {
+            return Ok((Number::Int(n), remaining));
+        }
+    }
+
+    match text.parse::<f64>() {
+        Ok(n) if n.is_finite() => Ok((Number::Float(n), remaining)),
+        _ => Err(overflow(text)),
+    }
+}
+
+fn string(input: &str) -> ParseResult<'_, String> {
+    let mut output = String::new();
+    let mut rest = &input[1..];
+
+    loop {
+        let Some(end) = rest.find(|c: char| c == '"' || c == '\\' || c < ' ') else {
+            examine(input.len())?;
+            return Err(Error::Expected("'\"' to close the string"));
+        };
+
+        output.push_str(&rest[..end]);
+        rest = &rest[end..];
+
+        match rest.as_bytes()[0] {
+            b'"' => {
+                let remaining = &rest[1..];
+                examine(input.len() - remaining.len())?;
+                return Ok((output, remaining));
+            }
+            b'\\' => {
+                let (c, after) = escape(rest)?;
+                output.push(c);
+                rest = after;
+            }
+            _ => {
+                examine(input.len() - rest.len() + 1)?;
+                return Err(Error::Expected(
+                    "control characters in a string to be escaped",
+                ));
+            }
+        }
+    }
+}
+
+/// Parses an escape sequence that starts with the `\` at the beginning of `input`.
+fn escape(input: &str) -> ParseResult<'_, char> {
+    let escaped = match input.as_bytes().get(1) {
+        Some(b'"') => '"',
+        Some(b'\\') => '\\',
+        Some(b'/') => '/',
+        Some(b'b') => '\x08',
+        Some(b'f') => '\x0c',
+        Some(b'n') => '\n',
+        Some(b'r') => '\r',
+        Some(b't') => '\t',
+        Some(b'u') => return unicode(input),
+        _ => {
+            let end = input[1..].chars().next().map_or(1, |c| 1 + c.len_utf8());
+            return Err(failed_conversion(
+                &input[..end],
+                "not a JSON escape sequence",
+            ));
+        }
+    };
+
+    Ok((escaped, &input[2..]))
+}
+
+/// Parses a `\u` escape at the beginning of `input`, combining a surrogate pair into one char.
+fn unicode(input: &str) -> ParseResult<'_, char> {
+    let code_unit = |escape: &str| {
+        let digits = escape.strip_prefix("\\u")?.get(..4)?;
+
+        if digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            u16::from_str_radix(digits, 16).ok()
+        } else {
+            None
+        }
+    };
+
+    let invalid = |len: usize| {
+        let end = input.len().min(len);
+        let end = (0..=end)
+            .rev()
+            .find(|&i| input.is_char_boundary(i))
+            .unwrap_or(0);
+        failed_conversion(&input[..end], "not a valid \\u escape")
+    };
+
+    let high = code_unit(input).ok_or_else(|| invalid(6))?;
+    let after = &input[6..];
+
+    let (c, len) = match char::from_u32(u32::from(high)) {
+        Some(c) => (Some(c), 6),
+        None => {
+            // the high half of a surrogate pair must be followed by the low half
+            let low = code_unit(after).filter(|low| (0xDC00..0xE000).contains(low));
+            match low {
+                Some(low) if (0xD800..0xDC00).contains(&high) => {
+                    let c = char::decode_utf16([high, low]).next().and_then(Result::ok);
+                    (c, 12)
+                }
+                _ => (None, 6),
+            }
+        }
+    };
+
+    examine(len)?;
+
+    match c {
+        Some(c) => Ok((c, &input[len..])),
+        None => Err(failed_conversion(&input[..len], "a lone UTF-16 surrogate")),
+    }
+}
+
+fn array(input: &str, depth: usize, limit: usize) -> ParseResult<'_, Vec<Value>> {
+    let mut values = Vec::new();
+    let mut rest = skip_ws(&input[1..])?;
+
+    if let Some(remaining) = rest.strip_prefix(']') {
+        return Ok((values, remaining));
+    }
+
+    loop {
+        let (value, after) = item(rest, depth, limit)?;
+        values.push(value);
+        rest = skip_ws(after)?;
+
+        match rest.as_bytes().first() {
+            Some(b',') => rest = skip_ws(&rest[1..])?,
+            Some(b']') => return Ok((values, &rest[1..])),
+            _ => return Err(Error::Expected("',' or ']'")),
+        }
+    }
+}
+
+fn object(input: &str, depth: usize, limit: usize) -> ParseResult<'_, BTreeMap<String, Value>> {
+    let mut object = BTreeMap::new();
+    let mut rest = skip_ws(&input[1..])?;
+
+    if let Some(remaining) = rest.strip_prefix('}') {
+        return Ok((object, remaining));
+    }
+
+    loop {
+        if !rest.starts_with('"') {
+            return Err(Error::Expected("a string key"));
+        }
+
+        let (key, after) = string(rest)?;
+        rest = skip_ws(after)?;
+
+        rest = rest
+            .strip_prefix(':')
+            .ok_or(Error::Expected("':' after the key"))?;
+
+        let (value, after) = item(skip_ws(rest)?, depth, limit)?;
+        object.insert(key, value);
+        rest = skip_ws(after)?;
+
+        match rest.as_bytes().first() {
+            Some(b',') => rest = skip_ws(&rest[1..])?,
+            Some(b'}') => return Ok((object, &rest[1..])),
+            _ => return Err(Error::Expected("',' or '}'")),
+        }
+    }
+}
+
+fn write_value(value: &Value, output: &mut String) {
+    match value {
+        Value::Null => output.push_str("null"),
+        Value::Bool(bool) => output.push_str(if *bool { "true" } else { "false" }),
+        Value::Number(Number::Int(n)) => output.push_str(&n.to_string()),
+        // the debug format of a float always has a '.' or exponent, so it's read back as a float
+        Value::Number(Number::Float(n)) if n.is_finite() => output.push_str(&format!("{n:?}")),
+        Value::Number(Number::Float(_)) => output.push_str("null"),
+        Value::String(string) => write_string(string, output),
+        Value::Array(values) => {
+            output.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                write_value(value, output);
+            }
+            output.push(']');
+        }
+        Value::Object(object) => {
+            output.push('{');
+            for (i, (key, value)) in object.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                write_string(key, output);
+                output.push(':');
+                write_value(value, output);
+            }
+            output.push('}');
+        }
+    }
+}
+
+fn write_string(string: &str, output: &mut String) {
+    output.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\x08' => output.push_str("\\b"),
+            '\x0c' => output.push_str("\\f"),
+            c if c < ' ' => output.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+impl fmt::Debug for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Json(max_depth: {})", self.max_depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    fn int(n: i64) -> Value {
+        Value::Number(Number::Int(n))
+    }
+
+    fn float(n: f64) -> Value {
+        Value::Number(Number::Float(n))
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(s.to_string())
+    }
+
+    #[test]
+    fn values() {
+        test_parser_batch(
+            "primitives",
+            json(),
+            &[
+                ("null", Some(Value::Null), ""),
+                (" true ,", Some(Value::Bool(true)), ","),
+                ("false", Some(Value::Bool(false)), ""),
+                ("nul", None, "nul"),
+                ("", None, ""),
+                ("+1", None, "+1"),
+            ],
+        );
+
+        test_parser_batch(
+            "numbers",
+            json(),
+            &[
+                ("0", Some(int(0)), ""),
+                ("-12", Some(int(-12)), ""),
+                ("012", Some(int(0)), "12"),
+                ("1.5", Some(float(1.5)), ""),
+                ("-0.25e+2", Some(float(-25.0)), ""),
+                ("1E3", Some(float(1000.0)), ""),
+                ("9223372036854775807", Some(int(i64::MAX)), ""),
+                (
+                    "9223372036854775808",
+                    Some(float(9223372036854775808.0)),
+                    "",
+                ),
+            ],
+        );
+
+        test_parser_batch(
+            "strings",
+            json(),
+            &[
+                (r#""""#, Some(string("")), ""),
+                (r#""a\"b\\c\/d""#, Some(string("a\"b\\c/d")), ""),
+                (r#""\b\f\n\r\t""#, Some(string("\x08\x0c\n\r\t")), ""),
+                (r#""caf\u00E9""#, Some(string("café")), ""),
+                (r#""\ud83d\ude00!""#, Some(string("😀!")), ""),
+                (r#""😀""#, Some(string("😀")), ""),
+            ],
+        );
+
+        test_parser_batch(
+            "arrays and objects",
+            json(),
+            &[
+                ("[]", Some(Value::Array(vec![])), ""),
+                (
+                    "[ 1 ,[ ] , \"x\" ]",
+                    Some(Value::Array(vec![
+                        int(1),
+                        Value::Array(vec![]),
+                        string("x"),
+                    ])),
+                    "",
+                ),
+                ("{}", Some(Value::Object(BTreeMap::new())), ""),
+                (
+                    r#"{"b": 1, "a": {"c": null}, "b": 2}"#,
+                    Some(Value::Object(BTreeMap::from([
+                        (
+                            "a".to_string(),
+                            Value::Object(BTreeMap::from([("c".to_string(), Value::Null)])),
+                        ),
+                        ("b".to_string(), int(2)),
+                    ]))),
+                    "",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn errors() {
+        test_parser_errors(
+            "invalid json",
+            json(),
+            &[
+                ("-", Error::Expected("a digit after '-'")),
+                ("1.", Error::Expected("a digit after '.'")),
+                ("1e+", Error::Expected("a digit in the exponent")),
+                ("1e400", overflow("1e400")),
+                (r#""abc"#, Error::Expected("'\"' to close the string")),
+                (
+                    "\"a\nb\"",
+                    Error::Expected("control characters in a string to be escaped"),
+                ),
+                (
+                    r#""\z""#,
+                    failed_conversion("\\z", "not a JSON escape sequence"),
+                ),
+                (
+                    r#""\u12""#,
+                    failed_conversion("\\u12\"", "not a valid \\u escape"),
+                ),
+                (
+                    r#""\ud800x""#,
+                    failed_conversion("\\ud800", "a lone UTF-16 surrogate"),
+                ),
+                (
+                    r#""\udc00\ud800""#,
+                    failed_conversion("\\udc00", "a lone UTF-16 surrogate"),
+                ),
+                ("[1 2]", Error::Expected("',' or ']'")),
+                ("[1,]", Error::Expected("a value")),
+                ("[", Error::Expected("a value")),
+                ("{1: 2}", Error::Expected("a string key")),
+                (r#"{"a" 1}"#, Error::Expected("':' after the key")),
+                (r#"{"a": 1,}"#, Error::Expected("a string key")),
+                (r#"{"a": 1"#, Error::Expected("',' or '}'")),
+            ],
+        );
+    }
+
+    #[test]
+    fn depth() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+        assert!(json().parse_all(&nested(DEFAULT_MAX_DEPTH)).is_ok());
+        assert_eq!(
+            json().parse_all(&nested(DEFAULT_MAX_DEPTH + 1)),
+            Err(Error::DepthExceeded(DEFAULT_MAX_DEPTH))
+        );
+
+        // deep input fails quickly rather than overflowing the stack
+        assert!(json().parse(&nested(100_000)).is_err());
+    }
+
+    #[test]
+    fn unparsing() -> Result<(), Error> {
+        let input = r#"{"a": [1, -2.5, 1e100, true, null], "s": "q\"\u0001\n😀"}"#;
+        let value = json().parse_all(input)?;
+        let unparsed = json().unparse(&value)?;
+
+        assert_eq!(
+            unparsed,
+            r#"{"a":[1,-2.5,1e100,true,null],"s":"q\"\u0001\n😀"}"#
+        );
+        assert_eq!(json().parse_all(&unparsed)?, value);
+        assert_eq!(value.to_string(), unparsed);
+
+        assert_eq!(json().unparse(&float(1.0))?, "1.0");
+        assert_eq!(json().unparse(&float(f64::NAN))?, "null");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+//! Parse URL query strings such as `name=Ada+Lovelace&tag=math&tag=%F0%9F%A7%AE`, as used in URLs and HTML forms.
+//!
+//! Parsing follows the [`application/x-www-form-urlencoded`](https://url.spec.whatwg.org/#urlencoded-parsing) rules that browsers use:
+//!
+//! * pairs are separated by `&`, and empty pairs are ignored
+//! * a pair is split at its first `=`, and a pair without one has an empty value
+//! * `+` decodes to a space, and `%` followed by two hex digits decodes to that byte
+//! * a `%` that isn't followed by two hex digits is kept as it is
+//! * bytes that aren't valid UTF-8 are replaced with `U+FFFD`, the replacement character
+//!
+//! The query string ends at a `#`, which starts the fragment of a URL, or at whitespace, which ends the URL. It doesn't start with the `?`.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! use parsely::{char, formats::query::query, Lex, Parse};
+//!
+//! let url = char('?').skip_then(query());
+//!
+//! let (pairs, remaining) = url.parse("?q=rust+parser&lang=en&page=2#results")?;
+//! assert_eq!(pairs[0], ("q".to_string(), "rust parser".to_string()));
+//! assert_eq!(pairs.len(), 3);
+//! assert_eq!(remaining, "#results");
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    combinator::budget::examine, grammar::Grammar, unparse::Unparse, Error, Parse, ParseResult,
+};
+
+/// This parser is returned by [`query()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Query;
+
+impl Query {
+    /// Creates a parser that collects the pairs into a map from each key to all of its values, in the order they appear.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{formats::query::query, Parse};
+    ///
+    /// let params = query().multimap().parse_all("tag=a&id=1&tag=b")?;
+    ///
+    /// assert_eq!(params["tag"], vec!["a", "b"]);
+    /// assert_eq!(params["id"], vec!["1"]);
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn multimap(self) -> Multimap {
+        Multimap
+    }
+}
+
+impl Parse for Query {
+    type Output = Vec<(String, String)>;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let end = input
+            .find(|c: char| c == '#' || c.is_whitespace())
+            .unwrap_or(input.len());
+        examine(end)?;
+
+        let (query, remaining) = input.split_at(end);
+
+        let pairs = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (decode(key), decode(value))
+            })
+            .collect();
+
+        Ok((pairs, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        let component = || Grammar::Special("percent encoded text".to_string());
+        let pair = Grammar::then(
+            component(),
+            Grammar::Optional(Box::new(Grammar::then(
+                Grammar::Token("=".to_string()),
+                component(),
+            ))),
+        );
+
+        Grammar::separated(0, None, pair, Grammar::Token("&".to_string()))
+    }
+}
+
+impl Unparse for Query {
+    /// Writes the pairs percent encoded, with spaces written as `+`.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        for (i, (key, value)) in value.iter().enumerate() {
+            if i > 0 {
+                output.push('&');
+            }
+
+            encode_into(key, output);
+            output.push('=');
+            encode_into(value, output);
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a query string, outputting the decoded `(key, value)` pairs in the order they appear.
+///
+/// Keys can appear more than once, use [`Query::multimap()`] to group their values together.
+///
+/// This always matches, and outputs no pairs if the input doesn't start with a query string.
+///
+/// See the [module documentation](self) for more details.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{formats::query::query, Parse, Unparse};
+///
+/// let pairs = query().parse_all("a=1&&flag&b=x%3Dy&c=%zz")?;
+/// assert_eq!(
+///     pairs,
+///     vec![
+///         ("a".to_string(), "1".to_string()),
+///         ("flag".to_string(), "".to_string()),
+///         ("b".to_string(), "x=y".to_string()),
+///         ("c".to_string(), "%zz".to_string()),
+///     ]
+/// );
+///
+/// assert_eq!(query().unparse(&pairs)?, "a=1&flag=&b=x%3Dy&c=%25zz");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn query() -> Query {
+    Query
+}
+
+/// This parser is returned by [`Query::multimap()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Multimap;
+
+impl Parse for Multimap {
+    type Output = HashMap<String, Vec<String>>;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (pairs, remaining) = Query.parse(input)?;
+
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        for (key, value) in pairs {
+            map.entry(key).or_default().push(value);
+        }
+
+        Ok((map, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        Query.describe()
+    }
+}
+
+/// Decodes one percent encoded key or value of a query string, with `+` decoded as a space.
+///
+/// This is lenient like a browser: a `%` that isn't followed by two hex digits is kept as it is,
+/// and bytes that aren't valid UTF-8 are replaced with `U+FFFD`, the replacement character.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::formats::query::decode;
+///
+/// assert_eq!(decode("caf%C3%A9+au+lait"), "café au lait");
+/// assert_eq!(decode("100%"), "100%");
+/// assert_eq!(decode("%FF"), "\u{FFFD}");
+/// ```
+pub fn decode(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => match bytes.get(i + 1..i + 3).and_then(hex_byte) {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 2;
+                }
+                None => decoded.push(b'%'),
+            },
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn hex_byte(digits: &[u8]) -> Option<u8> {
+    let digits = std::str::from_utf8(digits).ok()?;
+    if !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    u8::from_str_radix(digits, 16).ok()
+}
+
+/// Percent encodes `text` as a key or value of a query string, appending it to `output`.
+///
+/// Spaces are encoded as `+`. ASCII letters, digits and `*-._` are written as they are, everything else is percent encoded.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::formats::query::encode_into;
+///
+/// let mut output = String::from("q=");
+/// encode_into("a+b & café", &mut output);
+/// assert_eq!(output, "q=a%2Bb+%26+caf%C3%A9");
+/// ```
+pub fn encode_into(text: &str, output: &mut String) {
+    for byte in text.bytes() {
+        match byte {
+            b' ' => output.push('+'),
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'*' | b'-' | b'.' | b'_' => {
+                output.push(byte as char)
+            }
+            byte => output.push_str(&format!("%{byte:02X}")),
+        }
+    }
+}
+
+impl fmt::Debug for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Query")
+    }
+}
+
+impl fmt::Debug for Multimap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Multimap")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "query strings",
+            query(),
+            &[
+                ("", Some(pairs(&[])), ""),
+                ("a=1&b=2", Some(pairs(&[("a", "1"), ("b", "2")])), ""),
+                ("a=1&a=2", Some(pairs(&[("a", "1"), ("a", "2")])), ""),
+                ("&a&=b&", Some(pairs(&[("a", ""), ("", "b")])), ""),
+                ("a=b=c", Some(pairs(&[("a", "b=c")])), ""),
+                ("a+b=c+d", Some(pairs(&[("a b", "c d")])), ""),
+                ("a=%2B%2b%", Some(pairs(&[("a", "++%")])), ""),
+                ("a=%4", Some(pairs(&[("a", "%4")])), ""),
+                ("a=%e2%82%ac", Some(pairs(&[("a", "€")])), ""),
+                ("a=1#frag", Some(pairs(&[("a", "1")])), "#frag"),
+                ("a=1 HTTP/1.1", Some(pairs(&[("a", "1")])), " HTTP/1.1"),
+            ],
+        );
+    }
+
+    #[test]
+    fn multimap() -> Result<(), Error> {
+        let map = query().multimap().parse_all("x=1&y=2&x=3&z")?;
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["x"], vec!["1", "3"]);
+        assert_eq!(map["z"], vec![""]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        let value = pairs(&[("a b", "c&d=e"), ("100%", "~é+"), ("", "")]);
+        let text = query().unparse(&value)?;
+
+        assert_eq!(text, "a+b=c%26d%3De&100%25=%7E%C3%A9%2B&=");
+        assert_eq!(query().parse_all(&text)?, value);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+//! Implementing [`FromStr`] for your types using a parser.
+
+use std::str::FromStr;
+
+use crate::{Error, Parse};
+
+/// Types that have a parser which parses the whole of a string into the type.
+///
+/// Implementing this trait provides [`from_parsely()`](FromParsely::from_parsely()), which parses all of the input with [`parser()`](FromParsely::parser()).
+///
+/// Rust's orphan rules prevent parsely from implementing [`FromStr`] for every `FromParsely` type, so there are two ways to get a [`FromStr`] implementation:
+///
+/// * implement [`FromStr`] yourself, with [`from_parsely()`](FromParsely::from_parsely()) as the body of `from_str`
+/// * use the [`Parsed`] wrapper, which implements [`FromStr`] for any `FromParsely` type
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use parsely::{char, int, Error, FromParsely, Lex, Parse, Parsed};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Point(i32, i32);
+///
+/// impl FromParsely for Point {
+///     fn parser() -> impl Parse<Output = Self> {
+///         int().then_skip(char(',')).then(int()).map(|(x, y)| Point(x, y))
+///     }
+/// }
+///
+/// impl FromStr for Point {
+///     type Err = Error;
+///
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         Self::from_parsely(s)
+///     }
+/// }
+///
+/// assert_eq!("1,-2".parse(), Ok(Point(1, -2)));
+/// assert_eq!("1,-2,".parse::<Point>(), Err(Error::NoMatch));
+///
+/// // without implementing FromStr
+/// let Parsed(point) = "3,4".parse::<Parsed<Point>>()?;
+/// assert_eq!(point, Point(3, 4));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub trait FromParsely: Sized {
+    /// Returns the parser for this type.
+    ///
+    /// The parser doesn't need to match the end of the input, [`from_parsely()`](FromParsely::from_parsely()) checks that all of the input was parsed.
+    fn parser() -> impl Parse<Output = Self>;
+
+    /// Parses all of the input into this type.
+    ///
+    /// This fails if any input remains after parsing, see [`Parse::parse_all()`].
+    fn from_parsely(input: &str) -> Result<Self, Error> {
+        Self::parser().parse_all(input)
+    }
+}
+
+/// Wraps a type that implements [`FromParsely`], implementing [`FromStr`] for it.
+///
+/// See [`FromParsely`] for more details and examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Parsed<T>(pub T);
+
+impl<T> Parsed<T> {
+    /// Unwraps the parsed value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: FromParsely> FromStr for Parsed<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        T::from_parsely(s).map(Parsed)
+    }
+}
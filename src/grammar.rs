@@ -0,0 +1,534 @@
+//! A description of the grammar a parser or lexer matches, built by walking its combinators.
+//!
+//! Call [`Lex::describe()`] or [`Parse::describe()`] to get a [`Grammar`], which displays as an approximate [EBNF](https://en.wikipedia.org/wiki/Extended_Backus%E2%80%93Naur_form).
+//! Parts of the grammar labelled with [`.name()`](crate::Lex::name()) become separate rules, see [`Grammar::to_ebnf()`].
+//!
+//! Tooling can walk the parts of a grammar with a [`Visitor`], see [`Grammar::walk()`].
+//!
+//! With the `railroad` feature enabled, the grammar can also be drawn as an SVG railroad diagram with `Grammar::to_railroad_svg()`.
+//!
+//! The description is approximate: parsers and lexers written as functions can't be looked inside, so they are referred to by their name.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! use parsely::{char, digit, non_zero_digit, Lex};
+//!
+//! let int = char('-').optional().then(non_zero_digit()).then(digit().many(..)).name("int");
+//! let list = char('[').then(int.many(..).delimiter(char(','))).then(char(']'));
+//!
+//! assert_eq!(list.describe().to_string(), "'[' (int (',' int)*)? ','? ']'");
+//! assert_eq!(
+//!     list.describe().to_ebnf(),
+//!     "'[' (int (',' int)*)? ','? ']'\nint ::= '-'? non_zero_digit digit*\n"
+//! );
+//! ```
+//!
+//! [`Lex::describe()`]: crate::Lex::describe()
+//! [`Parse::describe()`]: crate::Parse::describe()
+
+use std::fmt;
+
+#[cfg(feature = "railroad")]
+mod railroad;
+mod validate;
+mod visit;
+
+pub use validate::Problem;
+pub use visit::{Visitor, Walk};
+
+/// A description of the grammar matched by a parser or lexer.
+///
+/// See the [module documentation](self) for more details.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Grammar {
+    /// Matches exactly this text.
+    Token(String),
+
+    /// Matches this text, ignoring case.
+    TokenAnyCase(String),
+
+    /// Matches a single char from a class of chars, such as `digit` or `alpha`.
+    Class(String),
+
+    /// Matches something described in words, because it can't be described in terms of the other grammar.
+    Special(String),
+
+    /// A parser or lexer that can't be looked inside, such as a function, referred to by its name.
+    Reference(String),
+
+    /// Matches without consuming any input.
+    Empty,
+
+    /// Matches each item in turn.
+    Sequence(Vec<Grammar>),
+
+    /// Matches one of the items, trying them in turn.
+    Choice(Vec<Grammar>),
+
+    /// Matches the item repeatedly.
+    Repeat {
+        /// The minimum number of times the item must match.
+        min: usize,
+
+        /// The maximum number of times the item can match, if there is one.
+        max: Option<usize>,
+
+        /// The repeated item.
+        item: Box<Grammar>,
+
+        /// What must be matched in between each item, if anything.
+        separator: Option<Box<Grammar>>,
+    },
+
+    /// Matches the item or nothing.
+    Optional(Box<Grammar>),
+
+    /// A part of the grammar labelled with a name.
+    Rule {
+        /// The name of the rule.
+        name: &'static str,
+
+        /// What the rule matches.
+        body: Box<Grammar>,
+    },
+}
+
+impl Grammar {
+    /// Refers to a type that can't be described by its name, e.g. `my_parser` for a `fn my_parser(input: &str)`.
+    pub(crate) fn reference<T: ?Sized>() -> Grammar {
+        let name = std::any::type_name::<T>();
+        let path = name.split('<').next().unwrap_or(name);
+
+        let name = path
+            .rsplit("::")
+            .find(|segment| !segment.starts_with('{'))
+            .unwrap_or(path);
+
+        Grammar::Reference(name.to_string())
+    }
+
+    /// Matches `first` then `second`, flattening nested sequences.
+    pub(crate) fn then(first: Grammar, second: Grammar) -> Grammar {
+        let mut items = Vec::new();
+
+        for grammar in [first, second] {
+            match grammar {
+                Grammar::Sequence(more) => items.extend(more),
+                Grammar::Empty => {}
+                grammar => items.push(grammar),
+            }
+        }
+
+        match items.len() {
+            0 => Grammar::Empty,
+            1 => items.pop().expect("one item"),
+            _ => Grammar::Sequence(items),
+        }
+    }
+
+    /// Matches `first` or `second`, flattening nested choices.
+    pub(crate) fn or(first: Grammar, second: Grammar) -> Grammar {
+        let mut items = Vec::new();
+
+        for grammar in [first, second] {
+            match grammar {
+                Grammar::Choice(more) => items.extend(more),
+                grammar => items.push(grammar),
+            }
+        }
+
+        Grammar::Choice(items)
+    }
+
+    /// Matches the item repeatedly.
+    pub(crate) fn repeat(min: usize, max: Option<usize>, item: Grammar) -> Grammar {
+        Grammar::Repeat {
+            min,
+            max,
+            item: Box::new(item),
+            separator: None,
+        }
+    }
+
+    /// Matches the item repeatedly, separated by the separator.
+    pub(crate) fn separated(
+        min: usize,
+        max: Option<usize>,
+        item: Grammar,
+        separator: Grammar,
+    ) -> Grammar {
+        Grammar::Repeat {
+            min,
+            max,
+            item: Box::new(item),
+            separator: Some(Box::new(separator)),
+        }
+    }
+
+    /// Renders the grammar as EBNF rules, one per line.
+    ///
+    /// The first line is the grammar itself, followed by a `name ::= ...` line for each named rule used inside it.
+    /// If the grammar itself is a named rule, the first line is its rule.
+    ///
+    /// See the [module documentation](self) for an example.
+    pub fn to_ebnf(&self) -> String {
+        let mut rules = Vec::new();
+
+        match self {
+            Grammar::Rule { .. } => collect_rules(self, &mut rules),
+            grammar => {
+                for child in grammar.children() {
+                    collect_rules(child, &mut rules);
+                }
+            }
+        }
+
+        let mut output = String::new();
+
+        if !matches!(self, Grammar::Rule { .. }) {
+            output.push_str(&self.to_string());
+            output.push('\n');
+        }
+
+        for (name, body) in rules {
+            output.push_str(&format!("{name} ::= {body}\n"));
+        }
+
+        output
+    }
+
+    /// Checks for constructs that would loop forever instead of parsing, see [`Problem`].
+    ///
+    /// A repetition with no maximum, like `many(..)` or `many(1..)`, whose item matches without consuming any input, matches that empty input again and again.
+    /// The repetition never ends, so this is reported as a problem before it's run.
+    ///
+    /// Parsers and lexers written as functions can't be looked inside, so they are assumed to consume input.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, ws, Lex};
+    ///
+    /// assert_eq!(ws().many(..).then(char(';')).many(..).describe().validate(), Ok(()));
+    ///
+    /// let problems = ws().many(..).many(..).describe().validate().unwrap_err();
+    /// assert_eq!(problems[0].to_string(), "ws** loops forever once its item matches empty input");
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<Problem>> {
+        let mut problems = Vec::new();
+        validate::validate(self, &mut problems);
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// The grammar directly inside this one.
+    pub fn children(&self) -> Vec<&Grammar> {
+        match self {
+            Grammar::Token(_)
+            | Grammar::TokenAnyCase(_)
+            | Grammar::Class(_)
+            | Grammar::Special(_)
+            | Grammar::Reference(_)
+            | Grammar::Empty => Vec::new(),
+            Grammar::Sequence(items) | Grammar::Choice(items) => items.iter().collect(),
+            Grammar::Repeat {
+                item, separator, ..
+            } => std::iter::once(item.as_ref())
+                .chain(separator.as_deref())
+                .collect(),
+            Grammar::Optional(item) => vec![item.as_ref()],
+            Grammar::Rule { body, .. } => vec![body.as_ref()],
+        }
+    }
+
+    /// Walks this grammar and everything inside it depth first, calling the visitor on each part, see [`Visitor`].
+    ///
+    /// This is how tooling such as printers, linters and diagram generators can inspect the structure of a parser without knowing its combinators.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, token, Lex};
+    /// use parsely::grammar::{Grammar, Walk};
+    ///
+    /// let keyword = token("let").or(token("const")).then(char(' '));
+    ///
+    /// let mut tokens = Vec::new();
+    /// keyword.describe().walk(&mut |grammar: &Grammar| {
+    ///     if let Grammar::Token(token) = grammar {
+    ///         tokens.push(token.clone());
+    ///     }
+    ///     Walk::Continue
+    /// });
+    ///
+    /// assert_eq!(tokens, ["let", "const", " "]);
+    /// ```
+    pub fn walk(&self, visitor: &mut impl Visitor) {
+        visit::walk(self, visitor);
+    }
+
+    /// Whether this grammar needs brackets around it when a postfix operator such as `*` is applied to it.
+    fn is_compound(&self) -> bool {
+        match self {
+            Grammar::Sequence(items) | Grammar::Choice(items) => items.len() > 1,
+            Grammar::Repeat { separator, .. } => separator.is_some(),
+            _ => false,
+        }
+    }
+}
+
+/// Collects each distinct named rule, in the order they are first used.
+fn collect_rules<'g>(grammar: &'g Grammar, rules: &mut Vec<(&'static str, &'g Grammar)>) {
+    if let Grammar::Rule { name, body } = grammar {
+        if rules.iter().any(|(existing, _)| existing == name) {
+            return;
+        }
+        rules.push((name, body));
+    }
+
+    for child in grammar.children() {
+        collect_rules(child, rules);
+    }
+}
+
+/// Displays a grammar, wrapped in brackets if it is compound.
+struct Atom<'g>(&'g Grammar);
+
+impl fmt::Display for Atom<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_compound() {
+            write!(f, "({})", self.0)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+/// Displays a grammar as part of a sequence, wrapped in brackets if it is a choice.
+struct InSequence<'g>(&'g Grammar);
+
+impl fmt::Display for InSequence<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Grammar::Choice(choices) if choices.len() > 1 => write!(f, "({})", self.0),
+            grammar => write!(f, "{grammar}"),
+        }
+    }
+}
+
+/// Writes `{min,max}` style bounds, or the shorthand for them.
+fn write_bounds(f: &mut fmt::Formatter<'_>, min: usize, max: Option<usize>) -> fmt::Result {
+    match (min, max) {
+        (0, None) => write!(f, "*"),
+        (1, None) => write!(f, "+"),
+        (0, Some(1)) => write!(f, "?"),
+        (1, Some(1)) => Ok(()),
+        (min, None) => write!(f, "{{{min},}}"),
+        (min, Some(max)) if min == max => write!(f, "{{{min}}}"),
+        (min, Some(max)) => write!(f, "{{{min},{max}}}"),
+    }
+}
+
+fn write_token(f: &mut fmt::Formatter<'_>, token: &str) -> fmt::Result {
+    let quote = if token.contains('\'') { '"' } else { '\'' };
+
+    write!(f, "{quote}")?;
+    for c in token.chars() {
+        match c {
+            '\'' | '"' if c != quote => write!(f, "{c}")?,
+            c => write!(f, "{}", c.escape_debug())?,
+        }
+    }
+    write!(f, "{quote}")
+}
+
+impl fmt::Display for Grammar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Grammar::Token(token) => write_token(f, token),
+            Grammar::TokenAnyCase(token) => {
+                write_token(f, token)?;
+                write!(f, "i")
+            }
+            Grammar::Class(name) | Grammar::Reference(name) => write!(f, "{name}"),
+            Grammar::Special(description) => write!(f, "? {description} ?"),
+            Grammar::Empty => write!(f, "''"),
+            Grammar::Sequence(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", InSequence(item))?;
+                }
+                Ok(())
+            }
+            Grammar::Choice(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " | ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                Ok(())
+            }
+            Grammar::Repeat {
+                min,
+                max,
+                item,
+                separator: None,
+            } => {
+                write!(f, "{}", Atom(item))?;
+                write_bounds(f, *min, *max)
+            }
+            Grammar::Repeat {
+                min,
+                max,
+                item,
+                separator: Some(separator),
+            } => {
+                if *max == Some(0) {
+                    return write!(f, "''");
+                }
+
+                // item (separator item){min - 1, max - 1}, which is optional altogether if min is 0
+                if *min == 0 {
+                    write!(f, "(")?;
+                }
+                write!(
+                    f,
+                    "{} ({} {})",
+                    InSequence(item),
+                    InSequence(separator),
+                    InSequence(item)
+                )?;
+                write_bounds(
+                    f,
+                    min.saturating_sub(1),
+                    max.map(|max| max.saturating_sub(1)),
+                )?;
+                if *min == 0 {
+                    write!(f, ")?")?;
+                }
+                Ok(())
+            }
+            Grammar::Optional(item) => write!(f, "{}?", Atom(item)),
+            Grammar::Rule { name, .. } => write!(f, "{name}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{alpha, char, digit, int, switch, token, token_ci, until, ws, Lex, Parse};
+
+    fn my_lexer(input: &str) -> crate::LexResult<'_> {
+        Ok(("", input))
+    }
+
+    #[test]
+    fn display() {
+        let lexer = token("a")
+            .then(char('b'))
+            .or(token_ci("c").then(ws().many(1..)));
+        assert_eq!(lexer.describe().to_string(), "'a' 'b' | 'c'i ws+");
+
+        let lexer = token("a").or(token("b")).then(alpha().many(2..=3));
+        assert_eq!(lexer.describe().to_string(), "('a' | 'b') alpha{2,3}");
+
+        let lexer = token("a").then(token("b")).many(3..).then(until("x"));
+        assert_eq!(
+            lexer.describe().to_string(),
+            "('a' 'b'){3,} ? until \"x\" ?"
+        );
+
+        let lexer = token("it's").then(char('\n')).optional();
+        assert_eq!(lexer.describe().to_string(), r#"("it's" '\n')?"#);
+
+        assert_eq!(my_lexer.describe().to_string(), "my_lexer");
+    }
+
+    #[test]
+    fn parsers() {
+        let parser = switch([("a", 1), ("b", 2)])
+            .then_skip(char(';'))
+            .many(1..)
+            .delimiter(ws());
+        assert_eq!(
+            parser.describe().to_string(),
+            "('a' | 'b') ';' (ws ('a' | 'b') ';')* ws?"
+        );
+
+        let parser = int::<i32>()
+            .map(|n| n * 2)
+            .many(..=2)
+            .interleave(switch([("+", 1)]));
+        assert_eq!(
+            parser.describe().to_string(),
+            format!("({0} ('+' {0})?)?", int::<i32>().describe())
+        );
+
+        let parser = digit().many(1..).name("number").pad();
+        assert_eq!(
+            parser.describe().to_ebnf(),
+            "ws* number ws*\nnumber ::= digit+\n"
+        );
+    }
+
+    #[test]
+    fn walking() {
+        use super::{Grammar, Visitor, Walk};
+
+        /// Records each part entered and left, skipping inside rules and stopping at `'!'`
+        #[derive(Default)]
+        struct Record(Vec<String>);
+
+        impl Visitor for Record {
+            fn enter(&mut self, grammar: &Grammar) -> Walk {
+                self.0.push(format!("> {grammar}"));
+                match grammar {
+                    Grammar::Rule { .. } => Walk::SkipChildren,
+                    Grammar::Token(token) if token == "!" => Walk::Stop,
+                    _ => Walk::Continue,
+                }
+            }
+
+            fn leave(&mut self, grammar: &Grammar) {
+                self.0.push(format!("< {grammar}"));
+            }
+        }
+
+        let lexer = alpha()
+            .name("word")
+            .then(char(',').optional())
+            .then(char('!'))
+            .then(char('?'));
+
+        let mut record = Record::default();
+        lexer.describe().walk(&mut record);
+        assert_eq!(
+            record.0,
+            [
+                "> word ','? '!' '?'",
+                "> word",
+                "< word",
+                "> ','?",
+                "> ','",
+                "< ','",
+                "< ','?",
+                "> '!'",
+            ]
+        );
+    }
+}
@@ -0,0 +1,403 @@
+//! Renders a [`Grammar`] as an SVG railroad diagram.
+//!
+//! Each part of the grammar is laid out as a box around a horizontal "main line" that the diagram is read along, left to right.
+//! A box knows its width, and how far it extends above and below its main line.
+
+use std::fmt::Write;
+
+use super::Grammar;
+
+const CHAR_WIDTH: f32 = 8.0;
+const TEXT_PADDING: f32 = 10.0;
+const BOX_HEIGHT: f32 = 22.0;
+
+/// The horizontal space in between items of a sequence.
+const GAP: f32 = 10.0;
+
+/// The horizontal space used to branch off and rejoin the main line.
+const BRANCH: f32 = 20.0;
+
+/// The vertical space in between the lines of a choice or loop.
+const SPACING: f32 = 10.0;
+
+/// The space around the whole diagram.
+const MARGIN: f32 = 20.0;
+
+const STYLE: &str = "\
+path { fill: none; stroke: #333; stroke-width: 2; }
+rect { fill: #e8f0ff; stroke: #333; stroke-width: 2; }
+rect.special { fill: #f4f4f4; stroke-dasharray: 4 2; }
+text { font-family: monospace; font-size: 14px; text-anchor: middle; }
+text.label { font-size: 11px; }
+text.title { font-weight: bold; text-anchor: start; }";
+
+/// The size of a laid out part of the grammar.
+#[derive(Clone, Copy)]
+struct Size {
+    width: f32,
+
+    /// How far the part extends above its main line.
+    up: f32,
+
+    /// How far the part extends below its main line.
+    down: f32,
+}
+
+/// The parts of a railroad diagram, simplified from the grammar they are drawn from.
+enum Node {
+    Terminal(String),
+    NonTerminal(String),
+    Special(String),
+    Skip,
+    Sequence(Vec<Node>),
+    Choice(Vec<Node>),
+    Loop {
+        item: Box<Node>,
+        back: Box<Node>,
+        label: Option<String>,
+    },
+}
+
+impl Node {
+    fn from_grammar(grammar: &Grammar) -> Node {
+        match grammar {
+            Grammar::Token(token) => Node::Terminal(token.clone()),
+            Grammar::TokenAnyCase(token) => Node::Terminal(format!("{token} (any case)")),
+            Grammar::Class(name) | Grammar::Reference(name) => Node::NonTerminal(name.clone()),
+            Grammar::Rule { name, .. } => Node::NonTerminal(name.to_string()),
+            Grammar::Special(description) => Node::Special(description.clone()),
+            Grammar::Empty => Node::Skip,
+            Grammar::Sequence(items) => {
+                Node::Sequence(items.iter().map(Node::from_grammar).collect())
+            }
+            Grammar::Choice(items) => Node::Choice(items.iter().map(Node::from_grammar).collect()),
+            Grammar::Optional(item) => Node::Choice(vec![Node::Skip, Node::from_grammar(item)]),
+            Grammar::Repeat {
+                min,
+                max,
+                item,
+                separator,
+            } => {
+                let label = match (min, max) {
+                    (0 | 1, None) => None,
+                    (min, None) => Some(format!("at least {min}")),
+                    (min, Some(max)) if min == max => Some(format!("exactly {min}")),
+                    (min, Some(max)) => Some(format!("{min} to {max}")),
+                };
+
+                let repeat = Node::Loop {
+                    item: Box::new(Node::from_grammar(item)),
+                    back: Box::new(separator.as_deref().map_or(Node::Skip, Node::from_grammar)),
+                    label,
+                };
+
+                if *min == 0 {
+                    Node::Choice(vec![Node::Skip, repeat])
+                } else {
+                    repeat
+                }
+            }
+        }
+    }
+
+    fn size(&self) -> Size {
+        match self {
+            Node::Terminal(text) | Node::NonTerminal(text) | Node::Special(text) => Size {
+                width: text_width(text),
+                up: BOX_HEIGHT / 2.0,
+                down: BOX_HEIGHT / 2.0,
+            },
+            Node::Skip => Size {
+                width: 0.0,
+                up: 0.0,
+                down: 0.0,
+            },
+            Node::Sequence(items) => {
+                let sizes: Vec<Size> = items.iter().map(Node::size).collect();
+
+                Size {
+                    width: sizes.iter().map(|size| size.width).sum::<f32>()
+                        + GAP * sizes.len().saturating_sub(1) as f32,
+                    up: sizes.iter().map(|size| size.up).fold(0.0, f32::max),
+                    down: sizes.iter().map(|size| size.down).fold(0.0, f32::max),
+                }
+            }
+            Node::Choice(items) => {
+                let sizes: Vec<Size> = items.iter().map(Node::size).collect();
+                let first = sizes.first().copied().unwrap_or(Size {
+                    width: 0.0,
+                    up: 0.0,
+                    down: 0.0,
+                });
+
+                Size {
+                    width: sizes.iter().map(|size| size.width).fold(0.0, f32::max) + BRANCH * 2.0,
+                    up: first.up,
+                    down: first.down
+                        + sizes[1.min(sizes.len())..]
+                            .iter()
+                            .map(|size| SPACING + size.up + size.down)
+                            .sum::<f32>(),
+                }
+            }
+            Node::Loop { item, back, label } => {
+                let item = item.size();
+                let back = back.size();
+
+                Size {
+                    width: item.width.max(back.width) + BRANCH * 2.0,
+                    up: item.up,
+                    down: item.down
+                        + SPACING
+                        + back.up
+                        + back.down
+                        + if label.is_some() { 14.0 } else { 0.0 },
+                }
+            }
+        }
+    }
+
+    /// Draws this node with its main line starting at `(x, y)`.
+    fn draw(&self, x: f32, y: f32, svg: &mut String) {
+        match self {
+            Node::Terminal(text) => draw_box(x, y, text, "rx=\"10\"", svg),
+            Node::NonTerminal(text) => draw_box(x, y, text, "", svg),
+            Node::Special(text) => draw_box(x, y, text, "class=\"special\"", svg),
+            Node::Skip => {}
+            Node::Sequence(items) => {
+                let mut x = x;
+
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        line(x, y, x + GAP, y, svg);
+                        x += GAP;
+                    }
+                    item.draw(x, y, svg);
+                    x += item.size().width;
+                }
+            }
+            Node::Choice(items) => {
+                let size = self.size();
+                let end = x + size.width;
+                let mut item_y = y;
+
+                for (i, item) in items.iter().enumerate() {
+                    let item_size = item.size();
+
+                    if i > 0 {
+                        item_y += SPACING + item_size.up;
+                    }
+
+                    // branch off the main line, draw the item, then rejoin the main line
+                    path(
+                        &[
+                            (x, y),
+                            (x + BRANCH / 2.0, y),
+                            (x + BRANCH / 2.0, item_y),
+                            (x + BRANCH, item_y),
+                        ],
+                        svg,
+                    );
+                    item.draw(x + BRANCH, item_y, svg);
+                    path(
+                        &[
+                            (x + BRANCH + item_size.width, item_y),
+                            (end - BRANCH / 2.0, item_y),
+                            (end - BRANCH / 2.0, y),
+                            (end, y),
+                        ],
+                        svg,
+                    );
+
+                    item_y += item_size.down;
+                }
+            }
+            Node::Loop { item, back, label } => {
+                let size = self.size();
+                let item_size = item.size();
+                let back_size = back.size();
+                let end = x + size.width;
+
+                line(x, y, x + BRANCH, y, svg);
+                item.draw(x + BRANCH, y, svg);
+                line(x + BRANCH + item_size.width, y, end, y, svg);
+
+                // the line looping back from the end of the item to its start, through the separator
+                let back_y = y + item_size.down + SPACING + back_size.up;
+                let back_x = x + (size.width - back_size.width) / 2.0;
+                path(
+                    &[
+                        (end - BRANCH / 2.0, y),
+                        (end - BRANCH / 2.0, back_y),
+                        (back_x + back_size.width, back_y),
+                    ],
+                    svg,
+                );
+                back.draw(back_x, back_y, svg);
+                path(
+                    &[
+                        (back_x, back_y),
+                        (x + BRANCH / 2.0, back_y),
+                        (x + BRANCH / 2.0, y),
+                    ],
+                    svg,
+                );
+
+                if let Some(label) = label {
+                    let _ = write!(
+                        svg,
+                        r#"<text class="label" x="{}" y="{}">{}</text>"#,
+                        x + size.width / 2.0,
+                        back_y + back_size.down + 12.0,
+                        escape(label)
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn text_width(text: &str) -> f32 {
+    text.chars().count() as f32 * CHAR_WIDTH + TEXT_PADDING * 2.0
+}
+
+fn draw_box(x: f32, y: f32, text: &str, attributes: &str, svg: &mut String) {
+    let _ = write!(
+        svg,
+        r#"<rect x="{x}" y="{}" width="{}" height="{BOX_HEIGHT}" {attributes}/><text x="{}" y="{}">{}</text>"#,
+        y - BOX_HEIGHT / 2.0,
+        text_width(text),
+        x + text_width(text) / 2.0,
+        y + 5.0,
+        escape(text)
+    );
+}
+
+fn line(x1: f32, y1: f32, x2: f32, y2: f32, svg: &mut String) {
+    path(&[(x1, y1), (x2, y2)], svg);
+}
+
+fn path(points: &[(f32, f32)], svg: &mut String) {
+    svg.push_str("<path d=\"");
+    for (i, (x, y)) in points.iter().enumerate() {
+        let command = if i == 0 { 'M' } else { 'L' };
+        let _ = write!(svg, "{command}{x} {y} ");
+    }
+    svg.push_str("\"/>");
+}
+
+/// Escapes text for use in XML.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+impl Grammar {
+    /// Renders the grammar as an SVG railroad diagram.
+    ///
+    /// Named rules used inside the grammar are drawn as boxes containing their name.
+    /// If the grammar itself is a named rule, its body is drawn with its name as a title.
+    ///
+    /// This method requires the `railroad` feature.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, hex, Lex};
+    ///
+    /// let hex_color = char('#').then(hex().many(6..=6)).name("hex_color");
+    ///
+    /// let svg = hex_color.describe().to_railroad_svg();
+    ///
+    /// assert!(svg.starts_with("<svg"));
+    /// assert!(svg.contains(">hex_color</text>"));
+    /// assert!(svg.contains(">#</text>"));
+    /// ```
+    pub fn to_railroad_svg(&self) -> String {
+        let (title, grammar) = match self {
+            Grammar::Rule { name, body } => (Some(*name), body.as_ref()),
+            grammar => (None, grammar),
+        };
+
+        let node = Node::from_grammar(grammar);
+        let size = node.size();
+
+        let title_height = if title.is_some() { 24.0 } else { 0.0 };
+
+        // short lines marking the start and end of the diagram
+        let start = MARGIN;
+        let diagram_x = start + GAP;
+        let y = MARGIN + title_height + size.up;
+
+        let width = diagram_x + size.width + GAP + MARGIN;
+        let height = y + size.down + MARGIN;
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}"><style>{STYLE}</style>"#
+        );
+
+        if let Some(title) = title {
+            let _ = write!(
+                svg,
+                r#"<text class="title" x="{MARGIN}" y="{}">{}</text>"#,
+                MARGIN + 10.0,
+                escape(title)
+            );
+        }
+
+        line(start, y - 8.0, start, y + 8.0, &mut svg);
+        line(start, y, diagram_x, y, &mut svg);
+        node.draw(diagram_x, y, &mut svg);
+
+        let end = diagram_x + size.width;
+        line(end, y, end + GAP, y, &mut svg);
+        line(end + GAP, y - 8.0, end + GAP, y + 8.0, &mut svg);
+
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, digit, token, Lex};
+
+    #[test]
+    fn svg() {
+        let lexer = token("<a & b>").or(digit().many(2..=3).delimiter(char(',')));
+        let svg = lexer.describe().to_railroad_svg();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>"));
+
+        assert!(svg.contains(">&lt;a &amp; b&gt;</text>"));
+        assert!(svg.contains(">digit</text>"));
+        assert!(svg.contains(">2 to 3</text>"));
+        assert!(!svg.contains("class=\"title\""));
+    }
+
+    #[test]
+    fn sizes() {
+        let node = Node::from_grammar(&token("ab").or(token("c")).optional().describe());
+        let size = node.size();
+
+        assert_eq!(size.width, text_width("ab") + BRANCH * 4.0);
+        assert_eq!(size.up, 0.0);
+        assert_eq!(size.down, (SPACING + BOX_HEIGHT) * 2.0);
+    }
+}
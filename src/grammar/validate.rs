@@ -0,0 +1,190 @@
+use std::fmt;
+
+use super::Grammar;
+
+/// A construct found by [`Grammar::validate()`] that would loop instead of parsing.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// A repetition with no maximum whose item can match without consuming input.
+    ///
+    /// Once the item matches empty input it matches again at the same place, over and over.
+    EmptyLoop(Grammar),
+
+    /// A repetition with no maximum whose item might match without consuming input.
+    ///
+    /// The item contains something that can't be looked inside, such as [`until()`](crate::until()) or a regular expression, which might match empty input.
+    MaybeEmptyLoop(Grammar),
+}
+
+impl Problem {
+    /// The repetition with the problem.
+    pub fn grammar(&self) -> &Grammar {
+        match self {
+            Problem::EmptyLoop(grammar) | Problem::MaybeEmptyLoop(grammar) => grammar,
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::EmptyLoop(grammar) => {
+                write!(
+                    f,
+                    "{grammar} loops forever once its item matches empty input"
+                )
+            }
+            Problem::MaybeEmptyLoop(grammar) => {
+                write!(
+                    f,
+                    "{grammar} loops forever if its item can match empty input"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Problem {}
+
+/// Whether a grammar can match without consuming any input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Empty {
+    Never,
+    Unknown,
+    Possible,
+}
+
+impl Empty {
+    /// Every item must match empty input for the sequence to.
+    fn all(items: impl IntoIterator<Item = Empty>) -> Empty {
+        items
+            .into_iter()
+            .fold(Empty::Possible, |acc, item| match (acc, item) {
+                (Empty::Never, _) | (_, Empty::Never) => Empty::Never,
+                (Empty::Unknown, _) | (_, Empty::Unknown) => Empty::Unknown,
+                _ => Empty::Possible,
+            })
+    }
+
+    /// Any item matching empty input is enough for the choice to.
+    fn any(items: impl IntoIterator<Item = Empty>) -> Empty {
+        items
+            .into_iter()
+            .fold(Empty::Never, |acc, item| match (acc, item) {
+                (Empty::Possible, _) | (_, Empty::Possible) => Empty::Possible,
+                (Empty::Unknown, _) | (_, Empty::Unknown) => Empty::Unknown,
+                _ => Empty::Never,
+            })
+    }
+}
+
+/// References are assumed to consume input: they are usually functions written to parse something.
+fn matches_empty(grammar: &Grammar) -> Empty {
+    match grammar {
+        Grammar::Token(token) | Grammar::TokenAnyCase(token) => {
+            if token.is_empty() {
+                Empty::Possible
+            } else {
+                Empty::Never
+            }
+        }
+        Grammar::Class(_) | Grammar::Reference(_) => Empty::Never,
+        Grammar::Special(_) => Empty::Unknown,
+        Grammar::Empty | Grammar::Optional(_) => Empty::Possible,
+        Grammar::Sequence(items) => Empty::all(items.iter().map(matches_empty)),
+        Grammar::Choice(items) => Empty::any(items.iter().map(matches_empty)),
+        Grammar::Repeat { min: 0, .. } => Empty::Possible,
+        Grammar::Repeat {
+            min,
+            item,
+            separator,
+            ..
+        } => {
+            let separator = separator.as_deref().filter(|_| *min > 1);
+            Empty::all(
+                std::iter::once(item.as_ref())
+                    .chain(separator)
+                    .map(matches_empty),
+            )
+        }
+        Grammar::Rule { body, .. } => matches_empty(body),
+    }
+}
+
+/// Collects the problems in the grammar and everything inside it.
+pub(super) fn validate(grammar: &Grammar, problems: &mut Vec<Problem>) {
+    if let Grammar::Repeat {
+        max: None,
+        item,
+        separator,
+        ..
+    } = grammar
+    {
+        // every repetition after the first matches the separator and then the item
+        let repetition = std::iter::once(item.as_ref()).chain(separator.as_deref());
+
+        match Empty::all(repetition.map(matches_empty)) {
+            Empty::Possible => problems.push(Problem::EmptyLoop(grammar.clone())),
+            Empty::Unknown => problems.push(Problem::MaybeEmptyLoop(grammar.clone())),
+            Empty::Never => {}
+        }
+    }
+
+    for child in grammar.children() {
+        validate(child, problems);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, digit, end, token, until, ws, Lex};
+
+    fn problems(lexer: &impl Lex) -> Vec<Problem> {
+        lexer.validate().err().unwrap_or_default()
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(problems(&digit().many(..)), vec![]);
+        assert_eq!(problems(&ws().many(..).then(char(';')).many(1..)), vec![]);
+        assert_eq!(problems(&char('a').optional().many(..=3)), vec![]);
+        assert_eq!(problems(&until("\n").then(char('\n')).many(..)), vec![]);
+        assert_eq!(
+            problems(&char('a').optional().many(..).delimiter(char(','))),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn empty_loops() {
+        let lexer = char('a').optional().many(..);
+        assert_eq!(problems(&lexer), vec![Problem::EmptyLoop(lexer.describe())]);
+
+        let lexer = token("").or(char('b')).many(1..);
+        assert_eq!(problems(&lexer), vec![Problem::EmptyLoop(lexer.describe())]);
+
+        let inner = || ws().many(..);
+        let lexer = char('[').then(inner().many(..)).then(char(']'));
+        assert_eq!(
+            problems(&lexer),
+            vec![Problem::EmptyLoop(inner().many(..).describe())]
+        );
+    }
+
+    #[test]
+    fn maybe_empty_loops() {
+        let lexer = until("\n").many(..);
+        assert_eq!(
+            problems(&lexer),
+            vec![Problem::MaybeEmptyLoop(lexer.describe())]
+        );
+
+        let lexer = char('a').or(end()).many(..);
+        assert_eq!(
+            problems(&lexer)[0].to_string(),
+            "('a' | ? end of input ?)* loops forever if its item can match empty input"
+        );
+    }
+}
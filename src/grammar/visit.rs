@@ -0,0 +1,95 @@
+use super::Grammar;
+
+/// Walks the structure of a [`Grammar`], see [`Grammar::walk()`].
+///
+/// Each part of the grammar is entered before its children and left after them, so a visitor can keep track of nesting.
+/// The part's kind, tokens and repetition ranges are all there to match on in the [`Grammar`] itself.
+///
+/// A closure taking a `&Grammar` and returning a [`Walk`] is a visitor that only enters.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, digit, Lex};
+/// use parsely::grammar::{Grammar, Visitor, Walk};
+///
+/// /// Prints the grammar as an indented outline
+/// #[derive(Default)]
+/// struct Outline {
+///     depth: usize,
+///     lines: Vec<String>,
+/// }
+///
+/// impl Visitor for Outline {
+///     fn enter(&mut self, grammar: &Grammar) -> Walk {
+///         let line = match grammar {
+///             Grammar::Repeat { min, max, .. } => format!("repeat {min}..{max:?}"),
+///             Grammar::Sequence(_) => "sequence".to_string(),
+///             grammar => grammar.to_string(),
+///         };
+///         self.lines.push(format!("{}{line}", "  ".repeat(self.depth)));
+///
+///         self.depth += 1;
+///         Walk::Continue
+///     }
+///
+///     fn leave(&mut self, _: &Grammar) {
+///         self.depth -= 1;
+///     }
+/// }
+///
+/// let mut outline = Outline::default();
+/// char('#').then(digit().many(1..=3)).describe().walk(&mut outline);
+///
+/// assert_eq!(outline.lines, ["sequence", "  '#'", "  repeat 1..Some(3)", "    digit"]);
+/// ```
+pub trait Visitor {
+    /// Called on each part of the grammar before its children, returning whether to visit them.
+    fn enter(&mut self, grammar: &Grammar) -> Walk;
+
+    /// Called on each part of the grammar after its children, unless the walk was stopped.
+    ///
+    /// This is called even if the children were skipped.
+    fn leave(&mut self, grammar: &Grammar) {
+        let _ = grammar;
+    }
+}
+
+impl<F: FnMut(&Grammar) -> Walk> Visitor for F {
+    fn enter(&mut self, grammar: &Grammar) -> Walk {
+        self(grammar)
+    }
+}
+
+/// What to do next while walking a [`Grammar`], returned by [`Visitor::enter()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Walk {
+    /// Visit the children of this part of the grammar, then carry on.
+    Continue,
+
+    /// Don't visit the children of this part of the grammar, but carry on with the rest.
+    SkipChildren,
+
+    /// Stop walking the grammar altogether.
+    Stop,
+}
+
+/// Walks the grammar depth first, returning [`Walk::Stop`] if the visitor stopped the walk.
+pub(super) fn walk(grammar: &Grammar, visitor: &mut impl Visitor) -> Walk {
+    match visitor.enter(grammar) {
+        Walk::Stop => return Walk::Stop,
+        Walk::SkipChildren => {}
+        Walk::Continue => {
+            for child in grammar.children() {
+                if walk(child, visitor) == Walk::Stop {
+                    return Walk::Stop;
+                }
+            }
+        }
+    }
+
+    visitor.leave(grammar);
+    Walk::Continue
+}
@@ -1,11 +1,19 @@
-use std::ops::RangeBounds;
+use std::{ops::RangeBounds, str::FromStr};
 
 use crate::{
     combinator::{
-        count, many, map, optional, or, pad, sequence::LexMany, skip_then, then, then_skip,
-        try_map, Many, Map, Optional, Or, Pad, SkipThen, Then, ThenSkip, TryMap,
+        self, both, budget, capture, count, cut, dbg, depth_limited, lexeme, limit, many, map,
+        map_with_input, optional, or, pad, pad_left, pad_right, parse_str, recover, scoped,
+        sequence::LexMany, skip_then, then, then_skip, try_map, warn, Both, Budget, Capture, Cut,
+        Dbg, DepthLimited, Expected, Lexeme, Limit, Many, Map, MapWithInput, Named, NoPadding,
+        Optional, Or, Pad, ParseStr, Recover, Scoped, SkipThen, Then, ThenSkip, TryMap, Warn,
     },
-    ws, Parse, WhiteSpace,
+    error::track_failures,
+    grammar::{Grammar, Problem},
+    sample::{sample, SampleError, Sampler},
+    scan::{replace, FindIter, Split},
+    unparse::unsupported,
+    ws, ws0, Error, Parse, WhiteSpace, WhiteSpaces,
 };
 
 /// The type returned by a lex: the order of the tuple is `(matched, remaining)`
@@ -50,6 +58,120 @@ pub trait Lex {
     /// Match part or all of an input str, breaking it down into smaller pieces to make parsing easier.
     fn lex<'i>(&self, input: &'i str) -> LexResult<'i>;
 
+    /// Describes the grammar this lexer matches, see the [`grammar`](crate::grammar) module.
+    ///
+    /// Lexers that can't be looked inside, such as functions, are described by their name.
+    fn describe(&self) -> Grammar {
+        Grammar::reference::<Self>()
+    }
+
+    /// Checks the grammar of this lexer for constructs that would loop forever, such as `many(..)` of something that can match empty input.
+    ///
+    /// See [`Grammar::validate()`] for more details.
+    fn validate(&self) -> Result<(), Vec<Problem>> {
+        self.describe().validate()
+    }
+
+    /// Generates input that this lexer should match, appending it to `output`. See the [`sample`](crate::sample) module.
+    ///
+    /// Implement this for your own lexers so that lexers containing them can generate input.
+    /// The generated input doesn't have to match, [`sample()`](Lex::sample()) checks it and tries again,
+    /// but it should match as often as possible.
+    ///
+    /// Lexers that can't be looked inside, such as functions, return [`SampleError::Unsupported`].
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        let _ = (sampler, output);
+        Err(SampleError::Unsupported(self.describe()))
+    }
+
+    /// Generates random input that this lexer matches entirely. See the [`sample`](crate::sample) module.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, hex, Lex};
+    /// use parsely::sample::Sampler;
+    ///
+    /// let hex_color = char('#').then(hex().many(6..=6));
+    ///
+    /// let input = hex_color.sample(&mut Sampler::new(42))?;
+    ///
+    /// assert_eq!(input.len(), 7);
+    /// assert!(hex_color.lex_all(&input).is_ok());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    fn sample(&self, sampler: &mut Sampler) -> Result<String, SampleError> {
+        sample(self, sampler)
+    }
+
+    /// Writes the input this lexer matches when there's no output to unparse, appending it to `output`. See the [`unparse`](crate::unparse) module.
+    ///
+    /// This is used for lexers whose match is skipped, such as delimiters and padding.
+    /// Implement this for your own lexers so that parsers containing them can be unparsed.
+    ///
+    /// Lexers that can't be looked inside, or that have no obvious input to write such as [`any()`](crate::any()), return an error.
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        let _ = output;
+        Err(unsupported(self.describe()))
+    }
+
+    /// The text that every match of this lexer starts with, if there is any.
+    ///
+    /// Combinators that search the input for a match, such as [`crawl()`](crate::combinator::crawl()), use this to skip straight to where a match could start.
+    /// Implement this for your own lexers only if every match really does start with the text, otherwise matches will be missed.
+    ///
+    /// Lexers that can't be looked inside, or whose matches can start in different ways, return `None`.
+    fn prefix(&self) -> Option<&str> {
+        None
+    }
+
+    /// Match all of the input, returning the matched input.
+    ///
+    /// If any input remains after lexing, then this fails with [`Error::NoMatch`](crate::Error::NoMatch).
+    ///
+    /// This saves checking the remaining input yourself, e.g. when implementing [`FromStr`](std::str::FromStr).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{digit, Error, Lex};
+    ///
+    /// let digits = digit().many(1..);
+    ///
+    /// assert_eq!(digits.lex_all("123")?, "123");
+    /// assert_eq!(digits.lex_all("123abc"), Err(Error::NoMatch));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn lex_all<'i>(&self, input: &'i str) -> Result<&'i str, crate::Error> {
+        match self.lex(input)? {
+            (matched, "") => Ok(matched),
+            _ => Err(crate::Error::NoMatch),
+        }
+    }
+
+    /// Match all of the input like [`lex_all()`](Lex::lex_all()), but if it fails return an [`ErrorOwned`](crate::ErrorOwned) which holds the input and where lexing failed.
+    ///
+    /// See [`ErrorOwned::to_report()`](crate::ErrorOwned::to_report()) to describe the error to a person.
+    fn lex_all_owned<'i>(&self, input: &'i str) -> Result<&'i str, crate::ErrorOwned> {
+        let (result, failures) = track_failures(input, || self.lex(input));
+
+        match result {
+            Ok((matched, "")) => Ok(matched),
+            Ok((_, remaining)) => {
+                let offset = failures.furthest.max(input.len() - remaining.len());
+                Err(crate::ErrorOwned::new(crate::Error::NoMatch, input, offset))
+            }
+            Err(error) => {
+                let offset = failures.offset_of(&error);
+                Err(crate::ErrorOwned::new(error, input, offset))
+            }
+        }
+    }
+
     /// Creates a new lexer that will attempt to lex with this lexer multiple times.
     ///
     /// See [`crate::combinator::many()`] for more details.
@@ -125,6 +247,18 @@ pub trait Lex {
         or(self, lexer)
     }
 
+    /// Creates a new lexer that requires both this lexer and the given lexer to match at the same place in the input.
+    ///
+    /// The longer of the two matches is consumed.
+    ///
+    /// See [`crate::combinator::both()`] for more details and examples.
+    fn and<L: Lex>(self, lexer: L) -> Both<Self, L>
+    where
+        Self: Sized,
+    {
+        both(self, lexer)
+    }
+
     /// Creates a new lexer that applies two lexers in sequence.
     ///
     /// First this lexer is run, and then if successful, the remaining input will be fed to the given lexer.
@@ -168,6 +302,8 @@ pub trait Lex {
     ///
     /// This is useful when there is input you need to match but don't want to keep as part of the match.
     ///
+    /// The skipped input is consumed, but it isn't part of the matched input. Use [`Lex::then()`] to match both.
+    ///
     /// For a full side by side comparison of all the `skip_then()` and `then_skip()` methods see the [`skip module`](crate::combinator::skip) documentation.
     ///
     /// # Examples
@@ -222,6 +358,31 @@ pub trait Lex {
         skip_then(self, parser)
     }
 
+    /// Creates a parser (or lexer) that runs the item on only the part of the input this lexer matches, which it must match entirely.
+    ///
+    /// See [`scoped()`](crate::combinator::scoped()) for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{balanced, char, int, Lex, Parse};
+    ///
+    /// let list = char('[').skip_then(int::<u8>().many(..).delimiter(char(','))).then_skip(char(']'));
+    /// let group = balanced("[", "]").scoped(list);
+    ///
+    /// assert_eq!(group.parse("[1,2] [3]")?, (vec![1, 2], " [3]"));
+    /// assert!(group.parse("[1,2,[3]]").is_err());
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn scoped<T>(self, item: T) -> Scoped<Self, T>
+    where
+        Self: Sized,
+    {
+        scoped(self, item)
+    }
+
     /// Creates a parser by mapping the matched part of this lexer to an output type.
     ///
     /// This is best for mapping specific known tokens. If the conversion might fail you must use [`Lex::try_map()`] instead.
@@ -250,6 +411,45 @@ pub trait Lex {
         map(self, f)
     }
 
+    /// Creates a parser by mapping the matched part of this lexer, along with the remaining input after it, to an output type.
+    ///
+    /// The remaining input is only there to be looked at: it is still the remaining input of the parser.
+    /// This is useful for conversions that depend on the context of the match, such as what follows it,
+    /// or where it is, e.g. `remaining.len()` is how far the match ends from the end of the input.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{ascii_word, Lex, Parse};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Name {
+    ///     Function(String),
+    ///     Variable(String),
+    /// }
+    ///
+    /// let name = ascii_word().map_with_input(|name, remaining| {
+    ///     if remaining.starts_with('(') {
+    ///         Name::Function(name.to_string())
+    ///     } else {
+    ///         Name::Variable(name.to_string())
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(name.parse("max(a, b)")?, (Name::Function("max".to_string()), "(a, b)"));
+    /// assert_eq!(name.parse("a, b")?, (Name::Variable("a".to_string()), ", b"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn map_with_input<F, O>(self, f: F) -> MapWithInput<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&str, &str) -> O,
+    {
+        map_with_input(self, f)
+    }
+
     /// Creates a parser by mapping the matched part of this lexer to an output type.
     ///
     /// Unlike [`map()`], this returns a `Result<T, parsely::Error>` in case of failed conversions.
@@ -278,10 +478,71 @@ pub trait Lex {
     where
         Self: Sized,
         F: Fn(&str) -> Result<O, E>,
+        E: std::fmt::Display,
     {
         try_map(self, f)
     }
 
+    /// Creates a parser by converting the matched part of this lexer to an output type using [`FromStr`](std::str::FromStr).
+    ///
+    /// This is a shorthand for `.try_map(FromStr::from_str)`. The output type is usually chosen with a turbofish or inferred.
+    ///
+    /// If the conversion fails, the parser fails with [`Error::FailedConversion`](crate::Error::FailedConversion),
+    /// which holds the matched input and the conversion error's message.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    ///
+    /// use parsely::{char, digit, Error, Lex, Parse};
+    ///
+    /// let ip = digit().many(1..=3).count(4).delimiter(char('.')).parse_str::<Ipv4Addr>();
+    ///
+    /// assert_eq!(ip.parse("127.0.0.1")?, (Ipv4Addr::LOCALHOST, ""));
+    /// assert_eq!(
+    ///     ip.parse("127.0.0.999").unwrap_err().to_string(),
+    ///     r#"Failed to convert matched input "127.0.0.999": invalid IPv4 address syntax"#
+    /// );
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn parse_str<T: FromStr>(self) -> ParseStr<Self, T>
+    where
+        Self: Sized,
+        T::Err: std::fmt::Display,
+    {
+        parse_str(self)
+    }
+
+    /// Creates a parser by converting the matched part of this lexer to an output type using [`Into`].
+    ///
+    /// This is a shorthand for `.map(|matched| matched.into())`, for types that any `&str` converts into, such as [`String`] or `Box<str>`.
+    /// The output type is usually chosen with a turbofish or inferred.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    ///
+    /// use parsely::{until, Lex, Parse};
+    ///
+    /// let path = until(":").map_into::<PathBuf>();
+    ///
+    /// assert_eq!(path.parse("/usr/bin:/bin")?, (PathBuf::from("/usr/bin"), ":/bin"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn map_into<T>(self) -> Map<Self, fn(&str) -> T>
+    where
+        Self: Sized,
+        for<'a> &'a str: Into<T>,
+    {
+        map(self, |matched| matched.into())
+    }
+
     /// Pad this lexer with zero or more whitespace lexers so that leading and/or trailing whitespace in the input is ignored.
     ///
     /// This is an opionated default usage of the pad combinator for convenience.
@@ -335,6 +596,392 @@ pub trait Lex {
     {
         pad(left, right, self)
     }
+
+    /// Pad the left side of this lexer with the given lexer, leaving the input after it as it is.
+    ///
+    /// See also [`pad_left_ws()`](Lex::pad_left_ws()) which pads with zero or more whitespace characters.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, digit, Lex};
+    ///
+    /// let lexer = digit().pad_left(char('>'));
+    ///
+    /// assert_eq!(lexer.lex(">1>2")?, ("1", ">2"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn pad_left<L: Lex>(self, left: L) -> Pad<L, NoPadding, Self>
+    where
+        Self: Sized,
+    {
+        pad_left(left, self)
+    }
+
+    /// Pad the right side of this lexer with the given lexer, leaving the input before it as it is.
+    ///
+    /// See also [`pad_right_ws()`](Lex::pad_right_ws()) which pads with zero or more whitespace characters.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, digit, Lex};
+    ///
+    /// let lexer = digit().pad_right(char(';'));
+    ///
+    /// assert_eq!(lexer.lex("1;2;")?, ("1", "2;"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn pad_right<R: Lex>(self, right: R) -> Pad<NoPadding, R, Self>
+    where
+        Self: Sized,
+    {
+        pad_right(right, self)
+    }
+
+    /// Pad the left side of this lexer with zero or more whitespace characters, so that leading whitespace is ignored
+    /// but whitespace after it, such as a significant newline, is left in the remaining input.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, digit, Lex};
+    ///
+    /// let line = digit().pad_left_ws().then_skip(char('\n'));
+    ///
+    /// assert_eq!(line.lex("   1\n")?, ("1", ""));
+    /// assert!(line.lex("1 \n").is_err());
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn pad_left_ws(self) -> Pad<WhiteSpaces, NoPadding, Self>
+    where
+        Self: Sized,
+    {
+        pad_left(ws0(), self)
+    }
+
+    /// Pad the right side of this lexer with zero or more whitespace characters, so that trailing whitespace is ignored
+    /// but whitespace before it is significant.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{digit, Lex};
+    ///
+    /// let item = digit().pad_right_ws();
+    ///
+    /// assert_eq!(item.lex("1 \t 2")?, ("1", "2"));
+    /// assert!(item.lex(" 1").is_err());
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn pad_right_ws(self) -> Pad<NoPadding, WhiteSpaces, Self>
+    where
+        Self: Sized,
+    {
+        pad_right(ws0(), self)
+    }
+
+    /// Skip zero or more whitespace characters after this lexer.
+    ///
+    /// Wrapping each item of a [`then()`](Lex::then) chain with `lexeme()` skips the whitespace in between them,
+    /// see [`lexeme()`](crate::combinator::lexeme()) for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, Lex};
+    ///
+    /// let lexer = char('a').lexeme().then(char('b').lexeme());
+    ///
+    /// assert_eq!(lexer.lex("a  b\n;")?, ("a  b\n", ";"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn lexeme(self) -> Lexeme<Self>
+    where
+        Self: Sized,
+    {
+        lexeme(self)
+    }
+
+    /// Print the input given to this lexer and what it matched, labelled and indented by nesting, to help debug it.
+    ///
+    /// See [`dbg()`](crate::combinator::dbg()) for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, Lex};
+    ///
+    /// let lexer = char('a').dbg("a").many(..);
+    ///
+    /// // prints "[a] input: ..." followed by "[a] matched ..." or "[a] failed ..." for each attempt
+    /// assert_eq!(lexer.lex("aab")?, ("aa", "b"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn dbg(self, label: &'static str) -> Dbg<Self>
+    where
+        Self: Sized,
+    {
+        dbg(label, self)
+    }
+
+    /// Label this lexer with a name, which is used in place of its structure in Debug output and attached to its errors.
+    ///
+    /// See [`name()`](crate::combinator::name()) for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, digit, Lex};
+    ///
+    /// let version = digit().then(char('.')).then(digit()).name("version");
+    ///
+    /// assert_eq!(format!("{version:?}"), "version");
+    /// assert_eq!(version.lex("1.x").unwrap_err().to_string(), "version: No Match");
+    /// ```
+    fn name(self, name: &'static str) -> Named<Self>
+    where
+        Self: Sized,
+    {
+        combinator::name(name, self)
+    }
+
+    /// Marks any failure of this lexer as fatal, so that alternatives aren't tried and the error is reported instead.
+    ///
+    /// See [`cut()`](crate::combinator::cut()) for more details and examples.
+    fn cut(self) -> Cut<Self>
+    where
+        Self: Sized,
+    {
+        cut(self)
+    }
+
+    /// Limits how deeply this lexer can be nested inside itself, failing with [`Error::DepthExceeded`](crate::Error::DepthExceeded) instead of overflowing the stack.
+    ///
+    /// See [`depth_limited()`](crate::combinator::depth_limited()) for more details and examples.
+    fn depth_limited(self, limit: usize) -> DepthLimited<Self>
+    where
+        Self: Sized,
+    {
+        depth_limited(limit, self)
+    }
+
+    /// Runs this lexer on only the first `max_chars` chars of the input, so it can't match any further.
+    ///
+    /// See [`limit()`](crate::combinator::limit()) for more details and examples.
+    fn limit(self, max_chars: usize) -> Limit<Self>
+    where
+        Self: Sized,
+    {
+        limit(max_chars, self)
+    }
+
+    /// Fails with [`Error::BudgetExceeded`](crate::Error::BudgetExceeded) once this lexer has examined more than `max_bytes` of input, including input examined again after backtracking.
+    ///
+    /// See [`budget()`](crate::combinator::budget()) for more details and examples.
+    fn budget(self, max_bytes: usize) -> Budget<Self>
+    where
+        Self: Sized,
+    {
+        budget(max_bytes, self)
+    }
+
+    /// Describes what was expected if this lexer doesn't match, using [`Error::Expected`](crate::Error::Expected).
+    ///
+    /// See [`expected()`](crate::combinator::expected()) for more details and examples.
+    fn expected(self, expected: &'static str) -> Expected<Self>
+    where
+        Self: Sized,
+    {
+        combinator::expected(expected, self)
+    }
+
+    /// Describes what was expected if this lexer doesn't match, and marks any failure of it as fatal.
+    ///
+    /// This is equivalent to `.expected(expected).cut()`, see [`cut()`](crate::combinator::cut()) for more details and examples.
+    fn expect(self, expected: &'static str) -> Cut<Expected<Self>>
+    where
+        Self: Sized,
+    {
+        cut(combinator::expected(expected, self))
+    }
+
+    /// Tag this lexer so that what it matches is recorded by name in an enclosing [`with_captures()`](crate::combinator::with_captures()).
+    ///
+    /// See the [`capture`](mod@crate::combinator::capture) module for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, digit, Lex, Parse};
+    /// use parsely::combinator::with_captures;
+    ///
+    /// let version = digit().many(1..).capture("major")
+    ///     .then(char('.'))
+    ///     .then(digit().many(1..).capture("minor"));
+    ///
+    /// let ((_, captures), _) = with_captures(version.map(|_| ())).parse("1.23")?;
+    /// assert_eq!(&captures["major"], "1");
+    /// assert_eq!(&captures["minor"], "23");
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn capture(self, name: &'static str) -> Capture<Self>
+    where
+        Self: Sized,
+    {
+        capture(name, self)
+    }
+
+    /// Recover from a failure of this lexer by skipping the input matched by the strategy, recording the error in an enclosing [`with_recovery()`](crate::combinator::with_recovery()).
+    ///
+    /// A recovered lexer matches the skipped input. See the [`recover`](mod@crate::combinator::recover) module for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, digit, Lex};
+    /// use parsely::combinator::skip_until;
+    ///
+    /// let field = digit().many(1..).recover(skip_until(char(',')));
+    ///
+    /// assert_eq!(field.lex("12,3")?, ("12", ",3"));
+    /// assert_eq!(field.lex("x1,3")?, ("x1", ",3"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn recover<S: Lex>(self, strategy: S) -> Recover<Self, S>
+    where
+        Self: Sized,
+    {
+        recover(self, strategy)
+    }
+
+    /// Emit a diagnostic with the message whenever this lexer matches, to be collected by an enclosing [`with_diagnostics()`](crate::combinator::with_diagnostics()).
+    ///
+    /// See the [`diagnostics`](mod@crate::combinator::diagnostics) module for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{alpha, token, Lex, Parse};
+    /// use parsely::combinator::with_diagnostics;
+    ///
+    /// let keyword = token("function").or(token("fn").warn("use `function` instead"));
+    ///
+    /// let ((_, diagnostics), _) = with_diagnostics(keyword.map(str::len)).parse("fn")?;
+    /// assert_eq!(diagnostics[0].message, "use `function` instead");
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn warn(self, message: &'static str) -> Warn<Self>
+    where
+        Self: Sized,
+    {
+        warn(message, self)
+    }
+
+    /// Returns an iterator over every non-overlapping match of this lexer in the input, along with the offset (in bytes) where it was found.
+    ///
+    /// Matches are searched for at each char boundary in turn, and searching resumes at the end of each match,
+    /// in the same way as [`str::match_indices()`].
+    ///
+    /// See also the [`scan`](crate::scan) module.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{digit, Lex};
+    ///
+    /// let numbers = digit().many(1..);
+    ///
+    /// let found: Vec<_> = numbers.find_iter("call 555 0123 now").collect();
+    /// assert_eq!(found, vec![(5, "555"), (9, "0123")]);
+    /// ```
+    fn find_iter<'i>(&self, input: &'i str) -> FindIter<'_, 'i, Self>
+    where
+        Self: Sized,
+    {
+        FindIter::new(self, input)
+    }
+
+    /// Returns an iterator over the segments of the input in between each match of this lexer, like [`str::split()`].
+    ///
+    /// Every match is used as a delimiter, found in the same way as [`Lex::find_iter()`].
+    /// All the input the delimiter consumed is left out of the segments, even if it matched less, such as a [padded](Lex::pad()) delimiter.
+    /// Segments can be empty, and there is always one more segment than there are matches.
+    ///
+    /// To parse each segment, see [`split_parse()`](crate::scan::split_parse()).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, ws, Lex};
+    ///
+    /// let delimiter = char(',').then(ws().many(..));
+    ///
+    /// let segments: Vec<_> = delimiter.split("a, b,,  c").collect();
+    /// assert_eq!(segments, vec!["a", "b", "", "c"]);
+    /// ```
+    fn split<'i>(&self, input: &'i str) -> Split<'_, 'i, Self>
+    where
+        Self: Sized,
+    {
+        Split::new(self, input)
+    }
+
+    /// Copies the input, replacing every non-overlapping match of this lexer with the result of the closure.
+    ///
+    /// Matches are found in the same way as [`Lex::find_iter()`], unmatched input is copied through unchanged.
+    ///
+    /// To replace using the output of a parser, see [`Parse::rewrite()`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{alpha, char, token, Lex};
+    ///
+    /// let variable = char('$').then(alpha().many(1..));
+    ///
+    /// let output = variable.replace_all("Hello $name, welcome to $place!", |matched| match matched {
+    ///     "$name" => "Ferris",
+    ///     "$place" => "Rust",
+    ///     _ => matched,
+    /// });
+    ///
+    /// assert_eq!(output, "Hello Ferris, welcome to Rust!");
+    /// ```
+    fn replace_all<'i, F, R>(&self, input: &'i str, f: F) -> String
+    where
+        Self: Sized,
+        F: FnMut(&'i str) -> R,
+        R: AsRef<str>,
+    {
+        replace(input, |input| self.lex(input), f)
+    }
 }
 
 /// Functions that take &str and return `Result<(&str, &str), parsely::Error>` are Lexers.
@@ -1,22 +1,38 @@
-use crate::Lex;
+use crate::{
+    combinator::budget::examine_char,
+    error::no_match,
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    Lex,
+};
 
 /// This lexer is returned by [`any()`]. See it's documentation for more details.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct Any;
 
 impl Lex for Any {
     fn lex<'i>(&self, input: &'i str) -> crate::LexResult<'i> {
+        examine_char(input)?;
         if let Some(c) = input.chars().next() {
             Ok(input.split_at(c.len_utf8()))
         } else {
-            Err(crate::Error::NoMatch)
+            Err(no_match(input))
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Class("any".to_string())
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        output.push(sampler.any_char());
+        Ok(())
+    }
 }
 
 /// This parser will match and consume 1 char of the input.
 ///
 /// If the input is empty then it fails.
-pub fn any() -> Any {
+pub const fn any() -> Any {
     Any
 }
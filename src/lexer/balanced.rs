@@ -0,0 +1,288 @@
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine,
+    error::no_match,
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    Error, Lex, LexResult,
+};
+
+/// This lexer is returned by [`balanced()`]. See it's documentation for more details.
+#[derive(Clone, Copy)]
+pub struct Balanced<'p> {
+    open: &'p str,
+    close: &'p str,
+}
+
+impl<'p> Balanced<'p> {
+    /// Returns the length of the group at the start of `input`, or `None` if `input` doesn't start with a group or it is never closed.
+    fn group_len(&self, input: &str) -> Result<Option<usize>, Error> {
+        if !input.starts_with(self.open) {
+            examine(self.open.len())?;
+            return Ok(None);
+        }
+
+        let mut depth = 0;
+        let mut end = 0;
+
+        while let Some(rest) = input.get(end..).filter(|rest| !rest.is_empty()) {
+            // closing first, so that a group whose open and close are the same can be closed
+            if depth > 0 && rest.starts_with(self.close) {
+                depth -= 1;
+                end += self.close.len();
+
+                if depth == 0 {
+                    examine(end)?;
+                    return Ok(Some(end));
+                }
+            } else if rest.starts_with(self.open) {
+                depth += 1;
+                end += self.open.len();
+            } else {
+                end += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+
+        examine(input.len())?;
+        Ok(None)
+    }
+
+    /// Returns an iterator over the top level chunks of `input`: the text outside of any group, and the inside of each group.
+    ///
+    /// Groups nested inside a group are part of its inside, they aren't split out.
+    /// A closing delimiter without an opening delimiter is part of the text.
+    ///
+    /// The iterator stops after the first error, which is [`Error::Expected`] if a group is never closed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{balanced, Chunk};
+    ///
+    /// let chunks = balanced("{{", "}}")
+    ///     .chunks("Hello {{ name }}, {{ if {{admin}} }}!")
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    ///
+    /// assert_eq!(
+    ///     chunks,
+    ///     vec![
+    ///         Chunk::Text("Hello "),
+    ///         Chunk::Group(" name "),
+    ///         Chunk::Text(", "),
+    ///         Chunk::Group(" if {{admin}} "),
+    ///         Chunk::Text("!"),
+    ///     ]
+    /// );
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn chunks<'i>(&self, input: &'i str) -> Chunks<'p, 'i> {
+        Chunks {
+            balanced: *self,
+            remaining: input,
+        }
+    }
+}
+
+impl Lex for Balanced<'_> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        match self.group_len(input)? {
+            Some(len) => Ok(input.split_at(len)),
+            None => Err(no_match(input)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Sequence(vec![
+            Grammar::Token(self.open.to_string()),
+            Grammar::Special("balanced groups and text".to_string()),
+            Grammar::Token(self.close.to_string()),
+        ])
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        let _ = sampler;
+        output.push_str(self.open);
+        output.push_str(self.close);
+        Ok(())
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        output.push_str(self.open);
+        output.push_str(self.close);
+        Ok(())
+    }
+}
+
+/// Matches a group from `open` to its matching `close`, including any groups nested inside it, such as `{ a { b } c }`.
+///
+/// The whole group is matched, including the outer `open` and `close`.
+/// Input that doesn't start with `open`, or a group that is never closed, doesn't match.
+///
+/// Nothing inside the group is treated specially, so a `close` inside a string literal still closes the group.
+///
+/// Use [`Balanced::chunks()`] to split input into text and groups, e.g. to find the tags of a template.
+///
+/// # Panics
+///
+/// Panics if `open` or `close` is empty.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{balanced, Lex};
+///
+/// assert_eq!(balanced("(", ")").lex("(a (b) c) d")?, ("(a (b) c)", " d"));
+/// assert_eq!(balanced("/*", "*/").lex("/* /* nested */ */x")?, ("/* /* nested */ */", "x"));
+/// assert!(balanced("(", ")").lex("(a (b) c").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn balanced<'p>(open: &'p str, close: &'p str) -> Balanced<'p> {
+    assert!(
+        !open.is_empty() && !close.is_empty(),
+        "balanced() needs non empty delimiters"
+    );
+
+    Balanced { open, close }
+}
+
+/// A top level chunk of input, found by [`Balanced::chunks()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chunk<'i> {
+    /// Text outside of any group.
+    Text(&'i str),
+
+    /// The inside of a group, without its opening and closing delimiters.
+    Group(&'i str),
+}
+
+/// This iterator is returned by [`Balanced::chunks()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Chunks<'p, 'i> {
+    balanced: Balanced<'p>,
+    remaining: &'i str,
+}
+
+impl<'i> Chunks<'_, 'i> {
+    /// The input that hasn't been split yet.
+    pub fn remaining(&self) -> &'i str {
+        self.remaining
+    }
+}
+
+impl<'i> Iterator for Chunks<'_, 'i> {
+    type Item = Result<Chunk<'i>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+
+        let Balanced { open, close } = self.balanced;
+
+        match self.remaining.find(open) {
+            Some(0) => {}
+            Some(start) => {
+                let (text, remaining) = self.remaining.split_at(start);
+                self.remaining = remaining;
+                return Some(Ok(Chunk::Text(text)));
+            }
+            None => {
+                let text = std::mem::take(&mut self.remaining);
+                return Some(Ok(Chunk::Text(text)));
+            }
+        }
+
+        match self.balanced.group_len(self.remaining) {
+            Ok(Some(len)) => {
+                let (group, remaining) = self.remaining.split_at(len);
+                self.remaining = remaining;
+                Some(Ok(Chunk::Group(&group[open.len()..len - close.len()])))
+            }
+            Ok(None) => {
+                self.remaining = "";
+                Some(Err(Error::Expected("a closing delimiter")))
+            }
+            Err(error) => {
+                self.remaining = "";
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Balanced<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Balanced({:?} {:?})", self.open, self.close)
+    }
+}
+
+impl fmt::Debug for Chunks<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Chunks({:?}, {:?})", self.balanced, self.remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn lexing() {
+        test_lexer_batch(
+            "brackets",
+            balanced("[", "]"),
+            &[
+                ("[]", Some("[]"), ""),
+                ("[a]b", Some("[a]"), "b"),
+                ("[[a][b]]]", Some("[[a][b]]"), "]"),
+                ("[é[ü]]", Some("[é[ü]]"), ""),
+                ("[[a]", None, "[[a]"),
+                ("a[b]", None, "a[b]"),
+                ("]", None, "]"),
+                ("", None, ""),
+            ],
+        );
+
+        test_lexer_batch(
+            "same open and close",
+            balanced("$", "$"),
+            &[("$a$$b$", Some("$a$"), "$b$"), ("$a", None, "$a")],
+        );
+    }
+
+    #[test]
+    fn chunks() {
+        let chunks = |input| balanced("{", "}").chunks(input).collect::<Vec<_>>();
+
+        assert_eq!(chunks(""), vec![]);
+        assert_eq!(chunks("text"), vec![Ok(Chunk::Text("text"))]);
+        assert_eq!(
+            chunks("{a}{}} b{ {c} }"),
+            vec![
+                Ok(Chunk::Group("a")),
+                Ok(Chunk::Group("")),
+                Ok(Chunk::Text("} b")),
+                Ok(Chunk::Group(" {c} ")),
+            ]
+        );
+        assert_eq!(
+            chunks("a {b"),
+            vec![
+                Ok(Chunk::Text("a ")),
+                Err(Error::Expected("a closing delimiter"))
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_delimiters() {
+        balanced("", "}");
+    }
+}
@@ -1,13 +1,20 @@
 use std::fmt;
 
-use crate::{Lex, LexResult};
+use crate::{
+    combinator::budget::{examine, examine_char},
+    error::no_match,
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    Error, Lex, LexResult,
+};
 
 /// This lexer is returned by [`char()`]. See it's documentation for more details.
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 pub struct Char(pub char);
 
 impl Lex for Char {
     fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        examine_char(input)?;
         let mut chars = input.char_indices();
 
         match chars.next() {
@@ -19,9 +26,24 @@ impl Lex for Char {
 
                 Ok(input.split_at(boundary))
             }
-            _ => Err(crate::Error::NoMatch),
+            _ => Err(no_match(input)),
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Token(self.0.to_string())
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        let _ = sampler;
+        output.push(self.0);
+        Ok(())
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        output.push(self.0);
+        Ok(())
+    }
 }
 
 /// This lexer matches the given [`char`](prim@char) once.
@@ -40,14 +62,17 @@ impl Lex for Char {
 /// assert_eq!(remaining, "bc");
 /// # Ok::<(), parsely::Error>(())
 /// ```
-pub fn char(char: char) -> Char {
+pub const fn char(char: char) -> Char {
     Char(char)
 }
 
 /// This lexer is returned by [`char_if()`]. See it's documentation for more details.
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 pub struct CharIf<F> {
     condition: F,
+
+    /// The name of the class of chars matched, used to describe the grammar.
+    description: &'static str,
 }
 
 impl<F> Lex for CharIf<F>
@@ -55,16 +80,35 @@ where
     F: Fn(char) -> bool,
 {
     fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        examine_char(input)?;
         if let Some(c) = input.chars().next() {
             if (self.condition)(c) {
                 Ok(input.split_at(c.len_utf8()))
             } else {
-                Err(crate::Error::NoMatch)
+                Err(no_match(input))
             }
         } else {
-            Err(crate::Error::NoMatch)
+            Err(no_match(input))
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Class(self.description.to_string())
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        let c = sampler
+            .char_where(&self.condition)
+            .ok_or(SampleError::NoMatch)?;
+        output.push(c);
+        Ok(())
+    }
+}
+
+impl<F> fmt::Debug for CharIf<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharIf({})", self.description)
+    }
 }
 
 /// This lexer matches a single [`char`](prim@char) if it satisfies the given condition.
@@ -83,19 +127,31 @@ where
 /// assert_eq!(remaining, "BC");
 /// # Ok::<(), parsely::Error>(())
 /// ```
-pub fn char_if<F>(condition: F) -> CharIf<F>
+pub const fn char_if<F>(condition: F) -> CharIf<F>
 where
     F: Fn(char) -> bool,
 {
-    CharIf { condition }
+    char_class("char_if", condition)
+}
+
+/// Like [`char_if()`], naming the class of chars matched so that the grammar can be described.
+pub(crate) const fn char_class<F>(description: &'static str, condition: F) -> CharIf<F>
+where
+    F: Fn(char) -> bool,
+{
+    CharIf {
+        condition,
+        description,
+    }
 }
 
 /// This lexer is returned by [`ws()`]. See it's documentation for more details.
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 pub struct WhiteSpace;
 
 impl Lex for WhiteSpace {
     fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        examine_char(input)?;
         let mut chars = input.char_indices();
 
         match chars.next() {
@@ -107,44 +163,147 @@ impl Lex for WhiteSpace {
 
                 Ok(input.split_at(boundary))
             }
-            _ => Err(crate::Error::NoMatch),
+            _ => Err(no_match(input)),
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Class("ws".to_string())
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        output.push(
+            sampler
+                .char_where(char::is_whitespace)
+                .ok_or(SampleError::NoMatch)?,
+        );
+        Ok(())
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        output.push(' ');
+        Ok(())
+    }
 }
 
 /// This lexer matches a single [`char`](prim@char) if it is a whitespace character.
-pub fn ws() -> WhiteSpace {
+pub const fn ws() -> WhiteSpace {
     WhiteSpace
 }
 
+/// This lexer is returned by [`ws0()`] and [`ws1()`]. See their documentation for more details.
+#[derive(Clone, Copy)]
+pub struct WhiteSpaces {
+    min: usize,
+}
+
+impl Lex for WhiteSpaces {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let (count, boundary) = input
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .fold((0, 0), |(count, boundary), c| {
+                (count + 1, boundary + c.len_utf8())
+            });
+
+        // the char after the match was looked at too
+        examine(boundary + 1)?;
+
+        if count < self.min {
+            Err(no_match(input))
+        } else {
+            Ok(input.split_at(boundary))
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::repeat(self.min, None, Grammar::Class("ws".to_string()))
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        for _ in 0..sampler.count(self.min, usize::MAX) {
+            ws().sample_into(sampler, output)?;
+        }
+        Ok(())
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        output.push_str(&" ".repeat(self.min));
+        Ok(())
+    }
+}
+
+/// This lexer matches any amount of whitespace, including none at all.
+///
+/// This is the same as `ws().many(..)`, but matches all the whitespace in one go rather than one char at a time.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, ws0, Lex};
+///
+/// assert_eq!(ws0().lex(" \t\n x")?, (" \t\n ", "x"));
+/// assert_eq!(ws0().lex("x")?, ("", "x"));
+///
+/// let list_item = char(',').then(ws0());
+/// assert_eq!(list_item.lex(",   2")?, (",   ", "2"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn ws0() -> WhiteSpaces {
+    WhiteSpaces { min: 0 }
+}
+
+/// This lexer matches at least one whitespace character, and as many as it can.
+///
+/// This is the same as `ws().many(1..)`, but matches all the whitespace in one go rather than one char at a time.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{token, ws1, Lex};
+///
+/// let let_keyword = token("let").then(ws1());
+///
+/// assert_eq!(let_keyword.lex("let  x")?, ("let  ", "x"));
+/// assert!(let_keyword.lex("letx").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn ws1() -> WhiteSpaces {
+    WhiteSpaces { min: 1 }
+}
+
 /// Matches a single alphabetic character.
-pub fn alpha() -> CharIf<fn(char) -> bool> {
-    char_if(char::is_alphabetic)
+pub const fn alpha() -> CharIf<fn(char) -> bool> {
+    char_class("alpha", char::is_alphabetic)
 }
 
 /// Matches a single alphanumeric character.
-pub fn alphanum() -> CharIf<fn(char) -> bool> {
-    char_if(char::is_alphanumeric)
+pub const fn alphanum() -> CharIf<fn(char) -> bool> {
+    char_class("alphanum", char::is_alphanumeric)
 }
 
 /// Matches a single ascii alphanumeric character.
-pub fn ascii_alpha() -> CharIf<fn(char) -> bool> {
-    char_if(|c| c.is_ascii_alphabetic())
+pub const fn ascii_alpha() -> CharIf<fn(char) -> bool> {
+    char_class("ascii_alpha", |c| c.is_ascii_alphabetic())
 }
 
 /// Matches a single ascii alphanumeric character.
-pub fn ascii_alphanum() -> CharIf<fn(char) -> bool> {
-    char_if(|c| c.is_ascii_alphanumeric())
+pub const fn ascii_alphanum() -> CharIf<fn(char) -> bool> {
+    char_class("ascii_alphanum", |c| c.is_ascii_alphanumeric())
 }
 
 /// Matches a single lowercase character.
-pub fn lowercase() -> CharIf<fn(char) -> bool> {
-    char_if(char::is_lowercase)
+pub const fn lowercase() -> CharIf<fn(char) -> bool> {
+    char_class("lowercase", char::is_lowercase)
 }
 
 /// Matches an uppercase character.
-pub fn uppercase() -> CharIf<fn(char) -> bool> {
-    char_if(char::is_uppercase)
+pub const fn uppercase() -> CharIf<fn(char) -> bool> {
+    char_class("uppercase", char::is_uppercase)
 }
 
 /// Matches a char that is one of the characters in the given string
@@ -165,8 +324,8 @@ pub fn uppercase() -> CharIf<fn(char) -> bool> {
 ///
 /// # Ok::<(), parsely::Error>(())
 /// ```
-pub fn one_of(chars: &str) -> impl Lex + '_ {
-    char_if(|c| chars.contains(c))
+pub fn one_of(chars: &str) -> impl Lex + Copy + '_ {
+    char_class("one_of", |c| chars.contains(c))
 }
 
 /// Matches a char that is *none* of the characters in the given string.
@@ -187,8 +346,8 @@ pub fn one_of(chars: &str) -> impl Lex + '_ {
 ///
 /// # Ok::<(), parsely::Error>(())
 /// ```
-pub fn none_of(chars: &str) -> impl Lex + '_ {
-    char_if(|c| !chars.contains(c))
+pub fn none_of(chars: &str) -> impl Lex + Copy + '_ {
+    char_class("none_of", |c| !chars.contains(c))
 }
 
 impl fmt::Debug for Char {
@@ -203,10 +362,16 @@ impl fmt::Debug for WhiteSpace {
     }
 }
 
+impl fmt::Debug for WhiteSpaces {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ws{}", self.min)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::*;
+    use crate::testing::*;
 
     #[test]
     fn parsing() {
@@ -273,4 +438,33 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn whitespaces() {
+        test_lexer_batch(
+            "ws0 matches any amount of whitespace",
+            ws0(),
+            &[
+                ("", Some(""), ""),
+                ("x", Some(""), "x"),
+                (" \t\r\nx", Some(" \t\r\n"), "x"),
+                ("\u{a0}\u{2003}â", Some("\u{a0}\u{2003}"), "â"),
+                ("   ", Some("   "), ""),
+            ],
+        );
+
+        test_lexer_batch(
+            "ws1 matches at least one whitespace char",
+            ws1(),
+            &[
+                ("", None, ""),
+                ("x", None, "x"),
+                (" x", Some(" "), "x"),
+                ("\n\n", Some("\n\n"), ""),
+            ],
+        );
+
+        assert_eq!(ws0().describe(), ws().many(..).describe());
+        assert_eq!(ws1().describe(), ws().many(1..).describe());
+    }
 }
@@ -1,17 +1,38 @@
-use crate::{Lex, LexResult};
+use crate::{
+    combinator::budget::examine_char,
+    error::no_match,
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    Error, Lex, LexResult,
+};
 
 /// This lexer is returned by [`end()`]. See it's documentation for more details.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct End;
 
 impl Lex for End {
     fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        examine_char(input)?;
         if input.is_empty() {
             Ok(("", ""))
         } else {
-            Err(crate::Error::NoMatch)
+            Err(no_match(input))
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special("end of input".to_string())
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        let _ = (sampler, output);
+        Ok(())
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        let _ = output;
+        Ok(())
+    }
 }
 
 /// Matches the end of input, i.e. if the input is empty.
@@ -51,6 +72,6 @@ impl Lex for End {
 /// assert_eq!(result, Err(parsely::Error::NoMatch));
 /// # Ok::<(), parsely::Error>(())
 /// ```
-pub fn end() -> End {
+pub const fn end() -> End {
     End
 }
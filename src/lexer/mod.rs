@@ -54,6 +54,21 @@
 //! # Ok::<(), parsely::Error>(())
 //! ```
 //!
+//! The simplest lexers, such as [`char()`], [`token()`], [`take()`] and [`digit()`], can be created in a `const` or `static` item and shared without any lazy initialization:
+//!
+//! ```
+//! use parsely::{char, digit, token, Char, Digit, Lex, Token};
+//! use parsely::lexer::CaseSensitive;
+//!
+//! const COMMA: Char = char(',');
+//! const ARROW: Token<'static, CaseSensitive> = token("->");
+//! static DIGIT: Digit = digit();
+//!
+//! assert_eq!(DIGIT.then(COMMA).lex("1,2")?, ("1,", "2"));
+//! assert_eq!(ARROW.lex("->x")?, ("->", "x"));
+//! # Ok::<(), parsely::Error>(())
+//! ```
+//!
 //! How do I make a parser from my lexer?
 //!
 //! Take a look at the [parser module](crate::parser) which has examples of building parsers out of custom lexers, built-in lexers and combinations there of!
@@ -61,20 +76,38 @@
 //! TL;DR: use [`map()`](crate::Lex::map)
 
 mod any;
+mod balanced;
 mod char;
 mod end;
 mod number;
+#[cfg(feature = "regex")]
+mod regex;
 mod take;
 mod token;
 mod until;
+#[cfg(feature = "aho-corasick")]
+mod until_any;
+mod word;
 
 pub use self::any::{any, Any};
+pub use self::balanced::{balanced, Balanced, Chunk, Chunks};
 pub use self::char::{
     alpha, alphanum, ascii_alpha, ascii_alphanum, char, char_if, lowercase, none_of, one_of,
-    uppercase, ws, Char, WhiteSpace,
+    uppercase, ws, ws0, ws1, Char, WhiteSpace, WhiteSpaces,
 };
+pub(crate) use self::char::{char_class, CharIf};
 pub use self::end::{end, End};
-pub use self::number::{digit, hex, non_zero_digit, Digit};
-pub use self::take::{take, take_while, Take, TakeWhile};
-pub use self::token::{token, token_ci, Token};
+pub use self::number::{
+    binary, digit, digits, digits_m_n, hex, non_zero_digit, octal, Digit, Digits, InvalidRadix,
+};
+#[cfg(feature = "regex")]
+pub use self::regex::{regex, AnchoredRegex};
+pub use self::take::{take, take_bytes, take_while, Take, TakeBytes, TakeWhile};
+#[cfg(feature = "unicode-normalization")]
+pub use self::token::NfcToken;
+pub use self::token::{token, token_ci, CaseInsensitive, CaseSensitive, CaseSensitivity, Token};
+pub(crate) use self::until::PatternLite;
 pub use self::until::{until, Until};
+#[cfg(feature = "aho-corasick")]
+pub use self::until_any::{find_any, until_any, FindAny, UntilAny};
+pub use self::word::{ascii_word, word, Word};
@@ -1,54 +1,116 @@
 use std::fmt;
 
-use crate::{char_if, Lex, LexResult};
+use crate::{
+    combinator::budget::{examine, examine_char},
+    error::no_match,
+    grammar::Grammar,
+    lexer::char::char_class,
+    sample::{SampleError, Sampler},
+    Error, Lex, LexResult,
+};
 
 /// This lexer is returned by [`digit()`]. See it's documentation for more details.
-#[derive(Clone)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Digit {
     radix: u32,
 }
 
 impl Lex for Digit {
     fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        examine_char(input)?;
         if let Some(c) = input.chars().next() {
             if c.is_digit(self.radix) {
                 Ok(input.split_at(c.len_utf8()))
             } else {
-                Err(crate::Error::NoMatch)
+                Err(no_match(input))
             }
         } else {
-            Err(crate::Error::NoMatch)
+            Err(no_match(input))
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Class(match self.radix {
+            2 => "binary".to_string(),
+            8 => "octal".to_string(),
+            10 => "digit".to_string(),
+            16 => "hex".to_string(),
+            radix => format!("digit_base_{radix}"),
+        })
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        let digit = char::from_digit(sampler.below(self.radix as usize) as u32, self.radix)
+            .ok_or(SampleError::NoMatch)?;
+
+        if sampler.coin() {
+            output.push(digit.to_ascii_uppercase());
+        } else {
+            output.push(digit);
+        }
+        Ok(())
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        output.push('0');
+        Ok(())
+    }
 }
 
 /// This lexer matches a single base 10 digit i.e. one of "1234567890".
-pub fn digit() -> Digit {
+pub const fn digit() -> Digit {
     Digit { radix: 10 }
 }
 
 impl Digit {
     /// Create a new Digit lexer that matches digits with the base n.
     ///
+    /// Digits above 9 are the letters `a` to `z`, in upper or lower case, so the base must be between 2 and 36.
+    /// Any other base is an [`InvalidRadix`] error.
+    ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use parsely::digit;
+    /// use parsely::{digit, InvalidRadix, Lex};
     ///
     /// let base_10 = digit();
     ///
-    /// let base_32 = digit().base(32);
+    /// let base_32 = digit().base(32)?;
+    /// assert_eq!(base_32.lex("vw")?, ("v", "w"));
+    ///
+    /// assert_eq!(digit().base(37).unwrap_err(), InvalidRadix(37));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn base(&self, n: u32) -> Digit {
-        Digit { radix: n }
+    pub const fn base(&self, n: u32) -> Result<Digit, InvalidRadix> {
+        if 2 <= n && n <= 36 {
+            Ok(Digit { radix: n })
+        } else {
+            Err(InvalidRadix(n))
+        }
+    }
+}
+
+/// The error returned by [`Digit::base()`] for a base that digits can't be written in, i.e. one that isn't between 2 and 36.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidRadix(pub u32);
+
+impl fmt::Display for InvalidRadix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Digits can't be written in base {}, the base must be between 2 and 36",
+            self.0
+        )
     }
 }
 
+impl std::error::Error for InvalidRadix {}
+
 /// This lexer matches a single non-zero base 10 digit i.e. one of "123456789".
-pub fn non_zero_digit() -> impl Lex + Clone {
-    char_if(|c| c.is_ascii_digit() && c != '0')
+pub const fn non_zero_digit() -> impl Lex + Copy {
+    char_class("non_zero_digit", |c| c.is_ascii_digit() && c != '0')
 }
 
 /// This lexer matches a single hexadecimal character, i.e. one of "0123456789abcdefABCDEF".
@@ -91,20 +153,164 @@ pub fn non_zero_digit() -> impl Lex + Clone {
 ///
 /// # Ok::<(), parsely::Error>(())
 /// ```
-pub fn hex() -> Digit {
+///
+/// To parse whole bytes written as pairs of hex digits, see [`hex_bytes()`](crate::hex_bytes()) and [`hex_array()`](crate::hex_array()).
+pub const fn hex() -> Digit {
     Digit { radix: 16 }
 }
 
+/// This lexer matches a single binary digit, i.e. `0` or `1`.
+///
+/// No leading `0b` in the input is accepted.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{binary, Lex, Parse};
+///
+/// assert_eq!(binary().many(1..).lex("1012")?, ("101", "2"));
+///
+/// let byte = binary().many(1..=8).try_map(|s| u8::from_str_radix(s, 2));
+/// assert_eq!(byte.parse("11111111")?, (255, ""));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn binary() -> Digit {
+    Digit { radix: 2 }
+}
+
+/// This lexer matches a single octal digit, i.e. one of "01234567".
+///
+/// No leading `0o` in the input is accepted.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{octal, Lex, Parse};
+///
+/// assert_eq!(octal().many(1..).lex("0755 ")?, ("0755", " "));
+/// assert!(octal().lex("8").is_err());
+///
+/// let mode = octal().count(3).try_map(|s| u32::from_str_radix(s, 8));
+/// assert_eq!(mode.parse("644")?, (0o644, ""));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn octal() -> Digit {
+    Digit { radix: 8 }
+}
+
+/// This lexer is returned by [`digits()`] and [`digits_m_n()`]. See their documentation for more details.
+#[derive(Clone, Copy)]
+pub struct Digits {
+    min: usize,
+    max: usize,
+}
+
+impl Lex for Digits {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let len = input
+            .bytes()
+            .take(self.max)
+            .take_while(u8::is_ascii_digit)
+            .count();
+
+        // the char after the match was looked at too, unless the field was full
+        examine(if len < self.max { len + 1 } else { len })?;
+
+        if len < self.min {
+            Err(no_match(input))
+        } else {
+            Ok(input.split_at(len))
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::repeat(
+            self.min,
+            Some(self.max),
+            Grammar::Class("digit".to_string()),
+        )
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        for _ in 0..sampler.count(self.min, self.max) {
+            output.push(char::from(b'0' + sampler.below(10) as u8));
+        }
+        Ok(())
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        output.push_str(&"0".repeat(self.min));
+        Ok(())
+    }
+}
+
+/// This lexer matches exactly `n` base 10 digits, i.e. a fixed-width numeric field.
+///
+/// Leading zeros are matched like any other digit. If there are fewer than `n` digits then this lexer fails,
+/// and any digits after the first `n` are left in the remaining input.
+///
+/// This is the same as `digit().count(n)`, but quicker. See [`fixed_uint()`](crate::fixed_uint()) to convert the digits to a number.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, digits, Lex};
+///
+/// let date = digits(4).then(char('-')).then(digits(2)).then(char('-')).then(digits(2));
+///
+/// assert_eq!(date.lex("2024-01-31T")?, ("2024-01-31", "T"));
+/// assert_eq!(digits(2).lex("0123")?, ("01", "23"));
+/// assert!(digits(2).lex("1a").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn digits(n: usize) -> Digits {
+    Digits { min: n, max: n }
+}
+
+/// This lexer matches at least `m` and at most `n` base 10 digits, as many as it can.
+///
+/// This is the same as `digit().many(m..=n)`, but quicker. See [`digits()`] for exactly `n` digits.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{digits_m_n, Lex};
+///
+/// let year = digits_m_n(2, 4);
+///
+/// assert_eq!(year.lex("99 ")?, ("99", " "));
+/// assert_eq!(year.lex("202412")?, ("2024", "12"));
+/// assert!(year.lex("9").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn digits_m_n(m: usize, n: usize) -> Digits {
+    Digits { min: m, max: n }
+}
+
 impl fmt::Debug for Digit {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "digit({})", self.radix)
     }
 }
 
+impl fmt::Debug for Digits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "digits({}..={})", self.min, self.max)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::*;
+    use crate::testing::*;
 
     #[test]
     fn digit_char_find() {
@@ -157,6 +363,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_binary_and_octal() {
+        test_lexer_batch(
+            "binary works",
+            binary(),
+            &[
+                ("", None, ""), //
+                ("01", Some("0"), "1"),
+                ("10", Some("1"), "0"),
+                ("2", None, "2"),
+            ],
+        );
+
+        test_lexer_batch(
+            "octal works",
+            octal().many(1..),
+            &[
+                ("", None, ""), //
+                ("0755", Some("0755"), ""),
+                ("78", Some("7"), "8"),
+                ("9", None, "9"),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_base() {
+        assert_eq!(digit().base(2), Ok(binary()));
+        assert_eq!(digit().base(36).unwrap().lex("zZ"), Ok(("z", "Z")));
+        assert_eq!(digit().base(0), Err(InvalidRadix(0)));
+        assert_eq!(digit().base(1), Err(InvalidRadix(1)));
+        assert_eq!(digit().base(37), Err(InvalidRadix(37)));
+    }
+
     #[test]
     fn parsing() {
         test_lexer_batch(
@@ -169,4 +409,37 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_digits() {
+        test_lexer_batch(
+            "digits matches exactly n digits",
+            digits(2),
+            &[
+                ("", None, ""), //
+                ("1", None, "1"),
+                ("12", Some("12"), ""),
+                ("0123", Some("01"), "23"),
+                ("1a", None, "1a"),
+                ("٣٤", None, "٣٤"),
+            ],
+        );
+
+        test_lexer_batch(
+            "digits_m_n matches as many digits as it can",
+            digits_m_n(1, 3),
+            &[
+                ("", None, ""), //
+                ("1", Some("1"), ""),
+                ("12a", Some("12"), "a"),
+                ("1234", Some("123"), "4"),
+            ],
+        );
+
+        test_lexer_batch(
+            "no digits",
+            digits(0),
+            &[("", Some(""), ""), ("1", Some(""), "1")],
+        );
+    }
 }
@@ -0,0 +1,206 @@
+use std::fmt;
+
+use ::regex::Regex;
+
+use crate::{combinator::budget::examine, error::no_match, grammar::Grammar, Lex, LexResult};
+
+/// A [`Regex`] is a lexer that matches at the very start of the input.
+///
+/// This is available with the `regex` feature enabled.
+///
+/// **Deprecated:** use [`regex()`] instead, which compiles the pattern anchored to the start of the input.
+/// This impl is kept so existing code still compiles, and will be removed in the next major version.
+/// (A trait impl can't be marked `#[deprecated]`, so the compiler won't warn about it.)
+///
+/// Only a match beginning at the start of the input counts, a match found further along the input is not a match.
+/// A [`Regex`] can't be told to only look at the start of the input, so unless the pattern starts with `^`,
+/// the regex searches the rest of the input when there's no match at the start,
+/// which adds up when it is tried over and over, e.g. by [`many()`](crate::combinator::many()).
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, Lex};
+/// use regex::Regex;
+///
+/// let identifier = Regex::new(r"^[_a-zA-Z][_a-zA-Z0-9]*").unwrap();
+///
+/// assert_eq!(identifier.lex("foo_bar1 = 2")?, ("foo_bar1", " = 2"));
+/// assert_eq!(identifier.lex("1foo"), Err(parsely::Error::NoMatch));
+///
+/// let key_value = identifier.clone().then(char('=')).then(identifier);
+///
+/// assert_eq!(key_value.lex("foo=bar;")?, ("foo=bar", ";"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+impl Lex for Regex {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let found = self.find(input);
+        examine(found.map_or(input.len(), |found| found.end()))?;
+
+        match found {
+            Some(found) if found.start() == 0 => Ok(input.split_at(found.end())),
+            _ => Err(no_match(input)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special(format!("/{}/", self.as_str()))
+    }
+}
+
+/// This lexer is returned by [`regex()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct AnchoredRegex {
+    /// The pattern, anchored to the start of the input.
+    regex: Regex,
+
+    /// The pattern as it was given, to describe it.
+    pattern: String,
+}
+
+impl Lex for AnchoredRegex {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        match self.regex.find(input) {
+            Some(found) => {
+                examine(found.end())?;
+                Ok(input.split_at(found.end()))
+            }
+            None => {
+                // how far the regex looked before failing isn't known, so only the first byte is counted
+                examine(1)?;
+                Err(no_match(input))
+            }
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special(format!("/{}/", self.pattern))
+    }
+}
+
+/// Compiles a regex that only matches at the very start of the input, as a lexer.
+///
+/// This is available with the `regex` feature enabled.
+///
+/// This replaces using a [`Regex`] as a lexer directly, which is deprecated. Unlike that, the pattern is compiled anchored to the start of the input,
+/// so a failed match only looks at the start of the input rather than searching the rest of it.
+/// The pattern doesn't need to start with `^`, and flags such as `(?m)` inside it don't change where it is anchored.
+///
+/// Fails with the [`regex::Error`](::regex::Error) if the pattern is invalid.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, regex, Lex};
+///
+/// let identifier = regex(r"[_a-zA-Z][_a-zA-Z0-9]*").unwrap();
+///
+/// assert_eq!(identifier.lex("foo_bar1 = 2")?, ("foo_bar1", " = 2"));
+/// assert_eq!(identifier.lex("1foo"), Err(parsely::Error::NoMatch));
+///
+/// let key_value = identifier.clone().then(char('=')).then(identifier);
+/// assert_eq!(key_value.lex("foo=bar;")?, ("foo=bar", ";"));
+///
+/// assert!(regex("[unclosed").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn regex(pattern: &str) -> Result<AnchoredRegex, ::regex::Error> {
+    let regex = Regex::new(&format!("^(?:{pattern})"))?;
+
+    Ok(AnchoredRegex {
+        regex,
+        pattern: pattern.to_string(),
+    })
+}
+
+impl fmt::Debug for AnchoredRegex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AnchoredRegex(/{}/)", self.pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn anchored() {
+        test_lexer_batch(
+            "regex matches at the start of the input",
+            Regex::new(r"[0-9]+").unwrap(),
+            &[
+                ("123abc", Some("123"), "abc"), //
+                ("abc123", None, "abc123"),
+                ("", None, ""),
+            ],
+        );
+
+        test_lexer_batch(
+            "^ anchors to the start of the remaining input",
+            Regex::new(r"^â+").unwrap(),
+            &[
+                ("ââb", Some("ââ"), "b"), //
+                ("bââ", None, "bââ"),
+            ],
+        );
+    }
+
+    #[test]
+    fn compiled_anchored() {
+        test_lexer_batch(
+            "regex() matches at the start of the input",
+            regex(r"[0-9]+").unwrap(),
+            &[
+                ("123abc", Some("123"), "abc"), //
+                ("abc123", None, "abc123"),
+                ("", None, ""),
+            ],
+        );
+
+        test_lexer_batch(
+            "the whole pattern is anchored",
+            regex(r"x|[0-9]+").unwrap(),
+            &[
+                ("x1", Some("x"), "1"), //
+                ("12x", Some("12"), "x"),
+                ("a1", None, "a1"),
+            ],
+        );
+
+        test_lexer_batch(
+            "multi-line mode doesn't anchor to the start of a line",
+            regex(r"(?m)^b").unwrap(),
+            &[("b", Some("b"), ""), ("a\nb", None, "a\nb")],
+        );
+
+        assert!(regex("(").is_err());
+        assert_eq!(
+            regex(r"\d+").unwrap().describe(),
+            Grammar::Special(r"/\d+/".to_string())
+        );
+    }
+
+    #[test]
+    fn failing_examines_the_start_only() {
+        let digits = crate::combinator::budget(3, regex(r"[0-9]+").unwrap());
+        let long = "x".repeat(1000);
+
+        assert_eq!(digits.lex(&long), Err(crate::Error::NoMatch));
+    }
+
+    #[test]
+    fn combined() -> Result<(), crate::Error> {
+        let word = Regex::new(r"^\w+").unwrap();
+        let lexer = word.clone().then(crate::char(' ')).then(word);
+
+        assert_eq!(lexer.lex("hello world!")?, ("hello world", "!"));
+
+        Ok(())
+    }
+}
@@ -1,25 +1,88 @@
-use crate::Lex;
+use crate::{
+    combinator::budget::examine,
+    error::no_match,
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    Lex,
+};
 
 /// This lexer is returned by [`take()`]. See it's documentation for more details.
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct Take {
     count: usize,
 }
 
+/// This lexer is returned by [`take_bytes()`]. See it's documentation for more details.
+#[derive(Clone, Copy, Debug)]
+pub struct TakeBytes {
+    count: usize,
+}
+
 /// This lexer is returned by [`take_while()`]. See it's documentation for more details.
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct TakeWhile<F> {
     condition: F,
 }
 
 impl Lex for Take {
     fn lex<'i>(&self, input: &'i str) -> crate::LexResult<'i> {
-        if input.len() >= self.count {
+        let boundary = input
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(input.len()))
+            .nth(self.count);
+
+        examine(boundary.unwrap_or(input.len()))?;
+
+        match boundary {
+            Some(boundary) => Ok(input.split_at(boundary)),
+            None => Err(no_match(input)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::repeat(
+            self.count,
+            Some(self.count),
+            Grammar::Class("any".to_string()),
+        )
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        for _ in 0..self.count {
+            output.push(sampler.any_char());
+        }
+        Ok(())
+    }
+}
+
+impl Lex for TakeBytes {
+    fn lex<'i>(&self, input: &'i str) -> crate::LexResult<'i> {
+        examine(self.count.min(input.len()))?;
+
+        // the count must land on a char boundary, or the input can't be split there
+        if input.is_char_boundary(self.count) {
             Ok(input.split_at(self.count))
         } else {
-            Err(crate::Error::NoMatch)
+            Err(no_match(input))
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special(format!("any {} bytes", self.count))
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        // ASCII chars are a single byte each
+        for _ in 0..self.count {
+            output.push(
+                sampler
+                    .char_where(|c| c.is_ascii())
+                    .ok_or(SampleError::NoMatch)?,
+            );
+        }
+        Ok(())
+    }
 }
 
 impl<F> Lex for TakeWhile<F>
@@ -38,17 +101,72 @@ where
             }
         }
 
+        // the char after the match was looked at too
+        examine(boundary + 1)?;
+
         Ok(input.split_at(boundary))
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::repeat(0, None, Grammar::Class("char_if".to_string()))
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        for _ in 0..sampler.count(0, usize::MAX) {
+            match sampler.char_where(&self.condition) {
+                Some(c) => output.push(c),
+                None => break,
+            }
+        }
+        Ok(())
+    }
 }
 
 /// This lexer matches `count` characters if that many are available in the input.
 ///
 /// If there are fewer than `count` characters in the input then this lexer fails.
-pub fn take(count: usize) -> Take {
+///
+/// Characters are counted, not bytes, so multibyte UTF-8 characters are never split. See [`take_bytes()`] to count bytes instead.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{take, Lex};
+///
+/// assert_eq!(take(3).lex("abcd")?, ("abc", "d"));
+/// assert_eq!(take(3).lex("âßç!")?, ("âßç", "!"));
+///
+/// assert_eq!(take(3).lex("ab"), Err(parsely::Error::NoMatch));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn take(count: usize) -> Take {
     Take { count }
 }
 
+/// This lexer matches `count` bytes if that many are available in the input.
+///
+/// If there are fewer than `count` bytes in the input, or `count` bytes would end in the middle of a multibyte UTF-8 character, then this lexer fails.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{take_bytes, Lex};
+///
+/// assert_eq!(take_bytes(2).lex("abc")?, ("ab", "c"));
+/// assert_eq!(take_bytes(2).lex("âbc")?, ("â", "bc"));
+///
+/// // 'â' is 2 bytes long
+/// assert_eq!(take_bytes(1).lex("âbc"), Err(parsely::Error::NoMatch));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn take_bytes(count: usize) -> TakeBytes {
+    TakeBytes { count }
+}
+
 /// This lexer matches all characters that satisfy the condition.
 ///
 /// If no characters satisfy the condition, the lex is still successful.
@@ -81,7 +199,7 @@ pub fn take(count: usize) -> Take {
 /// assert_eq!(example.lex("what did you say?!?!?")?, ("what did you say", ""));
 /// # Ok::<(), parsely::Error>(())
 /// ```
-pub fn take_while<F>(condition: F) -> TakeWhile<F>
+pub const fn take_while<F>(condition: F) -> TakeWhile<F>
 where
     F: Fn(char) -> bool,
 {
@@ -91,7 +209,36 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::test_utils::test_lexer_batch;
+    use crate::testing::test_lexer_batch;
+
+    #[test]
+    fn test_take() {
+        test_lexer_batch(
+            "take counts chars",
+            take(2),
+            &[
+                ("abc", Some("ab"), "c"), //
+                ("ab", Some("ab"), ""),
+                ("âßç", Some("âß"), "ç"),
+                ("❤️", Some("❤️"), ""),
+                ("a", None, "a"),
+                ("", None, ""),
+            ],
+        );
+
+        test_lexer_batch(
+            "take_bytes counts bytes",
+            take_bytes(2),
+            &[
+                ("abc", Some("ab"), "c"), //
+                ("âßç", Some("â"), "ßç"),
+                ("aâ", None, "aâ"),
+                ("a", None, "a"),
+            ],
+        );
+
+        test_lexer_batch("take nothing", take(0), &[("abc", Some(""), "abc")]);
+    }
 
     #[test]
     fn test_take_while() {
@@ -1,14 +1,31 @@
 use std::{fmt, marker::PhantomData};
 
-use crate::{Lex, LexResult};
+use crate::{
+    combinator::budget::examine,
+    error::no_match,
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    Error, Lex, LexResult,
+};
+
+#[cfg(feature = "unicode-normalization")]
+mod nfc;
+#[cfg(feature = "unicode-normalization")]
+pub use self::nfc::NfcToken;
 
 /// This lexer is returned by [`token()`]. See its documentation for more details.
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 pub struct Token<'p, C: CaseSensitivity>(&'p str, PhantomData<C>);
 
+/// Whether a [`Token`] matches the case of the input exactly, either [`CaseSensitive`] or [`CaseInsensitive`].
 pub trait CaseSensitivity {}
 
+/// Marks a [`Token`] created by [`token()`] which only matches input with the same case.
+#[derive(Debug, Clone, Copy)]
 pub struct CaseSensitive;
+
+/// Marks a [`Token`] created by [`token_ci()`] which ignores the case of the input.
+#[derive(Debug, Clone, Copy)]
 pub struct CaseInsensitive;
 
 impl CaseSensitivity for CaseSensitive {}
@@ -32,30 +49,88 @@ impl<'p> Token<'p, CaseSensitive> {
     /// assert_eq!(case_insensitive.lex("aBc")?, ("aBc", ""));
     /// # Ok::<(), parsely::Error>(())
     /// ```
-    pub fn any_case(self) -> Token<'p, CaseInsensitive> {
+    pub const fn any_case(self) -> Token<'p, CaseInsensitive> {
         Token(self.0, PhantomData)
     }
 }
 
+/// Returns how many bytes of the input are compared against the token, up to and including the first char that differs.
+fn compared(input: &str, token: &str, eq: impl Fn(char, char) -> bool) -> usize {
+    let mut len = 0;
+
+    for (i, t) in input.chars().zip(token.chars()) {
+        len += i.len_utf8();
+        if !eq(i, t) {
+            break;
+        }
+    }
+
+    len
+}
+
 impl<'p> Lex for Token<'p, CaseSensitive> {
     fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
         if input.starts_with(self.0) {
+            examine(self.0.len())?;
             Ok(input.split_at(self.0.len()))
         } else {
-            Err(crate::Error::NoMatch)
+            examine(compared(input, self.0, |i, t| i == t))?;
+            Err(no_match(input))
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Token(self.0.to_string())
+    }
+
+    fn prefix(&self) -> Option<&str> {
+        Some(self.0)
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        let _ = sampler;
+        output.push_str(self.0);
+        Ok(())
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        output.push_str(self.0);
+        Ok(())
+    }
 }
 
 impl<'p> Lex for Token<'p, CaseInsensitive> {
     fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        examine(compared(input, self.0, |i, t| {
+            i.to_uppercase().eq(t.to_uppercase())
+        }))?;
         // NOTE: unicode uppercase could wreak havoc here
         if input.to_uppercase().starts_with(&self.0.to_uppercase()) {
             Ok(input.split_at(self.0.len()))
         } else {
-            Err(crate::Error::NoMatch)
+            Err(no_match(input))
         }
     }
+
+    fn describe(&self) -> Grammar {
+        Grammar::TokenAnyCase(self.0.to_string())
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        for c in self.0.chars() {
+            if sampler.coin() {
+                output.extend(c.to_uppercase());
+            } else {
+                output.extend(c.to_lowercase());
+            }
+        }
+        Ok(())
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        output.push_str(self.0);
+        Ok(())
+    }
 }
 
 /// A lexer that matches a specific string slice.
@@ -104,7 +179,7 @@ impl<'p> Lex for Token<'p, CaseInsensitive> {
 ///
 /// # Ok::<(), parsely::Error>(())
 /// ```
-pub fn token(token: &str) -> Token<CaseSensitive> {
+pub const fn token(token: &str) -> Token<'_, CaseSensitive> {
     Token(token, PhantomData)
 }
 
@@ -115,7 +190,7 @@ pub fn token(token: &str) -> Token<CaseSensitive> {
 ///
 /// Note: no additional action is taken to support all unicode characters,
 /// it is quite likely that this uppercase comparison will lead to unintuitive results for some unicode characters. Caution advised.
-pub fn token_ci(token: &str) -> Token<CaseInsensitive> {
+pub const fn token_ci(token: &str) -> Token<'_, CaseInsensitive> {
     Token(token, PhantomData)
 }
 
@@ -134,7 +209,7 @@ impl fmt::Debug for Token<'_, CaseInsensitive> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::*;
+    use crate::testing::*;
 
     #[test]
     fn parsing() {
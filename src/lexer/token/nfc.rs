@@ -0,0 +1,273 @@
+//! Comparing a [`Token`] with the input under Unicode NFC normalization, see [`Token::nfc()`].
+
+use std::{fmt, marker::PhantomData};
+
+use unicode_normalization::{char::canonical_combining_class, UnicodeNormalization};
+
+use crate::{
+    combinator::budget::examine,
+    error::no_match,
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    Error, Lex, LexResult,
+};
+
+use super::{CaseInsensitive, CaseSensitive, CaseSensitivity, Token};
+
+/// This lexer is returned by [`Token::nfc()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct NfcToken<C: CaseSensitivity> {
+    /// The token, normalized.
+    token: String,
+
+    /// The token as it is compared with the input, normalized and then case folded if the token is case insensitive.
+    folded: String,
+
+    /// The most chars that input equivalent to the token can have.
+    max_chars: usize,
+
+    case: PhantomData<C>,
+}
+
+impl<C: CaseSensitivity> NfcToken<C> {
+    fn new(token: &str, fold: fn(String) -> String) -> Self {
+        let token: String = token.nfc().collect();
+        let folded = fold(token.clone());
+
+        // a string never has more chars than its canonical decomposition
+        let max_chars = folded.nfd().count();
+
+        NfcToken {
+            token,
+            folded,
+            max_chars,
+            case: PhantomData,
+        }
+    }
+
+    /// Matches the shortest prefix of the input that is equivalent to the token, once normalized and folded.
+    fn lex_folded<'i>(&self, input: &'i str, fold: fn(String) -> String) -> LexResult<'i> {
+        if self.folded.is_empty() {
+            return Ok(("", input));
+        }
+
+        let normalized = |end: usize| fold(input[..end].nfc().collect());
+
+        let ends = input
+            .char_indices()
+            .map(|(i, _)| i)
+            .skip(1)
+            .chain(std::iter::once(input.len()))
+            .take(self.max_chars);
+
+        let mut examined = 0;
+        for end in ends {
+            examined = end;
+
+            if normalized(end) != self.folded {
+                continue;
+            }
+
+            // the next char mustn't combine with the end of the match, e.g. a combining accent
+            let next = input[end..].chars().next();
+            examine(end + next.map_or(0, char::len_utf8))?;
+
+            return match next {
+                Some(c)
+                    if canonical_combining_class(c) != 0
+                        || !normalized(end + c.len_utf8()).starts_with(&self.folded) =>
+                {
+                    Err(no_match(input))
+                }
+                _ => Ok(input.split_at(end)),
+            };
+        }
+
+        examine(examined)?;
+        Err(no_match(input))
+    }
+}
+
+impl<'p> Token<'p, CaseSensitive> {
+    /// Compares the token with the input under Unicode NFC normalization,
+    /// so a character matches whether it is written precomposed or as a base character followed by combining characters.
+    ///
+    /// For example `"é"` can be written as the single char `U+00E9`, or as `e` followed by the combining acute accent `U+0301`.
+    /// A plain [`token()`](crate::token()) compares bytes, so only matches the way the token itself is written.
+    ///
+    /// The matched input is returned as it was written, it isn't normalized.
+    /// The token is normalized once, when this is called.
+    ///
+    /// This requires the `unicode-normalization` feature.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{token, Lex};
+    ///
+    /// let precomposed = "caf\u{e9}!";
+    /// let combining = "cafe\u{301}!";
+    ///
+    /// assert!(token("caf\u{e9}").lex(combining).is_err());
+    ///
+    /// let cafe = token("caf\u{e9}").nfc();
+    /// assert_eq!(cafe.lex(precomposed)?, ("caf\u{e9}", "!"));
+    /// assert_eq!(cafe.lex(combining)?, ("cafe\u{301}", "!"));
+    ///
+    /// // case insensitive tokens can be normalized too
+    /// assert_eq!(token("CAF\u{c9}").any_case().nfc().lex(combining)?, ("cafe\u{301}", "!"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn nfc(self) -> NfcToken<CaseSensitive> {
+        NfcToken::new(self.0, case_sensitive)
+    }
+}
+
+impl<'p> Token<'p, CaseInsensitive> {
+    /// Compares the token with the input under Unicode NFC normalization, ignoring case.
+    ///
+    /// This works the same way as `nfc()` for a case sensitive token, see [`token()`](crate::token()).
+    /// Both the token and the input are normalized and then uppercased before comparing them.
+    ///
+    /// This requires the `unicode-normalization` feature.
+    pub fn nfc(self) -> NfcToken<CaseInsensitive> {
+        NfcToken::new(self.0, case_insensitive)
+    }
+}
+
+fn case_sensitive(normalized: String) -> String {
+    normalized
+}
+
+fn case_insensitive(normalized: String) -> String {
+    normalized.to_uppercase()
+}
+
+impl Lex for NfcToken<CaseSensitive> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        self.lex_folded(input, case_sensitive)
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Token(self.token.clone())
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        if sampler.coin() {
+            output.extend(self.token.nfd());
+        } else {
+            output.push_str(&self.token);
+        }
+        Ok(())
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        output.push_str(&self.token);
+        Ok(())
+    }
+}
+
+impl Lex for NfcToken<CaseInsensitive> {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        self.lex_folded(input, case_insensitive)
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::TokenAnyCase(self.token.clone())
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        let token: String = if sampler.coin() {
+            self.token.nfd().collect()
+        } else {
+            self.token.clone()
+        };
+
+        for c in token.chars() {
+            if sampler.coin() {
+                output.extend(c.to_uppercase());
+            } else {
+                output.extend(c.to_lowercase());
+            }
+        }
+        Ok(())
+    }
+
+    fn canonical_into(&self, output: &mut String) -> Result<(), Error> {
+        output.push_str(&self.token);
+        Ok(())
+    }
+}
+
+impl fmt::Debug for NfcToken<CaseSensitive> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Token({:?}, nfc)", self.token)
+    }
+}
+
+impl fmt::Debug for NfcToken<CaseInsensitive> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Token({:?}, i, nfc)", self.token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::*;
+    use crate::{token, token_ci, Lex};
+
+    #[test]
+    fn normalized() {
+        test_lexer_batch(
+            "precomposed token",
+            token("caf\u{e9}").nfc(),
+            &[
+                ("caf\u{e9}", Some("caf\u{e9}"), ""),
+                ("cafe\u{301}s", Some("cafe\u{301}"), "s"),
+                ("cafe", None, "cafe"),
+                ("caf\u{e9}\u{301}", None, "caf\u{e9}\u{301}"),
+                ("CAF\u{c9}", None, "CAF\u{c9}"),
+            ],
+        );
+
+        test_lexer_batch(
+            "combining token",
+            token("e\u{301}").nfc(),
+            &[
+                ("\u{e9}!", Some("\u{e9}"), "!"),
+                ("e\u{301}", Some("e\u{301}"), ""),
+                ("e", None, "e"),
+            ],
+        );
+
+        test_lexer_batch(
+            "the end of the match can't combine with the input after it",
+            token("e").nfc(),
+            &[
+                ("e\u{301}", None, "e\u{301}"),
+                ("ex", Some("e"), "x"),
+                ("", None, ""),
+            ],
+        );
+
+        test_lexer_batch(
+            "combining marks are reordered",
+            token("q\u{307}\u{323}").nfc(),
+            &[("q\u{323}\u{307}.", Some("q\u{323}\u{307}"), ".")],
+        );
+
+        test_lexer_batch(
+            "case insensitive",
+            token_ci("\u{e9}t\u{e9}").nfc(),
+            &[
+                ("E\u{301}T\u{c9}", Some("E\u{301}T\u{c9}"), ""),
+                ("\u{e9}t\u{e9}", Some("\u{e9}t\u{e9}"), ""),
+                ("ete", None, "ete"),
+            ],
+        );
+
+        assert_eq!(token("").nfc().lex("abc"), Ok(("", "abc")));
+    }
+}
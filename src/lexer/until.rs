@@ -1,26 +1,63 @@
-use crate::Lex;
+use std::fmt;
 
-use self::pattern::PatternLite;
+use crate::{
+    combinator::budget::examine,
+    error::no_match,
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    Lex,
+};
+
+pub(crate) use self::pattern::PatternLite;
 
 mod pattern;
 
 /// This lexer is returned by [`until()`]. See it's documentation for more details.
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 pub struct Until<'a> {
     pattern: PatternLite<'a>,
 }
 
 impl<'a> Lex for Until<'a> {
     fn lex<'i>(&self, input: &'i str) -> crate::LexResult<'i> {
-        let found_index = match &self.pattern {
-            PatternLite::Char(x) => input.find(*x),
-            PatternLite::Str(x) => input.find(x),
-            PatternLite::CharSlice(x) => input.find(*x),
-        };
+        let found = self.pattern.find(input);
+        examine(found.unwrap_or(input.len()))?;
 
-        match found_index {
+        match found {
             Some(boundary) => Ok(input.split_at(boundary)),
-            None => Err(crate::Error::NoMatch),
+            None => Err(no_match(input)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special(match &self.pattern {
+            PatternLite::Str(pattern) => format!("until {pattern:?}"),
+            PatternLite::Char(pattern) => format!("until {pattern:?}"),
+            PatternLite::CharSlice(pattern) => format!("until one of {pattern:?}"),
+        })
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        // any text that doesn't contain the pattern, which must come next
+        let start = output.len();
+
+        for _ in 0..sampler.count(0, usize::MAX) {
+            output.push(sampler.any_char());
+
+            if self.pattern.find(&output[start..]).is_some() {
+                output.pop();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Until<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.pattern {
+            PatternLite::Str(pattern) => write!(f, "Until({pattern:?})"),
+            PatternLite::Char(pattern) => write!(f, "Until({pattern:?})"),
+            PatternLite::CharSlice(pattern) => write!(f, "Until({pattern:?})"),
         }
     }
 }
@@ -56,7 +93,7 @@ impl<'a> Lex for Until<'a> {
 /// # Ok::<(), parsely::Error>(())
 /// ```
 ///
-///
+/// With the `memchr` feature (enabled by default), the input is searched using [memchr](https://docs.rs/memchr) which is significantly faster for large inputs.
 pub fn until<'a, P>(pattern: P) -> Until<'a>
 where
     P: Into<PatternLite<'a>> + Clone,
@@ -6,8 +6,10 @@
 //! Unfortunately this is done in a fairly crude fasion currently, using an enum over *some of* the types implementing [`Pattern`](std::str::pattern::Pattern).
 //!
 //! Notably, `FnMut(char) -> bool` and [char; N] are missing from this enum.
+//!
+//! With the `memchr` feature enabled, searching is done with [`memchr`] where possible rather than [`str::find()`].
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug)]
 pub enum PatternLite<'a> {
     Str(&'a str),
     Char(char),
@@ -31,3 +33,88 @@ impl<'a> From<&'a [char]> for PatternLite<'a> {
         PatternLite::CharSlice(value)
     }
 }
+
+impl PatternLite<'_> {
+    /// Returns the byte index of the first match of this pattern in the haystack, just like [`str::find()`].
+    pub(crate) fn find(&self, haystack: &str) -> Option<usize> {
+        #[cfg(feature = "memchr")]
+        {
+            let bytes = haystack.as_bytes();
+
+            // UTF-8 is self-synchronizing, so any match of a valid UTF-8 needle starts at a char boundary
+            match self {
+                PatternLite::Str(needle) => memchr::memmem::find(bytes, needle.as_bytes()),
+                PatternLite::Char(c) => {
+                    let mut buf = [0; 4];
+                    let needle = c.encode_utf8(&mut buf);
+
+                    match needle.as_bytes() {
+                        [b] => memchr::memchr(*b, bytes),
+                        needle => memchr::memmem::find(bytes, needle),
+                    }
+                }
+                PatternLite::CharSlice(chars) => match chars {
+                    [] => None,
+                    [a] if a.is_ascii() => memchr::memchr(*a as u8, bytes),
+                    [a, b] if a.is_ascii() && b.is_ascii() => {
+                        memchr::memchr2(*a as u8, *b as u8, bytes)
+                    }
+                    [a, b, c] if a.is_ascii() && b.is_ascii() && c.is_ascii() => {
+                        memchr::memchr3(*a as u8, *b as u8, *c as u8, bytes)
+                    }
+                    chars => haystack.find(*chars),
+                },
+            }
+        }
+
+        #[cfg(not(feature = "memchr"))]
+        match self {
+            PatternLite::Char(x) => haystack.find(*x),
+            PatternLite::Str(x) => haystack.find(x),
+            PatternLite::CharSlice(x) => haystack.find(*x),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_agrees_with_str_find() {
+        let haystacks = ["", "abc", "cba", "xyzâbc", "✓ done. ✗ not done", "aaaa"];
+
+        for haystack in haystacks {
+            for needle in ["", "a", "bc", "â", "done", "✗", "zzz"] {
+                assert_eq!(
+                    PatternLite::Str(needle).find(haystack),
+                    haystack.find(needle),
+                    "{needle:?} in {haystack:?}"
+                );
+            }
+
+            for needle in ['a', 'c', 'â', '✗', 'z'] {
+                assert_eq!(
+                    PatternLite::Char(needle).find(haystack),
+                    haystack.find(needle),
+                    "{needle:?} in {haystack:?}"
+                );
+            }
+
+            for needle in [
+                &[][..],
+                &['c'],
+                &['c', 'b'],
+                &['z', '.', 'b'],
+                &['z', '✗'],
+                &['1', '2', '3', 'a'],
+            ] {
+                assert_eq!(
+                    PatternLite::CharSlice(needle).find(haystack),
+                    haystack.find(needle),
+                    "{needle:?} in {haystack:?}"
+                );
+            }
+        }
+    }
+}
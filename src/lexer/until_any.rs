@@ -0,0 +1,225 @@
+use std::fmt;
+
+use aho_corasick::{AhoCorasick, MatchKind};
+
+use crate::{
+    combinator::budget::examine, error::no_match, grammar::Grammar, Lex, LexResult, Parse,
+    ParseResult,
+};
+
+/// This lexer is returned by [`until_any()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct UntilAny {
+    automaton: AhoCorasick,
+}
+
+/// This lexer/parser is returned by [`find_any()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct FindAny {
+    automaton: AhoCorasick,
+}
+
+fn automaton<I>(patterns: I) -> AhoCorasick
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    AhoCorasick::builder()
+        .match_kind(MatchKind::LeftmostFirst)
+        .build(patterns.into_iter().map(|p| p.as_ref().to_string()))
+        .expect("too many patterns to build a multi-pattern automaton")
+}
+
+impl Lex for UntilAny {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let found = self.automaton.find(input);
+        examine(found.map_or(input.len(), |found| found.end()))?;
+
+        match found {
+            Some(found) => Ok(input.split_at(found.start())),
+            None => Err(no_match(input)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special("until any of the patterns".to_string())
+    }
+}
+
+impl Lex for FindAny {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let found = self.automaton.find(input);
+        examine(found.map_or(input.len(), |found| found.end()))?;
+
+        match found {
+            Some(found) => Ok(input.split_at(found.end())),
+            None => Err(no_match(input)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special("through any of the patterns".to_string())
+    }
+}
+
+impl Parse for FindAny {
+    type Output = usize;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let found = self.automaton.find(input);
+        examine(found.map_or(input.len(), |found| found.end()))?;
+
+        match found {
+            Some(found) => Ok((found.pattern().as_usize(), &input[found.end()..])),
+            None => Err(no_match(input)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special("through any of the patterns".to_string())
+    }
+}
+
+/// Creates a lexer that matches all characters up until (but not including) the first occurrence of any of the `patterns`.
+///
+/// This is available with the `aho-corasick` feature enabled.
+///
+/// This is like [`until()`](crate::until()) but for several patterns at once. All the patterns are searched for in a single pass over the input,
+/// using a multi-pattern automaton from the [aho-corasick](https://docs.rs/aho-corasick) crate.
+///
+/// If more than one pattern matches at the same position, the pattern given first wins.
+///
+/// # Panics
+///
+/// Building the automaton panics if there are so many patterns that it would exceed aho-corasick's internal limits.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{until_any, Lex};
+///
+/// let text = until_any(["{@", "{%", "{#"]);
+///
+/// assert_eq!(text.lex("Hello {% name %}!")?, ("Hello ", "{% name %}!"));
+/// assert_eq!(text.lex("{# comment #}")?, ("", "{# comment #}"));
+/// assert_eq!(text.lex("no tags"), Err(parsely::Error::NoMatch));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn until_any<I>(patterns: I) -> UntilAny
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    UntilAny {
+        automaton: automaton(patterns),
+    }
+}
+
+/// Creates a lexer that matches all characters up to *and including* the first occurrence of any of the `patterns`.
+///
+/// This is available with the `aho-corasick` feature enabled.
+///
+/// As a parser, it outputs the index of the pattern that was found, which is useful to decide how to continue parsing.
+///
+/// If more than one pattern matches at the same position, the pattern given first wins.
+///
+/// See [`until_any()`] to stop just before the pattern instead.
+///
+/// # Panics
+///
+/// Building the automaton panics if there are so many patterns that it would exceed aho-corasick's internal limits.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{find_any, Lex, Parse};
+///
+/// let tag = find_any(["{@", "{%", "{#"]);
+///
+/// assert_eq!(tag.lex("Hello {% name %}!")?, ("Hello {%", " name %}!"));
+///
+/// assert_eq!(tag.parse("Hello {% name %}!")?, (1, " name %}!"));
+/// assert_eq!(tag.parse("{# comment #}")?, (2, " comment #}"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn find_any<I>(patterns: I) -> FindAny
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    FindAny {
+        automaton: automaton(patterns),
+    }
+}
+
+impl fmt::Debug for UntilAny {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "UntilAny({} patterns)", self.automaton.patterns_len())
+    }
+}
+
+impl fmt::Debug for FindAny {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FindAny({} patterns)", self.automaton.patterns_len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn lexing() {
+        test_lexer_batch(
+            "until_any stops before the first pattern",
+            until_any(["{@", "{%", "{#", "{"]),
+            &[
+                ("abc{@def", Some("abc"), "{@def"), //
+                ("abc{%def{@", Some("abc"), "{%def{@"),
+                ("abc{def", Some("abc"), "{def"),
+                ("âbc{#", Some("âbc"), "{#"),
+                ("abc", None, "abc"),
+                ("", None, ""),
+            ],
+        );
+
+        test_lexer_batch(
+            "find_any stops after the first pattern",
+            find_any(["{@", "{%", "{#", "{"]),
+            &[
+                ("abc{@def", Some("abc{@"), "def"), //
+                ("abc{%def{@", Some("abc{%"), "def{@"),
+                ("abc{def", Some("abc{"), "def"),
+                ("abc", None, "abc"),
+            ],
+        );
+    }
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "find_any outputs the index of the pattern found",
+            find_any(["{", "{@", "{%"]),
+            &[
+                ("abc{@def", Some(0), "@def"), //
+                ("%}{%", Some(0), "%"),
+                ("", None, ""),
+            ],
+        );
+
+        test_parser_batch(
+            "earlier patterns are preferred",
+            find_any(["{@", "{%", "{"]),
+            &[
+                ("abc{@def", Some(0), "def"), //
+                ("%}{%", Some(1), ""),
+                ("{", Some(2), ""),
+            ],
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine,
+    error::no_match,
+    grammar::Grammar,
+    sample::{SampleError, Sampler},
+    Lex, LexResult,
+};
+
+/// This lexer is returned by [`word()`] and [`ascii_word()`]. See their documentation for more details.
+#[derive(Clone, Copy)]
+pub struct Word {
+    ascii: bool,
+    digits: bool,
+    also: &'static str,
+}
+
+impl Word {
+    /// Also match digits anywhere in the word, including at the start.
+    ///
+    /// For [`word()`] these are any numeric characters, for [`ascii_word()`] only `0` to `9`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{ascii_word, Lex};
+    ///
+    /// assert_eq!(ascii_word().lex("abc123 ")?, ("abc", "123 "));
+    /// assert_eq!(ascii_word().digits().lex("abc123 ")?, ("abc123", " "));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub const fn digits(self) -> Word {
+        Word {
+            digits: true,
+            ..self
+        }
+    }
+
+    /// Also match any of the given chars anywhere in the word, such as `"_"` or `"-'"`.
+    ///
+    /// Calling this again replaces the chars given before.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{word, Lex};
+    ///
+    /// // the equivalent of the regex `\w+`
+    /// let identifier = word().digits().also("_");
+    ///
+    /// assert_eq!(identifier.lex("_über_2 = 1")?, ("_über_2", " = 1"));
+    ///
+    /// assert_eq!(word().also("-'").lex("isn't well-known.")?, ("isn't", " well-known."));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub const fn also(self, chars: &'static str) -> Word {
+        Word {
+            also: chars,
+            ..self
+        }
+    }
+
+    fn matches(&self, c: char) -> bool {
+        let letter = if self.ascii {
+            c.is_ascii_alphabetic()
+        } else {
+            c.is_alphabetic()
+        };
+
+        let digit = self.digits
+            && if self.ascii {
+                c.is_ascii_digit()
+            } else {
+                c.is_numeric()
+            };
+
+        letter || digit || self.also.contains(c)
+    }
+}
+
+impl Lex for Word {
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let boundary = input
+            .char_indices()
+            .find(|&(_, c)| !self.matches(c))
+            .map_or(input.len(), |(i, _)| i);
+
+        // the char after the match was looked at too
+        examine(boundary + 1)?;
+
+        if boundary == 0 {
+            Err(no_match(input))
+        } else {
+            Ok(input.split_at(boundary))
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        let mut class = match (self.ascii, self.digits) {
+            (false, false) => "alpha",
+            (false, true) => "alphanum",
+            (true, false) => "ascii_alpha",
+            (true, true) => "ascii_alphanum",
+        }
+        .to_string();
+
+        if !self.also.is_empty() {
+            class.push_str(&format!(" or one of {:?}", self.also));
+        }
+
+        Grammar::repeat(1, None, Grammar::Class(class))
+    }
+
+    fn sample_into(&self, sampler: &mut Sampler, output: &mut String) -> Result<(), SampleError> {
+        for _ in 0..sampler.count(1, usize::MAX) {
+            let c = sampler
+                .char_where(|c| self.matches(c))
+                .ok_or(SampleError::NoMatch)?;
+            output.push(c);
+        }
+        Ok(())
+    }
+}
+
+/// This lexer matches a word, i.e. one or more alphabetic characters, as many as it can.
+///
+/// Alphabetic characters are those that are [`char::is_alphabetic()`], so words in any script match.
+/// See [`ascii_word()`] to only match ascii letters.
+///
+/// Digits and other characters such as underscores aren't part of a word unless you ask for them with [`Word::digits()`] and [`Word::also()`].
+/// `word().digits().also("_")` is the equivalent of the regex `\w+`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{word, ws, Lex, Parse};
+///
+/// assert_eq!(word().lex("héllo wörld")?, ("héllo", " wörld"));
+/// assert!(word().lex("123").is_err());
+///
+/// let words = word().map(str::to_string).many(1..).delimiter(ws().many(1..));
+/// assert_eq!(words.parse("the quick  fox")?, (vec!["the".to_string(), "quick".to_string(), "fox".to_string()], ""));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn word() -> Word {
+    Word {
+        ascii: false,
+        digits: false,
+        also: "",
+    }
+}
+
+/// This lexer matches a word of ascii letters, i.e. one or more of `a` to `z` and `A` to `Z`, as many as it can.
+///
+/// This works the same way as [`word()`], but non-ascii letters aren't part of the word.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{ascii_word, Lex};
+///
+/// assert_eq!(ascii_word().lex("Hello, world")?, ("Hello", ", world"));
+/// assert_eq!(ascii_word().lex("café")?, ("caf", "é"));
+///
+/// let keyword = ascii_word().also("_");
+/// assert_eq!(keyword.lex("snake_case!")?, ("snake_case", "!"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn ascii_word() -> Word {
+    Word {
+        ascii: true,
+        digits: false,
+        also: "",
+    }
+}
+
+impl fmt::Debug for Word {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Word")
+            .field("ascii", &self.ascii)
+            .field("digits", &self.digits)
+            .field("also", &self.also)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn words() {
+        test_lexer_batch(
+            "unicode words",
+            word(),
+            &[
+                ("", None, ""),
+                ("123", None, "123"),
+                ("_a", None, "_a"),
+                ("abc", Some("abc"), ""),
+                ("naïve café", Some("naïve"), " café"),
+                ("слово.", Some("слово"), "."),
+                ("a1", Some("a"), "1"),
+            ],
+        );
+
+        test_lexer_batch(
+            "ascii words",
+            ascii_word(),
+            &[
+                ("", None, ""),
+                ("é", None, "é"),
+                ("abc", Some("abc"), ""),
+                ("café", Some("caf"), "é"),
+            ],
+        );
+
+        test_lexer_batch(
+            "words with digits and underscores",
+            ascii_word().digits().also("_"),
+            &[
+                ("_", Some("_"), ""),
+                ("1st_place!", Some("1st_place"), "!"),
+                ("a٣", Some("a"), "٣"),
+                ("-a", None, "-a"),
+            ],
+        );
+
+        test_lexer_batch(
+            "unicode digits",
+            word().digits(),
+            &[("a٣b", Some("a٣b"), ""), ("a_b", Some("a"), "_b")],
+        );
+    }
+
+    #[test]
+    fn describing() {
+        assert_eq!(word().describe().to_string(), repeated_class("alpha"));
+        assert_eq!(
+            ascii_word().digits().also("_").describe().to_string(),
+            repeated_class("ascii_alphanum or one of \"_\"")
+        );
+    }
+
+    fn repeated_class(class: &str) -> String {
+        Grammar::repeat(1, None, Grammar::Class(class.to_string())).to_string()
+    }
+}
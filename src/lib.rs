@@ -28,6 +28,7 @@
 //!
 //! | crate   | style                    | notes |
 //! |---------|--------------------------|-------|
+//! | json    | Adds `formats::json`, parsing [JSON](https://www.rfc-editor.org/rfc/rfc8259) into a `Value` without further dependencies |
 //! | nom     | Parser Combinators       | Excellent at parsing bytes (and strings). Generic over input and error types and streaming support. Mature and battle tested. Can be quite complex when error handling. [Lots of parser and combinators](https://github.com/rust-bakery/nom/blob/main/doc/choosing_a_combinator.md) to choose from. |
 //! | yap     | [`Iterator`]-like design | Generic over input type. Simple for those unfamiliar with parser combinators. Tends to be verbose. Well documented |
 //! | combine | Parser Combinators       | Trait based approach. Generic over input type and streaming support - including `Read` instances. Zero copy parsing. |
@@ -39,13 +40,56 @@
 //!
 //! [`Iterator`]: std::iter::Iterator
 //!
+//! ## Sharing parsers between threads
+//!
+//! Lexers and parsers are [`Send`] and [`Sync`] whenever the parts they are built from are, including any closures given to combinators like [`map()`](Lex::map()).
+//! So a parser can be built once, e.g. in a [`OnceLock`](std::sync::OnceLock), and shared between threads:
+//!
+//! ```
+//! use parsely::{char, int, Lex, Parse};
+//!
+//! let point = int::<i32>().then_skip(char(',')).then(int::<i32>());
+//!
+//! std::thread::scope(|scope| {
+//!     let first = scope.spawn(|| point.parse("1,2"));
+//!     let second = scope.spawn(|| point.parse("3,4"));
+//!
+//!     assert_eq!(first.join().unwrap(), Ok(((1, 2), "")));
+//!     assert_eq!(second.join().unwrap(), Ok(((3, 4), "")));
+//! });
+//! ```
+//!
+//! ## Optional features
+//!
+//! | feature | description |
+//! |---------|-------------|
+//! | memchr  | **Enabled by default.** Uses [memchr](https://docs.rs/memchr) to speed up searching the input, e.g. in [`until()`] and when [`crawl()`](combinator::crawl()) looks for a [`token()`] |
+//! | aho-corasick | Adds `until_any()` and `find_any()` which search for many patterns at once using [aho-corasick](https://docs.rs/aho-corasick) |
+//! | json    | Adds `formats::json`, parsing [JSON](https://www.rfc-editor.org/rfc/rfc8259) into a `Value` without further dependencies |
+//! | nom     | Adds the `nom` module, adapting [nom](https://docs.rs/nom) parsers into parsely lexers and parsers and back |
+//! | proptest | Adds `sample::strategy()`, a [proptest](https://docs.rs/proptest) strategy generating input that a lexer matches |
+//! | railroad | Adds `Grammar::to_railroad_svg()`, drawing the [grammar](crate::grammar) of a parser or lexer as an SVG railroad diagram |
+//! | regex   | Adds `regex()` which compiles a pattern anchored to the start of the input, and implements [`Lex`] for [`regex::Regex`](https://docs.rs/regex/latest/regex/struct.Regex.html) (deprecated in favour of `regex()`) |
+//! | smallvec | Re-exports [smallvec](https://docs.rs/smallvec) so that repetitions can be [collected](combinator::Many::collect()) into a `SmallVec`, keeping a few outputs inline without allocating |
+//! | tracing | Emits [tracing](https://docs.rs/tracing) spans and events from the `then`, `or`, `many` and `delimiter` combinators, at the TRACE level |
+//! | unicode-normalization | Adds [`Token::nfc()`](Token::nfc()), comparing tokens with the input under Unicode NFC normalization using [unicode-normalization](https://docs.rs/unicode-normalization) |
+//!
 //! [^terminology]: These are the terms as used and understood in this library.
 //! I believe what we call "lexing", many would call "tokenising"; and what we call "parsing" many would call "lexing".
 //! Parsely doesn't parse into a tree-like structure at any point, that would be up to the user to do.
 //! If our inexact usage of these terms irks you, then I recommend a parser combinator library intended for parsing programming languages such as [Chumsky](https://docs.rs/chumsky/latest/chumsky/).
 
 mod error;
-pub use error::Error;
+pub use error::{Error, ErrorOwned};
+
+mod cursor;
+pub use cursor::{Checkpoint, Cursor};
+
+mod from_parsely;
+pub use from_parsely::{FromParsely, Parsed};
+
+mod location;
+pub use location::Location;
 
 mod lex;
 pub mod lexer;
@@ -61,9 +105,27 @@ pub use parser::*;
 
 pub mod combinator;
 
-#[doc(hidden)]
-#[cfg(test)]
-pub(crate) mod test_utils;
+pub mod expr;
+
+pub mod formats;
+
+pub mod grammar;
+
+#[cfg(feature = "nom")]
+pub mod nom;
+
+pub mod sample;
+
+pub mod scan;
+
+/// The [smallvec](https://docs.rs/smallvec) crate, to [collect](combinator::Many::collect()) repetitions into a [`SmallVec`](smallvec::SmallVec) without depending on a matching version of it yourself.
+#[cfg(feature = "smallvec")]
+pub use smallvec;
+
+pub mod testing;
+
+pub mod unparse;
+pub use unparse::Unparse;
 
 #[doc(hidden)]
 #[cfg(test)]
@@ -104,3 +166,250 @@ mod test_automation {
         Ok(())
     }
 }
+
+#[doc(hidden)]
+#[cfg(test)]
+mod thread_safety {
+    use crate::combinator::*;
+    use crate::formats::{csv::csv, dotenv::dotenv, ini::ini, query::query};
+    use crate::*;
+
+    /// Fails to compile unless the parser or lexer can be shared between threads.
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    #[test]
+    fn lexers_are_send_and_sync() {
+        assert_send_sync(&any());
+        assert_send_sync(&balanced("(", ")"));
+        assert_send_sync(&char('a'));
+        assert_send_sync(&char_if(|c| c == 'a'));
+        assert_send_sync(&ws());
+        assert_send_sync(&ws0());
+        assert_send_sync(&ws1());
+        assert_send_sync(&alpha());
+        assert_send_sync(&one_of("abc"));
+        assert_send_sync(&none_of("abc"));
+        assert_send_sync(&end());
+        assert_send_sync(&digit());
+        assert_send_sync(&non_zero_digit());
+        assert_send_sync(&hex());
+        assert_send_sync(&binary());
+        assert_send_sync(&octal());
+        assert_send_sync(&take(1));
+        assert_send_sync(&take_bytes(1));
+        assert_send_sync(&take_while(|c| c == 'a'));
+        assert_send_sync(&token("a"));
+        assert_send_sync(&token_ci("a"));
+        assert_send_sync(&until("a"));
+        #[cfg(feature = "aho-corasick")]
+        assert_send_sync(&until_any(["a", "b"]));
+        #[cfg(feature = "regex")]
+        assert_send_sync(&regex("a").unwrap());
+        assert_send_sync(&skip_until(char(';')));
+        assert_send_sync(&skip_balanced("(", ")"));
+        assert_send_sync(&insert_default());
+    }
+
+    #[test]
+    fn parsers_are_send_and_sync() {
+        #[derive(Clone, PartialEq)]
+        enum Keyword {
+            Let,
+        }
+
+        impl TokenEnum for Keyword {
+            fn variants() -> &'static [(&'static str, Self)] {
+                &[("let", Keyword::Let)]
+            }
+        }
+
+        assert_send_sync(&int::<i32>());
+        assert_send_sync(&uint::<u32>());
+        assert_send_sync(&float::<f64>());
+        assert_send_sync(&number::<f64>());
+        assert_send_sync(&entity());
+        assert_send_sync(&key_value(
+            alpha().map(str::to_string),
+            char('='),
+            int::<u8>(),
+        ));
+        assert_send_sync(&position());
+        assert_send_sync(&shell_words());
+        assert_send_sync(&string_literal());
+        assert_send_sync(&escape_sequence());
+        assert_send_sync(&switch([("a", 1), ("b", 2)]).longest());
+        assert_send_sync(&switch_enum::<Keyword>());
+        assert_send_sync(&byte_size());
+        assert_send_sync(&fraction());
+        assert_send_sync(&glob());
+        assert_send_sync(&logical_line());
+        assert_send_sync(&logical_line().scoped(int::<i32>()));
+        assert_send_sync(&ordinal());
+        assert_send_sync(&path());
+        assert_send_sync(&path_buf());
+        assert_send_sync(&percent());
+        assert_send_sync(&phone_number());
+        assert_send_sync(&utc_offset());
+        assert_send_sync(&csv());
+        assert_send_sync(&dotenv());
+        assert_send_sync(&ini());
+        assert_send_sync(&query());
+        assert_send_sync(&crate::formats::http::header());
+        assert_send_sync(&crate::formats::http::parameterized());
+        #[cfg(feature = "json")]
+        assert_send_sync(&crate::formats::json::json());
+        assert_send_sync(
+            &expr::expression(int::<i32>())
+                .prefix(char('-'), 3, |a| -a)
+                .infix(char('+'), 1, expr::Associativity::Left, |a, b| a + b),
+        );
+    }
+
+    #[test]
+    fn combinators_are_send_and_sync() {
+        // the output collection is only a marker, so it doesn't need to be Send or Sync
+        let rc = || int::<u8>().map(std::rc::Rc::new);
+        assert_send_sync(&rc().many(..));
+        assert_send_sync(&rc().many(..).delimiter(char(',')));
+        assert_send_sync(&rc().many(..).with_index());
+        assert_send_sync(&rc().many(..).collect::<std::collections::VecDeque<_>>());
+
+        let item = || int::<u8>();
+        let lexer = || digit().many(1..);
+
+        assert_send_sync(&budget(10, item()));
+        assert_send_sync(&both(item(), item()));
+        assert_send_sync(&char('a').and(any()));
+        assert_send_sync(&with_captures(item().capture("a")));
+        assert_send_sync(&chainl1(item(), char('+').map(|_| |a: u8, b: u8| a + b)));
+        assert_send_sync(&chainr1(item(), char('^').map(|_| |a: u8, b: u8| a * b)));
+        assert_send_sync(&crawl(item()));
+        assert_send_sync(&crawl_at(item()));
+        assert_send_sync(&crawl_consume(item()));
+        assert_send_sync(&item().cut());
+        assert_send_sync(&item().dbg("a"));
+        assert_send_sync(&item().depth_limited(3));
+        assert_send_sync(&with_diagnostics(item().warn("a")));
+        assert_send_sync(&item().expect("a number"));
+        assert_send_sync(&expected("a number", item()));
+        assert_send_sync(&left_recursive(item()));
+        assert_send_sync(&item().lexeme());
+        assert_send_sync(&item().limit(3));
+        assert_send_sync(&located(item()));
+        assert_send_sync(&lexer().map(str::len));
+        assert_send_sync(&lexer().try_map(str::parse::<u8>));
+        assert_send_sync(&lexer().parse_str::<u8>());
+        assert_send_sync(&item().bimap(|a| a, |a| *a));
+        assert_send_sync(&item().or_else_with(item));
+        assert_send_sync(&item().map_into::<u16>());
+        assert_send_sync(&item().name("a"));
+        assert_send_sync(&item().optional());
+        assert_send_sync(&item().optional_or(1));
+        assert_send_sync(&item().optional_or_else(|| 1));
+        assert_send_sync(&item().or_default());
+        assert_send_sync(&item().or(item()));
+        assert_send_sync(&item().pad());
+        assert_send_sync(&program(Rule::lexer(char('a'))));
+        assert_send_sync(&with_recovery(item().recover(skip_until(char(';')))));
+        assert_send_sync(&lexer().scoped(item()));
+        assert_send_sync(&item().many(..).delimiter(char(',')).max_iterations(3));
+        assert_send_sync(&item().many(..).interleave(char('+')));
+        assert_send_sync(&max_iterations(3, item().count(2)));
+        assert_send_sync(&item().then(item()));
+        assert_send_sync(&item().then_skip(char(',')));
+        assert_send_sync(&char(',').skip_then(item()));
+        assert_send_sync(&item().void());
+        assert_send_sync(&ws_insensitive(item()));
+        assert_send_sync(&atomic(item()));
+    }
+
+    #[test]
+    fn static_lexers() {
+        static ANY: Any = any();
+        static BRACKETS: Balanced = balanced("[", "]");
+        static LETTER: CharIf<fn(char) -> bool> = alpha();
+        static HEX: Digit = hex();
+        static THREE: Take = take(3);
+        static KEYWORD: Token<CaseInsensitive> = token_ci("let");
+        static RESYNC: SkipBalanced = skip_balanced("(", ")");
+        const SPACES: TakeWhile<fn(char) -> bool> = take_while(char::is_whitespace);
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| assert_eq!(ANY.lex("ab"), Ok(("a", "b"))));
+            scope.spawn(|| assert_eq!(BRACKETS.lex("[[]]x"), Ok(("[[]]", "x"))));
+            scope.spawn(|| assert_eq!(LETTER.then(HEX).lex("af"), Ok(("af", ""))));
+            scope.spawn(|| assert_eq!(THREE.lex("abcd"), Ok(("abc", "d"))));
+            scope.spawn(|| assert_eq!(KEYWORD.then(SPACES).lex("LET x"), Ok(("LET ", "x"))));
+            scope.spawn(|| assert_eq!(RESYNC.lex("a(b))"), Ok(("a(b)", ")"))));
+        });
+    }
+
+    #[cfg(feature = "nom")]
+    #[test]
+    fn nom_adapters_are_send_and_sync() {
+        use ::nom::character::complete::{alpha1, u8};
+
+        assert_send_sync(&crate::nom::from_nom_lexer(|i| alpha1(i)));
+        assert_send_sync(&crate::nom::from_nom(|i| u8(i)));
+    }
+}
+
+#[doc(hidden)]
+#[cfg(test)]
+mod cloning {
+    use crate::combinator::*;
+    use crate::*;
+
+    fn assert_copy<T: Copy>(_: &T) {}
+
+    #[test]
+    fn lexers_are_copy() {
+        assert_copy(&any());
+        assert_copy(&balanced("(", ")"));
+        assert_copy(&char('a'));
+        assert_copy(&char_if(|c| c == 'a'));
+        assert_copy(&ws());
+        assert_copy(&alpha());
+        assert_copy(&one_of("abc"));
+        assert_copy(&none_of("abc"));
+        assert_copy(&end());
+        assert_copy(&digit());
+        assert_copy(&non_zero_digit());
+        assert_copy(&take(1));
+        assert_copy(&take_bytes(1));
+        assert_copy(&take_while(|c| c == 'a'));
+        assert_copy(&token("a"));
+        assert_copy(&token_ci("a"));
+        assert_copy(&until("a"));
+        assert_copy(&skip_balanced("(", ")"));
+        assert_copy(&insert_default());
+    }
+
+    #[test]
+    fn cloning_a_deep_chain() -> Result<(), Error> {
+        let atom = crawl(int::<i32>().map(|n| n * 2))
+            .or(switch([("one", 1), ("two", 2)]).pad())
+            .then_skip(char(';').optional())
+            .many(1..)
+            .delimiter(char(','))
+            .max_iterations(5);
+        let fields = crate::scan::split_parse(char('|'), atom.clone());
+        let calculator = expr::expression(int::<i32>()).infix(
+            char('+'),
+            1,
+            expr::Associativity::Left,
+            |a, b| a + b,
+        );
+
+        let input = "x 3; one ,two";
+        assert_eq!(atom.clone().parse(input)?, atom.parse(input)?);
+        assert_eq!(fields.clone().parse("1|2,3")?, fields.parse("1|2,3")?);
+        assert_eq!(calculator.clone().parse("1+2")?, (3, ""));
+
+        let comma = char(',');
+        let matches = comma.find_iter("a,b,c");
+        assert_eq!(matches.clone().count(), matches.count());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+//! Describes a position in the input in terms of byte offset, line and column.
+
+use std::fmt;
+
+/// A position in the input.
+///
+/// The byte offset is counted from the start of the input, and is suitable for slicing the input with.
+///
+/// Lines and columns are counted from 1, as text editors do. The column is counted in chars, not bytes.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::Location;
+///
+/// let input = "first line\nsecond line";
+///
+/// let location = Location::at(input, 13);
+///
+/// assert_eq!(location, Location { offset: 13, line: 2, column: 3 });
+/// assert_eq!(location.to_string(), "2:3");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Location {
+    /// The number of bytes from the start of the input.
+    pub offset: usize,
+
+    /// The line number, starting from 1.
+    pub line: usize,
+
+    /// The column number, starting from 1, counted in chars.
+    pub column: usize,
+}
+
+impl Location {
+    /// The location of the start of any input.
+    pub const START: Location = Location {
+        offset: 0,
+        line: 1,
+        column: 1,
+    };
+
+    /// Finds the location of the given byte offset in the input.
+    ///
+    /// Offsets past the end of the input are treated as the end of the input.
+    /// Offsets that aren't on a char boundary count the partial char as part of the column.
+    pub fn at(input: &str, offset: usize) -> Location {
+        let offset = offset.min(input.len());
+        let before = &input.as_bytes()[..offset];
+
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |newline| newline + 1);
+
+        let line = 1 + before.iter().filter(|&&b| b == b'\n').count();
+
+        // count chars by counting the bytes that begin one
+        let column = 1 + before[line_start..]
+            .iter()
+            .filter(|&&b| (b as i8) >= -0x40)
+            .count();
+
+        Location {
+            offset,
+            line,
+            column,
+        }
+    }
+
+    /// Finds the location of the `remaining` input, which must be what remains of the `input` after lexing or parsing.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{token, Lex, Location};
+    ///
+    /// let input = "foo\nbar";
+    /// let (_, remaining) = token("foo\nb").lex(input)?;
+    ///
+    /// assert_eq!(Location::of(input, remaining), Location { offset: 5, line: 2, column: 2 });
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn of(input: &str, remaining: &str) -> Location {
+        Location::at(input, input.len().saturating_sub(remaining.len()))
+    }
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Location::START
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at() {
+        let input = "ab\nâb\n\ncd";
+
+        assert_eq!(Location::at(input, 0), Location::START);
+        assert_eq!(
+            Location::at(input, 2),
+            Location {
+                offset: 2,
+                line: 1,
+                column: 3
+            }
+        );
+        assert_eq!(
+            Location::at(input, 3),
+            Location {
+                offset: 3,
+                line: 2,
+                column: 1
+            }
+        );
+        assert_eq!(
+            Location::at(input, 5),
+            Location {
+                offset: 5,
+                line: 2,
+                column: 2
+            }
+        );
+        assert_eq!(
+            Location::at(input, 8),
+            Location {
+                offset: 8,
+                line: 4,
+                column: 1
+            }
+        );
+        assert_eq!(
+            Location::at(input, 100),
+            Location {
+                offset: 10,
+                line: 4,
+                column: 3
+            }
+        );
+    }
+}
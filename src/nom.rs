@@ -0,0 +1,367 @@
+//! Adapters between [nom](https://docs.rs/nom) parsers and parsely lexers and parsers.
+//!
+//! This is available with the `nom` feature enabled.
+//!
+//! These make it possible to migrate between the two libraries one piece at a time, or to borrow the odd combinator from the other library.
+//!
+//! * [`from_nom_lexer()`] and [`from_nom()`] turn a nom parser into a parsely [`Lex`] or [`Parse`]
+//! * [`to_nom_lexer()`] and [`to_nom()`] turn a parsely lexer or parser into a nom parser
+//!
+//! nom's parsers are generic over their input, so to be used for any input they have to be called from a closure: `from_nom(|i| alpha1(i))` rather than `from_nom(alpha1)`.
+//!
+//! Errors are translated as faithfully as the two error models allow:
+//!
+//! * a nom `Err::Error` is [`Error::NoMatch`], and is recorded at the position where nom failed
+//! * a nom `Err::Failure` is a [fatal](Error::is_fatal()) [`Error::NoMatch`], so it won't backtrack into alternatives
+//! * a nom `Err::Incomplete` (only returned by streaming parsers) is [`Error::Expected`] `"more input"`
+//! * a parsely [fatal](Error::is_fatal()) error is a nom `Err::Failure`, other errors are `Err::Error`
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! use nom::{bytes::complete::tag, character::complete::alpha1, sequence::preceded};
+//! use parsely::{char, int, Lex, Parse};
+//! use parsely::nom::{from_nom_lexer, to_nom};
+//!
+//! // a nom parser in the middle of a parsely lexer
+//! let variable = char('$').then(from_nom_lexer(|i| alpha1(i)));
+//! assert_eq!(variable.lex("$abc + 1")?, ("$abc", " + 1"));
+//!
+//! // a parsely parser in the middle of a nom parser
+//! let mut port = preceded(tag(":"), to_nom::<_, nom::error::Error<&str>>(int::<u16>()));
+//! assert_eq!(port(":8080/"), Ok(("/", 8080)));
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use std::{
+    any::type_name,
+    fmt,
+    marker::PhantomData,
+    sync::{Mutex, PoisonError},
+};
+
+use ::nom::{
+    error::{ErrorKind, FromExternalError, ParseError},
+    Err, IResult,
+};
+
+use crate::{
+    combinator::budget::examine, error::no_match, grammar::Grammar, Error, Lex, LexResult, Parse,
+    ParseResult,
+};
+
+/// This lexer is returned by [`from_nom_lexer()`]. See it's documentation for more details.
+pub struct FromNomLexer<P> {
+    // nom parsers are run with `&mut self`, a mutex lets the lexer still be shared between threads
+    parser: Mutex<P>,
+}
+
+/// Uses a nom parser that returns the `&str` it matched, such as `tag` or `alpha1`, as a parsely lexer.
+///
+/// Any nom parser can be used this way, what it returns doesn't matter: the lexer matches all of the input the nom parser consumed.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use nom::{branch::alt, bytes::complete::tag_no_case};
+/// use parsely::Lex;
+/// use parsely::nom::from_nom_lexer;
+///
+/// let boolean = from_nom_lexer(|i| alt((tag_no_case("true"), tag_no_case("false")))(i));
+///
+/// assert_eq!(boolean.lex("TRUE or false")?, ("TRUE", " or false"));
+/// assert!(boolean.lex("yes").is_err());
+/// assert_eq!(boolean.pad().many(1..).lex("true False")?, ("true False", ""));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn from_nom_lexer<P>(parser: P) -> FromNomLexer<P>
+where
+    P: for<'i> FnMut(&'i str) -> IResult<&'i str, &'i str>,
+{
+    FromNomLexer {
+        parser: Mutex::new(parser),
+    }
+}
+
+impl<P> Lex for FromNomLexer<P>
+where
+    P: for<'i> FnMut(&'i str) -> IResult<&'i str, &'i str>,
+{
+    fn lex<'i>(&self, input: &'i str) -> LexResult<'i> {
+        let (remaining, _) = run(&self.parser, input)?;
+        let consumed = consumed(input, remaining)?;
+
+        Ok(input.split_at(consumed))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special("nom parser".to_string())
+    }
+}
+
+/// This parser is returned by [`from_nom()`]. See it's documentation for more details.
+pub struct FromNom<P, O> {
+    // nom parsers are run with `&mut self`, a mutex lets the parser still be shared between threads
+    parser: Mutex<P>,
+    output: PhantomData<fn() -> O>,
+}
+
+/// Uses a nom parser as a parsely parser, with the same output.
+///
+/// As with function parsers, the output can't borrow from the input, use [`from_nom_lexer()`] for nom parsers that return a `&str`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use nom::{character::complete::u32, multi::separated_list1, bytes::complete::tag};
+/// use parsely::{char, Lex, Parse};
+/// use parsely::nom::from_nom;
+///
+/// let version = from_nom(|i| separated_list1(tag("."), u32)(i));
+///
+/// assert_eq!(version.parse("1.20.3 ")?, (vec![1, 20, 3], " "));
+///
+/// let requirement = char('=').skip_then(version);
+/// assert_eq!(requirement.parse("=2.0")?, (vec![2, 0], ""));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn from_nom<P, O>(parser: P) -> FromNom<P, O>
+where
+    P: for<'i> FnMut(&'i str) -> IResult<&'i str, O>,
+{
+    FromNom {
+        parser: Mutex::new(parser),
+        output: PhantomData,
+    }
+}
+
+impl<P, O> Parse for FromNom<P, O>
+where
+    P: for<'i> FnMut(&'i str) -> IResult<&'i str, O>,
+{
+    type Output = O;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (remaining, output) = run(&self.parser, input)?;
+        consumed(input, remaining)?;
+
+        Ok((output, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special("nom parser".to_string())
+    }
+}
+
+/// Runs the nom parser, translating its error.
+///
+/// Threads sharing the same nom parser take turns running it.
+fn run<'i, P, O>(parser: &Mutex<P>, input: &'i str) -> Result<(&'i str, O), Error>
+where
+    P: FnMut(&'i str) -> IResult<&'i str, O>,
+{
+    // a panic inside the nom parser doesn't leave it in a worse state than it would be without the mutex
+    let mut parser = parser.lock().unwrap_or_else(PoisonError::into_inner);
+
+    parser(input).map_err(|error| match error {
+        Err::Error(error) => no_match(error.input),
+        Err::Failure(error) => no_match(error.input).fatal(),
+        Err::Incomplete(_) => Error::Expected("more input"),
+    })
+}
+
+/// Checks the remaining input returned by a nom parser, returning how much it consumed.
+fn consumed(input: &str, remaining: &str) -> Result<usize, Error> {
+    let consumed = input
+        .len()
+        .checked_sub(remaining.len())
+        .expect("the nom parser returned more input than it was given");
+    examine(consumed)?;
+
+    Ok(consumed)
+}
+
+/// Uses a parsely lexer as a nom parser, returning the `&str` it matched.
+///
+/// The error type is chosen by the caller, it must be able to hold a parsely [`Error`] (nom's default error type discards it).
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use nom::multi::many1;
+/// use parsely::{char, digit, Lex};
+/// use parsely::nom::to_nom_lexer;
+///
+/// let mut numbers = many1(to_nom_lexer::<_, nom::error::Error<&str>>(
+///     digit().many(1..).then_skip(char(',').optional()),
+/// ));
+///
+/// assert_eq!(numbers("1,20,3!"), Ok(("!", vec!["1", "20", "3"])));
+/// assert!(numbers("!").is_err());
+/// ```
+pub fn to_nom_lexer<'i, L, E>(lexer: L) -> impl FnMut(&'i str) -> IResult<&'i str, &'i str, E>
+where
+    L: Lex,
+    E: ParseError<&'i str> + FromExternalError<&'i str, Error>,
+{
+    move |input| match lexer.lex(input) {
+        Ok((matched, remaining)) => Ok((remaining, matched)),
+        Err(error) => Err(to_nom_error(input, error)),
+    }
+}
+
+/// Uses a parsely parser as a nom parser, with the same output.
+///
+/// The error type is chosen by the caller, it must be able to hold a parsely [`Error`] (nom's default error type discards it).
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use nom::{bytes::complete::tag, sequence::separated_pair};
+/// use parsely::{int, Parse};
+/// use parsely::nom::to_nom;
+///
+/// let mut size = separated_pair(
+///     to_nom::<_, nom::error::Error<&str>>(int::<u32>()),
+///     tag("x"),
+///     to_nom(int::<u32>()),
+/// );
+///
+/// assert_eq!(size("640x480"), Ok(("", (640, 480))));
+/// ```
+pub fn to_nom<'i, P, E>(parser: P) -> impl FnMut(&'i str) -> IResult<&'i str, P::Output, E>
+where
+    P: Parse,
+    E: ParseError<&'i str> + FromExternalError<&'i str, Error>,
+{
+    move |input| match parser.parse(input) {
+        Ok((output, remaining)) => Ok((remaining, output)),
+        Err(error) => Err(to_nom_error(input, error)),
+    }
+}
+
+fn to_nom_error<'i, E>(input: &'i str, error: Error) -> Err<E>
+where
+    E: FromExternalError<&'i str, Error>,
+{
+    if error.is_fatal() {
+        Err::Failure(E::from_external_error(input, ErrorKind::Fail, error))
+    } else {
+        Err::Error(E::from_external_error(input, ErrorKind::Fail, error))
+    }
+}
+
+impl<P: Clone> Clone for FromNomLexer<P> {
+    fn clone(&self) -> Self {
+        FromNomLexer {
+            parser: Mutex::new(
+                self.parser
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .clone(),
+            ),
+        }
+    }
+}
+
+impl<P: Clone, O> Clone for FromNom<P, O> {
+    fn clone(&self) -> Self {
+        FromNom {
+            parser: Mutex::new(
+                self.parser
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .clone(),
+            ),
+            output: PhantomData,
+        }
+    }
+}
+
+impl<P> fmt::Debug for FromNomLexer<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FromNomLexer<{}>", type_name::<P>())
+    }
+}
+
+impl<P, O> fmt::Debug for FromNom<P, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FromNom<{}>", type_name::<P>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::nom::{
+        bytes::complete::tag,
+        character::complete::{alpha1, digit1},
+        combinator::cut,
+        error::VerboseError,
+        sequence::preceded,
+    };
+
+    use super::*;
+    use crate::testing::*;
+    use crate::{char, int, token};
+
+    #[test]
+    fn lexing() {
+        test_lexer_batch(
+            "nom alpha1",
+            from_nom_lexer(|i| alpha1(i)),
+            &[
+                ("abc1", Some("abc"), "1"),
+                ("abc", Some("abc"), ""),
+                ("1abc", None, ""),
+                ("", None, ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "nom number",
+            from_nom(|i| ::nom::character::complete::u8(i)),
+            &[("12x", Some(12), "x"), ("300", None, ""), ("x", None, "")],
+        );
+    }
+
+    #[test]
+    fn errors() {
+        // the failure is recorded where nom failed, after the tag
+        let sum = from_nom_lexer(|i| preceded(tag("1+"), digit1)(i));
+        assert_eq!(
+            sum.lex_all_owned("1+x").unwrap_err().to_string(),
+            "No Match at 1:3"
+        );
+
+        // a nom failure is fatal, so it isn't backtracked
+        let committed = from_nom_lexer(|i| preceded(tag("#"), cut(digit1))(i)).or(token("#x"));
+        let error = committed.lex("#x").unwrap_err();
+        assert!(error.is_fatal());
+
+        let recoverable = from_nom_lexer(|i| preceded(tag("#"), digit1)(i)).or(token("#x"));
+        assert_eq!(recoverable.lex("#x"), Ok(("#x", "")));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut number = to_nom::<_, VerboseError<&str>>(int::<u8>());
+        assert_eq!(number("12x"), Ok(("x", 12)));
+        assert!(matches!(number("300"), Err(Err::Error(_))));
+
+        let mut fatal = to_nom_lexer::<_, VerboseError<&str>>(char('a').then(char('b')).cut());
+        assert!(matches!(fatal("ac"), Err(Err::Failure(_))));
+    }
+}
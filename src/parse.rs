@@ -1,11 +1,19 @@
-use std::ops::RangeBounds;
+use std::{any::type_name, fmt, ops::RangeBounds};
 
 use crate::{
     combinator::{
-        count, many, optional, or, pad, sequence::LexMany, then, then_skip, Many, Optional, Or,
-        Pad, Then, ThenSkip,
+        self, all, bimap, both, budget, capture, count, cut, dbg, depth_limited, lexeme, limit,
+        many, map_into, optional, optional_or, optional_or_else, or, or_default, or_either,
+        or_else_with, pad, pad_left, pad_right, recover, sequence::LexMany, then, then_skip, void,
+        warn, BiMap, Both, Budget, Capture, Cut, Dbg, DepthLimited, Expected, Lexeme, Limit, Many,
+        MapInto, Named, NoPadding, Optional, OptionalOr, OptionalOrElse, Or, OrDefault, OrEither,
+        OrElseWith, Pad, Recover, Then, ThenSkip, Void, Warn,
     },
-    end, ws, End, Lex, WhiteSpace,
+    end,
+    error::track_failures,
+    grammar::{Grammar, Problem},
+    scan::replace,
+    ws, ws0, End, Lex, WhiteSpace, WhiteSpaces,
 };
 
 /// The type returned by a parse. The order of the tuple is `(output, remaining)`
@@ -45,6 +53,117 @@ pub trait Parse {
     /// This order reads left to right as the parser reads the input, and matches the return order of [`str::split_at`].
     fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output>;
 
+    /// Describes the grammar this parser matches, see the [`grammar`](crate::grammar) module.
+    ///
+    /// Parsers that can't be looked inside, such as functions, are described by their name.
+    fn describe(&self) -> Grammar {
+        Grammar::reference::<Self>()
+    }
+
+    /// Checks the grammar of this parser for constructs that would loop forever, such as `many(..)` of something that can match empty input.
+    ///
+    /// See [`Grammar::validate()`] for more details.
+    fn validate(&self) -> Result<(), Vec<Problem>> {
+        self.describe().validate()
+    }
+
+    /// Parse all of the input, returning just the output.
+    ///
+    /// If any input remains after parsing, then this fails with [`Error::NoMatch`](crate::Error::NoMatch).
+    ///
+    /// This is a convenient alternative to `.then_end()` when implementing [`FromStr`](std::str::FromStr), as the remaining input doesn't need to be ignored.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, int, Error, Lex, Parse};
+    /// use std::str::FromStr;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Point(i32, i32);
+    ///
+    /// impl FromStr for Point {
+    ///     type Err = Error;
+    ///
+    ///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+    ///         int()
+    ///             .then_skip(char(','))
+    ///             .then(int())
+    ///             .map(|(x, y)| Point(x, y))
+    ///             .parse_all(s)
+    ///     }
+    /// }
+    ///
+    /// assert_eq!("1,-2".parse(), Ok(Point(1, -2)));
+    /// assert_eq!("1,-2,3".parse::<Point>(), Err(Error::NoMatch));
+    /// ```
+    fn parse_all(&self, input: &str) -> Result<Self::Output, crate::Error> {
+        match self.parse(input)? {
+            (output, "") => Ok(output),
+            _ => Err(crate::Error::NoMatch),
+        }
+    }
+
+    /// Parse all of the input like [`parse_all()`](Parse::parse_all()), but if it fails return an [`ErrorOwned`](crate::ErrorOwned) which holds the input and where parsing failed.
+    ///
+    /// Where parsing failed is the furthest point in the input that any lexer failed to match,
+    /// or where the unparsed input starts if that is further.
+    /// If the error is an [`Error::FailedConversion`](crate::Error::FailedConversion) or an [`Error::Overflow`](crate::Error::Overflow), it is where the input that failed to convert starts.
+    ///
+    /// See [`ErrorOwned::to_report()`](crate::ErrorOwned::to_report()) to describe the error to a person.
+    fn parse_all_owned(&self, input: &str) -> Result<Self::Output, crate::ErrorOwned> {
+        let (result, failures) = track_failures(input, || self.parse(input));
+
+        match result {
+            Ok((output, "")) => Ok(output),
+            Ok((_, remaining)) => {
+                let offset = failures.furthest.max(input.len() - remaining.len());
+                Err(crate::ErrorOwned::new(crate::Error::NoMatch, input, offset))
+            }
+            Err(error) => {
+                let offset = failures.offset_of(&error);
+                Err(crate::ErrorOwned::new(error, input, offset))
+            }
+        }
+    }
+
+    /// Parse the input like [`parse()`](Parse::parse()), but also return the exact part of the input that was consumed.
+    ///
+    /// This method returns a tuple `(output, matched, remaining)`, where `matched` followed by `remaining` is the whole input.
+    /// This is useful when you need the raw text of a value as well as the value, e.g. to echo it back or hash it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this parser returns remaining input that isn't the end of the input it was given,
+    /// which no parser in Parsely does.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, int, Lex, Parse};
+    ///
+    /// let point = int::<i32>().then_skip(char(',').pad()).then(int::<i32>());
+    ///
+    /// assert_eq!(point.parse_spanned("1 , -2;")?, ((1, -2), "1 , -2", ";"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn parse_spanned<'i>(
+        &self,
+        input: &'i str,
+    ) -> Result<(Self::Output, &'i str, &'i str), crate::Error> {
+        let (output, remaining) = self.parse(input)?;
+        let consumed = input
+            .len()
+            .checked_sub(remaining.len())
+            .expect("the remaining input is longer than the input");
+
+        Ok((output, &input[..consumed], remaining))
+    }
+
     /// Creates a new parser that will attempt to parse with this parser multiple times.
     ///
     /// See [`crate::combinator::many()`] and the [`sequence module`](crate::combinator::sequence) for more details.
@@ -55,6 +174,21 @@ pub trait Parse {
         many(range, self)
     }
 
+    /// Creates a new parser that will attempt to parse with this parser multiple times, and then expects there to be no remaining input.
+    ///
+    /// This is equivalent to `.many(range).then_end()`.
+    ///
+    /// See [`crate::combinator::all()`] for more details.
+    fn all(
+        self,
+        range: impl RangeBounds<usize>,
+    ) -> ThenSkip<End, Many<Self, Vec<<Self as Parse>::Output>>>
+    where
+        Self: Sized,
+    {
+        all(range, self)
+    }
+
     /// Creates a new parser that will attempt to parse with this parser exactly n times.
     ///
     /// This is equivalent to `.many(n..=n)`.
@@ -97,6 +231,39 @@ pub trait Parse {
         optional(self)
     }
 
+    /// Creates a new parser from this one that outputs a clone of `default` if this parser doesn't match.
+    ///
+    /// See [`crate::combinator::optional_or()`] for more details and examples.
+    fn optional_or(self, default: Self::Output) -> OptionalOr<Self, Self::Output>
+    where
+        Self: Sized,
+        Self::Output: Clone,
+    {
+        optional_or(self, default)
+    }
+
+    /// Creates a new parser from this one that outputs the result of calling `f` if this parser doesn't match.
+    ///
+    /// See [`crate::combinator::optional_or_else()`] for more details and examples.
+    fn optional_or_else<F>(self, f: F) -> OptionalOrElse<Self, F>
+    where
+        Self: Sized,
+        F: Fn() -> Self::Output,
+    {
+        optional_or_else(self, f)
+    }
+
+    /// Creates a new parser from this one that outputs the [`Default`] value if this parser doesn't match.
+    ///
+    /// See [`crate::combinator::or_default()`] for more details and examples.
+    fn or_default(self) -> OrDefault<Self>
+    where
+        Self: Sized,
+        Self::Output: Default,
+    {
+        or_default(self)
+    }
+
     /// Creates a new parser that will attempt to parse with this parser, and if it fails, attempt to parse with the given parser.
     ///
     /// This can be used to build a chain of possible ways to parse the same input.
@@ -176,6 +343,45 @@ pub trait Parse {
         or(self, parser)
     }
 
+    /// Creates a new parser that will attempt to parse with this parser, and if it fails try to parse with the given parser,
+    /// where the two parsers can have different output types.
+    ///
+    /// The output is an [`Either`](combinator::Either) of the two outputs.
+    ///
+    /// See [`crate::combinator::or_either()`] for more details.
+    fn or_either<P: Parse>(self, parser: P) -> OrEither<Self, P>
+    where
+        Self: Sized,
+    {
+        or_either(self, parser)
+    }
+
+    /// Creates a new parser that will attempt to parse with this parser, and if it fails try to parse with the parser returned by `f`.
+    ///
+    /// The alternative isn't created until this parser first fails, and is then kept for later parses.
+    ///
+    /// See [`crate::combinator::or_else_with()`] for more details and examples.
+    fn or_else_with<F, P>(self, f: F) -> OrElseWith<Self, F, P>
+    where
+        Self: Sized,
+        F: Fn() -> P,
+        P: Parse<Output = Self::Output>,
+    {
+        or_else_with(self, f)
+    }
+
+    /// Creates a new parser that requires both this parser and the given parser to match at the same place in the input.
+    ///
+    /// The output is a tuple of both outputs, and the longer of the two matches is consumed.
+    ///
+    /// See [`crate::combinator::both()`] for more details and examples.
+    fn and<P: Parse>(self, parser: P) -> Both<Self, P>
+    where
+        Self: Sized,
+    {
+        both(self, parser)
+    }
+
     /// Creates a new parser that applies two parsers in sequence.
     ///
     /// First this parser is run, and then if successful, the remaining input will be fed to the given parser.
@@ -284,6 +490,92 @@ pub trait Parse {
         Mapped { f, parser: self }
     }
 
+    /// Map the output of this parser to some other type, and back again.
+    ///
+    /// Unlike [`map()`](Parse::map()), the parser can still be [unparsed](crate::unparse) when given a function that reverses the mapping.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, digit, Lex, Parse, Unparse};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Version {
+    ///     major: u16,
+    ///     minor: u16,
+    /// }
+    ///
+    /// let number = || digit().many(1..).parse_str::<u16>();
+    /// let version = number()
+    ///     .then_skip(char('.'))
+    ///     .then(number())
+    ///     .bimap(
+    ///         |(major, minor)| Version { major, minor },
+    ///         |version| (version.major, version.minor),
+    ///     );
+    ///
+    /// assert_eq!(version.parse("1.20")?, (Version { major: 1, minor: 20 }, ""));
+    /// assert_eq!(version.unparse(&Version { major: 2, minor: 0 })?, "2.0");
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn bimap<F, G, O>(self, f: F, g: G) -> BiMap<Self, F, G>
+    where
+        F: Fn(<Self as Parse>::Output) -> O,
+        G: Fn(&O) -> <Self as Parse>::Output,
+        Self: Sized,
+    {
+        bimap(self, f, g)
+    }
+
+    /// Converts the output of this parser to another type using [`Into`].
+    ///
+    /// This is a shorthand for `.map(Into::into)`, which is handy for wrapping outputs in newtypes. The output type is usually chosen with a turbofish or inferred.
+    ///
+    /// If the output can also be converted back with [`Into`], the parser can still be [unparsed](crate::unparse).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{int, Parse};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Meters(f64);
+    ///
+    /// impl From<u16> for Meters {
+    ///     fn from(meters: u16) -> Self {
+    ///         Meters(meters.into())
+    ///     }
+    /// }
+    ///
+    /// let distance = int::<u16>().map_into::<Meters>();
+    /// assert_eq!(distance.parse("300m")?, (Meters(300.0), "m"));
+    ///
+    /// let widened = int::<u8>().map_into::<i64>();
+    /// assert_eq!(widened.parse("255")?, (255, ""));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn map_into<T>(self) -> MapInto<Self, T>
+    where
+        <Self as Parse>::Output: Into<T>,
+        Self: Sized,
+    {
+        map_into(self)
+    }
+
+    /// Creates a parser that discards the output of this parser, outputting `()` instead.
+    ///
+    /// See [`crate::combinator::void()`] for more details and examples.
+    fn void(self) -> Void<Self>
+    where
+        Self: Sized,
+    {
+        void(self)
+    }
+
     /// Swaps around the tuple output by the [`then()`] parser.
     ///
     /// * `a.then(b)` outputs `(a, b)`
@@ -386,9 +678,345 @@ pub trait Parse {
     {
         pad(left, right, self)
     }
+
+    /// Pad the left side of this parser with the given lexer, leaving the input after it as it is.
+    ///
+    /// See also [`pad_left_ws()`](Parse::pad_left_ws()) which pads with zero or more whitespace characters.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, int, Parse};
+    ///
+    /// let parser = int::<u8>().pad_left(char('>'));
+    ///
+    /// assert_eq!(parser.parse(">1>2")?, (1, ">2"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn pad_left<L: Lex>(self, left: L) -> Pad<L, NoPadding, Self>
+    where
+        Self: Sized,
+    {
+        pad_left(left, self)
+    }
+
+    /// Pad the right side of this parser with the given lexer, leaving the input before it as it is.
+    ///
+    /// See also [`pad_right_ws()`](Parse::pad_right_ws()) which pads with zero or more whitespace characters.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, int, Parse};
+    ///
+    /// let parser = int::<u8>().pad_right(char(';'));
+    ///
+    /// assert_eq!(parser.parse("1;2;")?, (1, "2;"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn pad_right<R: Lex>(self, right: R) -> Pad<NoPadding, R, Self>
+    where
+        Self: Sized,
+    {
+        pad_right(right, self)
+    }
+
+    /// Pad the left side of this parser with zero or more whitespace characters, so that leading whitespace is ignored
+    /// but whitespace after it, such as a significant newline, is left in the remaining input.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, int, Parse};
+    ///
+    /// let line = int::<u8>().pad_left_ws().then_skip(char('\n'));
+    ///
+    /// assert_eq!(line.parse("   1\n")?, (1, ""));
+    /// assert!(line.parse("1 \n").is_err());
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn pad_left_ws(self) -> Pad<WhiteSpaces, NoPadding, Self>
+    where
+        Self: Sized,
+    {
+        pad_left(ws0(), self)
+    }
+
+    /// Pad the right side of this parser with zero or more whitespace characters, so that trailing whitespace is ignored
+    /// but whitespace before it is significant.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{int, Parse};
+    ///
+    /// let item = int::<u8>().pad_right_ws();
+    ///
+    /// assert_eq!(item.parse("1 \t 2")?, (1, "2"));
+    /// assert!(item.parse(" 1").is_err());
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn pad_right_ws(self) -> Pad<NoPadding, WhiteSpaces, Self>
+    where
+        Self: Sized,
+    {
+        pad_right(ws0(), self)
+    }
+
+    /// Skip zero or more whitespace characters after this parser.
+    ///
+    /// Wrapping each item of a [`then()`](Parse::then) chain with `lexeme()` skips the whitespace in between them,
+    /// see [`lexeme()`](crate::combinator::lexeme()) for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, int, Lex, Parse};
+    ///
+    /// let parser = int::<u8>().lexeme().then_skip(char(',').lexeme()).then(int::<u8>().lexeme());
+    ///
+    /// assert_eq!(parser.parse("1 , 2 ;")?, ((1, 2), ";"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn lexeme(self) -> Lexeme<Self>
+    where
+        Self: Sized,
+    {
+        lexeme(self)
+    }
+
+    /// Print the input given to this parser and what it output, labelled and indented by nesting, to help debug it.
+    ///
+    /// See [`dbg()`](crate::combinator::dbg()) for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{int, Parse};
+    ///
+    /// let parser = int::<i32>().dbg("int");
+    ///
+    /// // prints "[int] input: ..." followed by "[int] matched 42, remaining: ..."
+    /// assert_eq!(parser.parse("42!")?, (42, "!"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn dbg(self, label: &'static str) -> Dbg<Self>
+    where
+        Self: Sized,
+        Self::Output: std::fmt::Debug,
+    {
+        dbg(label, self)
+    }
+
+    /// Label this parser with a name, which is used in place of its structure in Debug output and attached to its errors.
+    ///
+    /// See [`name()`](crate::combinator::name()) for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{int, Parse};
+    ///
+    /// let port = int::<u16>().name("port");
+    ///
+    /// assert_eq!(port.parse("x").unwrap_err().to_string(), "port: No Match");
+    /// assert_eq!(format!("{:?}", port.many(..)), "Many(0.., port)");
+    /// ```
+    fn name(self, name: &'static str) -> Named<Self>
+    where
+        Self: Sized,
+    {
+        combinator::name(name, self)
+    }
+
+    /// Marks any failure of this parser as fatal, so that alternatives aren't tried and the error is reported instead.
+    ///
+    /// See [`cut()`](crate::combinator::cut()) for more details and examples.
+    fn cut(self) -> Cut<Self>
+    where
+        Self: Sized,
+    {
+        cut(self)
+    }
+
+    /// Limits how deeply this parser can be nested inside itself, failing with [`Error::DepthExceeded`](crate::Error::DepthExceeded) instead of overflowing the stack.
+    ///
+    /// See [`depth_limited()`](crate::combinator::depth_limited()) for more details and examples.
+    fn depth_limited(self, limit: usize) -> DepthLimited<Self>
+    where
+        Self: Sized,
+    {
+        depth_limited(limit, self)
+    }
+
+    /// Runs this parser on only the first `max_chars` chars of the input, so it can't match any further.
+    ///
+    /// See [`limit()`](crate::combinator::limit()) for more details and examples.
+    fn limit(self, max_chars: usize) -> Limit<Self>
+    where
+        Self: Sized,
+    {
+        limit(max_chars, self)
+    }
+
+    /// Fails with [`Error::BudgetExceeded`](crate::Error::BudgetExceeded) once this parser has examined more than `max_bytes` of input, including input examined again after backtracking.
+    ///
+    /// See [`budget()`](crate::combinator::budget()) for more details and examples.
+    fn budget(self, max_bytes: usize) -> Budget<Self>
+    where
+        Self: Sized,
+    {
+        budget(max_bytes, self)
+    }
+
+    /// Describes what was expected if this parser doesn't match, using [`Error::Expected`](crate::Error::Expected).
+    ///
+    /// See [`expected()`](crate::combinator::expected()) for more details and examples.
+    fn expected(self, expected: &'static str) -> Expected<Self>
+    where
+        Self: Sized,
+    {
+        combinator::expected(expected, self)
+    }
+
+    /// Describes what was expected if this parser doesn't match, and marks any failure of it as fatal.
+    ///
+    /// This is equivalent to `.expected(expected).cut()`, see [`cut()`](crate::combinator::cut()) for more details and examples.
+    fn expect(self, expected: &'static str) -> Cut<Expected<Self>>
+    where
+        Self: Sized,
+    {
+        cut(combinator::expected(expected, self))
+    }
+
+    /// Tag this parser so that what it matches is recorded by name in an enclosing [`with_captures()`](crate::combinator::with_captures()).
+    ///
+    /// See the [`capture`](mod@crate::combinator::capture) module for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, int, Lex, Parse};
+    /// use parsely::combinator::with_captures;
+    ///
+    /// let point = int::<i32>().capture("x").then_skip(char(',')).then(int::<i32>().capture("y"));
+    ///
+    /// let ((output, captures), _) = with_captures(point).parse("3,-4")?;
+    /// assert_eq!(output, (3, -4));
+    /// assert_eq!(captures.get("y").unwrap().range, 2..4);
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn capture(self, name: &'static str) -> Capture<Self>
+    where
+        Self: Sized,
+    {
+        capture(name, self)
+    }
+
+    /// Recover from a failure of this parser by skipping the input matched by the strategy and outputting the default, recording the error in an enclosing [`with_recovery()`](crate::combinator::with_recovery()).
+    ///
+    /// See the [`recover`](mod@crate::combinator::recover) module for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, int, Parse};
+    /// use parsely::combinator::{skip_until, with_recovery};
+    ///
+    /// let numbers = int::<u8>().recover(skip_until(char(','))).many(..).delimiter(char(','));
+    ///
+    /// let ((output, errors), _) = with_recovery(numbers).parse("1,x,3")?;
+    /// assert_eq!(output, vec![1, 0, 3]);
+    /// assert_eq!(errors.len(), 1);
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn recover<S: Lex>(self, strategy: S) -> Recover<Self, S>
+    where
+        Self: Sized,
+        Self::Output: Default,
+    {
+        recover(self, strategy)
+    }
+
+    /// Emit a diagnostic with the message whenever this parser matches, to be collected by an enclosing [`with_diagnostics()`](crate::combinator::with_diagnostics()).
+    ///
+    /// See the [`diagnostics`](mod@crate::combinator::diagnostics) module for more details.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, int, Lex, Parse};
+    /// use parsely::combinator::with_diagnostics;
+    ///
+    /// let plus = char('+').skip_then(int::<i32>()).warn("a leading `+` isn't needed");
+    /// let number = plus.or(int::<i32>());
+    ///
+    /// let ((value, diagnostics), _) = with_diagnostics(number).parse("+5")?;
+    /// assert_eq!(value, 5);
+    /// assert_eq!(diagnostics[0].range, 0..2);
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn warn(self, message: &'static str) -> Warn<Self>
+    where
+        Self: Sized,
+    {
+        warn(message, self)
+    }
+
+    /// Copies the input, replacing every non-overlapping match of this parser with the result of the closure applied to its output.
+    ///
+    /// Matches are searched for at each char boundary in turn, and searching resumes at the end of each match.
+    /// Unmatched input is copied through unchanged.
+    ///
+    /// See also [`Lex::replace_all()`] and the [`scan`](crate::scan) module.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, uint, Lex, Parse};
+    ///
+    /// let fahrenheit = uint::<u32>().then_skip(char('F'));
+    ///
+    /// let output = fahrenheit.rewrite("It was 212F at noon and 32F at night", |f| {
+    ///     format!("{}C", (f - 32) * 5 / 9)
+    /// });
+    ///
+    /// assert_eq!(output, "It was 100C at noon and 0C at night");
+    /// ```
+    fn rewrite<F, R>(&self, input: &str, f: F) -> String
+    where
+        Self: Sized,
+        F: FnMut(Self::Output) -> R,
+        R: AsRef<str>,
+    {
+        replace(input, |input| self.parse(input), f)
+    }
 }
 
 /// Maps the output of a parser to a different output
+#[derive(Clone)]
 pub struct Mapped<P, F> {
     f: F,
     parser: P,
@@ -412,6 +1040,20 @@ where
         let mapped = (self.f)(output);
         Ok((mapped, remaining))
     }
+
+    fn describe(&self) -> Grammar {
+        self.parser.describe()
+    }
+}
+
+impl<P, F, O> fmt::Debug for Mapped<P, F>
+where
+    P: Parse + fmt::Debug,
+    F: Fn(<P as Parse>::Output) -> O,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Mapped<{:?} -> {}>", self.parser, type_name::<O>())
+    }
 }
 
 /// Functions that take &str and return `Result<(O, &str), parsely::Error>` impl Parse and can be used with Parsely combinators.
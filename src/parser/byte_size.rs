@@ -0,0 +1,281 @@
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine,
+    error::{failed_conversion, no_match, overflow},
+    grammar::Grammar,
+    unparse::Unparse,
+    Error, Parse, ParseResult,
+};
+
+/// The units recognised by [`byte_size()`], longest first so that `KiB` is tried before `B`.
+const UNITS: &[(&str, u64)] = &[
+    ("KiB", 1 << 10),
+    ("MiB", 1 << 20),
+    ("GiB", 1 << 30),
+    ("TiB", 1 << 40),
+    ("PiB", 1 << 50),
+    ("EiB", 1 << 60),
+    ("kB", 1_000),
+    ("MB", 1_000_000),
+    ("GB", 1_000_000_000),
+    ("TB", 1_000_000_000_000),
+    ("PB", 1_000_000_000_000_000),
+    ("EB", 1_000_000_000_000_000_000),
+    ("B", 1),
+];
+
+/// This parser is returned by [`byte_size()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct ByteSize;
+
+impl Parse for ByteSize {
+    type Output = u64;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let digits = |input: &str| input.bytes().take_while(u8::is_ascii_digit).count();
+
+        let whole = digits(input);
+        if whole == 0 {
+            examine(1)?;
+            return Err(no_match(input));
+        }
+
+        // a '.' is only part of the number when digits follow it
+        let mut end = whole;
+        let mut decimals = "";
+        if let Some(after) = input[whole..].strip_prefix('.') {
+            let len = digits(after);
+            if len > 0 {
+                decimals = &after[..len];
+                end = whole + 1 + len;
+            }
+        }
+
+        let after = &input[end..];
+        let unit_text = after.trim_start_matches([' ', '\t']);
+
+        let unit = UNITS.iter().find_map(|&(unit, size)| {
+            let rest = unit_text.get(..unit.len())?;
+            let followed_by_letter =
+                unit_text[unit.len()..].starts_with(|c: char| c.is_alphabetic());
+
+            // a lower case 'b' means bits, so only the prefix can be any case
+            let (prefix, b) = rest.split_at(unit.len() - 1);
+            let matches = prefix.eq_ignore_ascii_case(&unit[..unit.len() - 1]) && b == "B";
+
+            (matches && !followed_by_letter).then_some((size, &unit_text[unit.len()..]))
+        });
+        let (size, remaining) = unit.unwrap_or((1, after));
+
+        // the char after the size was looked at too
+        examine(input.len() - remaining.len() + 1)?;
+        let matched = &input[..input.len() - remaining.len()];
+
+        let whole = input[..whole]
+            .parse::<u128>()
+            .map_err(|_| overflow(matched))?;
+
+        // work in units of 10^-decimals, so that 1.5 KiB is exactly 1536 bytes
+        let scale = u32::try_from(decimals.len())
+            .ok()
+            .and_then(|len| 10u128.checked_pow(len))
+            .ok_or_else(|| overflow(matched))?;
+        let fraction = if decimals.is_empty() {
+            0
+        } else {
+            decimals.parse::<u128>().map_err(|_| overflow(matched))?
+        };
+
+        let scaled = whole
+            .checked_mul(scale)
+            .and_then(|n| n.checked_add(fraction))
+            .and_then(|n| n.checked_mul(u128::from(size)))
+            .ok_or_else(|| overflow(matched))?;
+
+        if scaled % scale != 0 {
+            return Err(failed_conversion(matched, "not a whole number of bytes"));
+        }
+
+        let bytes = u64::try_from(scaled / scale).map_err(|_| overflow(matched))?;
+
+        Ok((bytes, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        let digits = || Grammar::repeat(1, None, Grammar::Class("digit".to_string()));
+        let units = UNITS
+            .iter()
+            .map(|(unit, _)| match unit.strip_suffix('B') {
+                Some("") | None => Grammar::Token(unit.to_string()),
+                Some(prefix) => Grammar::Sequence(vec![
+                    Grammar::TokenAnyCase(prefix.to_string()),
+                    Grammar::Token("B".to_string()),
+                ]),
+            })
+            .collect();
+
+        Grammar::Sequence(vec![
+            digits(),
+            Grammar::Optional(Box::new(Grammar::Sequence(vec![
+                Grammar::Token(".".to_string()),
+                digits(),
+            ]))),
+            Grammar::Optional(Box::new(Grammar::Sequence(vec![
+                Grammar::repeat(0, None, Grammar::Class("ws".to_string())),
+                Grammar::Choice(units),
+            ]))),
+        ])
+    }
+}
+
+impl Unparse for ByteSize {
+    /// Writes the size in the largest unit that it is a whole number of, such as `10 MiB` or `1500 B`.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let (unit, size) = UNITS
+            .iter()
+            .filter(|&&(_, size)| *value != 0 && value % size == 0)
+            .max_by_key(|&&(_, size)| size)
+            .unwrap_or(&("B", 1));
+
+        output.push_str(&format!("{} {unit}", value / size));
+        Ok(())
+    }
+}
+
+/// Parses a size in bytes such as `10 MiB`, `2GB` or `512`, outputting the number of bytes.
+///
+/// The number can have decimals, such as `1.5 GiB`, as long as the size is a whole number of bytes.
+/// There can be spaces or tabs between the number and its unit, and a number without a unit is a number of bytes.
+///
+/// The units are either decimal or binary, and their prefixes are matched case insensitively.
+/// The `B` must be upper case though, since a lower case `b` means bits: `1 kb` is a kilobit, so it is parsed as just `1`.
+///
+/// | decimal | bytes | binary | bytes |
+/// |---------|-------|--------|-------|
+/// | `B`     | 1     |        |       |
+/// | `kB`    | 1000  | `KiB`  | 1024  |
+/// | `MB`    | 1000² | `MiB`  | 1024² |
+/// | `GB`    | 1000³ | `GiB`  | 1024³ |
+/// | `TB`    | 1000⁴ | `TiB`  | 1024⁴ |
+/// | `PB`    | 1000⁵ | `PiB`  | 1024⁵ |
+/// | `EB`    | 1000⁶ | `EiB`  | 1024⁶ |
+///
+/// A unit followed by more letters, such as the `MB` in `MBps`, isn't taken as a unit.
+///
+/// A size of more than [`u64::MAX`] bytes fails with [`Error::Overflow`],
+/// and a size that isn't a whole number of bytes, such as `0.1 B`, fails with [`Error::FailedConversion`].
+///
+/// Unparsing writes the size in the largest unit that it is a whole number of.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{byte_size, Parse, Unparse};
+///
+/// assert_eq!(byte_size().parse("10 MiB")?, (10 * 1024 * 1024, ""));
+/// assert_eq!(byte_size().parse("2GB")?, (2_000_000_000, ""));
+/// assert_eq!(byte_size().parse("1.5 kiB")?, (1536, ""));
+/// assert_eq!(byte_size().parse("8 Mb")?, (8, " Mb"));
+/// assert_eq!(byte_size().parse("512 files")?, (512, " files"));
+/// assert!(byte_size().parse("20 EB").is_err());
+///
+/// assert_eq!(byte_size().unparse(&(4 * 1024 * 1024))?, "4 MiB");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn byte_size() -> ByteSize {
+    ByteSize
+}
+
+impl fmt::Debug for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ByteSize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "sizes",
+            byte_size(),
+            &[
+                ("0", Some(0), ""),
+                ("512", Some(512), ""),
+                ("512B", Some(512), ""),
+                ("1 kB", Some(1_000), ""),
+                ("1KB", Some(1_000), ""),
+                ("1 KiB", Some(1_024), ""),
+                ("3\tMB", Some(3_000_000), ""),
+                ("10 MiB", Some(10 << 20), ""),
+                ("2gB", Some(2_000_000_000), ""),
+                ("1 KIB", Some(1_024), ""),
+                ("1 b", Some(1), " b"),
+                ("1kb", Some(1), "kb"),
+                ("1 Kib", Some(1), " Kib"),
+                ("1 TiB", Some(1 << 40), ""),
+                ("1.5 GiB", Some(3 << 29), ""),
+                ("0.5 kB", Some(500), ""),
+                ("1.0", Some(1), ""),
+                ("15 EiB", Some(15 << 60), ""),
+                ("18446744073709551615", Some(u64::MAX), ""),
+                ("10 MBps", Some(10), " MBps"),
+                ("10 bananas", Some(10), " bananas"),
+                ("10 MB/s", Some(10_000_000), "/s"),
+                ("10 MiB.", Some(10 << 20), "."),
+                ("1. MB", Some(1), ". MB"),
+                ("", None, ""),
+                ("MB", None, ""),
+                ("-1 MB", None, ""),
+                (".5 MB", None, ""),
+            ],
+        );
+
+        test_parser_errors(
+            "invalid sizes",
+            byte_size(),
+            &[
+                ("16 EiB", overflow("16 EiB")),
+                ("18446744073709551616", overflow("18446744073709551616")),
+                (
+                    "0.1 B",
+                    failed_conversion("0.1 B", "not a whole number of bytes"),
+                ),
+                (
+                    "1.5",
+                    failed_conversion("1.5", "not a whole number of bytes"),
+                ),
+                (
+                    "1.5 kb",
+                    failed_conversion("1.5", "not a whole number of bytes"),
+                ),
+                (
+                    "0.0001 kB",
+                    failed_conversion("0.0001 kB", "not a whole number of bytes"),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        for value in [0, 1, 1500, 1024, 10 << 20, 3_000_000, u64::MAX, 1 << 60] {
+            let text = byte_size().unparse(&value)?;
+            assert_eq!(byte_size().parse_all(&text)?, value, "{text}");
+        }
+
+        assert_eq!(byte_size().unparse(&0)?, "0 B");
+        assert_eq!(byte_size().unparse(&1500)?, "1500 B");
+        assert_eq!(byte_size().unparse(&2_000)?, "2 kB");
+        assert_eq!(byte_size().unparse(&2_048)?, "2 KiB");
+        assert_eq!(byte_size().unparse(&(5 << 30))?, "5 GiB");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine,
+    error::{failed_conversion, no_match},
+    grammar::Grammar,
+    unparse::Unparse,
+    Error, Parse, ParseResult,
+};
+
+/// The named entities recognised by [`entity()`], the five that XML defines followed by common ones from HTML.
+const NAMED: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{a0}'),
+    ("copy", '©'),
+    ("reg", '®'),
+    ("trade", '™'),
+    ("deg", '°'),
+    ("plusmn", '±'),
+    ("times", '×'),
+    ("divide", '÷'),
+    ("micro", 'µ'),
+    ("para", '¶'),
+    ("sect", '§'),
+    ("middot", '·'),
+    ("bull", '•'),
+    ("hellip", '…'),
+    ("ndash", '–'),
+    ("mdash", '—'),
+    ("lsquo", '‘'),
+    ("rsquo", '’'),
+    ("ldquo", '“'),
+    ("rdquo", '”'),
+    ("laquo", '«'),
+    ("raquo", '»'),
+    ("cent", '¢'),
+    ("pound", '£'),
+    ("euro", '€'),
+    ("yen", '¥'),
+];
+
+/// How far to look for the `;` that ends an entity, which is plenty for any name or number of a valid char.
+const MAX_LEN: usize = 32;
+
+/// This parser is returned by [`entity()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Entity;
+
+impl Parse for Entity {
+    type Output = char;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let Some(rest) = input.strip_prefix('&') else {
+            examine(1)?;
+            return Err(no_match(input));
+        };
+
+        // only look a little way ahead, so a lone '&' doesn't search the whole input
+        let search = rest
+            .char_indices()
+            .take(MAX_LEN)
+            .find(|&(_, c)| c == ';')
+            .map(|(i, _)| i);
+        examine(1 + search.map_or(rest.len().min(MAX_LEN), |i| i + 1))?;
+
+        let Some(end) = search else {
+            return Err(no_match(input));
+        };
+
+        let name = &rest[..end];
+        let remaining = &rest[end + 1..];
+
+        let code_point = match name.strip_prefix('#') {
+            Some(number) => match number.strip_prefix(['x', 'X']) {
+                Some(hex) => parse_code_point(hex, 16),
+                None => parse_code_point(number, 10),
+            },
+            None => {
+                return NAMED
+                    .iter()
+                    .find(|(named, _)| *named == name)
+                    .map(|&(_, c)| (c, remaining))
+                    .ok_or_else(|| no_match(input));
+            }
+        };
+
+        match code_point {
+            // NUL and surrogates aren't allowed, even written as a number
+            Some(Some(c)) if c != '\0' => Ok((c, remaining)),
+            Some(_) => Err(failed_conversion(&input[..end + 2], "not a valid char")),
+            None => Err(no_match(input)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Sequence(vec![
+            Grammar::Token("&".to_string()),
+            Grammar::Special("entity name or number".to_string()),
+            Grammar::Token(";".to_string()),
+        ])
+    }
+}
+
+/// Returns `None` if `digits` aren't digits, and `Some(None)` if they are but aren't a valid char.
+fn parse_code_point(digits: &str, radix: u32) -> Option<Option<char>> {
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+
+    Some(
+        u32::from_str_radix(digits, radix)
+            .ok()
+            .and_then(char::from_u32),
+    )
+}
+
+impl Unparse for Entity {
+    /// Writes `&`, `<`, `>`, `"` and `'` as the entities XML defines for them, and any other char as a decimal entity.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        match NAMED.iter().take(5).find(|&&(_, c)| c == *value) {
+            Some((name, _)) => {
+                output.push('&');
+                output.push_str(name);
+                output.push(';');
+            }
+            None => output.push_str(&format!("&#{};", u32::from(*value))),
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses an HTML or XML character entity such as `&amp;`, `&#38;` or `&#x26;`, outputting the char it stands for.
+///
+/// Numeric entities can be written in decimal (`&#8364;`) or hex (`&#x20AC;` or `&#X20ac;`).
+/// Named entities must be one of the five that XML defines (`&amp;`, `&lt;`, `&gt;`, `&quot;` and `&apos;`),
+/// or one of these common ones from HTML, and are case sensitive:
+///
+/// `&nbsp;` `&copy;` `&reg;` `&trade;` `&deg;` `&plusmn;` `&times;` `&divide;` `&micro;` `&para;` `&sect;` `&middot;` `&bull;` `&hellip;`
+/// `&ndash;` `&mdash;` `&lsquo;` `&rsquo;` `&ldquo;` `&rdquo;` `&laquo;` `&raquo;` `&cent;` `&pound;` `&euro;` `&yen;`
+///
+/// Anything else starting with `&`, including an entity without its `;`, doesn't match, so it can be taken literally instead.
+/// A numeric entity for a number that isn't a valid char, such as `&#0;` or `&#xD800;`, fails with [`Error::FailedConversion`].
+///
+/// Unparsing writes the XML entities for `&`, `<`, `>`, `"` and `'` and decimal entities for everything else.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{entity, Parse};
+///
+/// assert_eq!(entity().parse("&amp;&lt;")?, ('&', "&lt;"));
+/// assert_eq!(entity().parse("&#x1F600;!")?, ('😀', "!"));
+/// assert!(entity().parse("&unknown;").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+///
+/// Unescape text, keeping anything that isn't an entity as it is:
+///
+/// ```
+/// use parsely::{any, entity, Lex, Parse};
+///
+/// let literal = any().map(|c: &str| c.chars().next().unwrap());
+/// let text = entity().or(literal).many(..).collect::<String>();
+///
+/// assert_eq!(text.parse_all("Tom &amp; Jerry &#8212; R&D &copy; 1940")?, "Tom & Jerry — R&D © 1940");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn entity() -> Entity {
+    Entity
+}
+
+impl fmt::Debug for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entity")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "entities",
+            entity(),
+            &[
+                ("&amp;", Some('&'), ""),
+                ("&apos;s", Some('\''), "s"),
+                ("&hellip;", Some('…'), ""),
+                ("&#38;", Some('&'), ""),
+                ("&#x26;", Some('&'), ""),
+                ("&#X2a;", Some('*'), ""),
+                ("&#0065;", Some('A'), ""),
+                ("&#x10FFFF;", Some('\u{10FFFF}'), ""),
+                ("&AMP;", None, ""),
+                ("&amp", None, ""),
+                ("& amp;", None, ""),
+                ("&;", None, ""),
+                ("&#;", None, ""),
+                ("&#x;", None, ""),
+                ("&#12a;", None, ""),
+                ("&#-1;", None, ""),
+                ("&averyveryverylongname;", None, ""),
+                ("amp;", None, ""),
+            ],
+        );
+
+        test_parser_errors(
+            "invalid chars",
+            entity(),
+            &[
+                ("&#0;", failed_conversion("&#0;", "not a valid char")),
+                (
+                    "&#xD800;",
+                    failed_conversion("&#xD800;", "not a valid char"),
+                ),
+                (
+                    "&#x110000;",
+                    failed_conversion("&#x110000;", "not a valid char"),
+                ),
+                (
+                    "&#99999999999;",
+                    failed_conversion("&#99999999999;", "not a valid char"),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        for c in ['&', '<', '>', '"', '\'', 'a', '€', '\u{10FFFF}'] {
+            let text = entity().unparse(&c)?;
+            assert_eq!(entity().parse_all(&text)?, c);
+        }
+
+        assert_eq!(entity().unparse(&'<')?, "&lt;");
+        assert_eq!(entity().unparse(&'a')?, "&#97;");
+
+        Ok(())
+    }
+}
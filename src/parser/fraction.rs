@@ -0,0 +1,294 @@
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine,
+    error::{failed_conversion, no_match, overflow},
+    grammar::Grammar,
+    unparse::Unparse,
+    Error, Parse, ParseResult,
+};
+
+/// This parser is returned by [`fraction()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Fraction;
+
+/// Returns how many ascii digits `input` starts with.
+fn digits(input: &str) -> usize {
+    input.bytes().take_while(u8::is_ascii_digit).count()
+}
+
+/// Returns the `numerator/denominator` at the start of `input` and the length of it, if there is one.
+fn simple(input: &str) -> Option<(&str, &str, usize)> {
+    let numerator = digits(input);
+    let after = input[numerator..].strip_prefix('/')?;
+    let denominator = digits(after);
+
+    if numerator == 0 || denominator == 0 {
+        return None;
+    }
+
+    Some((
+        &input[..numerator],
+        &after[..denominator],
+        numerator + 1 + denominator,
+    ))
+}
+
+impl Parse for Fraction {
+    type Output = (i64, u64);
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let sign = usize::from(input.starts_with(['-', '+']));
+        let negative = input.starts_with('-');
+
+        let whole_len = digits(&input[sign..]);
+        if whole_len == 0 {
+            examine(sign + 1)?;
+            return Err(no_match(input));
+        }
+        let whole = &input[sign..sign + whole_len];
+        let after_whole = &input[sign + whole_len..];
+
+        let (whole, numerator, denominator, len) =
+            if let Some(after) = after_whole.strip_prefix('/') {
+                let Some((numerator, denominator, len)) = simple(&input[sign..]) else {
+                    examine(input.len() - after.len() + 1)?;
+                    return Err(Error::Expected("a denominator after '/'"));
+                };
+                ("0", numerator, denominator, sign + len)
+            } else {
+                let fraction = after_whole.trim_start_matches([' ', '\t']);
+                let gap = after_whole.len() - fraction.len();
+
+                match simple(fraction) {
+                    Some((numerator, denominator, len)) if gap > 0 => {
+                        (whole, numerator, denominator, sign + whole_len + gap + len)
+                    }
+                    _ => (whole, "0", "1", sign + whole_len),
+                }
+            };
+
+        // the char after the fraction was looked at too
+        examine(len + 1)?;
+        let (matched, remaining) = input.split_at(len);
+
+        let parse = |digits: &str| digits.parse::<u64>().map_err(|_| overflow(digits));
+        let (whole, numerator, denominator) =
+            (parse(whole)?, parse(numerator)?, parse(denominator)?);
+
+        if denominator == 0 {
+            return Err(failed_conversion(matched, "the denominator is zero"));
+        }
+        if whole > 0 && numerator >= denominator {
+            return Err(failed_conversion(
+                matched,
+                "the fraction of a mixed number must be less than 1",
+            ));
+        }
+
+        let numerator = whole
+            .checked_mul(denominator)
+            .and_then(|n| n.checked_add(numerator))
+            .map(i128::from)
+            .map(|n| if negative { -n } else { n })
+            .and_then(|n| i64::try_from(n).ok())
+            .ok_or_else(|| overflow(matched))?;
+
+        Ok(((numerator, denominator), remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        let digits = || Grammar::repeat(1, None, Grammar::Class("digit".to_string()));
+        let fraction =
+            || Grammar::Sequence(vec![digits(), Grammar::Token("/".to_string()), digits()]);
+
+        Grammar::Sequence(vec![
+            Grammar::Optional(Box::new(Grammar::Choice(vec![
+                Grammar::Token("-".to_string()),
+                Grammar::Token("+".to_string()),
+            ]))),
+            Grammar::Choice(vec![
+                fraction(),
+                Grammar::Sequence(vec![
+                    digits(),
+                    Grammar::Optional(Box::new(Grammar::Sequence(vec![
+                        Grammar::repeat(1, None, Grammar::Class("ws".to_string())),
+                        fraction(),
+                    ]))),
+                ]),
+            ]),
+        ])
+    }
+}
+
+impl Unparse for Fraction {
+    /// Writes a whole number such as `2` when the denominator is 1, a simple fraction such as `-3/4` when it is less than 1,
+    /// and a mixed number such as `1 1/2` otherwise. The fraction isn't reduced.
+    ///
+    /// A denominator of 0 fails with a custom error.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let &(numerator, denominator) = value;
+
+        if denominator == 0 {
+            return Err(Error::custom(format!(
+                "{numerator}/0 can't be written as a fraction"
+            )));
+        }
+
+        if numerator < 0 {
+            output.push('-');
+        }
+
+        let size = numerator.unsigned_abs();
+        let (whole, rest) = (size / denominator, size % denominator);
+
+        if denominator == 1 || rest == 0 {
+            output.push_str(&whole.to_string());
+        } else if whole == 0 {
+            output.push_str(&format!("{rest}/{denominator}"));
+        } else {
+            output.push_str(&format!("{whole} {rest}/{denominator}"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a simple fraction such as `3/4`, a mixed number such as `1 1/2`, or a whole number such as `2`, outputting `(numerator, denominator)`.
+///
+/// A mixed number is converted to a single fraction, so `1 1/2` outputs `(3, 2)`, and a whole number has a denominator of 1.
+/// The fraction isn't reduced: `2/4` outputs `(2, 4)`.
+///
+/// The number can start with a `-` or `+`, which applies to the whole of a mixed number, so `-1 1/2` outputs `(-3, 2)`.
+/// The whole number and fraction of a mixed number are separated by one or more spaces or tabs, but there can't be whitespace around the `/`.
+/// If whitespace after a whole number isn't followed by a fraction, only the whole number is parsed.
+///
+/// A `/` that isn't followed by a digit fails with [`Error::Expected`].
+/// A denominator of 0, or a mixed number whose fraction isn't less than 1 such as `1 3/2`, fails with [`Error::FailedConversion`].
+/// A fraction too large for the output fails with [`Error::Overflow`].
+///
+/// Unparsing writes fractions greater than 1 as mixed numbers.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{fraction, Parse, Unparse};
+///
+/// assert_eq!(fraction().parse("3/4")?, ((3, 4), ""));
+/// assert_eq!(fraction().parse("1 1/2 cups")?, ((3, 2), " cups"));
+/// assert_eq!(fraction().parse("-2 1/3")?, ((-7, 3), ""));
+/// assert_eq!(fraction().parse("2 eggs")?, ((2, 1), " eggs"));
+/// assert!(fraction().parse("1/0").is_err());
+///
+/// assert_eq!(fraction().unparse(&(7, 4))?, "1 3/4");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn fraction() -> Fraction {
+    Fraction
+}
+
+impl fmt::Debug for Fraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fraction")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "fractions",
+            fraction(),
+            &[
+                ("3/4", Some((3, 4)), ""),
+                ("2/4", Some((2, 4)), ""),
+                ("0/5", Some((0, 5)), ""),
+                ("-3/4", Some((-3, 4)), ""),
+                ("+3/4", Some((3, 4)), ""),
+                ("5/4", Some((5, 4)), ""),
+                ("1 1/2", Some((3, 2)), ""),
+                ("1\t \t1/2", Some((3, 2)), ""),
+                ("-1 1/2", Some((-3, 2)), ""),
+                ("0 1/2", Some((1, 2)), ""),
+                ("7", Some((7, 1)), ""),
+                ("-0", Some((0, 1)), ""),
+                ("1 cup", Some((1, 1)), " cup"),
+                ("1 -1/2", Some((1, 1)), " -1/2"),
+                ("1 1 /2", Some((1, 1)), " 1 /2"),
+                ("1\n1/2", Some((1, 1)), "\n1/2"),
+                ("3/4/2024", Some((3, 4)), "/2024"),
+                ("3/4.5", Some((3, 4)), ".5"),
+                ("", None, ""),
+                ("-", None, ""),
+                ("/4", None, ""),
+                (" 3/4", None, ""),
+            ],
+        );
+
+        test_parser_errors(
+            "invalid fractions",
+            fraction(),
+            &[
+                ("3/", Error::Expected("a denominator after '/'")),
+                ("3/ 4", Error::Expected("a denominator after '/'")),
+                ("3/-4", Error::Expected("a denominator after '/'")),
+                ("3/0", failed_conversion("3/0", "the denominator is zero")),
+                (
+                    "1 1/0",
+                    failed_conversion("1 1/0", "the denominator is zero"),
+                ),
+                (
+                    "1 3/2",
+                    failed_conversion(
+                        "1 3/2",
+                        "the fraction of a mixed number must be less than 1",
+                    ),
+                ),
+                ("99999999999999999999/2", overflow("99999999999999999999")),
+                ("9223372036854775808", overflow("9223372036854775808")),
+                (
+                    "4611686018427387904 1/2",
+                    overflow("4611686018427387904 1/2"),
+                ),
+            ],
+        );
+
+        assert_eq!(
+            fraction().parse_all("-9223372036854775808"),
+            Ok((i64::MIN, 1))
+        );
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        for value in [
+            (3, 4),
+            (-3, 4),
+            (7, 4),
+            (-7, 4),
+            (2, 1),
+            (0, 1),
+            (6, 3),
+            (0, 5),
+        ] {
+            let text = fraction().unparse(&value)?;
+            let (numerator, denominator) = fraction().parse_all(&text)?;
+
+            // whole numbers are written without their denominator
+            assert_eq!(numerator * value.1 as i64, value.0 * denominator as i64);
+        }
+
+        assert_eq!(fraction().unparse(&(-7, 4))?, "-1 3/4");
+        assert_eq!(fraction().unparse(&(6, 3))?, "2");
+        assert_eq!(fraction().unparse(&(3, 1))?, "3");
+        assert!(fraction().unparse(&(1, 0)).is_err());
+
+        Ok(())
+    }
+}
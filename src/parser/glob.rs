@@ -0,0 +1,522 @@
+use std::{fmt, ops::RangeInclusive};
+
+use crate::{
+    combinator::budget::examine,
+    error::{failed_conversion, no_match},
+    grammar::Grammar,
+    unparse::Unparse,
+    Error, Parse, ParseResult,
+};
+
+/// How deeply `{a,b}` alternatives can be nested inside each other.
+const MAX_NESTING: usize = 32;
+
+/// The chars that are escaped with a `\` when unparsing a literal.
+const SPECIAL: &[char] = &['*', '?', '[', ']', '{', '}', ',', '\\'];
+
+/// This parser is returned by [`glob()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Glob;
+
+/// A part of a glob pattern parsed by [`glob()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GlobPart {
+    /// Text that must match exactly, with any escapes removed.
+    Literal(String),
+
+    /// `?`, matching any one char.
+    Any,
+
+    /// `*`, matching any number of chars within a path segment.
+    Star,
+
+    /// `**`, matching any number of chars including path separators.
+    DoubleStar,
+
+    /// A class such as `[a-z_]`, matching one char in (or with `negated`, not in) any of the ranges.
+    ///
+    /// A single char such as `_` is a range from itself to itself.
+    Class {
+        /// Whether the class started with `!` or `^`.
+        negated: bool,
+
+        /// The chars in the class.
+        ranges: Vec<RangeInclusive<char>>,
+    },
+
+    /// Alternatives such as `{*.rs,Cargo.toml}`, matching any one of the patterns.
+    Alternatives(Vec<Vec<GlobPart>>),
+}
+
+impl Parse for Glob {
+    type Output = Vec<GlobPart>;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (parts, remaining) = sequence(input, 0)?;
+        examine(input.len() - remaining.len() + 1)?;
+
+        if parts.is_empty() {
+            return Err(no_match(input));
+        }
+
+        Ok((parts, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special("glob".to_string())
+    }
+}
+
+/// Parses glob parts until whitespace or the end of the input, or the `,` or `}` that ends an alternative when `depth` is above 0.
+fn sequence(input: &str, depth: usize) -> ParseResult<'_, Vec<GlobPart>> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut rest = input;
+
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+
+        let part = match c {
+            c if c.is_whitespace() || c.is_control() => break,
+            ',' | '}' if depth > 0 => break,
+            '\\' => {
+                let escaped = after
+                    .chars()
+                    .next()
+                    .ok_or_else(|| Error::Expected("a char to escape after '\\'").fatal())?;
+                literal.push(escaped);
+                rest = &after[escaped.len_utf8()..];
+                continue;
+            }
+            '?' => {
+                rest = after;
+                GlobPart::Any
+            }
+            '*' => {
+                rest = after.trim_start_matches('*');
+                if rest.len() < after.len() {
+                    GlobPart::DoubleStar
+                } else {
+                    GlobPart::Star
+                }
+            }
+            '[' => {
+                let (class, after) = class(rest)?;
+                rest = after;
+                class
+            }
+            '{' => {
+                let (alternatives, after) = alternatives(rest, depth + 1)?;
+                rest = after;
+                alternatives
+            }
+            c => {
+                literal.push(c);
+                rest = after;
+                continue;
+            }
+        };
+
+        if !literal.is_empty() {
+            parts.push(GlobPart::Literal(std::mem::take(&mut literal)));
+        }
+        parts.push(part);
+    }
+
+    if !literal.is_empty() {
+        parts.push(GlobPart::Literal(literal));
+    }
+
+    Ok((parts, rest))
+}
+
+/// Parses a class that starts with the `[` at the beginning of `input`.
+fn class(input: &str) -> ParseResult<'_, GlobPart> {
+    let mut rest = &input[1..];
+
+    let negated = rest.starts_with(['!', '^']);
+    if negated {
+        rest = &rest[1..];
+    }
+
+    // reads one char of the class, which may be escaped
+    let next = |rest: &str| -> Result<(char, usize), Error> {
+        let mut chars = rest.chars();
+        match chars.next() {
+            Some('\\') => match chars.next() {
+                Some(c) => Ok((c, 1 + c.len_utf8())),
+                None => Err(Error::Expected("a char to escape after '\\'").fatal()),
+            },
+            Some(c) => Ok((c, c.len_utf8())),
+            None => Err(Error::Expected("']' to close the class").fatal()),
+        }
+    };
+
+    let mut ranges = Vec::new();
+
+    loop {
+        // a ']' straight after the '[' is part of the class
+        if !ranges.is_empty() && rest.starts_with(']') {
+            return Ok((GlobPart::Class { negated, ranges }, &rest[1..]));
+        }
+
+        let from = input.len() - rest.len();
+        let (start, len) = next(rest)?;
+        rest = &rest[len..];
+
+        // a '-' just before the ']' is part of the class
+        let end = match rest.strip_prefix('-') {
+            Some(after) if !after.starts_with(']') && !after.is_empty() => {
+                let (end, len) = next(after)?;
+                rest = &after[len..];
+
+                if end < start {
+                    let range = &input[from..input.len() - rest.len()];
+                    return Err(failed_conversion(range, "the range is backwards"));
+                }
+
+                end
+            }
+            _ => start,
+        };
+
+        ranges.push(start..=end);
+    }
+}
+
+/// Parses alternatives that start with the `{` at the beginning of `input`.
+fn alternatives(input: &str, depth: usize) -> ParseResult<'_, GlobPart> {
+    if depth > MAX_NESTING {
+        return Err(Error::DepthExceeded(MAX_NESTING).fatal());
+    }
+
+    let mut alternatives = Vec::new();
+    let mut rest = &input[1..];
+
+    loop {
+        let (alternative, after) = sequence(rest, depth)?;
+        alternatives.push(alternative);
+
+        match after.chars().next() {
+            Some(',') => rest = &after[1..],
+            Some('}') => return Ok((GlobPart::Alternatives(alternatives), &after[1..])),
+            _ => return Err(Error::Expected("'}' to close the alternatives").fatal()),
+        }
+    }
+}
+
+impl Unparse for Glob {
+    /// Writes the pattern, escaping special chars and whitespace in literals with a `\`.
+    ///
+    /// Patterns that would read back differently, such as a [`GlobPart::Star`] next to another star, an empty class or no alternatives, fail with a custom error.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        if value.is_empty() {
+            return Err(Error::NoMatch);
+        }
+
+        write_parts(value, output)
+    }
+}
+
+fn write_parts(parts: &[GlobPart], output: &mut String) -> Result<(), Error> {
+    let mut after_star = false;
+
+    for part in parts {
+        let is_star = matches!(part, GlobPart::Star | GlobPart::DoubleStar);
+        if is_star && after_star {
+            return Err(Error::custom(
+                "stars next to each other can't be written as separate glob parts",
+            ));
+        }
+        after_star = is_star;
+
+        match part {
+            GlobPart::Literal(literal) => {
+                for c in literal.chars() {
+                    if SPECIAL.contains(&c) || c.is_whitespace() || c.is_control() {
+                        output.push('\\');
+                    }
+                    output.push(c);
+                }
+            }
+            GlobPart::Any => output.push('?'),
+            GlobPart::Star => output.push('*'),
+            GlobPart::DoubleStar => output.push_str("**"),
+            GlobPart::Class { negated, ranges } => {
+                if ranges.is_empty() {
+                    return Err(Error::custom("an empty class can't be written as a glob"));
+                }
+
+                output.push('[');
+                if *negated {
+                    output.push('!');
+                }
+                for range in ranges {
+                    if range.is_empty() {
+                        return Err(Error::custom(format!(
+                            "the backwards range {range:?} can't be written as a glob"
+                        )));
+                    }
+
+                    write_class_char(*range.start(), output);
+                    if range.start() != range.end() {
+                        output.push('-');
+                        write_class_char(*range.end(), output);
+                    }
+                }
+                output.push(']');
+            }
+            GlobPart::Alternatives(alternatives) => {
+                if alternatives.is_empty() {
+                    return Err(Error::custom("no alternatives can't be written as a glob"));
+                }
+
+                output.push('{');
+                for (i, alternative) in alternatives.iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+                    write_parts(alternative, output)?;
+                }
+                output.push('}');
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_class_char(c: char, output: &mut String) {
+    if matches!(c, ']' | '\\' | '-' | '!' | '^') {
+        output.push('\\');
+    }
+    output.push(c);
+}
+
+/// Parses a glob pattern such as `src/**/*.{rs,toml}`, outputting its [`GlobPart`]s.
+///
+/// The syntax is:
+///
+/// * `?` matches any one char
+/// * `*` matches any number of chars, and `**` any number of chars including path separators
+/// * `[a-z_]` matches one char in the class, or not in the class if it starts with `!` or `^`
+/// * `{a,b}` matches any one of the comma separated patterns, which can be nested
+/// * `\` escapes the char after it, so that it's taken literally
+///
+/// Inside a class, a `]` straight after the `[` (or the `!` or `^`), or a `-` just before the closing `]`, is part of the class.
+/// A `,` or `}` outside of `{}` is taken literally.
+///
+/// The pattern ends at unescaped whitespace or a control char, or at the end of the input.
+/// A class or alternatives that aren't closed, or a `\` at the end of the input, are [fatal](crate::Error::fatal()) errors.
+/// A backwards range such as `[z-a]` fails with [`Error::FailedConversion`].
+///
+/// Unparsing writes the pattern back, escaping special chars in literals.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{glob, GlobPart, Parse, Unparse};
+///
+/// let (pattern, remaining) = glob().parse("src/**/*.{rs,toml} tests")?;
+/// assert_eq!(
+///     pattern,
+///     [
+///         GlobPart::Literal("src/".to_string()),
+///         GlobPart::DoubleStar,
+///         GlobPart::Literal("/".to_string()),
+///         GlobPart::Star,
+///         GlobPart::Literal(".".to_string()),
+///         GlobPart::Alternatives(vec![
+///             vec![GlobPart::Literal("rs".to_string())],
+///             vec![GlobPart::Literal("toml".to_string())],
+///         ]),
+///     ]
+/// );
+/// assert_eq!(remaining, " tests");
+///
+/// assert_eq!(
+///     glob().parse_all("[!a-z_]?")?,
+///     [
+///         GlobPart::Class { negated: true, ranges: vec!['a'..='z', '_'..='_'] },
+///         GlobPart::Any,
+///     ]
+/// );
+///
+/// assert_eq!(glob().unparse(&vec![GlobPart::Literal("a*b".to_string()), GlobPart::Star])?, r"a\*b*");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+///
+/// Read a list of globs from a config file:
+///
+/// ```
+/// use parsely::{glob, token, ws, Lex, Parse};
+///
+/// let exclude = token("exclude =").skip_then(glob().pad_left(ws().many(1..)).many(1..));
+///
+/// let patterns = exclude.parse_all("exclude = target/ *.{bak,tmp}")?;
+/// assert_eq!(patterns.len(), 2);
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn glob() -> Glob {
+    Glob
+}
+
+impl fmt::Debug for Glob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Glob")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    fn literal(text: &str) -> GlobPart {
+        GlobPart::Literal(text.to_string())
+    }
+
+    fn class(negated: bool, ranges: &[RangeInclusive<char>]) -> GlobPart {
+        GlobPart::Class {
+            negated,
+            ranges: ranges.to_vec(),
+        }
+    }
+
+    #[test]
+    fn parsing() {
+        use GlobPart::*;
+
+        test_parser_batch(
+            "globs",
+            glob(),
+            &[
+                ("abc", Some(vec![literal("abc")]), ""),
+                ("*.rs", Some(vec![Star, literal(".rs")]), ""),
+                (
+                    "a/**/b",
+                    Some(vec![literal("a/"), DoubleStar, literal("/b")]),
+                    "",
+                ),
+                ("***", Some(vec![DoubleStar]), ""),
+                ("??x", Some(vec![Any, Any, literal("x")]), ""),
+                (r"a\*\ b c", Some(vec![literal("a* b")]), " c"),
+                ("a,b}", Some(vec![literal("a,b}")]), ""),
+                (
+                    "[abc]",
+                    Some(vec![class(false, &['a'..='a', 'b'..='b', 'c'..='c'])]),
+                    "",
+                ),
+                (
+                    "[^0-9a-f]",
+                    Some(vec![class(true, &['0'..='9', 'a'..='f'])]),
+                    "",
+                ),
+                (
+                    "[]-]",
+                    Some(vec![class(false, &[']'..=']', '-'..='-'])]),
+                    "",
+                ),
+                ("[!]]", Some(vec![class(true, &[']'..=']'])]), ""),
+                (
+                    r"[\]\-x]",
+                    Some(vec![class(false, &[']'..=']', '-'..='-', 'x'..='x'])]),
+                    "",
+                ),
+                ("[ ]", Some(vec![class(false, &[' '..=' '])]), ""),
+                ("{}", Some(vec![Alternatives(vec![vec![]])]), ""),
+                (
+                    "{a,}",
+                    Some(vec![Alternatives(vec![vec![literal("a")], vec![]])]),
+                    "",
+                ),
+                (
+                    "x{a,{b,c}*}y",
+                    Some(vec![
+                        literal("x"),
+                        Alternatives(vec![
+                            vec![literal("a")],
+                            vec![
+                                Alternatives(vec![vec![literal("b")], vec![literal("c")]]),
+                                Star,
+                            ],
+                        ]),
+                        literal("y"),
+                    ]),
+                    "",
+                ),
+                ("", None, ""),
+                (" *", None, ""),
+            ],
+        );
+
+        test_parser_errors(
+            "invalid globs",
+            glob(),
+            &[
+                ("[abc", Error::Expected("']' to close the class").fatal()),
+                ("[]", Error::Expected("']' to close the class").fatal()),
+                (
+                    "{a,b",
+                    Error::Expected("'}' to close the alternatives").fatal(),
+                ),
+                (
+                    "{a b}",
+                    Error::Expected("'}' to close the alternatives").fatal(),
+                ),
+                (
+                    "a\\",
+                    Error::Expected("a char to escape after '\\'").fatal(),
+                ),
+                ("[z-a]", failed_conversion("z-a", "the range is backwards")),
+            ],
+        );
+    }
+
+    #[test]
+    fn nesting() {
+        let nested = format!("{}{}", "{".repeat(MAX_NESTING), "}".repeat(MAX_NESTING));
+        assert!(glob().parse_all(&nested).is_ok());
+
+        let nested = format!(
+            "{}{}",
+            "{".repeat(MAX_NESTING + 1),
+            "}".repeat(MAX_NESTING + 1)
+        );
+        assert_eq!(
+            glob().parse_all(&nested),
+            Err(Error::DepthExceeded(MAX_NESTING).fatal())
+        );
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        for input in [
+            "src/**/*.{rs,toml}",
+            r"a\*\ b",
+            "[!]a-c\\-]?",
+            "{a,{b,},}",
+            r"x\,y\}",
+        ] {
+            let parts = glob().parse_all(input)?;
+            assert_eq!(glob().parse_all(&glob().unparse(&parts)?)?, parts);
+        }
+
+        assert_eq!(
+            glob().unparse(&vec![class(false, &['^'..='^', 'a'..='z'])])?,
+            r"[\^a-z]"
+        );
+        assert!(glob().unparse(&vec![]).is_err());
+        assert!(glob()
+            .unparse(&vec![GlobPart::Star, GlobPart::DoubleStar])
+            .is_err());
+        assert!(glob().unparse(&vec![class(false, &[])]).is_err());
+        assert!(glob()
+            .unparse(&vec![GlobPart::Alternatives(vec![])])
+            .is_err());
+
+        Ok(())
+    }
+}
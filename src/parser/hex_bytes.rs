@@ -0,0 +1,279 @@
+use std::fmt;
+
+use crate::{
+    combinator::{atomic, budget::examine},
+    error::no_match,
+    grammar::Grammar,
+    unparse::Unparse,
+    Error, Parse, ParseResult,
+};
+
+/// This parser is returned by [`hex_bytes()`]. See it's documentation for more details.
+#[derive(Clone, Copy)]
+pub struct HexBytes;
+
+/// This parser is returned by [`hex_array()`]. See it's documentation for more details.
+#[derive(Clone, Copy)]
+pub struct HexArray<const N: usize>;
+
+impl Parse for HexBytes {
+    type Output = Vec<u8>;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (bytes, remaining) = atomic(lex_bytes::<{ usize::MAX }>).parse(input)?;
+
+        if bytes.is_empty() {
+            Err(no_match(input))
+        } else {
+            Ok((bytes, remaining))
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        describe(1, None)
+    }
+}
+
+impl<const N: usize> Parse for HexArray<N> {
+    type Output = [u8; N];
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (bytes, remaining) = atomic(lex_bytes::<N>).parse(input)?;
+
+        match bytes.try_into() {
+            Ok(array) => Ok((array, remaining)),
+            Err(_) => Err(no_match(input)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        describe(N, Some(N))
+    }
+}
+
+impl Unparse for HexBytes {
+    /// Writes each byte as two lowercase hex digits, without separators.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        if value.is_empty() {
+            return Err(Error::NoMatch);
+        }
+
+        unparse_bytes(value, output);
+        Ok(())
+    }
+}
+
+impl<const N: usize> Unparse for HexArray<N> {
+    /// Writes each byte as two lowercase hex digits, without separators.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        unparse_bytes(value, output);
+        Ok(())
+    }
+}
+
+/// Matches up to `MAX` bytes written as pairs of hex digits, all separated by the same separator or none at all.
+///
+/// Whitespace can be a separator, so this is run inside [`atomic()`] to keep [`ws_insensitive()`](crate::combinator::ws_insensitive()) from skipping it.
+fn lex_bytes<const MAX: usize>(input: &str) -> Result<(Vec<u8>, &str), Error> {
+    let mut bytes = Vec::new();
+    let mut remaining = input;
+
+    // decided by the first separator, or lack of one, between two bytes
+    let mut separator: Option<Option<char>> = None;
+
+    while bytes.len() < MAX {
+        let mut after = remaining;
+        let mut this_separator = None;
+
+        if !bytes.is_empty() {
+            let next = remaining.chars().next().filter(|&c| is_separator(c));
+
+            match (separator, next) {
+                (None, Some(c)) | (Some(Some(_)), Some(c)) => {
+                    this_separator = Some(c);
+                    after = &remaining[c.len_utf8()..];
+                }
+                _ => {}
+            }
+
+            if separator.is_some_and(|separator| separator != this_separator) {
+                break;
+            }
+        }
+
+        let Some(byte) = hex_byte(after) else {
+            break;
+        };
+
+        bytes.push(byte);
+        remaining = &after[2..];
+        if bytes.len() > 1 {
+            separator = Some(this_separator);
+        }
+    }
+
+    // the char after the match was looked at too
+    examine(input.len() - remaining.len() + 1)?;
+
+    Ok((bytes, remaining))
+}
+
+fn is_separator(c: char) -> bool {
+    matches!(c, ':' | '-') || c.is_whitespace()
+}
+
+/// Returns the byte written as the first two hex digits of `input`, if it starts with two hex digits.
+fn hex_byte(input: &str) -> Option<u8> {
+    let digits = input.get(..2)?;
+
+    if digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        u8::from_str_radix(digits, 16).ok()
+    } else {
+        None
+    }
+}
+
+fn unparse_bytes(bytes: &[u8], output: &mut String) {
+    for byte in bytes {
+        output.push_str(&format!("{byte:02x}"));
+    }
+}
+
+fn describe(min: usize, max: Option<usize>) -> Grammar {
+    Grammar::repeat(min, max, Grammar::Class("hex_byte".to_string()))
+}
+
+/// Parses one or more bytes written in hex, two digits per byte, outputting them as a `Vec<u8>`.
+///
+/// Upper and lowercase hex digits are both accepted, with no leading `0x`.
+/// The bytes can be separated by `:`, `-` or whitespace, as MAC addresses and hex dumps often are,
+/// but the same separator must be used between every byte: parsing stops where the separator changes.
+///
+/// A trailing odd digit or separator is left in the remaining input.
+/// Inside [`ws_insensitive()`](crate::combinator::ws_insensitive()) whitespace is still only accepted as the separator between every byte, it isn't skipped.
+/// See [`hex_array()`] to parse a fixed number of bytes, such as a digest.
+///
+/// Unparsing writes two lowercase hex digits per byte, without separators.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{hex_bytes, Parse};
+///
+/// assert_eq!(hex_bytes().parse("DEADbeef")?, (vec![0xde, 0xad, 0xbe, 0xef], ""));
+/// assert_eq!(hex_bytes().parse("00:1a:2B:3c")?, (vec![0x00, 0x1a, 0x2b, 0x3c], ""));
+/// assert_eq!(hex_bytes().parse("01 02 03!")?, (vec![1, 2, 3], "!"));
+///
+/// // parsing stops where the separator changes
+/// assert_eq!(hex_bytes().parse("01-02:03")?, (vec![1, 2], ":03"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn hex_bytes() -> HexBytes {
+    HexBytes
+}
+
+/// Parses exactly `N` bytes written in hex, two digits per byte, outputting them as a `[u8; N]`.
+///
+/// This works the same way as [`hex_bytes()`], except that it fails if there are fewer than `N` bytes
+/// and leaves any after the first `N` in the remaining input.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{hex_array, Parse};
+///
+/// let mac = hex_array::<6>();
+///
+/// assert_eq!(mac.parse("00:1A:2b:3C:4d:5E")?, ([0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e], ""));
+/// assert!(mac.parse("00:1A:2b").is_err());
+///
+/// let (digest, _) = hex_array::<16>().parse("d41d8cd98f00b204e9800998ecf8427e")?;
+/// assert_eq!(digest[..2], [0xd4, 0x1d]);
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn hex_array<const N: usize>() -> HexArray<N> {
+    HexArray
+}
+
+impl fmt::Debug for HexBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HexBytes")
+    }
+}
+
+impl<const N: usize> fmt::Debug for HexArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HexArray<{N}>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "hex bytes",
+            hex_bytes(),
+            &[
+                ("", None, ""),
+                ("a", None, "a"),
+                ("ag", None, "ag"),
+                ("0a", Some(vec![0x0a]), ""),
+                ("0aFf1", Some(vec![0x0a, 0xff]), "1"),
+                ("0a:ff:", Some(vec![0x0a, 0xff]), ":"),
+                ("0a-ff-10", Some(vec![0x0a, 0xff, 0x10]), ""),
+                ("0a\tff\t10", Some(vec![0x0a, 0xff, 0x10]), ""),
+                ("0a:ff10", Some(vec![0x0a, 0xff]), "10"),
+                ("0aff:10", Some(vec![0x0a, 0xff]), ":10"),
+                (":0a", None, ":0a"),
+                ("0a::ff", Some(vec![0x0a]), "::ff"),
+            ],
+        );
+
+        test_parser_batch(
+            "hex arrays",
+            hex_array::<2>(),
+            &[
+                ("0a", None, "0a"),
+                ("0aff", Some([0x0a, 0xff]), ""),
+                ("0a ff 10", Some([0x0a, 0xff]), " 10"),
+            ],
+        );
+    }
+
+    #[test]
+    fn whitespace_insensitive() {
+        use crate::combinator::ws_insensitive;
+
+        // whitespace between bytes is a separator, not skipped, so it must be used between every byte
+        let bytes = ws_insensitive(hex_bytes());
+        assert_eq!(
+            bytes.parse("de ad be ef"),
+            Ok((vec![0xde, 0xad, 0xbe, 0xef], ""))
+        );
+        assert_eq!(bytes.parse("de ad:be:ef"), Ok((vec![0xde, 0xad], ":be:ef")));
+        assert_eq!(bytes.parse("de  ad"), Ok((vec![0xde], "ad")));
+        assert_eq!(bytes.parse("d e a d"), Err(Error::NoMatch));
+
+        let array = ws_insensitive(hex_array::<2>());
+        assert_eq!(array.parse("de:ad"), Ok(([0xde, 0xad], "")));
+        assert_eq!(array.parse("de: ad"), Err(Error::NoMatch));
+    }
+
+    #[test]
+    fn unparsing() {
+        assert_eq!(
+            hex_bytes().unparse(&vec![0xde, 0xad, 0x01]),
+            Ok("dead01".to_string())
+        );
+        assert_eq!(hex_bytes().unparse(&vec![]), Err(Error::NoMatch));
+        assert_eq!(hex_array::<2>().unparse(&[0, 255]), Ok("00ff".to_string()));
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+
+use crate::{grammar::Grammar, unparse::Unparse, Error, Lex, Parse, ParseResult};
+
+/// Spaces and tabs around a key value pair are skipped, but not newlines because pairs are often one per line.
+fn skip_blanks(input: &str) -> &str {
+    input.trim_start_matches([' ', '\t'])
+}
+
+/// This parser is returned by [`key_value()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct KeyValue<K, S, V> {
+    key: K,
+    separator: S,
+    value: V,
+}
+
+impl<K, S, V> Parse for KeyValue<K, S, V>
+where
+    K: Parse,
+    S: Lex,
+    V: Parse,
+{
+    type Output = (<K as Parse>::Output, <V as Parse>::Output);
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (key, remaining) = self.key.parse(skip_blanks(input))?;
+        let (_, remaining) = self.separator.lex(skip_blanks(remaining))?;
+        let (value, remaining) = self.value.parse(skip_blanks(remaining))?;
+
+        Ok(((key, value), skip_blanks(remaining)))
+    }
+
+    fn describe(&self) -> Grammar {
+        let pair = Grammar::then(self.key.describe(), self.separator.describe());
+        Grammar::then(pair, self.value.describe())
+    }
+}
+
+impl<K, S, V> Unparse for KeyValue<K, S, V>
+where
+    K: Unparse,
+    S: Lex,
+    V: Unparse,
+{
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        self.key.unparse_into(&value.0, output)?;
+        self.separator.canonical_into(output)?;
+        self.value.unparse_into(&value.1, output)
+    }
+}
+
+/// This parser parses a key, then a separator, then a value, and outputs the key and value as a tuple `(key, value)`.
+///
+/// Spaces and tabs are skipped before and after each part, so `key=value`, `key = value` and `key\t= value ` all match.
+/// Newlines are not skipped, so pairs can be delimited by newlines.
+///
+/// The key and value parsers should stop before any whitespace that follows them, e.g. `alphanum().many(1..)` rather than `until("=")`,
+/// otherwise the whitespace is part of their output.
+///
+/// Pairs are usually repeated with [`many()`](crate::Parse::many()) and [`delimiter()`](crate::combinator::Many::delimiter()),
+/// then collected into a map with [`collect()`](crate::combinator::Delimited::collect()).
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use std::collections::HashMap;
+/// use parsely::{alphanum, char, int, key_value, Lex, Parse};
+///
+/// let key = alphanum().many(1..).map(str::to_string);
+/// let settings = key_value(key, char('='), int::<i32>())
+///     .many(..)
+///     .delimiter(char(','))
+///     .collect::<HashMap<_, _>>();
+///
+/// let (settings, remaining) = settings.parse("width = 10,height=-2 , depth\t=3")?;
+/// assert_eq!(settings.len(), 3);
+/// assert_eq!(settings["height"], -2);
+/// assert_eq!(remaining, "");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+///
+/// One pair per line:
+///
+/// ```
+/// use parsely::{alpha, char, key_value, until, Lex, Parse};
+///
+/// let line = key_value(alpha().many(1..).map(str::to_string), char(':'), until("\n").map(str::to_string));
+/// let headers = line.many(..).delimiter(char('\n'));
+///
+/// let (headers, _) = headers.parse("Host: example.com\nAccept:  text/html\n")?;
+/// assert_eq!(headers[1], ("Accept".to_string(), "text/html".to_string()));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn key_value<K, S, V>(key: K, separator: S, value: V) -> KeyValue<K, S, V>
+where
+    K: Parse,
+    S: Lex,
+    V: Parse,
+{
+    KeyValue {
+        key,
+        separator,
+        value,
+    }
+}
+
+impl<K: fmt::Debug, S: fmt::Debug, V: fmt::Debug> fmt::Debug for KeyValue<K, S, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "KeyValue({:?} {:?} {:?})",
+            self.key, self.separator, self.value
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{alpha, char, digit, uint, ws};
+
+    #[test]
+    fn parsing() {
+        let key = || alpha().many(1..).map(str::to_string);
+
+        test_parser_batch(
+            "key value pairs skip spaces and tabs",
+            key_value(key(), char('='), uint::<u8>()),
+            &[
+                ("a=1", Some(("a".to_string(), 1)), ""), //
+                (" a = 1 ;", Some(("a".to_string(), 1)), ";"),
+                ("a\t=\t1\n", Some(("a".to_string(), 1)), "\n"),
+                ("a\n=1", None, "a\n=1"),
+                ("a:1", None, "a:1"),
+                ("=1", None, "=1"),
+            ],
+        );
+
+        test_parser_batch(
+            "key value pairs delimited by newlines",
+            key_value(key(), char(':'), uint::<u8>())
+                .many(1..)
+                .delimiter(char('\n')),
+            &[(
+                "a: 1 \nb :2\n",
+                Some(vec![("a".to_string(), 1), ("b".to_string(), 2)]),
+                "",
+            )],
+        );
+    }
+
+    #[test]
+    fn unparsing() -> Result<(), Error> {
+        let key = alpha().many(1..).parse_str::<String>();
+        let value = digit().many(1..).parse_str::<u8>();
+        let pair = key_value(
+            key,
+            char('=').pad_with(ws().many(..), ws().many(1..)),
+            value,
+        );
+
+        assert_eq!(pair.unparse(&("a".to_string(), 1))?, "a= 1");
+
+        Ok(())
+    }
+}
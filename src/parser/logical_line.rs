@@ -0,0 +1,287 @@
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine, error::no_match, grammar::Grammar, unparse::Unparse, Error, Parse,
+    ParseResult,
+};
+
+/// This parser is returned by [`logical_line()`]. See it's documentation for more details.
+#[derive(Clone, Copy)]
+pub struct LogicalLine {
+    indented: bool,
+}
+
+/// This parser is returned by [`LogicalLine::scoped()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct ScopedLine<P> {
+    line: LogicalLine,
+    item: P,
+}
+
+impl LogicalLine {
+    /// Also continues the line onto the next physical line when that starts with a space or tab, as in HTTP and email headers.
+    ///
+    /// The line ending and the whitespace that starts the next line are replaced with a single space.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{logical_line, Parse};
+    ///
+    /// let header = logical_line().indented();
+    ///
+    /// let (line, remaining) = header.parse("Subject: a long\r\n   subject\r\nTo: me")?;
+    /// assert_eq!(line, "Subject: a long subject");
+    /// assert_eq!(remaining, "To: me");
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn indented(self) -> LogicalLine {
+        LogicalLine { indented: true }
+    }
+
+    /// Creates a parser that runs the item on the folded line, which it must match entirely.
+    ///
+    /// This keeps line continuations out of the item's grammar. Errors from the item refer to the folded line, not the original input.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, int, logical_line, Lex, Parse};
+    ///
+    /// let numbers = int::<i32>().pad().many(1..).delimiter(char(','));
+    /// let line = logical_line().scoped(numbers);
+    ///
+    /// let (lines, _) = line.many(..).parse("1, 2, \\\n 3\n4\n")?;
+    /// assert_eq!(lines, vec![vec![1, 2, 3], vec![4]]);
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn scoped<P: Parse>(self, item: P) -> ScopedLine<P> {
+        ScopedLine { line: self, item }
+    }
+}
+
+/// Returns the length of the line ending at the start of `input`, if there is one.
+fn line_ending(input: &str) -> Option<usize> {
+    if input.starts_with('\n') {
+        Some(1)
+    } else if input.starts_with("\r\n") {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+impl Parse for LogicalLine {
+    type Output = String;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        if input.is_empty() {
+            examine(1)?;
+            return Err(no_match(input));
+        }
+
+        let mut line = String::new();
+        let mut rest = input;
+
+        loop {
+            let end = rest.find(['\n', '\r']).unwrap_or(rest.len());
+            let Some(ending) = line_ending(&rest[end..]) else {
+                if end == rest.len() {
+                    line.push_str(rest);
+                    rest = "";
+                    break;
+                }
+
+                // a lone '\r' isn't a line ending
+                line.push_str(&rest[..=end]);
+                rest = &rest[end + 1..];
+                continue;
+            };
+
+            let physical = &rest[..end];
+            let next = &rest[end + ending..];
+
+            if let Some(folded) = physical.strip_suffix('\\') {
+                line.push_str(folded);
+                rest = next;
+            } else if self.indented && next.starts_with([' ', '\t']) {
+                line.push_str(physical);
+                line.push(' ');
+                rest = next.trim_start_matches([' ', '\t']);
+            } else {
+                line.push_str(physical);
+                rest = next;
+                break;
+            }
+        }
+
+        // the char after the line ending was looked at too, to see if it continued the line
+        examine(input.len() - rest.len() + 1)?;
+
+        Ok((line, rest))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special("logical line".to_string())
+    }
+}
+
+impl<P: Parse> Parse for ScopedLine<P> {
+    type Output = P::Output;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (line, remaining) = self.line.parse(input)?;
+        let output = self.item.parse_all(&line)?;
+
+        Ok((output, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.line.describe()
+    }
+}
+
+impl Unparse for LogicalLine {
+    /// Writes the line followed by `\n`.
+    ///
+    /// A line that contains a line ending, or that ends with `\`, can't be written and fails with a custom error.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        if value.contains('\n') || value.contains("\r\n") || value.ends_with('\\') {
+            return Err(Error::custom(format!(
+                "{value:?} can't be written as a single line"
+            )));
+        }
+
+        output.push_str(value);
+        output.push('\n');
+        Ok(())
+    }
+}
+
+impl<P: Unparse> Unparse for ScopedLine<P> {
+    /// Unparses the value with the item, and writes it as one line.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let line = self.item.unparse(value)?;
+        self.line.unparse_into(&line, output)
+    }
+}
+
+/// Parses one logical line, folding physical lines that end with a `\` into the line after them, outputting the folded line.
+///
+/// The `\` and the line ending after it are removed, so `a\` followed by `b` on the next line outputs `ab`.
+/// A `\` at the end of a physical line always continues it, even after another `\`.
+/// Use [`.indented()`](LogicalLine::indented()) to also continue lines onto physical lines that start with whitespace, as in HTTP headers.
+///
+/// The line ending (`\n` or `\r\n`) at the end of the logical line is consumed, but not part of the output.
+/// The last line doesn't need a line ending. Empty input doesn't match, so `.many(..)` stops at the end of the input.
+///
+/// Use [`.scoped()`](LogicalLine::scoped()) to parse the folded line with another parser,
+/// so that its grammar doesn't need to know about line continuations.
+///
+/// Unparsing writes the line followed by `\n`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{logical_line, Parse};
+///
+/// let input = "CFLAGS = -O2 \\\n    -Wall\nLDFLAGS = -lm\n";
+///
+/// let (lines, remaining) = logical_line().many(..).parse(input)?;
+/// assert_eq!(lines, vec!["CFLAGS = -O2     -Wall", "LDFLAGS = -lm"]);
+/// assert_eq!(remaining, "");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn logical_line() -> LogicalLine {
+    LogicalLine { indented: false }
+}
+
+impl fmt::Debug for LogicalLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.indented {
+            write!(f, "LogicalLine(indented)")
+        } else {
+            write!(f, "LogicalLine")
+        }
+    }
+}
+
+impl<P: fmt::Debug> fmt::Debug for ScopedLine<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ScopedLine({:?}, {:?})", self.line, self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::*, ws, Lex};
+
+    #[test]
+    fn folding() {
+        let line = |text: &str| Some(text.to_string());
+
+        test_parser_batch(
+            "backslash continuations",
+            logical_line(),
+            &[
+                ("one", line("one"), ""),
+                ("one\ntwo", line("one"), "two"),
+                ("one\r\ntwo", line("one"), "two"),
+                ("\ntwo", line(""), "two"),
+                ("a\\\nb\\\r\nc\nd", line("abc"), "d"),
+                ("a \\\n  b", line("a   b"), ""),
+                ("a\\\n", line("a"), ""),
+                ("a\\", line("a\\"), ""),
+                ("a\\\\\nb", line("a\\b"), ""),
+                ("a\rb\nc", line("a\rb"), "c"),
+                ("a\n b", line("a"), " b"),
+                ("", None, ""),
+            ],
+        );
+
+        test_parser_batch(
+            "indented continuations",
+            logical_line().indented(),
+            &[
+                ("a\n b\nc", line("a b"), "c"),
+                ("a\r\n\t \tb\r\n  c\r\nd", line("a b c"), "d"),
+                ("a\\\nb\n c", line("ab c"), ""),
+                ("a\nb", line("a"), "b"),
+                ("a\n", line("a"), ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn scoped_lines() -> Result<(), Error> {
+        let words = ws()
+            .many(..)
+            .skip_then(crate::alpha().many(1..).map(str::to_string))
+            .many(1..);
+        let line = logical_line().indented().scoped(words);
+
+        let (words, remaining) = line.parse("one two\n  three\nfour")?;
+        assert_eq!(words, ["one", "two", "three"]);
+        assert_eq!(remaining, "four");
+
+        assert!(line.parse("one 2\n").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn unparsing() -> Result<(), Error> {
+        assert_eq!(logical_line().unparse(&"a b".to_string())?, "a b\n");
+        assert!(logical_line().unparse(&"a\nb".to_string()).is_err());
+        assert!(logical_line().unparse(&"a\\".to_string()).is_err());
+
+        Ok(())
+    }
+}
@@ -1,7 +1,37 @@
 //! The built in parsers provided by parsely
 //!
+mod byte_size;
+mod entity;
+mod fraction;
+mod glob;
+mod hex_bytes;
+mod key_value;
+mod logical_line;
 mod number;
+mod ordinal;
+mod path;
+mod percent;
+mod phone_number;
+mod position;
+mod shell_words;
+mod string_literal;
 mod switch;
+mod utc_offset;
 
-pub use self::number::{float, int, number, uint};
-pub use self::switch::switch;
+pub use self::byte_size::{byte_size, ByteSize};
+pub use self::entity::{entity, Entity};
+pub use self::fraction::{fraction, Fraction};
+pub use self::glob::{glob, Glob, GlobPart};
+pub use self::hex_bytes::{hex_array, hex_bytes, HexArray, HexBytes};
+pub use self::key_value::{key_value, KeyValue};
+pub use self::logical_line::{logical_line, LogicalLine, ScopedLine};
+pub use self::number::{fixed_uint, float, int, number, uint};
+pub use self::ordinal::{ordinal, Ordinal};
+pub use self::path::{path, path_buf, Component, FilePath, FilePathBuf, Prefix};
+pub use self::percent::{percent, Percent};
+pub use self::phone_number::{phone_number, PhoneNumber};
+pub use self::position::{position, Position};
+pub use self::shell_words::{shell_words, ShellWords};
+pub use self::string_literal::{escape_sequence, string_literal, EscapeSequence, StringLiteral};
+pub use self::switch::{switch, switch_enum, SwitchEnum, TokenEnum};
+pub use self::utc_offset::{utc_offset, UtcOffset};
@@ -21,9 +21,22 @@
 //!
 //! I decided to avoid an unbound number of digits so it was more robust in the face of malicious input, but this library has not been tested for security yet.
 
-use std::str::FromStr;
+use std::{
+    any::Any,
+    fmt,
+    marker::PhantomData,
+    num::{IntErrorKind, ParseIntError},
+    str::FromStr,
+};
 
-use crate::{char, char_if, digit, non_zero_digit, Lex, Parse};
+use crate::{
+    char, char_if,
+    combinator::atomic,
+    digit, digits,
+    error::{failed_conversion, overflow},
+    grammar::Grammar,
+    non_zero_digit, Lex, Parse, ParseResult,
+};
 
 /// Parses a signed integer, i.e. one or more base 10 digits with or without a leading '-' indicating the sign.
 ///
@@ -45,12 +58,26 @@ use crate::{char, char_if, digit, non_zero_digit, Lex, Parse};
 /// # Ok::<(), parsely::Error>(())
 /// ```
 ///
-pub fn int<T: FromStr + Clone>() -> impl Parse<Output = T> + Clone {
-    char('-')
-        .optional()
-        .then(char_if(|c| c.is_ascii_digit() && c != '0'))
-        .then(digit().many(0..=100_000))
-        .try_map(FromStr::from_str)
+/// Digits that don't fit in the output type fail with [`Error::Overflow`](crate::Error::Overflow):
+///
+/// ```
+/// use parsely::{int, Error, Parse};
+///
+/// let error = int::<u8>().parse("300").unwrap_err();
+///
+/// assert_eq!(error, Error::Overflow { digits: "300".to_string() });
+/// assert_eq!(error.to_string(), "The number 300 doesn't fit in the output type");
+/// ```
+pub fn int<T: FromStr + Clone>() -> impl Parse<Output = T> + Clone
+where
+    T::Err: fmt::Display + 'static,
+{
+    integer(atomic(
+        char('-')
+            .optional()
+            .then(char_if(|c| c.is_ascii_digit() && c != '0'))
+            .then(digit().many(0..=100_000)),
+    ))
 }
 
 /// Parses an unsigned integer, i.e. one or more base 10 digits.
@@ -62,10 +89,97 @@ pub fn int<T: FromStr + Clone>() -> impl Parse<Output = T> + Clone {
 /// * [`float()`] which will parse only decimals
 /// * [`number()`] which will parse integers or decimals
 ///
-pub fn uint<T: FromStr + Clone>() -> impl Parse<Output = T> + Clone {
-    non_zero_digit()
-        .then(digit().many(0..100_000))
-        .try_map(FromStr::from_str)
+pub fn uint<T: FromStr + Clone>() -> impl Parse<Output = T> + Clone
+where
+    T::Err: fmt::Display + 'static,
+{
+    integer(atomic(non_zero_digit().then(digit().many(0..100_000))))
+}
+
+/// Parses exactly `n` base 10 digits as an unsigned integer, i.e. a fixed-width numeric field.
+///
+/// Unlike [`uint()`], leading zeros are allowed, so `"07"` parses as `7`.
+/// Digits after the first `n` are left in the remaining input.
+///
+/// Digits that don't fit in the output type fail with [`Error::Overflow`](crate::Error::Overflow).
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, fixed_uint, Parse};
+///
+/// let time = fixed_uint::<u8>(2)
+///     .then_skip(char(':'))
+///     .then(fixed_uint::<u8>(2));
+///
+/// assert_eq!(time.parse("09:05")?, ((9, 5), ""));
+/// assert_eq!(fixed_uint::<u16>(4).parse("202401")?, (2024, "01"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn fixed_uint<T: FromStr + Clone>(n: usize) -> impl Parse<Output = T> + Clone
+where
+    T::Err: fmt::Display + 'static,
+{
+    integer(digits(n))
+}
+
+/// Converts the digits matched by `lexer`, failing with [`Error::Overflow`](crate::Error::Overflow) if they don't fit in `T`.
+fn integer<L: Lex + Clone, T: FromStr>(lexer: L) -> Integer<L, T>
+where
+    T::Err: fmt::Display + 'static,
+{
+    Integer {
+        lexer,
+        output: PhantomData,
+    }
+}
+
+/// This parser is returned by [`int()`], [`uint()`] and [`fixed_uint()`]. See their documentation for more details.
+struct Integer<L, T> {
+    lexer: L,
+    output: PhantomData<fn() -> T>,
+}
+
+impl<L: Clone, T> Clone for Integer<L, T> {
+    fn clone(&self) -> Self {
+        Integer {
+            lexer: self.lexer.clone(),
+            output: PhantomData,
+        }
+    }
+}
+
+impl<L: Lex, T: FromStr> Parse for Integer<L, T>
+where
+    T::Err: fmt::Display + 'static,
+{
+    type Output = T;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (matched, remaining) = self.lexer.lex(input)?;
+
+        match matched.parse() {
+            Ok(output) => Ok((output, remaining)),
+            Err(error) if is_overflow(&error) => Err(overflow(matched)),
+            Err(error) => Err(failed_conversion(matched, error)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        self.lexer.describe()
+    }
+}
+
+/// Whether the error is a std integer type reporting a number out of its range.
+fn is_overflow(error: &dyn Any) -> bool {
+    error.downcast_ref::<ParseIntError>().is_some_and(|error| {
+        matches!(
+            error.kind(),
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow
+        )
+    })
 }
 
 /// Parses a floating point decimal in standard notation (not scientific notation)
@@ -97,30 +211,40 @@ pub fn uint<T: FromStr + Clone>() -> impl Parse<Output = T> + Clone {
 /// assert_eq!(remaining, "");
 /// # Ok::<(), parsely::Error>(())
 /// ```
-pub fn float<T: FromStr>() -> impl Parse<Output = T> {
-    float_scientific_notation().or(char('-')
-        .optional()
-        .then(non_zero_digit())
-        .then(digit().many(0..100_000))
-        .then(char('.').or(char(',')))
-        .then(digit().many(0..100_000))
-        // not every language uses '.' for decimals, but rust float parsing expects it
-        .try_map(|s| {
-            let s = s.replace(',', ".");
-            FromStr::from_str(&s)
-        }))
+pub fn float<T: FromStr>() -> impl Parse<Output = T> + Clone
+where
+    T::Err: fmt::Display,
+{
+    float_scientific_notation().or(atomic(
+        char('-')
+            .optional()
+            .then(non_zero_digit())
+            .then(digit().many(0..100_000))
+            .then(char('.').or(char(',')))
+            .then(digit().many(0..100_000))
+            // not every language uses '.' for decimals, but rust float parsing expects it
+            .try_map(|s| {
+                let s = s.replace(',', ".");
+                FromStr::from_str(&s)
+            }),
+    ))
 }
 
-pub fn float_scientific_notation<T: FromStr>() -> impl Parse<Output = T> {
-    (char('-').optional())
-        .then(non_zero_digit())
-        .then(digit().many(0..100_000))
-        .then(char('.'))
-        .then(digit().many(0..100_000))
-        .then(char('e').or(char('E')))
-        .then(char('-').or(char('+')).optional())
-        .then(digit().many(0..100_000))
-        .try_map(FromStr::from_str)
+pub fn float_scientific_notation<T: FromStr>() -> impl Parse<Output = T> + Clone
+where
+    T::Err: fmt::Display,
+{
+    atomic(
+        (char('-').optional())
+            .then(non_zero_digit())
+            .then(digit().many(0..100_000))
+            .then(char('.'))
+            .then(digit().many(0..100_000))
+            .then(char('e').or(char('E')))
+            .then(char('-').or(char('+')).optional())
+            .then(digit().many(0..100_000))
+            .parse_str(),
+    )
 }
 
 /// Parses a float or an int.
@@ -155,15 +279,27 @@ pub fn float_scientific_notation<T: FromStr>() -> impl Parse<Output = T> {
 /// # Ok::<(), parsely::Error>(())
 /// ```
 ///
-/// This happens because
-pub fn number<T: FromStr + Clone>() -> impl Parse<Output = T> {
+/// An integer too large or too small for the output type fails with [`Error::Overflow`](crate::Error::Overflow), rather than matching only some of its digits:
+///
+/// ```
+/// # use parsely::{number, Error, Parse};
+/// assert_eq!(
+///     number::<u8>().parse("300"),
+///     Err(Error::Overflow { digits: "300".to_string() }),
+/// );
+/// ```
+pub fn number<T: FromStr + Clone>() -> impl Parse<Output = T>
+where
+    T::Err: fmt::Display + 'static,
+{
     float::<T>().or(int::<T>())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::*;
+    use crate::testing::*;
+    use crate::Error;
 
     #[test]
     fn rust_float_parse() {
@@ -201,6 +337,27 @@ mod tests {
             ],
         );
 
+        test_parser_batch(
+            "fixed_uint matches exactly n digits",
+            fixed_uint::<u16>(2),
+            &[
+                ("7", None, "7"), //
+                ("07", Some(7), ""),
+                ("123", Some(12), "3"),
+                ("-12", None, "-12"),
+            ],
+        );
+
+        test_parser_batch(
+            "uint matches base 10 digits",
+            uint::<u16>(),
+            &[
+                ("-1", None, "-1"), //
+                ("65535", Some(65535), ""),
+                ("65536", None, "65536"),
+            ],
+        );
+
         test_parser_batch(
             "float matches only decimals",
             float::<f32>(),
@@ -282,4 +439,42 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn overflow() {
+        let overflowed = |digits: &str| Error::Overflow {
+            digits: digits.to_string(),
+        };
+
+        assert_eq!(int::<u8>().parse("300").unwrap_err(), overflowed("300"));
+        assert_eq!(int::<i8>().parse("-129").unwrap_err(), overflowed("-129"));
+        assert_eq!(int::<i8>().parse("-128"), Ok((-128, "")));
+        assert_eq!(
+            uint::<u32>().parse("4294967296").unwrap_err(),
+            overflowed("4294967296")
+        );
+
+        // the float doesn't match, then the integer overflows instead of matching partially
+        assert_eq!(number::<u8>().parse("300").unwrap_err(), overflowed("300"));
+
+        // the float got further than the integer, so its failure is kept
+        assert_eq!(
+            number::<u8>().parse("300.5"),
+            Err(Error::FailedConversion {
+                matched: "300.5".to_string(),
+                message: Some("number too large to fit in target type".to_string()),
+            })
+        );
+        assert_eq!(number::<u8>().parse("123.456"), Ok((123, ".456")));
+        assert_eq!(
+            fixed_uint::<u8>(3).parse("3001").unwrap_err(),
+            overflowed("300")
+        );
+
+        // other conversion failures are still reported as such
+        assert!(matches!(
+            int::<std::net::Ipv4Addr>().parse("123"),
+            Err(Error::FailedConversion { .. })
+        ));
+    }
 }
@@ -0,0 +1,199 @@
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine,
+    error::{failed_conversion, no_match, overflow},
+    grammar::Grammar,
+    unparse::Unparse,
+    Error, Parse, ParseResult,
+};
+
+/// This parser is returned by [`ordinal()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct Ordinal;
+
+/// Returns the English suffix of the ordinal `n`, such as `"st"` for 1 and 21 but `"th"` for 11.
+fn suffix(n: u64) -> &'static str {
+    match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+impl Parse for Ordinal {
+    type Output = u64;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let digits = input.bytes().take_while(u8::is_ascii_digit).count();
+        // the suffix and the char after it are looked at too
+        examine(digits + 3)?;
+
+        let written = input[digits..].get(..2).filter(|written| {
+            ["st", "nd", "rd", "th"]
+                .iter()
+                .any(|suffix| written.eq_ignore_ascii_case(suffix))
+        });
+
+        let Some(written) = written.filter(|_| digits > 0) else {
+            return Err(no_match(input));
+        };
+
+        let remaining = &input[digits + 2..];
+        if remaining.starts_with(char::is_alphabetic) {
+            return Err(no_match(input));
+        }
+
+        let n: u64 = input[..digits]
+            .parse()
+            .map_err(|_| overflow(&input[..digits]))?;
+
+        let expected = suffix(n);
+        if !written.eq_ignore_ascii_case(expected) {
+            return Err(failed_conversion(
+                &input[..digits + 2],
+                format!("the suffix of {n} should be '{expected}'"),
+            ));
+        }
+
+        Ok((n, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Sequence(vec![
+            Grammar::repeat(1, None, Grammar::Class("digit".to_string())),
+            Grammar::Choice(
+                ["st", "nd", "rd", "th"]
+                    .iter()
+                    .map(|suffix| Grammar::TokenAnyCase(suffix.to_string()))
+                    .collect(),
+            ),
+        ])
+    }
+}
+
+impl Unparse for Ordinal {
+    /// Writes the number followed by its lowercase suffix, such as `23rd`.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        output.push_str(&value.to_string());
+        output.push_str(suffix(*value));
+        Ok(())
+    }
+}
+
+/// Parses an English ordinal number such as `1st`, `2nd` or `23rd`, outputting the number.
+///
+/// The suffix is matched case insensitively, and must agree with the number:
+/// `st`, `nd` and `rd` for numbers ending in 1, 2 and 3, except for 11, 12 and 13 which like all other numbers take `th`.
+/// A suffix that doesn't agree, such as in `11st` or `2th`, fails with [`Error::FailedConversion`].
+///
+/// A number without a suffix, or whose suffix is followed by more letters, doesn't match.
+/// A number too large for a `u64` fails with [`Error::Overflow`].
+///
+/// Unparsing writes the number followed by its lowercase suffix.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{ordinal, Parse, Unparse};
+///
+/// assert_eq!(ordinal().parse("1st")?, (1, ""));
+/// assert_eq!(ordinal().parse("23rd place")?, (23, " place"));
+/// assert_eq!(ordinal().parse("112TH")?, (112, ""));
+/// assert!(ordinal().parse("3th").is_err());
+/// assert!(ordinal().parse("3").is_err());
+///
+/// assert_eq!(ordinal().unparse(&42)?, "42nd");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn ordinal() -> Ordinal {
+    Ordinal
+}
+
+impl fmt::Debug for Ordinal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ordinal")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "ordinals",
+            ordinal(),
+            &[
+                ("0th", Some(0), ""),
+                ("1st", Some(1), ""),
+                ("2nd", Some(2), ""),
+                ("3rd", Some(3), ""),
+                ("4th", Some(4), ""),
+                ("11th", Some(11), ""),
+                ("12th", Some(12), ""),
+                ("13th", Some(13), ""),
+                ("21st", Some(21), ""),
+                ("22ND", Some(22), ""),
+                ("101st", Some(101), ""),
+                ("111th", Some(111), ""),
+                ("01st", Some(1), ""),
+                ("5th.", Some(5), "."),
+                ("5th-6th", Some(5), "-6th"),
+                ("1", None, ""),
+                ("1s", None, ""),
+                ("1stly", None, ""),
+                ("1 st", None, ""),
+                ("st", None, ""),
+                ("", None, ""),
+            ],
+        );
+
+        test_parser_errors(
+            "wrong suffixes",
+            ordinal(),
+            &[
+                (
+                    "1th",
+                    failed_conversion("1th", "the suffix of 1 should be 'st'"),
+                ),
+                (
+                    "11st",
+                    failed_conversion("11st", "the suffix of 11 should be 'th'"),
+                ),
+                (
+                    "12nd",
+                    failed_conversion("12nd", "the suffix of 12 should be 'th'"),
+                ),
+                (
+                    "23th",
+                    failed_conversion("23th", "the suffix of 23 should be 'rd'"),
+                ),
+                (
+                    "4rd",
+                    failed_conversion("4rd", "the suffix of 4 should be 'th'"),
+                ),
+                ("99999999999999999999th", overflow("99999999999999999999")),
+            ],
+        );
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        for n in [0, 1, 2, 3, 4, 11, 12, 13, 21, 112, 1001, u64::MAX] {
+            let text = ordinal().unparse(&n)?;
+            assert_eq!(ordinal().parse_all(&text)?, n);
+        }
+
+        assert_eq!(ordinal().unparse(&13)?, "13th");
+        assert_eq!(ordinal().unparse(&103)?, "103rd");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,567 @@
+use std::{fmt, path::PathBuf};
+
+use crate::{
+    combinator::budget::examine,
+    error::{failed_conversion, no_match},
+    grammar::Grammar,
+    unparse::Unparse,
+    Error, Parse, ParseResult,
+};
+
+/// This parser is returned by [`path()`]. See it's documentation for more details.
+#[derive(Clone, Copy)]
+pub struct FilePath {
+    unix: bool,
+}
+
+/// This parser is returned by [`path_buf()`]. See it's documentation for more details.
+#[derive(Clone, Copy)]
+pub struct FilePathBuf {
+    path: FilePath,
+}
+
+/// A part of a path parsed by [`path()`], much like [`std::path::Component`] but the same for Unix and Windows paths.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Component {
+    /// A Windows drive letter or UNC share, which comes first.
+    Prefix(Prefix),
+
+    /// The root directory, from a leading separator.
+    RootDir,
+
+    /// A leading `.`, which is only kept at the start of the path.
+    CurDir,
+
+    /// A `..`
+    ParentDir,
+
+    /// A file or directory name.
+    Normal(String),
+}
+
+/// The prefix of a Windows path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Prefix {
+    /// A drive letter such as `C:`, always uppercase.
+    Disk(char),
+
+    /// A UNC share such as `\\server\share`.
+    Unc {
+        /// The server name.
+        server: String,
+
+        /// The name of the share on the server.
+        share: String,
+    },
+}
+
+impl FilePath {
+    /// Only treats `/` as a separator, and doesn't look for drive letters or UNC shares.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{path, Component, Parse};
+    ///
+    /// let (components, _) = path().unix().parse(r"dir\name")?;
+    /// assert_eq!(components, [Component::Normal(r"dir\name".to_string())]);
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn unix(self) -> FilePath {
+        FilePath { unix: true }
+    }
+
+    fn is_separator(&self, c: char) -> bool {
+        c == '/' || (!self.unix && c == '\\')
+    }
+
+    /// Writes the components with `separator` between them.
+    fn write(
+        &self,
+        components: &[Component],
+        separator: char,
+        output: &mut String,
+    ) -> Result<(), Error> {
+        if components.is_empty() {
+            return Err(Error::NoMatch);
+        }
+
+        let unwritable =
+            |name: &str| Error::custom(format!("{name:?} can't be written as part of a path"));
+        let mut needs_separator = false;
+
+        for (i, component) in components.iter().enumerate() {
+            let name = match component {
+                Component::Prefix(_) if i > 0 || self.unix => {
+                    return Err(Error::custom(
+                        "a path prefix can only come first in a Windows path",
+                    ))
+                }
+                Component::Prefix(Prefix::Disk(drive)) => {
+                    output.push(*drive);
+                    output.push(':');
+                    continue;
+                }
+                Component::Prefix(Prefix::Unc { server, share }) => {
+                    for name in [server, share] {
+                        if name.is_empty() || name.contains(|c| self.is_separator(c) || is_end(c)) {
+                            return Err(unwritable(name));
+                        }
+                    }
+                    // these would be read back as a verbatim or device path
+                    if matches!(server.as_str(), "?" | ".") {
+                        return Err(unwritable(server));
+                    }
+
+                    output.push(separator);
+                    output.push(separator);
+                    output.push_str(server);
+                    output.push(separator);
+                    output.push_str(share);
+                    continue;
+                }
+                // the root of a UNC share is written with the share
+                Component::RootDir
+                    if i > 0 && matches!(components[0], Component::Prefix(Prefix::Unc { .. })) =>
+                {
+                    needs_separator = true;
+                    continue;
+                }
+                Component::RootDir => {
+                    output.push(separator);
+                    needs_separator = false;
+                    continue;
+                }
+                Component::CurDir => ".",
+                Component::ParentDir => "..",
+                Component::Normal(name) => {
+                    if matches!(name.as_str(), "" | "." | "..")
+                        || name.contains(|c| self.is_separator(c) || is_end(c))
+                    {
+                        return Err(unwritable(name));
+                    }
+                    name
+                }
+            };
+
+            if needs_separator {
+                output.push(separator);
+            }
+            output.push_str(name);
+            needs_separator = true;
+        }
+
+        Ok(())
+    }
+}
+
+impl FilePathBuf {
+    /// Only treats `/` as a separator, and doesn't look for drive letters or UNC shares, see [`FilePath::unix()`].
+    pub fn unix(self) -> FilePathBuf {
+        FilePathBuf {
+            path: self.path.unix(),
+        }
+    }
+}
+
+/// Paths end at whitespace or a control char.
+fn is_end(c: char) -> bool {
+    c.is_whitespace() || c.is_control()
+}
+
+impl Parse for FilePath {
+    type Output = Vec<Component>;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let end = input.find(is_end).unwrap_or(input.len());
+        // the char after the path was looked at too
+        examine(end + 1)?;
+
+        if end == 0 {
+            return Err(no_match(input));
+        }
+
+        let (text, remaining) = input.split_at(end);
+        let mut rest = text;
+        let mut components = Vec::new();
+
+        if !self.unix {
+            if let Some(unc) = text.strip_prefix(r"\\") {
+                let mut names = unc.splitn(3, |c| self.is_separator(c));
+                let server = names.next().unwrap_or_default();
+
+                // `\\?\` and `\\.\` look like a UNC server, but aren't one
+                if matches!(server, "?" | ".") {
+                    return Err(failed_conversion(
+                        text,
+                        "verbatim and device paths aren't supported",
+                    ));
+                }
+
+                let share = names.next().unwrap_or_default();
+                if server.is_empty() || share.is_empty() {
+                    return Err(failed_conversion(
+                        text,
+                        "a UNC path needs a server and a share",
+                    ));
+                }
+
+                components.push(Component::Prefix(Prefix::Unc {
+                    server: server.to_string(),
+                    share: share.to_string(),
+                }));
+                // a UNC share is always the root of its path
+                components.push(Component::RootDir);
+                rest = names.next().unwrap_or("");
+            } else if let [drive @ (b'a'..=b'z' | b'A'..=b'Z'), b':', ..] = text.as_bytes() {
+                components.push(Component::Prefix(Prefix::Disk(
+                    char::from(*drive).to_ascii_uppercase(),
+                )));
+                rest = &text[2..];
+            }
+        }
+
+        if rest.starts_with(|c| self.is_separator(c)) && !components.contains(&Component::RootDir) {
+            components.push(Component::RootDir);
+        }
+
+        for name in rest.split(|c| self.is_separator(c)) {
+            match name {
+                // repeated and trailing separators don't add anything
+                "" => {}
+                "." if components.is_empty() => components.push(Component::CurDir),
+                "." => {}
+                ".." => components.push(Component::ParentDir),
+                name => components.push(Component::Normal(name.to_string())),
+            }
+        }
+
+        Ok((components, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        if self.unix {
+            Grammar::Special("unix path".to_string())
+        } else {
+            Grammar::Special("path".to_string())
+        }
+    }
+}
+
+impl Parse for FilePathBuf {
+    type Output = PathBuf;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let (components, remaining) = self.path.parse(input)?;
+
+        let mut path = String::new();
+        self.path
+            .write(&components, std::path::MAIN_SEPARATOR, &mut path)?;
+
+        Ok((PathBuf::from(path), remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        self.path.describe()
+    }
+}
+
+impl Unparse for FilePath {
+    /// Writes the components separated by `/`, or by `\` if the path has a Windows prefix.
+    ///
+    /// Names that are empty, `.` or `..`, or that contain a separator, whitespace or a control char can't be written, and fail with a custom error.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let separator = match value.first() {
+            Some(Component::Prefix(_)) => '\\',
+            _ => '/',
+        };
+
+        self.write(value, separator, output)
+    }
+}
+
+impl Unparse for FilePathBuf {
+    /// Writes the path as it is, failing with a custom error if it isn't UTF-8 or can't be parsed back.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let path = value
+            .to_str()
+            .ok_or_else(|| Error::custom(format!("{value:?} isn't valid UTF-8")))?;
+
+        if path.is_empty() || path.contains(is_end) {
+            return Err(Error::custom(format!(
+                "{path:?} can't be written as a path"
+            )));
+        }
+
+        output.push_str(path);
+        Ok(())
+    }
+}
+
+/// Parses a Unix or Windows file path, outputting its [`Component`]s.
+///
+/// Both `/` and `\` are separators, use [`.unix()`](FilePath::unix()) to only split on `/`.
+/// A Windows path can start with a drive letter such as `C:`, or a UNC share such as `\\server\share`, which becomes a [`Component::Prefix`].
+///
+/// The components are normalized the same way as [`std::path::Path::components()`]:
+/// repeated and trailing separators are ignored, and `.` is dropped unless it starts the path. `..` is always kept.
+///
+/// The path ends at whitespace or a control char, which must be quoted or escaped by the surrounding format to be part of a path.
+/// A UNC prefix without both a server and a share fails with [`Error::FailedConversion`],
+/// as do verbatim paths such as `\\?\C:\dir` and device paths such as `\\.\COM1`, which aren't supported.
+///
+/// Unparsing writes the components separated by `/`, or by `\` if the path starts with a Windows prefix.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{path, Component, Parse, Prefix};
+///
+/// let normal = |name: &str| Component::Normal(name.to_string());
+///
+/// let (components, remaining) = path().parse("/var/log/../lib/ rest")?;
+/// assert_eq!(
+///     components,
+///     [Component::RootDir, normal("var"), normal("log"), Component::ParentDir, normal("lib")]
+/// );
+/// assert_eq!(remaining, " rest");
+///
+/// assert_eq!(
+///     path().parse_all(r"c:\Users\.\me")?,
+///     [Component::Prefix(Prefix::Disk('C')), Component::RootDir, normal("Users"), normal("me")]
+/// );
+///
+/// assert_eq!(path().parse_all("./src//lib.rs")?, [Component::CurDir, normal("src"), normal("lib.rs")]);
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn path() -> FilePath {
+    FilePath { unix: false }
+}
+
+/// Parses a Unix or Windows file path like [`path()`], outputting a [`PathBuf`].
+///
+/// The components are joined with the separator of the platform the program is running on, so `a\b` becomes `a/b` on Unix.
+/// Repeated and trailing separators and `.` are normalized the same way as [`path()`].
+///
+/// Unparsing writes the path as it is.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use std::path::{Path, PathBuf};
+///
+/// use parsely::{path_buf, Parse};
+///
+/// let (path, _) = path_buf().parse("logs/./app.log")?;
+/// assert_eq!(path, Path::new("logs").join("app.log"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn path_buf() -> FilePathBuf {
+    FilePathBuf { path: path() }
+}
+
+impl fmt::Debug for FilePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.unix {
+            write!(f, "FilePath(unix)")
+        } else {
+            write!(f, "FilePath")
+        }
+    }
+}
+
+impl fmt::Debug for FilePathBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FilePathBuf({:?})", self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    fn normal(name: &str) -> Component {
+        Component::Normal(name.to_string())
+    }
+
+    fn unc(server: &str, share: &str) -> Component {
+        Component::Prefix(Prefix::Unc {
+            server: server.to_string(),
+            share: share.to_string(),
+        })
+    }
+
+    #[test]
+    fn parsing() {
+        use Component::*;
+
+        test_parser_batch(
+            "unix and windows paths",
+            path(),
+            &[
+                ("a", Some(vec![normal("a")]), ""),
+                ("/", Some(vec![RootDir]), ""),
+                (
+                    "/usr/bin/",
+                    Some(vec![RootDir, normal("usr"), normal("bin")]),
+                    "",
+                ),
+                (
+                    "a//b/./c",
+                    Some(vec![normal("a"), normal("b"), normal("c")]),
+                    "",
+                ),
+                ("./a", Some(vec![CurDir, normal("a")]), ""),
+                ("../../a", Some(vec![ParentDir, ParentDir, normal("a")]), ""),
+                ("a/.. b", Some(vec![normal("a"), ParentDir]), " b"),
+                (
+                    r"C:\Windows\System32",
+                    Some(vec![
+                        Prefix(super::Prefix::Disk('C')),
+                        RootDir,
+                        normal("Windows"),
+                        normal("System32"),
+                    ]),
+                    "",
+                ),
+                (
+                    "d:file.txt",
+                    Some(vec![Prefix(super::Prefix::Disk('D')), normal("file.txt")]),
+                    "",
+                ),
+                (
+                    r"a\b/c",
+                    Some(vec![normal("a"), normal("b"), normal("c")]),
+                    "",
+                ),
+                (
+                    r"\\server\share",
+                    Some(vec![unc("server", "share"), RootDir]),
+                    "",
+                ),
+                (
+                    r"\\server\share\dir\file",
+                    Some(vec![
+                        unc("server", "share"),
+                        RootDir,
+                        normal("dir"),
+                        normal("file"),
+                    ]),
+                    "",
+                ),
+                ("1:", Some(vec![normal("1:")]), ""),
+                ("", None, ""),
+                (" /a", None, ""),
+            ],
+        );
+
+        test_parser_batch(
+            "unix paths",
+            path().unix(),
+            &[
+                ("/a/b", Some(vec![RootDir, normal("a"), normal("b")]), ""),
+                (r"a\b", Some(vec![normal(r"a\b")]), ""),
+                ("C:/x", Some(vec![normal("C:"), normal("x")]), ""),
+                (r"\\server\share", Some(vec![normal(r"\\server\share")]), ""),
+            ],
+        );
+
+        test_parser_errors(
+            "unc paths",
+            path(),
+            &[
+                (
+                    r"\\server",
+                    failed_conversion(r"\\server", "a UNC path needs a server and a share"),
+                ),
+                (
+                    r"\\server\",
+                    failed_conversion(r"\\server\", "a UNC path needs a server and a share"),
+                ),
+                (
+                    r"\\\share",
+                    failed_conversion(r"\\\share", "a UNC path needs a server and a share"),
+                ),
+            ],
+        );
+
+        let unsupported =
+            |path| failed_conversion(path, "verbatim and device paths aren't supported");
+        test_parser_errors(
+            "verbatim and device paths",
+            path(),
+            &[
+                (r"\\?\C:\x", unsupported(r"\\?\C:\x")),
+                (
+                    r"\\?\UNC\server\share",
+                    unsupported(r"\\?\UNC\server\share"),
+                ),
+                (r"\\?/C:/x", unsupported(r"\\?/C:/x")),
+                (r"\\.\COM1", unsupported(r"\\.\COM1")),
+                (r"\\?", unsupported(r"\\?")),
+            ],
+        );
+
+        // only as the server name
+        assert_eq!(
+            path().parse_all(r"\\server\?\x"),
+            Ok(vec![unc("server", "?"), RootDir, normal("x")])
+        );
+    }
+
+    #[test]
+    fn path_bufs() -> Result<(), Error> {
+        let separator = std::path::MAIN_SEPARATOR;
+
+        assert_eq!(
+            path_buf().parse_all("/a/./b/")?,
+            PathBuf::from(format!("{separator}a{separator}b"))
+        );
+        assert_eq!(
+            path_buf().parse_all(r"a\b")?,
+            PathBuf::from(format!("a{separator}b"))
+        );
+        assert_eq!(path_buf().parse_all("C:")?, PathBuf::from("C:"));
+        assert_eq!(path_buf().unparse(&PathBuf::from("a/b"))?, "a/b");
+        assert!(path_buf().unparse(&PathBuf::from("a b")).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        for input in [
+            "/usr/bin",
+            "./a/b",
+            "../a",
+            r"C:\Windows",
+            "C:file",
+            r"\\server\share\dir",
+        ] {
+            let components = path().parse_all(input)?;
+            assert_eq!(path().unparse(&components)?, input);
+        }
+
+        assert!(path().unparse(&vec![]).is_err());
+        assert!(path().unparse(&vec![normal("a/b")]).is_err());
+        assert!(path()
+            .unparse(&vec![normal("a"), unc("server", "share")])
+            .is_err());
+        assert!(path()
+            .unparse(&vec![unc("?", "C:"), Component::RootDir])
+            .is_err());
+        assert!(path()
+            .unix()
+            .unparse(&vec![Component::Prefix(Prefix::Disk('C'))])
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,312 @@
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine,
+    error::{no_match, overflow},
+    grammar::Grammar,
+    unparse::Unparse,
+    Error, Parse, ParseResult,
+};
+
+/// This parser is returned by [`percent()`]. See it's documentation for more details.
+#[derive(Clone, Copy)]
+pub struct Percent {
+    raw: bool,
+    sign_required: bool,
+}
+
+impl Percent {
+    /// Outputs the number as it is written, so `42%` outputs `42.0` rather than `0.42`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{percent, Parse};
+    ///
+    /// assert_eq!(percent().raw().parse("42%")?, (42.0, ""));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn raw(self) -> Percent {
+        Percent { raw: true, ..self }
+    }
+
+    /// Also matches a number without a `%` sign after it, which is still taken as a percentage.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{percent, Parse};
+    ///
+    /// assert!(percent().parse("50").is_err());
+    /// assert_eq!(percent().sign_optional().parse("50")?, (0.5, ""));
+    /// assert_eq!(percent().sign_optional().parse("50 %")?, (0.5, ""));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn sign_optional(self) -> Percent {
+        Percent {
+            sign_required: false,
+            ..self
+        }
+    }
+}
+
+impl Parse for Percent {
+    type Output = f64;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let bytes = input.as_bytes();
+        let digits_from = |start: usize| {
+            start
+                + bytes[start..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_digit())
+                    .count()
+        };
+
+        let sign = usize::from(input.starts_with(['-', '+']));
+        let mut end = digits_from(sign);
+
+        if end == sign {
+            examine(sign + 1)?;
+            return Err(no_match(input));
+        }
+
+        // a '.' is only part of the number when digits follow it
+        if bytes.get(end) == Some(&b'.') {
+            let decimals_end = digits_from(end + 1);
+            if decimals_end > end + 1 {
+                end = decimals_end;
+            }
+        }
+
+        let number = &input[..end];
+        let after = &input[end..];
+        let percent_sign = after.trim_start_matches([' ', '\t']);
+
+        let remaining = match percent_sign.strip_prefix('%') {
+            Some(remaining) => remaining,
+            None if !self.sign_required => after,
+            None => {
+                examine(input.len() - percent_sign.len() + 1)?;
+                return Err(no_match(input));
+            }
+        };
+
+        // the char after the percentage was looked at too
+        examine(input.len() - remaining.len() + 1)?;
+
+        // moving the decimal point with an exponent avoids the rounding error of dividing by 100
+        let value: f64 = if self.raw {
+            number.parse()
+        } else {
+            format!("{number}e-2").parse()
+        }
+        .map_err(|_| no_match(input))?;
+
+        if !value.is_finite() {
+            return Err(overflow(number));
+        }
+
+        Ok((value, remaining))
+    }
+
+    fn describe(&self) -> Grammar {
+        let digits = || Grammar::repeat(1, None, Grammar::Class("digit".to_string()));
+        let mut percent_sign = Grammar::Sequence(vec![
+            Grammar::repeat(0, None, Grammar::Class("ws".to_string())),
+            Grammar::Token("%".to_string()),
+        ]);
+
+        if !self.sign_required {
+            percent_sign = Grammar::Optional(Box::new(percent_sign));
+        }
+
+        Grammar::Sequence(vec![
+            Grammar::Optional(Box::new(Grammar::Choice(vec![
+                Grammar::Token("-".to_string()),
+                Grammar::Token("+".to_string()),
+            ]))),
+            digits(),
+            Grammar::Optional(Box::new(Grammar::Sequence(vec![
+                Grammar::Token(".".to_string()),
+                digits(),
+            ]))),
+            percent_sign,
+        ])
+    }
+}
+
+impl Unparse for Percent {
+    /// Writes the percentage followed by a `%` sign, such as `42%` for `0.42`, or for `42.0` with [`raw()`](Percent::raw()).
+    ///
+    /// NaN and infinite values fail with a custom error.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        if !value.is_finite() {
+            return Err(Error::custom(format!(
+                "{value} can't be written as a percentage"
+            )));
+        }
+
+        if self.raw {
+            output.push_str(&value.to_string());
+        } else {
+            output.push_str(&times_100(*value));
+        }
+
+        output.push('%');
+        Ok(())
+    }
+}
+
+/// Writes `value` multiplied by 100 by moving the decimal point in its shortest representation,
+/// so that `0.42` is written as `42` rather than picking up rounding errors from the multiplication.
+fn times_100(value: f64) -> String {
+    let written = value.abs().to_string();
+    let (whole, decimals) = written.split_once('.').unwrap_or((&written, ""));
+
+    let mut digits = whole.to_string();
+    for i in 0..2 {
+        digits.push(decimals.as_bytes().get(i).map_or('0', |&b| char::from(b)));
+    }
+
+    let whole = digits.trim_start_matches('0');
+    let whole = if whole.is_empty() { "0" } else { whole };
+    let decimals = decimals.get(2..).unwrap_or("");
+
+    let sign = if value.is_sign_negative() && value != 0.0 {
+        "-"
+    } else {
+        ""
+    };
+
+    if decimals.is_empty() {
+        format!("{sign}{whole}")
+    } else {
+        format!("{sign}{whole}.{decimals}")
+    }
+}
+
+/// Parses a percentage such as `42%`, `3.5 %` or `-10%`, outputting it as a fraction: `42%` outputs `0.42`.
+///
+/// The number can start with a `-` or `+`, and can have decimals after a `.`. There can be spaces or tabs before the `%`.
+///
+/// By default the `%` sign is required, a number without one doesn't match.
+/// Use [`.sign_optional()`](Percent::sign_optional()) to also match numbers without a `%`,
+/// and [`.raw()`](Percent::raw()) to output the number as it's written rather than as a fraction.
+///
+/// A number too large for an `f64` fails with [`Error::Overflow`].
+///
+/// Unparsing writes the percentage followed by a `%` sign, without a space.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{percent, Parse, Unparse};
+///
+/// assert_eq!(percent().parse("42%")?, (0.42, ""));
+/// assert_eq!(percent().parse("3.5 % off")?, (0.035, " off"));
+/// assert_eq!(percent().parse("-10%")?, (-0.1, ""));
+/// assert!(percent().parse("42").is_err());
+///
+/// assert_eq!(percent().unparse(&0.125)?, "12.5%");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn percent() -> Percent {
+    Percent {
+        raw: false,
+        sign_required: true,
+    }
+}
+
+impl fmt::Debug for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Percent")
+            .field("raw", &self.raw)
+            .field("sign_required", &self.sign_required)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "percentages",
+            percent(),
+            &[
+                ("42%", Some(0.42), ""),
+                ("3.5 %", Some(0.035), ""),
+                ("3.5\t%", Some(0.035), ""),
+                ("-10%", Some(-0.1), ""),
+                ("+10%", Some(0.1), ""),
+                ("100%%", Some(1.0), "%"),
+                ("0.5%", Some(0.005), ""),
+                ("007%", Some(0.07), ""),
+                ("42", None, ""),
+                ("42 ", None, ""),
+                ("1.%", None, ""),
+                (".5%", None, ""),
+                ("%", None, ""),
+                ("- 5%", None, ""),
+                ("42\n%", None, ""),
+            ],
+        );
+
+        test_parser_batch(
+            "raw percentages",
+            percent().raw(),
+            &[
+                ("42%", Some(42.0), ""),
+                ("-2.5 %", Some(-2.5), ""),
+                ("42", None, ""),
+            ],
+        );
+
+        test_parser_batch(
+            "optional sign",
+            percent().sign_optional(),
+            &[
+                ("42%", Some(0.42), ""),
+                ("42", Some(0.42), ""),
+                ("42 apples", Some(0.42), " apples"),
+                ("1.", Some(0.01), "."),
+                ("x", None, ""),
+            ],
+        );
+
+        let huge = "9".repeat(400);
+        test_parser_errors(
+            "huge numbers",
+            percent(),
+            &[(&format!("{huge}%"), overflow(&huge))],
+        );
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        for value in [0.42, 0.035, -0.1, 1.0, 0.0, 12.5, 0.00001, -0.0, 1e-7] {
+            let text = percent().unparse(&value)?;
+            assert_eq!(percent().parse_all(&text)?, value, "{text}");
+        }
+
+        assert_eq!(percent().unparse(&0.42)?, "42%");
+        assert_eq!(percent().unparse(&0.035)?, "3.5%");
+        assert_eq!(percent().unparse(&-0.1)?, "-10%");
+        assert_eq!(percent().unparse(&12.5)?, "1250%");
+        assert_eq!(percent().unparse(&0.0)?, "0%");
+        assert_eq!(percent().raw().unparse(&42.0)?, "42%");
+        assert!(percent().unparse(&f64::NAN).is_err());
+
+        Ok(())
+    }
+}
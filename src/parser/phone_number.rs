@@ -0,0 +1,198 @@
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine, error::no_match, grammar::Grammar, unparse::Unparse, Error, Parse,
+    ParseResult,
+};
+
+/// The fewest digits in a phone number, the length of a local number without its area code.
+const MIN_DIGITS: usize = 7;
+
+/// The most digits in a phone number, as set by E.164 for international numbers.
+const MAX_DIGITS: usize = 15;
+
+/// This parser is returned by [`phone_number()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct PhoneNumber;
+
+/// Returns the length of the group of digits, which may be in parentheses, at the start of `input`.
+fn group(input: &str) -> Option<usize> {
+    let (open, rest) = match input.strip_prefix('(') {
+        Some(rest) => (1, rest),
+        None => (0, input),
+    };
+
+    let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+
+    if open == 0 {
+        Some(digits)
+    } else if rest[digits..].starts_with(')') {
+        Some(open + digits + 1)
+    } else {
+        None
+    }
+}
+
+impl Parse for PhoneNumber {
+    type Output = String;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let mut number = String::new();
+        let mut rest = input;
+
+        if let Some(after) = rest.strip_prefix('+') {
+            number.push('+');
+            rest = after;
+        }
+
+        let Some(len) = group(rest) else {
+            examine(input.len() - rest.len() + 1)?;
+            return Err(no_match(input));
+        };
+        number.extend(rest[..len].chars().filter(char::is_ascii_digit));
+        rest = &rest[len..];
+
+        loop {
+            let next = match rest.strip_prefix([' ', '-', '.']) {
+                Some(after) => after,
+                // a group can follow a closing parenthesis without a separator
+                None if input[..input.len() - rest.len()].ends_with(')') => rest,
+                None => break,
+            };
+
+            let Some(len) = group(next) else {
+                break;
+            };
+
+            number.extend(next[..len].chars().filter(char::is_ascii_digit));
+            rest = &next[len..];
+
+            if number.len() > MAX_DIGITS + 1 {
+                break;
+            }
+        }
+
+        // the char after the phone number was looked at too
+        examine(input.len() - rest.len() + 1)?;
+
+        let digits = number.trim_start_matches('+').len();
+        if !(MIN_DIGITS..=MAX_DIGITS).contains(&digits) {
+            return Err(no_match(input));
+        }
+
+        Ok((number, rest))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Sequence(vec![
+            Grammar::Optional(Box::new(Grammar::Token("+".to_string()))),
+            Grammar::Special("phone number digits".to_string()),
+        ])
+    }
+}
+
+impl Unparse for PhoneNumber {
+    /// Writes the normalized phone number as it is, without grouping its digits.
+    ///
+    /// Anything other than an optional `+` followed by 7 to 15 digits fails with a custom error.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let digits = value.strip_prefix('+').unwrap_or(value);
+
+        if !(MIN_DIGITS..=MAX_DIGITS).contains(&digits.len())
+            || !digits.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(Error::custom(format!(
+                "{value:?} isn't a normalized phone number"
+            )));
+        }
+
+        output.push_str(value);
+        Ok(())
+    }
+}
+
+/// Parses a phone number such as `+44 20 7946 0958` or `(555) 123-4567`, outputting its digits such as `+442079460958`.
+///
+/// The number can start with a `+`, which is kept in the output. Its digits can be split into groups,
+/// separated by a single space, `-` or `.`, and any group can be in parentheses, such as the area code in `(555) 123-4567`.
+/// A separator that isn't followed by another group isn't part of the number.
+///
+/// Phone numbers have 7 to 15 digits, the most allowed by E.164 for international numbers.
+/// Anything shorter, or longer, doesn't match.
+///
+/// Unparsing writes the normalized number as it is.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{phone_number, Parse};
+///
+/// assert_eq!(phone_number().parse("+44 20 7946 0958")?, ("+442079460958".to_string(), ""));
+/// assert_eq!(phone_number().parse("(555) 123-4567")?, ("5551234567".to_string(), ""));
+/// assert_eq!(phone_number().parse("555.123.4567 ext. 8")?, ("5551234567".to_string(), " ext. 8"));
+/// assert!(phone_number().parse("123-45").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn phone_number() -> PhoneNumber {
+    PhoneNumber
+}
+
+impl fmt::Debug for PhoneNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PhoneNumber")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn parsing() {
+        let number = |digits: &str| Some(digits.to_string());
+
+        test_parser_batch(
+            "phone numbers",
+            phone_number(),
+            &[
+                ("+44 20 7946 0958", number("+442079460958"), ""),
+                ("(555) 123-4567", number("5551234567"), ""),
+                ("(555)123-4567", number("5551234567"), ""),
+                ("555-123-4567", number("5551234567"), ""),
+                ("555.123.4567", number("5551234567"), ""),
+                ("+1 (555) 123 4567", number("+15551234567"), ""),
+                ("5551234567", number("5551234567"), ""),
+                ("123-4567.", number("1234567"), "."),
+                ("123-4567- x", number("1234567"), "- x"),
+                ("123 4567, 890 1234", number("1234567"), ", 890 1234"),
+                ("+123456789012345", number("+123456789012345"), ""),
+                ("123-456", None, ""),
+                ("+1234567890123456", None, ""),
+                ("123--4567", None, ""),
+                ("123  4567", None, ""),
+                ("(555 123-4567", None, ""),
+                ("+ 555 123 4567", None, ""),
+                ("phone", None, ""),
+                ("", None, ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn unparsing() -> Result<(), Error> {
+        assert_eq!(
+            phone_number().unparse(&"+442079460958".to_string())?,
+            "+442079460958"
+        );
+        assert!(phone_number().unparse(&"555-1234".to_string()).is_err());
+        assert!(phone_number().unparse(&"123".to_string()).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{combinator::located::location_of, grammar::Grammar, Location, Parse, ParseResult};
+
+/// This parser is returned by [`position()`]. See it's documentation for more details.
+#[derive(Clone, Copy, Debug)]
+pub struct Position;
+
+impl Parse for Position {
+    type Output = Location;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        Ok((location_of(input), input))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Empty
+    }
+}
+
+/// This parser outputs the current [`Location`] in the input, without consuming any input. It never fails.
+///
+/// The location is relative to the input given to the enclosing [`located()`](crate::combinator::located()).
+/// Outside of [`located()`](crate::combinator::located()), the location is always [`Location::START`].
+///
+/// This is useful to stamp the output of your parsers with where it was found in the input.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{position, token, Lex, Location, Parse};
+/// use parsely::combinator::located;
+///
+/// let parser = located(token("foo\n").skip_then(position()));
+///
+/// assert_eq!(parser.parse("foo\nbar")?, (Location { offset: 4, line: 2, column: 1 }, "bar"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn position() -> Position {
+    Position
+}
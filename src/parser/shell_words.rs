@@ -0,0 +1,202 @@
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine, grammar::Grammar, unparse::Unparse, Error, Parse, ParseResult,
+};
+
+/// This parser is returned by [`shell_words()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct ShellWords;
+
+impl Parse for ShellWords {
+    type Output = Vec<String>;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        examine(input.len())?;
+
+        let mut words = Vec::new();
+        // None until something, even an empty pair of quotes, starts a word
+        let mut word: Option<String> = None;
+        let mut chars = input.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                c if c.is_whitespace() => words.extend(word.take()),
+                '\'' => {
+                    let word = word.get_or_insert_with(String::new);
+                    loop {
+                        match chars.next() {
+                            Some('\'') => break,
+                            Some(c) => word.push(c),
+                            None => return Err(Error::Expected("closing quote").fatal()),
+                        }
+                    }
+                }
+                '"' => {
+                    let word = word.get_or_insert_with(String::new);
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            // inside double quotes, a backslash only escapes chars that would be special there
+                            Some('\\') => match chars.next() {
+                                Some('\n') => {}
+                                Some(c @ ('$' | '`' | '"' | '\\')) => word.push(c),
+                                Some(c) => {
+                                    word.push('\\');
+                                    word.push(c);
+                                }
+                                None => return Err(Error::Expected("closing quote").fatal()),
+                            },
+                            Some(c) => word.push(c),
+                            None => return Err(Error::Expected("closing quote").fatal()),
+                        }
+                    }
+                }
+                '\\' => match chars.next() {
+                    // an escaped newline continues the line
+                    Some('\n') => {}
+                    Some(c) => word.get_or_insert_with(String::new).push(c),
+                    None => return Err(Error::Expected("a char to escape after '\\'").fatal()),
+                },
+                c => word.get_or_insert_with(String::new).push(c),
+            }
+        }
+
+        words.extend(word);
+
+        Ok((words, ""))
+    }
+
+    fn describe(&self) -> Grammar {
+        let word = Grammar::Special("shell word".to_string());
+        Grammar::separated(0, None, word, Grammar::Class("ws".to_string()))
+    }
+}
+
+impl Unparse for ShellWords {
+    /// Writes the words separated by spaces, single quoting any that contain chars the shell would treat specially.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        for (i, word) in value.iter().enumerate() {
+            if i > 0 {
+                output.push(' ');
+            }
+
+            let plain = !word.is_empty()
+                && word
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || "-_./=:,+@%".contains(c));
+
+            if plain {
+                output.push_str(word);
+            } else {
+                output.push('\'');
+                // a single quote can't be escaped inside single quotes, so close them, escape it and open them again
+                output.push_str(&word.replace('\'', r"'\''"));
+                output.push('\'');
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits a command line into its words, the way a POSIX shell splits the arguments of a command.
+///
+/// * words are separated by whitespace
+/// * inside single quotes, every char is taken as it is, until the closing `'`
+/// * inside double quotes, a `\` escapes `$`, `` ` ``, `"`, `\` or a newline, and is taken as it is before any other char
+/// * outside quotes, a `\` escapes the char after it
+/// * a `\` before a newline removes both, continuing the line
+/// * quoted and unquoted parts next to each other are part of the same word, and `''` is an empty word
+///
+/// Nothing is expanded: `$HOME`, `*.rs` and `~` are words like any other. Comments, pipes and redirections aren't recognised either.
+///
+/// All of the input is split into words. A quote that is never closed, or a `\` at the end of the input, is a [fatal](crate::Error::fatal()) error.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{shell_words, Parse, Unparse};
+///
+/// let words = shell_words().parse_all(r#"git commit -m "fix: don't \"panic\"" --author='A. N. Other' a\ b"#)?;
+/// assert_eq!(
+///     words,
+///     vec!["git", "commit", "-m", r#"fix: don't "panic""#, "--author=A. N. Other", "a b"]
+/// );
+///
+/// assert_eq!(
+///     shell_words().unparse(&words)?,
+///     r#"git commit -m 'fix: don'\''t "panic"' '--author=A. N. Other' 'a b'"#
+/// );
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn shell_words() -> ShellWords {
+    ShellWords
+}
+
+impl fmt::Debug for ShellWords {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ShellWords")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn splitting() {
+        test_parser_batch(
+            "shell words",
+            shell_words(),
+            &[
+                ("", Some(words(&[])), ""),
+                ("  \t\n ", Some(words(&[])), ""),
+                (" a  b\tc\n", Some(words(&["a", "b", "c"])), ""),
+                ("'a b' \"c d\"", Some(words(&["a b", "c d"])), ""),
+                ("a'b'\"c\"d", Some(words(&["abcd"])), ""),
+                ("'' \"\" a", Some(words(&["", "", "a"])), ""),
+                (r"'\n' '\'", Some(words(&[r"\n", r"\"])), ""),
+                (r#""\$\`\"\\\a""#, Some(words(&[r#"$`"\\a"#])), ""),
+                (r"a\ b \'c\\", Some(words(&["a b", "'c\\"])), ""),
+                ("a\\\nb \"c\\\nd\"", Some(words(&["ab", "cd"])), ""),
+                ("$HOME *.rs ~", Some(words(&["$HOME", "*.rs", "~"])), ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn errors() {
+        test_parser_errors(
+            "unfinished input",
+            shell_words(),
+            &[
+                ("'a", Error::Expected("closing quote").fatal()),
+                ("a \"b", Error::Expected("closing quote").fatal()),
+                ("\"a\\", Error::Expected("closing quote").fatal()),
+                (
+                    "a\\",
+                    Error::Expected("a char to escape after '\\'").fatal(),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        let value = words(&["", "it's", "a b", "\"$x\"", "\\", "plain-word.txt"]);
+        let text = shell_words().unparse(&value)?;
+
+        assert_eq!(text, r#"'' 'it'\''s' 'a b' '"$x"' '\' plain-word.txt"#);
+        assert_eq!(shell_words().parse_all(&text)?, value);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,516 @@
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine,
+    error::{failed_conversion, no_match},
+    grammar::Grammar,
+    unparse::Unparse,
+    Error, Parse, ParseResult,
+};
+
+/// This parser is returned by [`escape_sequence()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct EscapeSequence;
+
+impl Parse for EscapeSequence {
+    type Output = char;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let mut chars = input.chars();
+
+        let Some(c) = chars.next() else {
+            examine(0)?;
+            return Err(no_match(input));
+        };
+
+        let escaped = match c {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            '\\' | '\'' | '"' => c,
+            'u' => return unicode(input, chars.as_str()),
+            _ => {
+                examine(1)?;
+                return Err(no_match(input));
+            }
+        };
+
+        examine(1)?;
+        Ok((escaped, chars.as_str()))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Special("escape sequence".to_string())
+    }
+}
+
+/// Parses the hex digits of a `u{1F600}` or `u00E9` escape, after the `u`.
+fn unicode<'i>(input: &'i str, after_u: &'i str) -> ParseResult<'i, char> {
+    let (digits, remaining) = match after_u.strip_prefix('{') {
+        Some(braced) => {
+            let end = braced
+                .char_indices()
+                .take(7)
+                .find(|&(_, c)| c == '}')
+                .map(|(i, _)| i)
+                .ok_or_else(|| no_match(input))?;
+            (&braced[..end], &braced[end + 1..])
+        }
+        None => (
+            after_u.get(..4).ok_or_else(|| no_match(input))?,
+            &after_u[4..],
+        ),
+    };
+
+    let matched = &input[..input.len() - remaining.len()];
+    examine(matched.len())?;
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(no_match(input));
+    }
+
+    u32::from_str_radix(digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .map(|c| (c, remaining))
+        .ok_or_else(|| failed_conversion(matched, "not a valid char"))
+}
+
+impl Unparse for EscapeSequence {
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        match value {
+            '\n' => output.push('n'),
+            '\r' => output.push('r'),
+            '\t' => output.push('t'),
+            '\0' => output.push('0'),
+            '\\' | '\'' | '"' => output.push(*value),
+            c => output.push_str(&format!("u{{{:x}}}", u32::from(*c))),
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the escape sequences common to Rust, C and JSON, outputting the char they stand for.
+///
+/// This parses what comes after the `\`:
+///
+/// * `n`, `r`, `t` and `0` for a newline, carriage return, tab and NUL
+/// * `\`, `'` and `"` for themselves
+/// * `u{1F600}` with 1 to 6 hex digits, or `u00E9` with exactly 4, for any char
+///
+/// A `u` escape of a number that isn't a valid char, such as a surrogate, fails with [`Error::FailedConversion`].
+///
+/// This is the default escape sequence of [`string_literal()`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{escape_sequence, Parse};
+///
+/// assert_eq!(escape_sequence().parse("n!")?, ('\n', "!"));
+/// assert_eq!(escape_sequence().parse("u{1F600}")?, ('😀', ""));
+/// assert_eq!(escape_sequence().parse("u00e9")?, ('é', ""));
+/// assert!(escape_sequence().parse("q").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn escape_sequence() -> EscapeSequence {
+    EscapeSequence
+}
+
+/// This parser is returned by [`string_literal()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct StringLiteral<E> {
+    open: &'static str,
+    close: &'static str,
+
+    /// The char that starts an escape, and the parser of what follows it, or `None` for a raw string.
+    escape: Option<(char, E)>,
+
+    /// What comes before a fenced string and the char repeated around it, see [`StringLiteral::fenced()`].
+    fence: Option<(&'static str, char)>,
+}
+
+impl<E> StringLiteral<E> {
+    /// Uses `open` and `close` around the string instead of `"`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{string_literal, Parse};
+    ///
+    /// let heredoc = string_literal().delimiters("<<EOF\n", "\nEOF");
+    /// assert_eq!(heredoc.parse("<<EOF\nsay \"hi\"\nEOF;")?, ("say \"hi\"".to_string(), ";"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn delimiters(self, open: &'static str, close: &'static str) -> Self {
+        StringLiteral {
+            open,
+            close,
+            ..self
+        }
+    }
+
+    /// Starts escapes with `introducer` instead of `\`, parsing what follows it with `escapes`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, string_literal, Lex, Parse};
+    ///
+    /// // SQL doubles the quote to escape it
+    /// let sql = string_literal().delimiters("'", "'").escape('\'', char('\'').map(|_| '\''));
+    /// assert_eq!(sql.parse("'it''s' AS x")?, ("it's".to_string(), " AS x"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn escape<F>(self, introducer: char, escapes: F) -> StringLiteral<F>
+    where
+        F: Parse<Output = char>,
+    {
+        StringLiteral {
+            open: self.open,
+            close: self.close,
+            escape: Some((introducer, escapes)),
+            fence: self.fence,
+        }
+    }
+
+    /// Takes the contents as they are, without any escapes.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{string_literal, Parse};
+    ///
+    /// let raw = string_literal().delimiters("`", "`").raw();
+    /// assert_eq!(raw.parse(r"`C:\path\n`")?, (r"C:\path\n".to_string(), ""));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn raw(self) -> Self {
+        StringLiteral {
+            escape: None,
+            ..self
+        }
+    }
+
+    /// Starts the string with `prefix`, then any number of `fence` chars, then the opening delimiter.
+    /// The string only ends at a closing delimiter followed by the same number of `fence` chars.
+    ///
+    /// Together with [`StringLiteral::raw()`] this parses Rust's raw strings, which can contain quotes by fencing them with `#`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{string_literal, Parse};
+    ///
+    /// let rust_raw = string_literal().raw().fenced("r", '#');
+    ///
+    /// assert_eq!(rust_raw.parse(r#"r"a\b" x"#)?, (r"a\b".to_string(), " x"));
+    /// assert_eq!(rust_raw.parse(r###"r##"say "#hi"#"##"###)?, (r##"say "#hi"#"##.to_string(), ""));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn fenced(self, prefix: &'static str, fence: char) -> Self {
+        StringLiteral {
+            fence: Some((prefix, fence)),
+            ..self
+        }
+    }
+
+    /// The closing delimiter followed by `fences` fence chars.
+    fn closing(&self, fences: usize) -> String {
+        let mut closing = self.close.to_string();
+        if let Some((_, fence)) = self.fence {
+            closing.extend(std::iter::repeat_n(fence, fences));
+        }
+        closing
+    }
+}
+
+impl<E> Parse for StringLiteral<E>
+where
+    E: Parse<Output = char>,
+{
+    type Output = String;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let mut remaining = input;
+        let mut fences = 0;
+
+        if let Some((prefix, fence)) = self.fence {
+            remaining = remaining
+                .strip_prefix(prefix)
+                .ok_or_else(|| no_match(input))?;
+
+            let after = remaining.trim_start_matches(fence);
+            fences = (remaining.len() - after.len()) / fence.len_utf8();
+            remaining = after;
+        }
+
+        remaining = remaining
+            .strip_prefix(self.open)
+            .ok_or_else(|| no_match(input))?;
+
+        let closing = self.closing(fences);
+        let mut value = String::new();
+
+        loop {
+            let mut chars = remaining.chars();
+            let next = chars.next();
+
+            // escapes come first, in case the introducer is also the closing delimiter, like SQL's `''`
+            if let (Some(c), Some((introducer, escapes))) = (next, &self.escape) {
+                if c == *introducer {
+                    match escapes.parse(chars.as_str()) {
+                        Ok((escaped, after)) => {
+                            value.push(escaped);
+                            remaining = after;
+                            continue;
+                        }
+                        Err(error) if !remaining.starts_with(closing.as_str()) => {
+                            return Err(error)
+                        }
+                        Err(_) => {}
+                    }
+                }
+            }
+
+            if let Some(after) = remaining.strip_prefix(closing.as_str()) {
+                examine(input.len() - after.len())?;
+                return Ok((value, after));
+            }
+
+            match next {
+                Some(c) => {
+                    value.push(c);
+                    remaining = chars.as_str();
+                }
+                None => {
+                    examine(input.len())?;
+                    return Err(Error::Expected("the end of the string literal"));
+                }
+            }
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        let mut items = Vec::new();
+
+        if let Some((prefix, fence)) = self.fence {
+            items.push(Grammar::Token(prefix.to_string()));
+            items.push(Grammar::repeat(0, None, Grammar::Token(fence.to_string())));
+        }
+
+        items.push(Grammar::Token(self.open.to_string()));
+        items.push(Grammar::Special("string contents".to_string()));
+        items.push(Grammar::Token(self.close.to_string()));
+
+        Grammar::Sequence(items)
+    }
+}
+
+impl<E> Unparse for StringLiteral<E>
+where
+    E: Unparse<Output = char>,
+{
+    /// Escapes the introducer, control chars and anything that would close the string, and writes raw strings as they are.
+    ///
+    /// Fenced strings use as few fence chars as they can. A raw string that isn't fenced can't contain its closing delimiter.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let body = match &self.escape {
+            None => value.clone(),
+            Some((introducer, escapes)) => {
+                let mut body = String::new();
+                for (i, c) in value.char_indices() {
+                    let closes = !self.close.is_empty() && value[i..].starts_with(self.close);
+
+                    if c == *introducer || c.is_control() || closes {
+                        body.push(*introducer);
+                        escapes.unparse_into(&c, &mut body)?;
+                    } else {
+                        body.push(c);
+                    }
+                }
+                body
+            }
+        };
+
+        // in a raw string, the first closing delimiter found must be the one written after the body
+        let ends_in_place =
+            |closing: &str| format!("{body}{closing}").find(closing) == Some(body.len());
+
+        let fences = match (&self.escape, self.fence) {
+            (Some(_), _) => Some(0),
+            (None, Some(_)) => (0..).find(|&fences| ends_in_place(&self.closing(fences))),
+            (None, None) => Some(0).filter(|_| ends_in_place(self.close)),
+        }
+        .ok_or_else(|| {
+            Error::custom(format!(
+                "{value:?} can't be written between {:?} and {:?} without escapes",
+                self.open, self.close
+            ))
+        })?;
+
+        if let Some((prefix, fence)) = self.fence {
+            output.push_str(prefix);
+            output.extend(std::iter::repeat_n(fence, fences));
+        }
+        output.push_str(self.open);
+        output.push_str(&body);
+        output.push_str(&self.closing(fences));
+
+        Ok(())
+    }
+}
+
+/// Parses a string literal, such as `"a \"quoted\" string\n"`, outputting its contents with the escapes replaced by the chars they stand for.
+///
+/// By default the string is wrapped in `"` and escapes start with `\`, followed by one of the [`escape_sequence()`]s.
+/// This can be configured:
+///
+/// * [`StringLiteral::delimiters()`] changes what opens and closes the string
+/// * [`StringLiteral::escape()`] changes the escapes, e.g. to support more of them
+/// * [`StringLiteral::raw()`] takes the contents as they are, without escapes
+/// * [`StringLiteral::fenced()`] allows a number of fence chars around the string, like Rust's `r#"..."#`
+///
+/// Once the string has been opened, failing to find its end is an [`Error::Expected`] error.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{string_literal, Parse, Unparse};
+///
+/// let string = string_literal();
+///
+/// let (value, remaining) = string.parse(r#""tab\tquote\"smile\u{1F600}", next"#)?;
+/// assert_eq!(value, "tab\tquote\"smile😀");
+/// assert_eq!(remaining, ", next");
+///
+/// assert_eq!(string.unparse(&value)?, r#""tab\tquote\"smile😀""#);
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn string_literal() -> StringLiteral<EscapeSequence> {
+    StringLiteral {
+        open: "\"",
+        close: "\"",
+        escape: Some(('\\', EscapeSequence)),
+        fence: None,
+    }
+}
+
+impl fmt::Debug for EscapeSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EscapeSequence")
+    }
+}
+
+impl<E: fmt::Debug> fmt::Debug for StringLiteral<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StringLiteral({:?} {:?}", self.open, self.close)?;
+        if let Some((introducer, escapes)) = &self.escape {
+            write!(f, ", escape {introducer:?} {escapes:?}")?;
+        }
+        if let Some((prefix, fence)) = &self.fence {
+            write!(f, ", fenced {prefix:?} {fence:?}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, testing::*, Lex};
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "default string literals",
+            string_literal(),
+            &[
+                (r#""""#, Some(String::new()), ""),
+                (r#""abc"def"#, Some("abc".to_string()), "def"),
+                (r#""a\"b\\c\n""#, Some("a\"b\\c\n".to_string()), ""),
+                (r#""\u00e9\u{e9}\0""#, Some("éé\0".to_string()), ""),
+                (
+                    r#""new
+line""#,
+                    Some("new\nline".to_string()),
+                    "",
+                ),
+                (r#""\q""#, None, ""),
+                (r#""\u{110000}""#, None, ""),
+                (r#""\u{}""#, None, ""),
+                (r#""\u12""#, None, ""),
+                ("'a'", None, ""),
+                ("abc", None, ""),
+            ],
+        );
+
+        test_parser_errors(
+            "unclosed string literals",
+            string_literal(),
+            &[
+                ("\"abc", Error::Expected("the end of the string literal")),
+                (
+                    "\"abc\\\"",
+                    Error::Expected("the end of the string literal"),
+                ),
+                (
+                    "\"\\u{D800}\"",
+                    failed_conversion("u{D800}", "not a valid char"),
+                ),
+            ],
+        );
+
+        test_parser_batch(
+            "fenced and escaped",
+            string_literal()
+                .delimiters("'", "'")
+                .fenced("", '#')
+                .escape('%', char('%').map(|_| '%')),
+            &[
+                ("'a%%b'", Some("a%b".to_string()), ""),
+                ("#'it's'#'", Some("it's".to_string()), "'"),
+                ("##'a'#'##", Some("a'#".to_string()), ""),
+                ("#'a'", None, ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        fn round_trip(string: &impl Unparse<Output = String>) -> Result<(), Error> {
+            for value in ["", "plain", "\"quoted\"", "back\\slash\n", "\"#", "é😀"] {
+                let value = value.to_string();
+                assert_eq!(string.parse_all(&string.unparse(&value)?)?, value);
+            }
+            Ok(())
+        }
+
+        round_trip(&string_literal())?;
+        round_trip(&string_literal().raw().fenced("r", '#'))?;
+        round_trip(&string_literal().delimiters("<<", ">>"))?;
+
+        let rust_raw = string_literal().raw().fenced("r", '#');
+        assert_eq!(rust_raw.unparse(&"a\"b".to_string())?, "r#\"a\"b\"#");
+        assert_eq!(rust_raw.unparse(&"a\"#b".to_string())?, "r##\"a\"#b\"##");
+
+        let raw = string_literal().raw();
+        assert!(raw.unparse(&"a\"b".to_string()).is_err());
+
+        Ok(())
+    }
+}
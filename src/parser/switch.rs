@@ -1,7 +1,33 @@
-use crate::{token, Error, Lex, Parse};
+use std::{fmt, sync::OnceLock};
 
+use crate::{
+    combinator::{
+        active,
+        budget::examine,
+        capture::{backtrack, mark, rewind},
+    },
+    error::no_match,
+    grammar::Grammar,
+    Error, Lex, Parse,
+};
+
+use self::trie::Trie;
+
+mod token_enum;
+mod trie;
+
+pub use self::token_enum::{switch_enum, SwitchEnum, TokenEnum};
+
+/// This parser is returned by [`switch()`]. See it's documentation for more details.
+#[derive(Clone)]
 pub struct Switch<L, T, const N: usize> {
     items: [(L, T); N],
+
+    /// Whether to pick the longest match rather than the first match, see [`Switch::longest()`].
+    longest: bool,
+
+    /// Only used when the keys are `&str`, built the first time the switch is used to parse.
+    trie: OnceLock<Trie>,
 }
 
 /// Creates a Switch parser that parses input by trying each provided lexer in turn and mapping them to the corresponding output.
@@ -60,8 +86,59 @@ pub struct Switch<L, T, const N: usize> {
 /// assert_eq!(my_token_parser.parse("foo 123")?, (MyTokens::Foo, " 123"));
 /// # Ok::<(), parsely::Error>(())
 /// ```
+///
+/// # Performance
+///
+/// When the keys are `&str`, they are built into a prefix trie the first time the switch is used.
+/// Finding the matching key then takes time proportional to the length of the key rather than the number of keys,
+/// so large keyword tables stay fast.
+///
+/// Keys that are lexers are still tried one at a time, in order.
+///
+/// To keep a table of `&str` keys next to the type it parses, see [`TokenEnum`] and [`switch_enum()`].
 pub fn switch<L, T, const N: usize>(items: [(L, T); N]) -> Switch<L, T, N> {
-    Switch { items }
+    Switch {
+        items,
+        longest: false,
+        trie: OnceLock::new(),
+    }
+}
+
+impl<L, T, const N: usize> Switch<L, T, N> {
+    /// Makes this switch pick the key with the longest match, rather than the first key (in declaration order) that matches.
+    ///
+    /// When several keys match the same length of input, the first declared one is picked.
+    /// Only the picked key's [captures](crate::combinator::capture) are kept.
+    ///
+    /// Keyword tables almost always want this, otherwise a key such as `"in"` declared before `"int"` would prevent `"int"` from ever matching.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{switch, Parse};
+    ///
+    /// #[derive(Debug, PartialEq, Clone, Copy)]
+    /// pub enum Keyword {
+    ///     In,
+    ///     Int,
+    /// }
+    ///
+    /// let first = switch([("in", Keyword::In), ("int", Keyword::Int)]);
+    /// assert_eq!(first.parse("int x")?, (Keyword::In, "t x"));
+    ///
+    /// let longest = switch([("in", Keyword::In), ("int", Keyword::Int)]).longest();
+    /// assert_eq!(longest.parse("int x")?, (Keyword::Int, " x"));
+    /// assert_eq!(longest.parse("in x")?, (Keyword::In, " x"));
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    pub fn longest(self) -> Self {
+        Switch {
+            longest: true,
+            ..self
+        }
+    }
 }
 
 impl<L, T, const N: usize> Parse for Switch<L, T, N>
@@ -72,13 +149,48 @@ where
     type Output = T;
 
     fn parse<'i>(&self, input: &'i str) -> crate::ParseResult<'i, Self::Output> {
+        if self.longest {
+            let mut best: Option<(&L, &T, &'i str)> = None;
+
+            // every key is an attempt that is abandoned, even the ones that match
+            for (lexer, output) in self.items.iter() {
+                let mark = mark();
+                let result = lexer.lex(input);
+                rewind(mark);
+
+                if let Ok((_, remaining)) = result {
+                    match best {
+                        Some((.., best_remaining)) if best_remaining.len() <= remaining.len() => {}
+                        _ => best = Some((lexer, output, remaining)),
+                    }
+                }
+            }
+
+            let Some((lexer, output, remaining)) = best else {
+                return Err(Error::NoMatch);
+            };
+
+            // the winning key is lexed again to keep its captures, diagnostics and recovered errors
+            if active::is_active() {
+                lexer.lex(input)?;
+            }
+
+            return Ok((output.clone(), remaining));
+        }
+
         for (lexer, output) in self.items.iter() {
-            if let Ok((_, remaining)) = lexer.lex(input) {
+            if let Ok((_, remaining)) = backtrack(|| lexer.lex(input)) {
                 return Ok((output.clone(), remaining));
             }
         }
         Err(Error::NoMatch)
     }
+
+    fn describe(&self) -> Grammar {
+        let mut keys = self.items.iter().map(|(lexer, _)| lexer.describe());
+        let first = keys.next().unwrap_or(Grammar::Empty);
+        keys.fold(first, Grammar::or)
+    }
 }
 
 impl<T, const N: usize> Parse for Switch<&'static str, T, N>
@@ -88,12 +200,170 @@ where
     type Output = T;
 
     fn parse<'i>(&self, input: &'i str) -> crate::ParseResult<'i, Self::Output> {
-        for (s, output) in self.items.iter() {
-            let lexer = token(s);
-            if let Ok((_, remaining)) = lexer.lex(input) {
-                return Ok((output.clone(), remaining));
+        let trie = self
+            .trie
+            .get_or_init(|| Trie::new(self.items.iter().map(|(key, _)| *key)));
+
+        let found = if self.longest {
+            trie.longest(input)
+        } else {
+            trie.first(input)
+        };
+        examine(found.map_or(0, |(_, len)| len))?;
+
+        match found {
+            Some((index, len)) => Ok((self.items[index].1.clone(), &input[len..])),
+            None => Err(no_match(input)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        let mut keys = self
+            .items
+            .iter()
+            .map(|(key, _)| Grammar::Token(key.to_string()));
+        let first = keys.next().unwrap_or(Grammar::Empty);
+        keys.fold(first, Grammar::or)
+    }
+}
+
+impl<L, T, const N: usize> fmt::Debug for Switch<L, T, N>
+where
+    L: fmt::Debug,
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Switch([")?;
+        for (i, (lexer, output)) in self.items.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
             }
+            write!(f, "{lexer:?} => {output:?}")?;
         }
-        Err(Error::NoMatch)
+        write!(f, "])")?;
+
+        if self.longest {
+            write!(f, ".longest()")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::token;
+
+    #[test]
+    fn str_keys() {
+        test_parser_batch(
+            "str keys match in declaration order",
+            switch([("foo", 1), ("bar", 2), ("foobar", 3), ("fo", 4)]),
+            &[
+                ("foo", Some(1), ""), //
+                ("foobar", Some(1), "bar"),
+                ("barfoo", Some(2), "foo"),
+                ("fob", Some(4), "b"),
+                ("baz", None, "baz"),
+                ("", None, ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn lexer_keys() {
+        test_parser_batch(
+            "lexer keys match in declaration order",
+            switch([(token("foo"), 1), (token("bar"), 2), (token("foobar"), 3)]),
+            &[
+                ("foo", Some(1), ""), //
+                ("foobar", Some(1), "bar"),
+                ("barfoo", Some(2), "foo"),
+                ("baz", None, "baz"),
+            ],
+        );
+    }
+
+    #[test]
+    fn longest() {
+        test_parser_batch(
+            "str keys pick the longest match",
+            switch([("in", 1), ("int", 2), ("integer", 3), ("int", 4)]).longest(),
+            &[
+                ("in", Some(1), ""), //
+                ("int", Some(2), ""),
+                ("integers", Some(3), "s"),
+                ("intege", Some(2), "ege"),
+                ("i", None, "i"),
+            ],
+        );
+
+        test_parser_batch(
+            "lexer keys pick the longest match",
+            switch([
+                (token("in"), 1),
+                (token("int"), 2),
+                (token("integer"), 3),
+                (token("int"), 4),
+            ])
+            .longest(),
+            &[
+                ("in", Some(1), ""), //
+                ("int", Some(2), ""),
+                ("integers", Some(3), "s"),
+                ("intege", Some(2), "ege"),
+                ("i", None, "i"),
+            ],
+        );
+    }
+
+    #[test]
+    fn longest_discards_losing_keys() {
+        use crate::combinator::capture::with_captures;
+
+        let keywords = switch([
+            (token("i").capture("short").then(token("n")), 1),
+            (token("in").capture("long").then(token("t")), 2),
+            (token("in").capture("failed").then(token("x")), 3),
+        ])
+        .longest();
+
+        let ((output, captures), remaining) = with_captures(keywords).parse("int x").unwrap();
+        assert_eq!((output, remaining), (2, " x"));
+        assert_eq!(&captures["long"], "in");
+        assert_eq!(captures.len(), 1);
+    }
+
+    #[test]
+    fn many_str_keys() {
+        let keywords: [(&'static str, usize); 64] = std::array::from_fn(|i| {
+            let key: &'static str = Box::leak(format!("keyword{i}").into_boxed_str());
+            (key, i)
+        });
+
+        test_parser_batch(
+            "large keyword tables",
+            switch(keywords),
+            &[
+                ("keyword0", Some(0), ""), //
+                ("keyword7 ", Some(7), " "),
+                ("keyword63", Some(6), "3"),
+                ("keyword", None, "keyword"),
+            ],
+        );
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(
+            format!("{:?}", switch([("a", 1), ("b", 2)]).longest()),
+            r#"Switch(["a" => 1, "b" => 2]).longest()"#
+        );
+
+        assert_eq!(
+            format!("{:?}", switch([(token("a"), 'a')])),
+            r#"Switch([Token("a") => 'a'])"#
+        );
     }
 }
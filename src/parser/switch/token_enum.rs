@@ -0,0 +1,241 @@
+use std::{any::type_name, fmt, marker::PhantomData, sync::OnceLock};
+
+use crate::{
+    combinator::budget::examine, error::no_match, grammar::Grammar, unparse::Unparse, Error, Parse,
+    ParseResult,
+};
+
+use super::trie::Trie;
+
+/// A type, usually an enum, whose values are each written as a token, such as keywords or operators.
+///
+/// Implement this to keep the table of tokens next to the type, and parse it with [`switch_enum()`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{switch_enum, Parse, TokenEnum};
+///
+/// #[derive(Debug, PartialEq, Clone, Copy)]
+/// enum Op {
+///     Add,
+///     Sub,
+/// }
+///
+/// impl TokenEnum for Op {
+///     fn variants() -> &'static [(&'static str, Self)] {
+///         &[("+", Op::Add), ("-", Op::Sub)]
+///     }
+/// }
+///
+/// assert_eq!(switch_enum::<Op>().parse("-1")?, (Op::Sub, "1"));
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub trait TokenEnum: Sized + 'static {
+    /// Each token and the value it stands for, in the order they are tried.
+    ///
+    /// A value can have more than one token, the first one is used when unparsing.
+    fn variants() -> &'static [(&'static str, Self)];
+}
+
+/// This parser is returned by [`switch_enum()`]. See it's documentation for more details.
+pub struct SwitchEnum<T> {
+    /// Whether to pick the longest match rather than the first match, see [`SwitchEnum::longest()`].
+    longest: bool,
+
+    /// Built the first time the switch is used to parse.
+    trie: OnceLock<Trie>,
+
+    output: PhantomData<fn() -> T>,
+}
+
+/// Creates a parser for a [`TokenEnum`], from the table of tokens returned by [`TokenEnum::variants()`].
+///
+/// This works like [`switch()`](crate::switch()) with `&str` keys, but the table is kept with the type instead of being repeated wherever it is parsed.
+/// The tokens are tried in order, use [`SwitchEnum::longest()`] to pick the longest match instead.
+///
+/// Unparsing writes the first token of the value, so the type must implement `PartialEq`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{switch_enum, Parse, TokenEnum, Unparse};
+///
+/// #[derive(Debug, PartialEq, Clone, Copy)]
+/// enum Keyword {
+///     In,
+///     Int,
+///     Let,
+/// }
+///
+/// impl TokenEnum for Keyword {
+///     fn variants() -> &'static [(&'static str, Self)] {
+///         &[("in", Keyword::In), ("int", Keyword::Int), ("let", Keyword::Let)]
+///     }
+/// }
+///
+/// let keyword = switch_enum::<Keyword>().longest();
+///
+/// assert_eq!(keyword.parse("int x")?, (Keyword::Int, " x"));
+/// assert_eq!(keyword.unparse(&Keyword::Let)?, "let");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn switch_enum<T: TokenEnum>() -> SwitchEnum<T> {
+    SwitchEnum {
+        longest: false,
+        trie: OnceLock::new(),
+        output: PhantomData,
+    }
+}
+
+impl<T> SwitchEnum<T> {
+    /// Makes this switch pick the token with the longest match, rather than the first token (in declaration order) that matches.
+    ///
+    /// When several tokens match the same length of input, the first declared one is picked.
+    pub fn longest(self) -> Self {
+        SwitchEnum {
+            longest: true,
+            ..self
+        }
+    }
+}
+
+impl<T: TokenEnum + Clone> Parse for SwitchEnum<T> {
+    type Output = T;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let variants = T::variants();
+        let trie = self
+            .trie
+            .get_or_init(|| Trie::new(variants.iter().map(|(key, _)| *key)));
+
+        let found = if self.longest {
+            trie.longest(input)
+        } else {
+            trie.first(input)
+        };
+        examine(found.map_or(0, |(_, len)| len))?;
+
+        match found {
+            Some((index, len)) => Ok((variants[index].1.clone(), &input[len..])),
+            None => Err(no_match(input)),
+        }
+    }
+
+    fn describe(&self) -> Grammar {
+        let mut keys = T::variants()
+            .iter()
+            .map(|(key, _)| Grammar::Token(key.to_string()));
+        let first = keys.next().unwrap_or(Grammar::Empty);
+        keys.fold(first, Grammar::or)
+    }
+}
+
+impl<T: TokenEnum + Clone + PartialEq> Unparse for SwitchEnum<T> {
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let (key, _) = T::variants()
+            .iter()
+            .find(|(_, variant)| variant == value)
+            .ok_or_else(|| {
+                Error::custom(format!("the value has no token in {}", type_name::<T>()))
+            })?;
+
+        output.push_str(key);
+        Ok(())
+    }
+}
+
+impl<T> Clone for SwitchEnum<T> {
+    fn clone(&self) -> Self {
+        SwitchEnum {
+            longest: self.longest,
+            trie: self.trie.clone(),
+            output: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for SwitchEnum<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SwitchEnum<{}>", type_name::<T>())?;
+
+        if self.longest {
+            write!(f, ".longest()")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    enum Compare {
+        Less,
+        LessOrEqual,
+        Equal,
+        NotEqual,
+    }
+
+    impl TokenEnum for Compare {
+        fn variants() -> &'static [(&'static str, Self)] {
+            &[
+                ("<", Compare::Less),
+                ("<=", Compare::LessOrEqual),
+                ("==", Compare::Equal),
+                ("!=", Compare::NotEqual),
+                ("<>", Compare::NotEqual),
+            ]
+        }
+    }
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "first match",
+            switch_enum::<Compare>(),
+            &[
+                ("<", Some(Compare::Less), ""),
+                ("<=", Some(Compare::Less), "="),
+                ("<>", Some(Compare::Less), ">"),
+                ("==1", Some(Compare::Equal), "1"),
+                ("=", None, "="),
+            ],
+        );
+
+        test_parser_batch(
+            "longest match",
+            switch_enum::<Compare>().longest(),
+            &[
+                ("<", Some(Compare::Less), ""),
+                ("<=", Some(Compare::LessOrEqual), ""),
+                ("<>", Some(Compare::NotEqual), ""),
+                ("!=", Some(Compare::NotEqual), ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn unparsing() -> Result<(), Error> {
+        let compare = switch_enum::<Compare>();
+
+        assert_eq!(compare.unparse(&Compare::LessOrEqual)?, "<=");
+        assert_eq!(compare.unparse(&Compare::NotEqual)?, "!=");
+
+        Ok(())
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(
+            format!("{:?}", switch_enum::<Compare>().longest()),
+            "SwitchEnum<parsely::parser::switch::token_enum::tests::Compare>.longest()"
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! A byte-wise prefix trie used by [`switch()`](crate::switch) to look up `&str` keys.
+//!
+//! Looking up the keys that prefix some input walks the trie once, so it costs `O(key length)` regardless of how many keys there are.
+
+/// A prefix trie of keys, remembering the index each key was inserted with.
+#[derive(Clone, Debug)]
+pub(crate) struct Trie {
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Node {
+    /// Sorted by byte so children can be binary searched.
+    children: Vec<(u8, usize)>,
+
+    /// The index of the key that ends at this node, if any.
+    ///
+    /// When the same key is inserted twice, the first index is kept.
+    key: Option<usize>,
+}
+
+impl Trie {
+    /// Builds a trie from keys, each key is identified by its position in the iterator.
+    pub(crate) fn new<'k>(keys: impl IntoIterator<Item = &'k str>) -> Self {
+        let mut trie = Trie {
+            nodes: vec![Node::default()],
+        };
+
+        for (index, key) in keys.into_iter().enumerate() {
+            trie.insert(index, key);
+        }
+
+        trie
+    }
+
+    fn insert(&mut self, index: usize, key: &str) {
+        let mut node = 0;
+
+        for byte in key.bytes() {
+            node = match self.nodes[node]
+                .children
+                .binary_search_by_key(&byte, |(b, _)| *b)
+            {
+                Ok(i) => self.nodes[node].children[i].1,
+                Err(i) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children.insert(i, (byte, child));
+                    child
+                }
+            };
+        }
+
+        self.nodes[node].key.get_or_insert(index);
+    }
+
+    /// Returns every key that is a prefix of `input` as `(index, length)`, shortest first.
+    pub(crate) fn prefixes<'t>(
+        &'t self,
+        input: &'t str,
+    ) -> impl Iterator<Item = (usize, usize)> + 't {
+        let mut bytes = input.bytes().enumerate();
+        let mut node = Some(0);
+
+        std::iter::from_fn(move || loop {
+            let current = node?;
+            let key = self.nodes[current].key;
+
+            let (depth, next) = match bytes.next() {
+                Some((depth, byte)) => (
+                    depth,
+                    self.nodes[current]
+                        .children
+                        .binary_search_by_key(&byte, |(b, _)| *b)
+                        .ok()
+                        .map(|i| self.nodes[current].children[i].1),
+                ),
+                None => (input.len(), None),
+            };
+
+            node = next;
+
+            if let Some(index) = key {
+                return Some((index, depth));
+            }
+        })
+    }
+
+    /// Returns the key that was inserted first out of all the keys that prefix `input`.
+    pub(crate) fn first(&self, input: &str) -> Option<(usize, usize)> {
+        self.prefixes(input).min_by_key(|(index, _)| *index)
+    }
+
+    /// Returns the longest of all the keys that prefix `input`.
+    pub(crate) fn longest(&self, input: &str) -> Option<(usize, usize)> {
+        self.prefixes(input).last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefixes() {
+        let trie = Trie::new(["int", "in", "i", "out", "in"]);
+
+        assert_eq!(
+            trie.prefixes("integer").collect::<Vec<_>>(),
+            vec![(2, 1), (1, 2), (0, 3)]
+        );
+        assert_eq!(
+            trie.prefixes("in").collect::<Vec<_>>(),
+            vec![(2, 1), (1, 2)]
+        );
+        assert_eq!(trie.prefixes("outer").collect::<Vec<_>>(), vec![(3, 3)]);
+        assert_eq!(trie.prefixes("xyz").collect::<Vec<_>>(), vec![]);
+        assert_eq!(trie.prefixes("").collect::<Vec<_>>(), vec![]);
+    }
+
+    #[test]
+    fn first() {
+        let trie = Trie::new(["int", "in", "", "âb"]);
+
+        assert_eq!(trie.first("integer"), Some((0, 3)));
+        assert_eq!(trie.first("inside"), Some((1, 2)));
+        assert_eq!(trie.first("âbc"), Some((2, 0)));
+        assert_eq!(trie.first(""), Some((2, 0)));
+
+        let trie = Trie::new(["âb", "â"]);
+        assert_eq!(trie.first("âbc"), Some((0, 3)));
+        assert_eq!(trie.first("âc"), Some((1, 2)));
+        assert_eq!(trie.first("a"), None);
+    }
+
+    #[test]
+    fn longest() {
+        let trie = Trie::new(["in", "int", "", "integer", "int"]);
+
+        assert_eq!(trie.longest("integer"), Some((3, 7)));
+        assert_eq!(trie.longest("intege"), Some((1, 3)));
+        assert_eq!(trie.longest("inside"), Some((0, 2)));
+        assert_eq!(trie.longest("out"), Some((2, 0)));
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+
+use crate::{
+    combinator::budget::examine,
+    error::{failed_conversion, no_match},
+    grammar::Grammar,
+    unparse::Unparse,
+    Error, Parse, ParseResult,
+};
+
+/// This parser is returned by [`utc_offset()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct UtcOffset;
+
+impl Parse for UtcOffset {
+    type Output = i32;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let bytes = input.as_bytes();
+
+        let sign = match bytes.first() {
+            Some(b'Z' | b'z') => {
+                examine(1)?;
+                return Ok((0, &input[1..]));
+            }
+            Some(b'+') => 1,
+            Some(b'-') => -1,
+            _ => {
+                examine(1)?;
+                return Err(no_match(input));
+            }
+        };
+
+        let two_digits = |at: usize| match bytes.get(at..at + 2) {
+            Some(&[a, b]) if a.is_ascii_digit() && b.is_ascii_digit() => {
+                Some(i32::from(a - b'0') * 10 + i32::from(b - b'0'))
+            }
+            _ => None,
+        };
+
+        // the minutes come straight after the hours, or after a ':'
+        let minutes_at = if bytes.get(3) == Some(&b':') { 4 } else { 3 };
+        let len = minutes_at + 2;
+        examine(len)?;
+
+        let (Some(hours), Some(minutes)) = (two_digits(1), two_digits(minutes_at)) else {
+            return Err(no_match(input));
+        };
+
+        if hours > 23 || minutes > 59 {
+            return Err(failed_conversion(&input[..len], "not a valid UTC offset"));
+        }
+
+        Ok((sign * (hours * 60 + minutes), &input[len..]))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::Choice(vec![
+            Grammar::TokenAnyCase("Z".to_string()),
+            Grammar::Sequence(vec![
+                Grammar::Choice(vec![
+                    Grammar::Token("+".to_string()),
+                    Grammar::Token("-".to_string()),
+                ]),
+                Grammar::repeat(2, Some(2), Grammar::Class("digit".to_string())),
+                Grammar::Optional(Box::new(Grammar::Token(":".to_string()))),
+                Grammar::repeat(2, Some(2), Grammar::Class("digit".to_string())),
+            ]),
+        ])
+    }
+}
+
+impl Unparse for UtcOffset {
+    /// Writes an offset of 0 as `Z`, and any other offset as `+HH:MM` or `-HH:MM`.
+    ///
+    /// Offsets of a whole day or more can't be written and fail with a custom error.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error> {
+        let minutes = value.unsigned_abs();
+
+        if minutes >= 24 * 60 {
+            return Err(Error::custom(format!(
+                "{value} minutes can't be written as a UTC offset"
+            )));
+        }
+
+        if minutes == 0 {
+            output.push('Z');
+        } else {
+            let sign = if *value < 0 { '-' } else { '+' };
+            output.push_str(&format!("{sign}{:02}:{:02}", minutes / 60, minutes % 60));
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a UTC offset such as `Z`, `+02:00` or `-0530`, outputting the offset in minutes east of UTC.
+///
+/// The offset is either `Z` (or `z`) for UTC itself, or a sign followed by two digit hours and minutes, optionally separated by a `:`,
+/// as found at the end of RFC 3339 and ISO 8601 timestamps. Offsets west of UTC are negative.
+///
+/// Anything else doesn't match, including offsets without minutes such as `+02`.
+/// Hours above 23 or minutes above 59 fail with [`Error::FailedConversion`].
+///
+/// Unparsing writes `Z` for an offset of 0, and `+HH:MM` or `-HH:MM` otherwise.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{utc_offset, Parse, Unparse};
+///
+/// assert_eq!(utc_offset().parse("Z")?, (0, ""));
+/// assert_eq!(utc_offset().parse("+02:00")?, (120, ""));
+/// assert_eq!(utc_offset().parse("-0530 ")?, (-330, " "));
+/// assert!(utc_offset().parse("+25:00").is_err());
+///
+/// assert_eq!(utc_offset().unparse(&-330)?, "-05:30");
+/// # Ok::<(), parsely::Error>(())
+/// ```
+///
+/// Read the offset at the end of a log timestamp:
+///
+/// ```
+/// use parsely::{take_while, utc_offset, Lex, Parse};
+///
+/// let time = take_while(|c| c.is_ascii_digit() || c == ':').map(str::to_string);
+/// let timestamp = time.then(utc_offset());
+///
+/// let ((time, offset), _) = timestamp.parse("12:30:00+01:00")?;
+/// assert_eq!(time, "12:30:00");
+/// assert_eq!(offset, 60);
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub const fn utc_offset() -> UtcOffset {
+    UtcOffset
+}
+
+impl fmt::Debug for UtcOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "UtcOffset")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn parsing() {
+        test_parser_batch(
+            "offsets",
+            utc_offset(),
+            &[
+                ("Z", Some(0), ""),
+                ("z ", Some(0), " "),
+                ("+00:00", Some(0), ""),
+                ("-00:00", Some(0), ""),
+                ("+02:00", Some(120), ""),
+                ("-0530", Some(-330), ""),
+                ("+23:59", Some(23 * 60 + 59), ""),
+                ("+01:30:00", Some(90), ":00"),
+                ("+12345", Some(12 * 60 + 34), "5"),
+                ("+02", None, ""),
+                ("+2:00", None, ""),
+                ("+02:0", None, ""),
+                ("02:00", None, ""),
+                ("UTC", None, ""),
+                ("", None, ""),
+            ],
+        );
+
+        test_parser_errors(
+            "out of range",
+            utc_offset(),
+            &[
+                (
+                    "+24:00",
+                    failed_conversion("+24:00", "not a valid UTC offset"),
+                ),
+                (
+                    "-0560",
+                    failed_conversion("-0560", "not a valid UTC offset"),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        for offset in [0, 60, -330, 23 * 60 + 59, -(23 * 60 + 59)] {
+            let text = utc_offset().unparse(&offset)?;
+            assert_eq!(utc_offset().parse_all(&text)?, offset);
+        }
+
+        assert_eq!(utc_offset().unparse(&0)?, "Z");
+        assert_eq!(utc_offset().unparse(&345)?, "+05:45");
+        assert!(utc_offset().unparse(&(24 * 60)).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,298 @@
+//! Generating random input that matches a lexer, e.g. to fuzz the round trip of your parsers.
+//!
+//! Call [`Lex::sample()`] with a [`Sampler`] to get a string that the lexer matches entirely.
+//! The generated input follows the lexer's own structure, so it can't drift from the grammar the way a hand written generator can.
+//!
+//! Lexers written as functions can't be looked inside, so they can't generate input.
+//! Neither can a few lexers that search the input, such as regular expressions.
+//! Sampling a lexer that contains one of them fails with [`SampleError::Unsupported`].
+//!
+//! With the `proptest` feature enabled, [`strategy()`] turns a lexer into a [proptest](https://docs.rs/proptest) strategy.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! use parsely::{char, digit, hex, Lex};
+//! use parsely::sample::Sampler;
+//!
+//! let version = digit().many(1..=3).then(char('.')).then(digit().many(1..=3));
+//!
+//! let mut sampler = Sampler::new(7);
+//!
+//! for _ in 0..10 {
+//!     let input = version.sample(&mut sampler)?;
+//!     assert_eq!(version.lex(&input)?, (input.as_str(), ""));
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::fmt;
+
+use crate::{grammar::Grammar, Lex};
+
+/// How many times generated input is checked against the lexer before giving up, see [`Lex::sample()`].
+const ATTEMPTS: usize = 100;
+
+/// Repetitions generate up to this many more items than their minimum.
+const SPREAD: usize = 4;
+
+const WHITESPACE: &[char] = &[' ', '\t', '\n', '\r', '\u{a0}'];
+const NON_ASCII: &[char] = &['é', 'Ä', 'ß', 'λ', 'Ж', '٣', '中', '😀'];
+
+/// A source of randomness for generating input, see the [module documentation](self).
+///
+/// The same seed always generates the same input, so a failing case can be reproduced.
+#[derive(Clone, Debug)]
+pub struct Sampler {
+    state: u64,
+}
+
+impl Sampler {
+    /// Creates a sampler from a seed.
+    pub fn new(seed: u64) -> Self {
+        Sampler { state: seed }
+    }
+
+    /// Returns the next random number.
+    pub fn next_u64(&mut self) -> u64 {
+        // splitmix64
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number less than `n`, or 0 if `n` is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        if n == 0 {
+            0
+        } else {
+            (self.next_u64() % n as u64) as usize
+        }
+    }
+
+    /// Returns true or false with equal chance.
+    pub fn coin(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Returns a random char: mostly printable ASCII, with some whitespace and non-ASCII chars.
+    pub fn any_char(&mut self) -> char {
+        match self.below(8) {
+            0 => WHITESPACE[self.below(WHITESPACE.len())],
+            1 => NON_ASCII[self.below(NON_ASCII.len())],
+            _ => char::from(b' ' + self.below(95) as u8),
+        }
+    }
+
+    /// Returns a random char that satisfies the condition, or `None` if no char could be found.
+    ///
+    /// Only the chars that [`any_char()`](Sampler::any_char()) returns are tried.
+    pub fn char_where(&mut self, condition: impl Fn(char) -> bool) -> Option<char> {
+        for _ in 0..ATTEMPTS {
+            let c = self.any_char();
+            if condition(c) {
+                return Some(c);
+            }
+        }
+
+        // fall back to checking every candidate, starting from a random one
+        let candidates: Vec<char> = (b' '..=b'~')
+            .map(char::from)
+            .chain(WHITESPACE.iter().copied())
+            .chain(NON_ASCII.iter().copied())
+            .collect();
+        let start = self.below(candidates.len());
+
+        candidates[start..]
+            .iter()
+            .chain(&candidates[..start])
+            .copied()
+            .find(|&c| condition(c))
+    }
+
+    /// Chooses how many times to repeat an item that must repeat between `min` and `max` times.
+    pub(crate) fn count(&mut self, min: usize, max: usize) -> usize {
+        (min + self.below(SPREAD + 1)).min(max)
+    }
+
+    /// Generates `count` items, with a separator in between each one.
+    pub(crate) fn repeat(
+        &mut self,
+        count: usize,
+        output: &mut String,
+        item: &(impl Lex + ?Sized),
+        separator: Option<&dyn Lex>,
+    ) -> Result<(), SampleError> {
+        for i in 0..count {
+            if let (true, Some(separator)) = (i > 0, separator) {
+                separator.sample_into(self, output)?;
+            }
+            item.sample_into(self, output)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The reason input couldn't be generated for a lexer.
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+pub enum SampleError {
+    /// Part of the lexer doesn't know how to generate input, e.g. a lexer written as a function
+    Unsupported(Grammar),
+
+    /// None of the generated inputs were entirely matched by the lexer.
+    ///
+    /// This happens when part of the lexer matches more than the input generated for it,
+    /// such as `digit().many(1..).then(digit())` where the repetition consumes every digit.
+    NoMatch,
+}
+
+impl fmt::Display for SampleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SampleError::Unsupported(grammar) => {
+                write!(f, "Input can't be generated for {grammar}")
+            }
+            SampleError::NoMatch => write!(f, "No generated input matched"),
+        }
+    }
+}
+
+impl std::error::Error for SampleError {}
+
+/// Generates input until the lexer matches all of it.
+///
+/// This backs [`Lex::sample()`].
+pub(crate) fn sample<L: Lex + ?Sized>(
+    lexer: &L,
+    sampler: &mut Sampler,
+) -> Result<String, SampleError> {
+    for _ in 0..ATTEMPTS {
+        let mut output = String::new();
+        lexer.sample_into(sampler, &mut output)?;
+
+        if lexer.lex_all(&output).is_ok() {
+            return Ok(output);
+        }
+    }
+
+    Err(SampleError::NoMatch)
+}
+
+/// Returns a [proptest](https://docs.rs/proptest) strategy generating input that the lexer matches.
+///
+/// This function requires the `proptest` feature.
+///
+/// # Panics
+///
+/// The strategy panics when generating a value if input can't be generated for the lexer, see [`Lex::sample()`].
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{alpha, char, Lex, Parse};
+/// use proptest::prelude::*;
+///
+/// let words = alpha().many(1..).map(str::to_string).many(1..).delimiter(char(' '));
+///
+/// proptest!(|(input in parsely::sample::strategy(alpha().many(1..8).many(1..5).delimiter(char(' '))))| {
+///     let parsed: Vec<String> = words.parse_all(&input).unwrap();
+///     prop_assert_eq!(parsed.join(" "), input);
+/// });
+/// ```
+#[cfg(feature = "proptest")]
+pub fn strategy<L>(lexer: L) -> impl proptest::strategy::Strategy<Value = String>
+where
+    L: Lex + fmt::Debug,
+{
+    use proptest::strategy::Strategy;
+
+    proptest::num::u64::ANY.prop_map(move |seed| {
+        lexer
+            .sample(&mut Sampler::new(seed))
+            .unwrap_or_else(|e| panic!("can't generate input for {lexer:?}: {e}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alpha, any, char, digit, end, hex, none_of, take, take_bytes, take_while, token, until, ws,
+        Lex,
+    };
+
+    /// Checks that each sample matches the lexer entirely.
+    fn check(lexer: impl Lex) {
+        let mut sampler = Sampler::new(1);
+
+        for _ in 0..50 {
+            let input = lexer.sample(&mut sampler).unwrap();
+            assert_eq!(lexer.lex(&input).map(|(_, remaining)| remaining), Ok(""));
+        }
+    }
+
+    #[test]
+    fn lexers() {
+        check(char('a'));
+        check(token("abc"));
+        check(token("abc").any_case());
+        check(digit());
+        check(hex().many(6..=6));
+        check(alpha().many(1..));
+        check(none_of("abc"));
+        check(ws().many(..));
+        check(any().many(3..=3));
+        check(take(4));
+        check(take_bytes(4));
+        check(take_while(|c| c.is_ascii_digit()).then(char('x')));
+        check(until("x").then(token("x")));
+        check(end());
+    }
+
+    #[test]
+    fn combinators() {
+        check(char('a').or(char('b')).many(2..5).delimiter(char(',')));
+        check(char('-').optional().then(digit().many(1..)).pad());
+        check(alpha().many(1..).interleave(char(';')));
+        check(char('(').then(digit().name("digit")).then_skip(char(')')));
+    }
+
+    #[test]
+    fn errors() {
+        fn letter(input: &str) -> crate::LexResult<'_> {
+            alpha().lex(input)
+        }
+
+        let mut sampler = Sampler::new(1);
+
+        assert_eq!(
+            char('a').then(letter).sample(&mut sampler),
+            Err(SampleError::Unsupported(Grammar::Reference(
+                "letter".to_string()
+            )))
+        );
+        assert_eq!(
+            digit().many(1..).then(digit()).sample(&mut sampler),
+            Err(SampleError::NoMatch)
+        );
+    }
+
+    #[test]
+    fn reproducible() {
+        let lexer = alpha().many(1..10);
+
+        assert_eq!(
+            lexer.sample(&mut Sampler::new(3)),
+            lexer.sample(&mut Sampler::new(3))
+        );
+    }
+}
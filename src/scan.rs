@@ -0,0 +1,354 @@
+//! Tools for scanning through an input for every match, rather than only matching at the start of the input.
+//!
+//! * [`Lex::find_iter()`] - iterate over every non-overlapping match and where it was found
+//! * [`Lex::split()`] - iterate over the segments of input in between each match
+//! * [`split_parse()`] - parse each segment of input in between each match of a delimiter
+//! * [`Lex::replace_all()`] and [`Parse::rewrite()`] - copy the input, replacing each match
+
+use std::fmt;
+
+use crate::{combinator::capture::backtrack, grammar::Grammar, Error, Lex, Parse, ParseResult};
+
+/// This iterator is returned by [`Lex::find_iter()`]. See it's documentation for more details.
+pub struct FindIter<'l, 'i, L> {
+    lexer: &'l L,
+    input: &'i str,
+
+    /// The offset to start searching for the next match from, `None` once the input is exhausted.
+    offset: Option<usize>,
+}
+
+impl<'l, 'i, L> FindIter<'l, 'i, L> {
+    pub(crate) fn new(lexer: &'l L, input: &'i str) -> Self {
+        FindIter {
+            lexer,
+            input,
+            offset: Some(0),
+        }
+    }
+}
+
+/// A match found by [`find_from()`].
+struct Found<O> {
+    offset: usize,
+    output: O,
+
+    /// The offset of the end of the match.
+    end: usize,
+
+    /// The offset to search for the next match from, `None` if the input is exhausted.
+    next: Option<usize>,
+}
+
+/// Finds the first match at or after the start offset, trying each char boundary in turn, including the very end of the input.
+fn find_from<'i, O>(
+    input: &'i str,
+    start: usize,
+    attempt: impl Fn(&'i str) -> Result<(O, &'i str), Error>,
+) -> Option<Found<O>> {
+    let boundaries = input[start..]
+        .char_indices()
+        .map(|(n, _)| start + n)
+        .chain(std::iter::once(input.len()));
+
+    for boundary in boundaries {
+        let at = &input[boundary..];
+
+        if let Ok((output, remaining)) = backtrack(|| attempt(at)) {
+            let end = input.len() - remaining.len();
+
+            // an empty match advances by a char, so the same empty match isn't found forever
+            let next = if end > boundary {
+                Some(end)
+            } else {
+                at.chars().next().map(|c| boundary + c.len_utf8())
+            };
+
+            return Some(Found {
+                offset: boundary,
+                output,
+                end,
+                next,
+            });
+        }
+    }
+
+    None
+}
+
+impl<'i, L: Lex> FindIter<'_, 'i, L> {
+    /// Finds the next match, including where the input it consumed ends.
+    fn next_found(&mut self) -> Option<Found<&'i str>> {
+        let start = self.offset?;
+        let found = find_from(self.input, start, |input| self.lexer.lex(input));
+        self.offset = found.as_ref().and_then(|found| found.next);
+
+        found
+    }
+}
+
+impl<'i, L: Lex> Iterator for FindIter<'_, 'i, L> {
+    type Item = (usize, &'i str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_found().map(|found| (found.offset, found.output))
+    }
+}
+
+// the lexer is borrowed, so it doesn't need to be Clone
+impl<L> Clone for FindIter<'_, '_, L> {
+    fn clone(&self) -> Self {
+        FindIter {
+            lexer: self.lexer,
+            input: self.input,
+            offset: self.offset,
+        }
+    }
+}
+
+impl<L: fmt::Debug> fmt::Debug for FindIter<'_, '_, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FindIter")
+            .field("lexer", &self.lexer)
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+/// This iterator is returned by [`Lex::split()`]. See it's documentation for more details.
+pub struct Split<'l, 'i, L> {
+    matches: FindIter<'l, 'i, L>,
+
+    /// Where the next segment starts, `None` once the last segment has been returned.
+    start: Option<usize>,
+}
+
+impl<'l, 'i, L> Split<'l, 'i, L> {
+    pub(crate) fn new(delimiter: &'l L, input: &'i str) -> Self {
+        Split {
+            matches: FindIter::new(delimiter, input),
+            start: Some(0),
+        }
+    }
+}
+
+impl<'i, L: Lex> Iterator for Split<'_, 'i, L> {
+    type Item = &'i str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.start?;
+        let input = self.matches.input;
+
+        match self.matches.next_found() {
+            // the delimiter may consume more than it outputs, such as the spaces around a padded delimiter
+            Some(found) => {
+                self.start = Some(found.end);
+                Some(&input[start..found.offset])
+            }
+            None => {
+                self.start = None;
+                Some(&input[start..])
+            }
+        }
+    }
+}
+
+impl<L> Clone for Split<'_, '_, L> {
+    fn clone(&self) -> Self {
+        Split {
+            matches: self.matches.clone(),
+            start: self.start,
+        }
+    }
+}
+
+impl<L: fmt::Debug> fmt::Debug for Split<'_, '_, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Split")
+            .field("delimiter", &self.matches.lexer)
+            .field("start", &self.start)
+            .finish()
+    }
+}
+
+/// This parser is returned by [`split_parse()`]. See it's documentation for more details.
+#[derive(Clone)]
+pub struct SplitParse<L, P> {
+    delimiter: L,
+    item: P,
+}
+
+impl<L, P> Parse for SplitParse<L, P>
+where
+    L: Lex,
+    P: Parse,
+{
+    type Output = Vec<<P as Parse>::Output>;
+
+    fn parse<'i>(&self, input: &'i str) -> ParseResult<'i, Self::Output> {
+        let mut outputs = Vec::new();
+
+        for segment in self.delimiter.split(input) {
+            match self.item.parse(segment)? {
+                (output, "") => outputs.push(output),
+                _ => return Err(Error::NoMatch),
+            }
+        }
+
+        Ok((outputs, ""))
+    }
+
+    fn describe(&self) -> Grammar {
+        Grammar::separated(1, None, self.item.describe(), self.delimiter.describe())
+    }
+}
+
+/// Splits all of the input into segments in between each match of the delimiter, and parses each segment with the item parser.
+///
+/// Unlike [`.many(..).delimiter(delimiter)`](crate::combinator::Many::delimiter()), the input is split first,
+/// so segments can be empty and the item doesn't need to avoid matching the delimiter.
+///
+/// The item must match the whole of each segment, otherwise this parser fails. All of the input is consumed.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use parsely::{char, int, Lex, Parse};
+/// use parsely::scan::split_parse;
+///
+/// let optional_numbers = split_parse(char(','), int::<i32>().optional());
+///
+/// assert_eq!(optional_numbers.parse("1,,-3")?, (vec![Some(1), None, Some(-3)], ""));
+/// assert!(optional_numbers.parse("1,2a").is_err());
+/// # Ok::<(), parsely::Error>(())
+/// ```
+pub fn split_parse<L: Lex, P: Parse>(delimiter: L, item: P) -> SplitParse<L, P> {
+    SplitParse { delimiter, item }
+}
+
+impl<L: fmt::Debug, P: fmt::Debug> fmt::Debug for SplitParse<L, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SplitParse({:?}, {:?})", self.delimiter, self.item)
+    }
+}
+
+/// Copies the input, replacing each non-overlapping match with the result of the closure.
+///
+/// This is shared by [`Lex::replace_all()`] and [`Parse::rewrite()`].
+pub(crate) fn replace<'i, O, R: AsRef<str>>(
+    input: &'i str,
+    attempt: impl Fn(&'i str) -> Result<(O, &'i str), Error>,
+    mut f: impl FnMut(O) -> R,
+) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut copied = 0;
+    let mut start = Some(0);
+
+    while let Some(found) = start.and_then(|start| find_from(input, start, &attempt)) {
+        output.push_str(&input[copied..found.offset]);
+        output.push_str(f(found.output).as_ref());
+
+        copied = found.end;
+        start = found.next;
+    }
+
+    output.push_str(&input[copied..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::{char, digit, end, int, token, ws, Lex};
+
+    #[test]
+    fn find_iter() {
+        let found: Vec<_> = digit().many(1..).find_iter("a1b22c333").collect();
+        assert_eq!(found, vec![(1, "1"), (3, "22"), (6, "333")]);
+
+        let found: Vec<_> = token("aa").find_iter("aaaaa").collect();
+        assert_eq!(found, vec![(0, "aa"), (2, "aa")]);
+
+        let found: Vec<_> = token("é").find_iter("éaé").collect();
+        assert_eq!(found, vec![(0, "é"), (3, "é")]);
+
+        assert_eq!(token("x").find_iter("abc").next(), None);
+        assert_eq!(token("x").find_iter("").next(), None);
+    }
+
+    #[test]
+    fn empty_matches() {
+        let found: Vec<_> = digit().many(..).find_iter("a1").collect();
+        assert_eq!(found, vec![(0, ""), (1, "1"), (2, "")]);
+
+        let found: Vec<_> = end().find_iter("").collect();
+        assert_eq!(found, vec![(0, "")]);
+    }
+
+    #[test]
+    fn split() {
+        let segments: Vec<_> = char(',').split("a,b,,c,").collect();
+        assert_eq!(segments, "a,b,,c,".split(',').collect::<Vec<_>>());
+
+        let segments: Vec<_> = ws().many(1..).split("a  b\n c").collect();
+        assert_eq!(segments, vec!["a", "b", "c"]);
+
+        let segments: Vec<_> = char(',').split("").collect();
+        assert_eq!(segments, vec![""]);
+
+        let segments: Vec<_> = end().optional().split("ab").collect();
+        assert_eq!(segments, "ab".split("").collect::<Vec<_>>());
+
+        // the next segment starts after all the input the delimiter consumed
+        let segments: Vec<_> = char(',').pad().split("a , b").collect();
+        assert_eq!(segments, vec!["a", "b"]);
+
+        let segments: Vec<_> = char(',').then_skip(char(' ')).split("a, b, c").collect();
+        assert_eq!(segments, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn parse_segments() {
+        test_parser_batch(
+            "each segment is parsed",
+            split_parse(token("::"), int::<i32>()),
+            &[
+                ("1::2::3", Some(vec![1, 2, 3]), ""), //
+                ("1", Some(vec![1]), ""),
+                ("1::2:", None, "1::2:"),
+                ("1::", None, "1::"),
+            ],
+        );
+    }
+
+    #[test]
+    fn replace_all() {
+        assert_eq!(
+            digit()
+                .many(1..)
+                .replace_all("a1b22c", |n| format!("<{n}>")),
+            "a<1>b<22>c"
+        );
+        assert_eq!(token("x").replace_all("abc", |_| "y"), "abc");
+        assert_eq!(token("é").replace_all("éaé", |_| "e"), "eae");
+
+        // empty matches are replaced between each char, like str::replace
+        assert_eq!(
+            digit().many(..).replace_all("a1", |n| format!("[{n}]")),
+            "[]a[1][]"
+        );
+        assert_eq!(
+            end().optional().replace_all("ab", |_| "-"),
+            "ab".replace("", "-")
+        );
+    }
+
+    #[test]
+    fn rewrite() {
+        let doubled = int::<i32>().rewrite("1 + -2 = -1", |n| (n * 2).to_string());
+        assert_eq!(doubled, "2 + -4 = -2");
+    }
+}
@@ -0,0 +1,228 @@
+//! Table driven tests for your own lexers and parsers.
+//!
+//! Each helper runs a lexer or parser against a list of cases, and panics with the test name and the index of the case that failed.
+//! They are meant to be called from `#[test]` functions.
+//!
+//! * [`test_lexer_batch()`] and [`test_parser_batch()`] check the output and remaining input of each case, or that it fails
+//! * [`test_lexer_errors()`] and [`test_parser_errors()`] check the exact [`Error`] each case fails with
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```
+//! use parsely::{char, int, Error, Lex, Parse};
+//! use parsely::testing::{test_parser_batch, test_parser_errors};
+//!
+//! let point = || int::<i32>().then_skip(char(',')).then(int::<i32>());
+//!
+//! test_parser_batch(
+//!     "points",
+//!     point(),
+//!     &[
+//!         ("1,2", Some((1, 2)), ""),
+//!         ("-1,2,3", Some((-1, 2)), ",3"),
+//!         ("1;2", None, ""),
+//!     ],
+//! );
+//!
+//! test_parser_errors(
+//!     "point errors",
+//!     point().expected("a point"),
+//!     &[("1;2", Error::Expected("a point"))],
+//! );
+//! ```
+
+use std::fmt;
+
+use crate::{Error, Lex, Parse};
+
+#[track_caller]
+fn test_lexer(
+    test_index: usize,
+    test_name: &str,
+    lexer: &impl Lex,
+    input: &str,
+    expected_output: &str,
+    expected_remaining: &str,
+) {
+    assert_eq!(
+        (expected_output, expected_remaining),
+        lexer
+            .lex(input)
+            .unwrap_or_else(|_| panic!("lexer:{test_name}:{test_index} should match")),
+        "lexer:{test_name}:{test_index}. Expected left; Got right",
+    );
+}
+
+#[track_caller]
+fn test_lexer_error(test_index: usize, test_name: &str, lexer: &impl Lex, input: &str) {
+    assert!(
+        lexer.lex(input).is_err(),
+        "lexer:{test_name}:{test_index} should error"
+    );
+}
+
+/// Tests a lexer against each case of `(input, expected_output, expected_remaining)`.
+///
+/// If the expected output is `None` the lexer is expected to fail, and the expected remaining input is ignored.
+///
+/// # Panics
+///
+/// Panics if any case doesn't lex as expected, naming the test and the index of the case.
+#[track_caller]
+pub fn test_lexer_batch(test_name: &str, lexer: impl Lex, cases: &[(&str, Option<&str>, &str)]) {
+    for (i, (input, expected_output, expected_remaining)) in cases.iter().enumerate() {
+        if let Some(expected_output) = expected_output {
+            test_lexer(
+                i,
+                test_name,
+                &lexer,
+                input,
+                expected_output,
+                expected_remaining,
+            )
+        } else {
+            test_lexer_error(i, test_name, &lexer, input)
+        }
+    }
+}
+
+/// Tests that a lexer fails with the expected error for each case of `(input, expected_error)`.
+///
+/// # Panics
+///
+/// Panics if any case matches, or fails with a different error, naming the test and the index of the case.
+#[track_caller]
+pub fn test_lexer_errors(test_name: &str, lexer: impl Lex, cases: &[(&str, Error)]) {
+    for (i, (input, expected_error)) in cases.iter().enumerate() {
+        match lexer.lex(input) {
+            Err(error) => assert_eq!(
+                expected_error, &error,
+                "lexer:{test_name}:{i}. Expected left; Got right",
+            ),
+            Ok(matched) => panic!("lexer:{test_name}:{i} should error, got {matched:?}"),
+        }
+    }
+}
+
+#[track_caller]
+fn test_parser<T: PartialEq + fmt::Debug>(
+    test_index: usize,
+    test_name: &str,
+    parser: &impl Parse<Output = T>,
+    input: &str,
+    expected_output: T,
+    expected_remaining: &str,
+) {
+    assert_eq!(
+        (expected_output, expected_remaining),
+        parser
+            .parse(input)
+            .unwrap_or_else(|_| panic!("parser:{test_name}:{test_index} should match")),
+        "parser:{test_name}:{test_index}. Expected left; Got right",
+    );
+}
+
+#[track_caller]
+fn test_parser_error<T>(
+    test_index: usize,
+    test_name: &str,
+    parser: &impl Parse<Output = T>,
+    input: &str,
+) {
+    assert!(
+        parser.parse(input).is_err(),
+        "parser:{test_name}:{test_index} should error"
+    );
+}
+
+/// Tests a parser against each case of `(input, expected_output, expected_remaining)`.
+///
+/// If the expected output is `None` the parser is expected to fail, and the expected remaining input is ignored.
+///
+/// # Panics
+///
+/// Panics if any case doesn't parse as expected, naming the test and the index of the case.
+#[track_caller]
+pub fn test_parser_batch<T: PartialEq + Clone + fmt::Debug>(
+    test_name: &str,
+    parser: impl Parse<Output = T>,
+    cases: &[(&str, Option<T>, &str)],
+) {
+    for (i, (input, expected_output, expected_remaining)) in cases.iter().enumerate() {
+        if let Some(expected_output) = expected_output {
+            test_parser(
+                i,
+                test_name,
+                &parser,
+                input,
+                expected_output.clone(),
+                expected_remaining,
+            )
+        } else {
+            test_parser_error(i, test_name, &parser, input)
+        }
+    }
+}
+
+/// Tests that a parser fails with the expected error for each case of `(input, expected_error)`.
+///
+/// # Panics
+///
+/// Panics if any case parses successfully, or fails with a different error, naming the test and the index of the case.
+#[track_caller]
+pub fn test_parser_errors<T: fmt::Debug>(
+    test_name: &str,
+    parser: impl Parse<Output = T>,
+    cases: &[(&str, Error)],
+) {
+    for (i, (input, expected_error)) in cases.iter().enumerate() {
+        match parser.parse(input) {
+            Err(error) => assert_eq!(
+                expected_error, &error,
+                "parser:{test_name}:{i}. Expected left; Got right",
+            ),
+            Ok(matched) => panic!("parser:{test_name}:{i} should error, got {matched:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{char, digit, error::failed_conversion};
+
+    #[test]
+    fn errors() {
+        test_lexer_errors(
+            "digits",
+            digit().many(1..).expected("digits"),
+            &[
+                ("x", Error::Expected("digits")),
+                ("", Error::Expected("digits")),
+            ],
+        );
+        test_parser_errors(
+            "bytes",
+            digit().many(1..).parse_str::<u8>().then_skip(char(';')),
+            &[
+                (
+                    "256;",
+                    failed_conversion("256", "number too large to fit in target type"),
+                ),
+                ("1", Error::NoMatch),
+            ],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "lexer:digits:1. Expected left; Got right")]
+    fn unexpected_error() {
+        test_lexer_errors(
+            "digits",
+            digit(),
+            &[("x", Error::NoMatch), ("y", Error::custom("y"))],
+        );
+    }
+}
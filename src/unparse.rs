@@ -0,0 +1,186 @@
+//! Formatting a parser's output back into input that it parses, so that one grammar definition both reads and writes a format.
+//!
+//! Parsers that implement [`Unparse`] can turn a value back into a string with [`Unparse::unparse()`].
+//! This saves maintaining a `Display` impl that mirrors the parser by hand, which drifts as soon as either changes.
+//!
+//! Lexers whose match isn't part of the output, such as the lexer of [`then_skip()`](crate::combinator::then_skip()) or the padding of [`pad()`](crate::combinator::pad()),
+//! write their canonical input from [`Lex::canonical_into()`]: a token writes itself, optional and repeated lexers write as little as they can, and so on.
+//! Whitespace is only written where the grammar requires it, so unparsing normalises the formatting of the input.
+//!
+//! Some parsers can't be reversed:
+//!
+//! * [`map()`](crate::Parse::map()) has no way to turn its output back into the value it was given, use [`bimap()`](crate::Parse::bimap()) instead.
+//! * lexers mapped to a value need [`parse_str()`](crate::Lex::parse_str()), which writes the value with its [`Display`](std::fmt::Display) impl.
+//! * parsers written as functions can't be looked inside at all.
+//!
+//! # Examples
+//!
+//! Round trip a config file entry:
+//!
+//! ```
+//! use parsely::{alpha, char, digit, ws, Lex, Parse, Unparse};
+//!
+//! let key = alpha().many(1..).parse_str::<String>();
+//! let value = digit().many(1..).parse_str::<u32>();
+//! let entry = key.then_skip(char('=').pad_with(ws().many(..), ws().many(..))).then(value);
+//!
+//! let (parsed, _) = entry.parse("retries =  3")?;
+//! assert_eq!(parsed, ("retries".to_string(), 3));
+//!
+//! assert_eq!(entry.unparse(&("timeout".to_string(), 30))?, "timeout=30");
+//! # Ok::<(), parsely::Error>(())
+//! ```
+
+use crate::{grammar::Grammar, Error, Lex, Parse};
+
+/// A parser that can also format its output back into input that it parses.
+///
+/// See the [module documentation](self) for more details.
+pub trait Unparse: Parse {
+    /// Writes input that this parser parses as `value`, appending it to `output`.
+    ///
+    /// If this fails, `output` may have been partly written to.
+    fn unparse_into(&self, value: &Self::Output, output: &mut String) -> Result<(), Error>;
+
+    /// Returns input that this parser parses as `value`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use parsely::{char, digit, Lex, Parse, Unparse};
+    ///
+    /// let list = digit().many(1..).parse_str::<u8>().many(..).delimiter(char(','));
+    ///
+    /// assert_eq!(list.unparse(&vec![1, 20, 3])?, "1,20,3");
+    /// # Ok::<(), parsely::Error>(())
+    /// ```
+    fn unparse(&self, value: &Self::Output) -> Result<String, Error> {
+        let mut output = String::new();
+        self.unparse_into(value, &mut output)?;
+        Ok(output)
+    }
+}
+
+/// The error for a lexer or parser that has nothing to unparse with.
+pub(crate) fn unsupported(grammar: Grammar) -> Error {
+    Error::custom(format!("{grammar} can't be unparsed"))
+}
+
+/// Writes `text`, after checking that the lexer matches all of it.
+///
+/// This catches values whose `Display` impl formats them in a way the lexer doesn't accept.
+pub(crate) fn matched(lexer: &impl Lex, text: &str, output: &mut String) -> Result<(), Error> {
+    if lexer.lex_all(text).is_err() {
+        return Err(Error::custom(format!(
+            "{text:?} doesn't match {}",
+            lexer.describe()
+        )));
+    }
+
+    output.push_str(text);
+    Ok(())
+}
+
+/// Writes `item` for each value, with the canonical input of the `separator` in between.
+pub(crate) fn repeat<P: Unparse>(
+    grammar: impl FnOnce() -> Grammar,
+    (min, max): (usize, usize),
+    values: &[P::Output],
+    item: &P,
+    separator: Option<&dyn Lex>,
+    output: &mut String,
+) -> Result<(), Error> {
+    if values.len() < min || values.len() > max {
+        return Err(Error::custom(format!(
+            "{} items can't be unparsed by {}",
+            values.len(),
+            grammar()
+        )));
+    }
+
+    for (i, value) in values.iter().enumerate() {
+        if let (Some(separator), true) = (separator, i > 0) {
+            separator.canonical_into(output)?;
+        }
+        item.unparse_into(value, output)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{any, char, digit, hex, token, ws, Lex};
+
+    #[test]
+    fn round_trips() -> Result<(), Error> {
+        let number = || digit().many(1..).parse_str::<u32>();
+        let point = char('(')
+            .skip_then(number())
+            .then_skip(char(','))
+            .then(number())
+            .then_skip(char(')'));
+
+        let (value, _) = point.parse("(12,3)")?;
+        assert_eq!(point.unparse(&value)?, "(12,3)");
+        assert_eq!(point.unparse(&(0, 45))?, "(0,45)");
+
+        let signed = char('-')
+            .optional()
+            .then(digit().many(1..))
+            .parse_str::<i64>();
+        assert_eq!(signed.unparse(&-5)?, "-5");
+
+        let keyword = token("true")
+            .parse_str::<bool>()
+            .or(token("false").parse_str());
+        assert_eq!(keyword.unparse(&false)?, "false");
+
+        let list = number().pad().many(..).delimiter(char(';'));
+        let (value, _) = list.parse(" 1 ; 2;3 ")?;
+        assert_eq!(list.unparse(&value)?, "1;2;3");
+        assert_eq!(list.unparse(&vec![])?, "");
+
+        let maybe = token("0x")
+            .skip_then(hex().many(1..).parse_str::<String>())
+            .optional();
+        assert_eq!(maybe.unparse(&None)?, "");
+        assert_eq!(maybe.unparse(&Some("ff".to_string()))?, "0xff");
+
+        Ok(())
+    }
+
+    #[test]
+    fn canonical_input() -> Result<(), Error> {
+        let canonical = |lexer: &dyn Lex| {
+            let mut output = String::new();
+            lexer.canonical_into(&mut output).map(|()| output)
+        };
+
+        assert_eq!(canonical(&token("abc"))?, "abc");
+        assert_eq!(canonical(&ws().many(..))?, "");
+        assert_eq!(canonical(&ws().many(2..))?, "  ");
+        assert_eq!(canonical(&char('a').or(char('b')))?, "a");
+        assert_eq!(canonical(&digit().many(3..).delimiter(char('_')))?, "0_0_0");
+        assert_eq!(canonical(&char('a').optional().then(char('b')))?, "b");
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        let digits = digit().parse_str::<u8>().many(2..=3);
+        assert!(digits.unparse(&vec![1]).is_err());
+        assert!(digits.unparse(&vec![1, 2, 3, 4]).is_err());
+
+        // 10 is displayed as two digits
+        assert!(digit().parse_str::<u8>().unparse(&10).is_err());
+
+        // any() has no canonical input
+        let skipped = any().skip_then(digit().parse_str::<u8>());
+        assert!(skipped.unparse(&1).is_err());
+    }
+}